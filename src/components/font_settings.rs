@@ -1,61 +1,158 @@
 //! This module contains the functions for changing the font settings as defined in the [FontRepresentation] struct.
 
-use crate::components::shared_components::NumberedValidatedLengthInput;
-use crate::logic::settings::{CssSize, FontRepresentation, HorizontalAlign};
+use crate::components::shared_components::{NumberedValidatedLengthInput, PaletteSwatches};
+use crate::logic::css::{CssFontFamily, CssString, GenericFontFamily};
+use crate::logic::settings::{
+    CssSize, FontListChange, FontOutline, FontRepresentation, FontRole, HorizontalAlign,
+    LINE_HEIGHT_MAX, LINE_HEIGHT_MIN, TextTransform, clamp_line_height,
+};
+use crate::logic::system_fonts::system_font_families;
 use dioxus::logger::tracing;
 use dioxus::prelude::*;
-use rgb::RGB8;
+use rgb::{RGB8, RGBA8};
 use rust_i18n::t;
 
 use crate::logic::conversions::*;
 
 rust_i18n::i18n!("locales", fallback = "en");
 
-/// A component which renders and provides the manipulation features for [FontRepresentation]s
+/// A component which renders and provides the manipulation features for [FontRepresentation]s.
+/// Fonts can be added, removed and reordered and can be assigned to the headline, spoiler,
+/// meta information and the secondary languages of multi-language songs.
 #[component]
 pub fn FontRepresentationsComponent(
     /// The font representation as a vector
     fonts: Vec<FontRepresentation>,
 
+    /// A number which is changed by the parent when the fonts have been changed from outside
+    /// (e.g. by a color preset), so that the font components are recreated
+    #[props(default)]
+    version: usize,
+
+    /// The index of the font configuration for headlines
+    headline_index: Option<Option<u16>>,
+
     /// The index of the font configuration for default spoilers
     spoiler_index: Option<Option<u16>>,
 
     /// The index of the font configuration for default meta-block
     meta_index: Option<Option<u16>>,
 
-    /// The event which will be triggered if the given font representation has been changed by the user
-    onchange: EventHandler<Vec<FontRepresentation>>,
+    /// The index of the font configuration for the secondary languages of multi-language songs
+    secondary_language_index: Option<Option<u16>>,
+
+    /// The event which will be triggered if a font has been changed by the user, with the index of the font
+    onchange: EventHandler<(usize, FontRepresentation)>,
+
+    /// The event which will be triggered if fonts are added, removed, reordered or assigned to a role
+    onfontlistchange: EventHandler<FontListChange>,
 ) -> Element {
-    let mut fonts = use_signal(|| fonts);
-    let fonts_count = use_memo(move || fonts.len());
+    let fonts_count = fonts.len();
+
+    // The font components keep their own state, so they are recreated whenever the list changes
+    let mut list_version = use_signal(|| 0usize);
+    let mut dragged_font = use_signal(|| None::<usize>);
+
+    let mut change_list = move |change: FontListChange| {
+        if !matches!(change, FontListChange::SetRole(_, _)) {
+            list_version += 1;
+        }
+        onfontlistchange.call(change);
+    };
+
+    let roles = [
+        (FontRole::Headline, headline_index, "settings.fonts.use_as_headline"),
+        (FontRole::Spoiler, spoiler_index, "settings.fonts.use_as_spoiler"),
+        (FontRole::Meta, meta_index, "settings.fonts.use_as_meta"),
+        (FontRole::SecondaryLanguage, secondary_language_index, "settings.fonts.use_as_secondary_language"),
+    ];
 
     rsx!(
         article {
-            for (idx, font) in fonts().into_iter().enumerate() {
-                SingleFontRepresentationComponent {
-                    font: font,
-                    is_primary: match idx {
-                        0 => true,
-                        _ => false
+            small { { t!("settings.fonts.reorder_hint").to_string() } }
+            for (idx, font) in fonts.into_iter().enumerate() {
+                div {
+                    key: "{version}-{list_version}-{idx}",
+                    class: if dragged_font() == Some(idx) { "font-entry dragging" } else { "font-entry" },
+                    ondragover: move |event| event.prevent_default(),
+                    ondrop: move |event| {
+                        event.prevent_default();
+                        if let Some(from) = dragged_font.take() {
+                            change_list(FontListChange::Move { from, to: idx });
+                        }
                     },
-                    is_spoiler: spoiler_index == Some(Some(idx as u16)),
-                    is_meta: meta_index == Some(Some(idx as u16)),
-                    onchange: move |new_font| {
-                        match fonts.write().get_mut(idx) {
-                            Some(reference) => {
-                                *reference = new_font;
-                            },
-                            None => tracing::error!("Error while overriding font.")
+
+                    div {
+                        class: "font-entry-actions",
+                        span {
+                            class: "font-drag-handle",
+                            draggable: "true",
+                            title: t!("settings.fonts.reorder_hint").to_string(),
+                            ondragstart: move |_| dragged_font.set(Some(idx)),
+                            ondragend: move |_| dragged_font.set(None),
+                            "⠿"
+                        }
+                        for (role, role_index, label) in roles {
+                            label {
+                                input {
+                                    r#type: "checkbox",
+                                    role: "switch",
+                                    checked: role_index == Some(Some(idx as u16)),
+                                    onchange: move |event| {
+                                        let index = event.checked().then_some(idx as u16);
+                                        change_list(FontListChange::SetRole(role, index));
+                                    }
+                                }
+                                { t!(label).to_string() }
+                            }
+                        }
+                        button {
+                            class: "outline secondary smaller-buttons",
+                            r#type: "button",
+                            disabled: idx == 0,
+                            aria_label: t!("settings.move_up").to_string(),
+                            onclick: move |_| change_list(FontListChange::Move { from: idx, to: idx.saturating_sub(1) }),
+                            "↑"
+                        }
+                        button {
+                            class: "outline secondary smaller-buttons",
+                            r#type: "button",
+                            disabled: idx + 1 >= fonts_count,
+                            aria_label: t!("settings.move_down").to_string(),
+                            onclick: move |_| change_list(FontListChange::Move { from: idx, to: idx + 1 }),
+                            "↓"
+                        }
+                        // The first font is the main font and can't be removed
+                        if idx > 0 {
+                            button {
+                                class: "outline secondary smaller-buttons",
+                                r#type: "button",
+                                onclick: move |_| change_list(FontListChange::Remove(idx)),
+                                { t!("settings.fonts.remove_font").to_string() }
+                            }
                         }
-                        onchange.call(fonts());
                     }
-                }
 
-                // Add a horizontal line between fonts
-                if idx < fonts_count() -1 {
-                    hr { }
+                    SingleFontRepresentationComponent {
+                        font: font,
+                        is_primary: idx == 0,
+                        is_spoiler: spoiler_index == Some(Some(idx as u16)),
+                        is_meta: meta_index == Some(Some(idx as u16)),
+                        onchange: move |new_font| onchange.call((idx, new_font)),
+                    }
+
+                    // Add a horizontal line between fonts
+                    if idx < fonts_count - 1 {
+                        hr { }
+                    }
                 }
             }
+            button {
+                class: "outline",
+                r#type: "button",
+                onclick: move |_| change_list(FontListChange::Add),
+                { t!("settings.fonts.add_font").to_string() }
+            }
         }
     )
 }
@@ -107,6 +204,16 @@ fn SingleFontRepresentationComponent(
         }
 
         form {
+            FontFamilySelector {
+                font_family: font().font_family.unwrap_or_default(),
+                onchange: move |new_family: CssFontFamily| {
+                    // Without a named family the web default is used, so nothing has to be stored
+                    font.write().font_family = Some(new_family)
+                        .filter(|family| *family != CssFontFamily::without_family());
+                    onchange.call(font());
+                }
+            }
+
             label {
                 { t!("settings.fonts.size").to_string() }
                 fieldset {
@@ -130,33 +237,305 @@ fn SingleFontRepresentationComponent(
                 }
             }
 
+            RgbaColorInput {
+                label: t!("settings.color").to_string(),
+                color: font().color,
+                onchange: move |new_color| {
+                    font.write().color = new_color;
+                    onchange.call(font());
+                }
+            }
+
+            HorizontalAlignmentSelector {
+                default: font().horizontal_alignment,
+                onchange: move |new_align| {
+                    font.write().horizontal_alignment = new_align;
+                    onchange.call(font());
+                }
+            }
+
+            label {
+                { t!("settings.fonts.letter_spacing").to_string() }
+                fieldset {
+                    role: "group",
+                    NumberedValidatedLengthInput {
+                        value: font().letter_spacing.unwrap_or(CssSize::Null),
+                        placeholder: "0",
+                        onchange: move |new_spacing: CssSize| {
+                            // No spacing is stored as no setting
+                            font.write().letter_spacing = Some(new_spacing).filter(|spacing| !spacing.is_null());
+                            onchange.call(font());
+                        }
+                    }
+                }
+            }
+
+            TextTransformSelector {
+                default: font().text_transform.unwrap_or_default(),
+                onchange: move |new_transform| {
+                    font.write().text_transform = Some(new_transform);
+                    onchange.call(font());
+                }
+            }
+
             fieldset {
                 label {
-                    { t!("settings.color").to_string() }
                     input {
-                        type: "color",
-                        value: font().color.to_hex(),
+                        r#type: "checkbox",
+                        role: "switch",
+                        checked: font().shadow,
                         onchange: move |event| {
-                            let new_color = event.value().to_rgb8().unwrap_or(RGB8::new(255,255,255));
-                            font.write().color = new_color.into();
+                            font.write().shadow = event.value().parse().unwrap_or(false);
                             onchange.call(font());
                         }
                     }
+                    { t!("settings.fonts.shadow").to_string() }
                 }
             }
 
-            HorizontalAlignmentSelector {
-                default: font().horizontal_alignment,
-                onchange: move |new_align| {
-                    font.write().horizontal_alignment = new_align;
-                    onchange.call(font());
+            if font().shadow {
+                RgbaColorInput {
+                    label: t!("settings.fonts.shadow_color").to_string(),
+                    color: font().shadow_color,
+                    onchange: move |new_color| {
+                        font.write().shadow_color = new_color;
+                        onchange.call(font());
+                    }
+                }
+                label {
+                    { t!("settings.fonts.shadow_blur").to_string() }
+                    fieldset {
+                        role: "group",
+                        NumberedValidatedLengthInput {
+                            value: font().shadow_blur,
+                            placeholder: "",
+                            onchange: move |new_blur: CssSize| {
+                                font.write().shadow_blur = new_blur;
+                                onchange.call(font());
+                            }
+                        }
+                    }
+                }
+            }
+
+            fieldset {
+                label {
+                    input {
+                        r#type: "checkbox",
+                        role: "switch",
+                        checked: font().outline.is_some(),
+                        onchange: move |event| {
+                            font.write().outline = event.checked().then(FontOutline::default);
+                            onchange.call(font());
+                        }
+                    }
+                    { t!("settings.fonts.outline").to_string() }
+                }
+            }
+
+            if let Some(outline) = font().outline {
+                label {
+                    { t!("settings.fonts.outline_width").to_string() }
+                    fieldset {
+                        role: "group",
+                        NumberedValidatedLengthInput {
+                            value: outline.width,
+                            placeholder: "",
+                            onchange: move |new_width: CssSize| {
+                                if let Some(outline) = font.write().outline.as_mut() {
+                                    outline.width = new_width;
+                                }
+                                onchange.call(font());
+                            }
+                        }
+                    }
+                }
+                fieldset {
+                    label {
+                        { t!("settings.fonts.outline_color").to_string() }
+                        input {
+                            type: "color",
+                            value: outline.color.to_hex(),
+                            onchange: move |event| {
+                                let new_color = event.value().to_rgb8().unwrap_or(RGB8::new(0,0,0));
+                                if let Some(outline) = font.write().outline.as_mut() {
+                                    outline.color = new_color.into();
+                                }
+                                onchange.call(font());
+                            }
+                        }
+                    }
+                    PaletteSwatches {
+                        onselect: move |new_color: RGBA8| {
+                            if let Some(outline) = font.write().outline.as_mut() {
+                                outline.color = new_color;
+                            }
+                            onchange.call(font());
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
+
+/// A component for selecting the font family from the installed fonts and a generic fallback
+#[component]
+fn FontFamilySelector(
+    font_family: CssFontFamily,
+    onchange: EventHandler<CssFontFamily>,
+) -> Element {
+    let mut value_signal = use_signal(|| font_family);
+    let preview_css = use_memo(move || format!("font-family: {};", value_signal().to_css_string()));
+    // A family which is not installed on this system (e.g. from an imported design) is kept
+    let missing_family = use_memo(move || {
+        value_signal()
+            .family
+            .filter(|family| !system_font_families().contains(family))
+    });
+
+    rsx!(
+        fieldset {
+            label {
+                { t!("settings.fonts.font_family").to_string() }
+                select {
+                    name: "font_family",
+                    aria_label: t!("settings.fonts.font_family").to_string(),
+                    onchange: move |event| {
+                        let family = Some(event.value()).filter(|family| !family.is_empty());
+                        value_signal.write().family = family;
+                        onchange.call(value_signal());
+                    },
+                    option {
+                        value: "",
+                        selected: value_signal().family.is_none(),
+                        { t!("settings.fonts.no_font_family").to_string() }
+                    }
+                    if let Some(family) = missing_family() {
+                        option {
+                            value: "{family}",
+                            selected: true,
+                            { t!("settings.fonts.missing_font_family", name = family).to_string() }
+                        }
+                    }
+                    for family in system_font_families() {
+                        option {
+                            key: "{family}",
+                            value: "{family}",
+                            style: format!("font-family: {};", CssFontFamily::with_family(family.clone()).to_css_string()),
+                            selected: value_signal().family.as_ref() == Some(family),
+                            "{family}"
+                        }
+                    }
+                }
+            }
+            label {
+                { t!("settings.fonts.generic_font_family.title").to_string() }
+                select {
+                    name: "generic_font_family",
+                    required: true,
+                    aria_label: t!("settings.fonts.generic_font_family.title").to_string(),
+                    onchange: move |event| {
+                        let new_generic_family = match event.value().as_str() {
+                            "serif" => GenericFontFamily::Serif,
+                            "sans-serif" => GenericFontFamily::SansSerif,
+                            "monospace" => GenericFontFamily::Monospace,
+                            "cursive" => GenericFontFamily::Cursive,
+                            "fantasy" => GenericFontFamily::Fantasy,
+                            "system-ui" => GenericFontFamily::SystemUi,
+                            other => {
+                                tracing::error!("Invalid option for the generic font family selected, the value is: {}", other);
+                                GenericFontFamily::SansSerif
+                            }
+                        };
+                        value_signal.write().genereric_family = new_generic_family;
+                        onchange.call(value_signal());
+                    },
+                    for (generic_family, translation_key) in [
+                        (GenericFontFamily::SansSerif, "settings.fonts.generic_font_family.sans_serif"),
+                        (GenericFontFamily::Serif, "settings.fonts.generic_font_family.serif"),
+                        (GenericFontFamily::Monospace, "settings.fonts.generic_font_family.monospace"),
+                        (GenericFontFamily::Cursive, "settings.fonts.generic_font_family.cursive"),
+                        (GenericFontFamily::Fantasy, "settings.fonts.generic_font_family.fantasy"),
+                        (GenericFontFamily::SystemUi, "settings.fonts.generic_font_family.system_ui"),
+                    ] {
+                        option {
+                            value: generic_family.to_css_string(),
+                            style: format!("font-family: {};", generic_family.to_css_string()),
+                            selected: value_signal().genereric_family == generic_family,
+                            { t!(translation_key).to_string() }
+                        }
+                    }
                 }
             }
+            small {
+                style: preview_css(),
+                { t!("settings.fonts.font_family_preview").to_string() }
+            }
         }
     )
 }
 
-/// An input field to change the line height
+/// A color input with an opacity slider. The color can also be entered as `#RRGGBBAA`.
+#[component]
+fn RgbaColorInput(
+    label: String,
+    color: RGBA8,
+    onchange: EventHandler<RGBA8>,
+) -> Element {
+    let opacity_percent = (color.a as f64 / 255.0 * 100.0).round() as u8;
+    rsx!(
+        fieldset {
+            label {
+                { label }
+                div {
+                    role: "group",
+                    input {
+                        type: "color",
+                        value: color.to_hex(),
+                        onchange: move |event| {
+                            // The color input has no alpha channel, so the opacity is kept
+                            let new_color = event.value().to_rgb8().unwrap_or(RGB8::new(255,255,255));
+                            onchange.call(RGBA8::new(new_color.r, new_color.g, new_color.b, color.a));
+                        }
+                    }
+                    input {
+                        type: "text",
+                        value: color.to_hex_with_alpha(),
+                        aria_label: t!("settings.fonts.hex_color").to_string(),
+                        onchange: move |event| {
+                            match event.value().to_rgba8() {
+                                Some(new_color) => onchange.call(new_color),
+                                None => tracing::error!("Invalid color entered, the value is: {}", event.value()),
+                            }
+                        }
+                    }
+                }
+            }
+            PaletteSwatches {
+                onselect: move |new_color: RGBA8| onchange.call(new_color)
+            }
+            label {
+                { format!("{}: {}%", t!("settings.fonts.opacity"), opacity_percent) }
+                input {
+                    type: "range",
+                    min: 0,
+                    max: 100,
+                    value: opacity_percent,
+                    oninput: move |event| {
+                        if let Ok(percent) = event.value().parse::<f64>() {
+                            let alpha = (percent.clamp(0.0, 100.0) / 100.0 * 255.0).round() as u8;
+                            onchange.call(RGBA8::new(color.r, color.g, color.b, alpha));
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
+
+/// A numeric input for the line height of a font, limited to the range from
+/// [LINE_HEIGHT_MIN] to [LINE_HEIGHT_MAX]
 #[component]
 fn LineHeightInput(
     line_height: f64,
@@ -165,16 +544,18 @@ fn LineHeightInput(
     rsx!(
         fieldset {
             label {
-                { { format!("{}: {}", t!("settings.fonts.line_height"), line_height) } }
+                { t!("settings.fonts.line_height").to_string() }
                 input {
-                    type: "range",
-                    min: "1",
-                    max: "2",
+                    type: "number",
+                    min: LINE_HEIGHT_MIN,
+                    max: LINE_HEIGHT_MAX,
                     step: 0.1,
                     value: line_height,
                     onchange: move |event| {
-                        let new_line_height = event.value().parse::<f64>().unwrap_or(1.0);
-                        onchange.call(new_line_height);
+                        match event.value().parse::<f64>() {
+                            Ok(new_line_height) => onchange.call(clamp_line_height(new_line_height)),
+                            Err(_) => tracing::error!("Invalid line height entered, the value is: {}", event.value()),
+                        }
                     }
                 }
             }
@@ -182,63 +563,115 @@ fn LineHeightInput(
     )
 }
 
-/// A component for selecting the horizontal text alignment
+/// A component for selecting the case of a text
 #[component]
-fn HorizontalAlignmentSelector(
-    default: HorizontalAlign,
-    onchange: EventHandler<HorizontalAlign>,
+fn TextTransformSelector(
+    default: TextTransform,
+    onchange: EventHandler<TextTransform>,
 ) -> Element {
     let mut value_signal = use_signal(|| default);
     rsx!(
         fieldset {
             label {
-                { t!("settings.horizontal_alignment.title").to_string() }
+                { t!("settings.text_transform.title").to_string() }
                 select {
-                    name: "horizontal_align",
+                    name: "text_transform",
                     required: true,
-                    aria_label: t!("settings.horizontal_alignment.title").to_string(),
+                    aria_label: t!("settings.text_transform.title").to_string(),
                     onchange: move |event| {
-                        let new_align = match event.value().as_str() {
-                            "left" => HorizontalAlign::Left,
-                            "centered" => HorizontalAlign::Centered,
-                            "right" => HorizontalAlign::Right,
-                            "justify" => HorizontalAlign::Justify,
-                            "justify_with_hyphenation" => HorizontalAlign::JustifyWithHyphenation,
+                        let new_transform = match event.value().as_str() {
+                            "none" => TextTransform::None,
+                            "uppercase" => TextTransform::Uppercase,
+                            "lowercase" => TextTransform::Lowercase,
+                            "capitalize" => TextTransform::Capitalize,
                             other => {
-                                tracing::error!("Invalid option for horizontal alignment selected, the value is: {}", other);
-                                HorizontalAlign::Centered
+                                tracing::error!("Invalid option for the text transform selected, the value is: {}", other);
+                                TextTransform::None
                             }
                         };
-                        value_signal.set(new_align);
-                        onchange.call(new_align);
+                        value_signal.set(new_transform);
+                        onchange.call(new_transform);
                     },
                     option {
-                        value: "left",
-                        selected: value_signal() == HorizontalAlign::Left,
-                        { t!("settings.horizontal_alignment.left").to_string() }
+                        value: "none",
+                        selected: value_signal() == TextTransform::None,
+                        { t!("settings.text_transform.none").to_string() }
                     }
                     option {
-                        value: "centered",
-                        selected: value_signal() == HorizontalAlign::Centered,
-                        { t!("settings.horizontal_alignment.centered").to_string() }
+                        value: "uppercase",
+                        selected: value_signal() == TextTransform::Uppercase,
+                        { t!("settings.text_transform.uppercase").to_string() }
                     }
                     option {
-                        value: "right",
-                        selected: value_signal() == HorizontalAlign::Right,
-                        { t!("settings.horizontal_alignment.right").to_string() }
+                        value: "lowercase",
+                        selected: value_signal() == TextTransform::Lowercase,
+                        { t!("settings.text_transform.lowercase").to_string() }
                     }
                     option {
-                        value: "justify",
-                        selected: value_signal() == HorizontalAlign::Justify,
-                        { t!("settings.horizontal_alignment.justify").to_string() }
+                        value: "capitalize",
+                        selected: value_signal() == TextTransform::Capitalize,
+                        { t!("settings.text_transform.capitalize").to_string() }
                     }
-                    option {
-                        value: "justify_with_hyphenation",
-                        selected: value_signal() == HorizontalAlign::JustifyWithHyphenation,
-                        { t!("settings.horizontal_alignment.justify_with_hyphenation").to_string() }
+                }
+            }
+        }
+    )
+}
+
+/// A segmented control for the horizontal alignment of a text. Justified text can additionally
+/// be hyphenated.
+#[component]
+fn HorizontalAlignmentSelector(
+    default: HorizontalAlign,
+    onchange: EventHandler<HorizontalAlign>,
+) -> Element {
+    let mut value_signal = use_signal(|| default);
+    let mut set_alignment = move |new_align: HorizontalAlign| {
+        value_signal.set(new_align);
+        onchange.call(new_align);
+    };
+    let is_justified = matches!(
+        value_signal(),
+        HorizontalAlign::Justify | HorizontalAlign::JustifyWithHyphenation
+    );
+    let options = [
+        (HorizontalAlign::Left, "settings.horizontal_alignment.left"),
+        (HorizontalAlign::Centered, "settings.horizontal_alignment.centered"),
+        (HorizontalAlign::Right, "settings.horizontal_alignment.right"),
+        (HorizontalAlign::Justify, "settings.horizontal_alignment.justify"),
+    ];
+    rsx!(
+        fieldset {
+            legend { { t!("settings.horizontal_alignment.title").to_string() } }
+            div {
+                role: "group",
+                aria_label: t!("settings.horizontal_alignment.title").to_string(),
+                for (align, label) in options {
+                    button {
+                        r#type: "button",
+                        class: if value_signal() == align
+                            || (align == HorizontalAlign::Justify && is_justified) { "" } else { "outline secondary" },
+                        aria_pressed: value_signal() == align,
+                        onclick: move |_| set_alignment(align),
+                        { t!(label).to_string() }
                     }
                 }
             }
+            if is_justified {
+                label {
+                    input {
+                        r#type: "checkbox",
+                        role: "switch",
+                        checked: value_signal() == HorizontalAlign::JustifyWithHyphenation,
+                        onchange: move |event| set_alignment(if event.checked() {
+                            HorizontalAlign::JustifyWithHyphenation
+                        } else {
+                            HorizontalAlign::Justify
+                        })
+                    }
+                    { t!("settings.horizontal_alignment.hyphenation").to_string() }
+                }
+            }
         }
     )
-}
\ No newline at end of file
+}
@@ -5,12 +5,23 @@ use cantara_songlib::slides::*;
 use dioxus::html::completions::CompleteWithBraces::strong;
 use dioxus::prelude::*;
 use regex::Regex;
-use rgb::RGBA8;
 use rust_i18n::t;
 
-use crate::logic::css::{CssHandler, PlaceItems};
-use crate::logic::presentation::{get_markdown_html, get_picture_path};
-use crate::logic::settings::{CssSize, HorizontalAlign, VerticalAlign};
+use super::shared_components::js_yes_no_box;
+use crate::logic::css::{CssHandler, PlaceItems, sanitize_custom_css, scope_custom_css};
+use crate::logic::presentation::{
+    MultiLanguageTexts, first_main_content_line, get_markdown_html, get_multi_language_texts,
+    get_picture_path, main_content_text,
+};
+use crate::logic::css::CssString;
+use crate::logic::reporting;
+use crate::logic::settings::{
+    AspectRatio, ClockFormat, CssSize, OverlayCorner, PictureSettings, ShownLanguages, SlideBlock,
+    SlideLayout, TitleEmphasis, TopBottomLeftRight, VerticalAlign, Watermark,
+};
+use crate::logic::shortcuts::{
+    PresentationAction, ShortcutKey, find_action, shortcuts_by_action,
+};
 #[cfg(target_arch = "wasm32")]
 use crate::logic::sync::{
     SYNC_KEY_ACTIVE, SYNC_KEY_POSITION, SYNC_KEY_POSITION_FROM_CONSOLE, SYNC_KEY_PRESENTATION,
@@ -19,10 +30,11 @@ use crate::logic::sync::{
 use crate::{
     MAIN_CSS,
     logic::{
-        settings::{AfterLastSlide, FontRepresentation, PresentationDesign, PresentationDesignSettings, PresentationDesignTemplate, SlideTransition},
-        states::RunningPresentation,
+        settings::{BackgroundMedia, FontRepresentation, PresentationDesign, PresentationDesignSettings, PresentationDesignTemplate, SlideTransition},
+        states::{RunningPresentation, RunningPresentationId, find_presentation, find_presentation_mut},
     },
 };
+use std::path::PathBuf;
 
 const PRESENTATION_CSS: Asset = asset!("/assets/presentation.css");
 const PRESENTATION_JS: Asset = asset!("/assets/presentation_positioning.js");
@@ -36,6 +48,18 @@ const PDFJS_WORKER: Asset = asset!("/node_modules/pdfjs-dist/build/pdf.worker.mi
 const PDFJS_CDN_LIB: &str = "https://cdn.jsdelivr.net/npm/pdfjs-dist@4.10.38/build/pdf.min.mjs";
 #[cfg(target_arch = "wasm32")]
 const PDFJS_CDN_WORKER: &str = "https://cdn.jsdelivr.net/npm/pdfjs-dist@4.10.38/build/pdf.worker.min.mjs";
+/// The time in milliseconds without mouse movement after which the cursor is hidden
+const CURSOR_HIDE_DELAY_MS: u32 = 2000;
+/// The horizontal distance in pixels a finger has to move to count as a swipe
+const SWIPE_THRESHOLD_PX: f64 = 60.0;
+/// The distance in pixels a finger may move during a long press
+const TOUCH_MOVE_TOLERANCE_PX: f64 = 10.0;
+/// The time in milliseconds a finger has to rest on the slide to open the slide overview
+const LONG_PRESS_MS: u32 = 600;
+/// The time in milliseconds after a wheel navigation in which further wheel events are ignored
+const WHEEL_DEBOUNCE_MS: u32 = 250;
+/// The time in milliseconds after which the chapter sidebar hides itself
+const CHAPTER_SIDEBAR_HIDE_MS: u32 = 5000;
 
 rust_i18n::i18n!("locales", fallback = "en");
 
@@ -45,6 +69,8 @@ rust_i18n::i18n!("locales", fallback = "en");
 #[component]
 pub fn PresentationPage() -> Element {
     let mut running_presentations: Signal<Vec<RunningPresentation>> = use_context();
+    // Presentation windows get the id of their presentation as root context
+    let presentation_id_context = try_use_context::<RunningPresentationId>();
 
     // On web, check if this is a synced new-tab presentation (opened by the presenter console).
     // In that case the running_presentations signal will be empty, and we load data from localStorage.
@@ -81,8 +107,14 @@ pub fn PresentationPage() -> Element {
     #[cfg(not(target_arch = "wasm32"))]
     let is_synced_tab = false;
 
+    // Without an id (routed page or synced tab), the most recently started presentation is shown
+    let shown_presentation = match presentation_id_context {
+        Some(RunningPresentationId(id)) => find_presentation(&running_presentations.read(), id).cloned(),
+        None => running_presentations.read().last().cloned(),
+    };
+
     // If there's still no presentation data, close the window (desktop) or show an error
-    if running_presentations.read().is_empty() {
+    let Some(shown_presentation) = shown_presentation else {
         #[cfg(feature = "desktop")]
         dioxus::desktop::window().close();
 
@@ -93,20 +125,21 @@ pub fn PresentationPage() -> Element {
                 p { "No presentation data found." }
             }
         };
-    }
+    };
 
+    let presentation_id = shown_presentation.id;
     let mut running_presentation: Signal<RunningPresentation> =
-        use_signal(move || running_presentations.get(0).unwrap().clone());
+        use_signal(move || shown_presentation);
 
     // When this window/component is destroyed (e.g. user closes the window),
-    // clear the shared running presentations so the presenter console also closes.
+    // remove this presentation from the shared running presentations so its presenter console also closes.
     // Use try_write() instead of write() to avoid a panic when the owning scope
     // (the main window's App component) has already been dropped before this
     // use_drop callback fires — which can happen on Windows when a drag-drop
     // event triggers an unexpected teardown sequence.
     use_drop(move || {
         if let Ok(mut guard) = running_presentations.try_write() {
-            guard.clear();
+            guard.retain(|rp| rp.id != presentation_id);
         }
     });
 
@@ -144,47 +177,48 @@ pub fn PresentationPage() -> Element {
     // `MarkdownSlideComponent`. This prevents scroll position updates from
     // triggering full component re-renders or interfering with slide navigation.
     //
-    // The loop also monitors whether the shared signal was cleared (presentation
-    // ended) and closes the window in that case.
+    // The loop also monitors whether the presentation was removed from the shared
+    // signal (presentation ended) and closes the window in that case. It also brings
+    // the window to the front when the selection page requests it.
     #[cfg(feature = "desktop")]
     use_future(move || async move {
-        let mut last_seen_shared = running_presentations.peek()
-            .first().cloned().unwrap_or_else(|| running_presentation.peek().clone());
+        let mut last_seen_shared = find_presentation(&running_presentations.peek(), presentation_id)
+            .cloned().unwrap_or_else(|| running_presentation.peek().clone());
         let mut last_seen_local = running_presentation.peek().clone();
 
         loop {
             let _ = document::eval("await new Promise(r => setTimeout(r, 50))").await;
 
-            // Presentation ended (signal cleared by use_drop) → close window
-            if running_presentations.peek().is_empty() {
+            let current_shared = find_presentation(&running_presentations.peek(), presentation_id)
+                .cloned();
+            // Presentation ended (removed by use_drop or the selection page) → close window
+            let Some(shared_rp) = current_shared else {
                 dioxus::desktop::window().close();
                 return;
-            }
-
-            let current_shared = running_presentations.peek()
-                .first().cloned();
+            };
             let current_local = running_presentation.peek().clone();
 
-            if let Some(ref shared_rp) = current_shared {
-                // Shared signal changed (other window pushed an update) → pull into local
-                if !shared_rp.eq_ignoring_scroll(&last_seen_shared) {
-                    last_seen_shared = shared_rp.clone();
-                    if !shared_rp.eq_ignoring_scroll(&current_local) {
-                        last_seen_local = shared_rp.clone();
-                        running_presentation.set(shared_rp.clone());
-                    }
+            // Shared signal changed (other window pushed an update) → pull into local
+            if !shared_rp.eq_ignoring_scroll(&last_seen_shared) {
+                if shared_rp.focus_requests != last_seen_shared.focus_requests {
+                    dioxus::desktop::window().set_focus();
                 }
-                // Local signal changed (this window's user action) → push to shared
-                else if !current_local.eq_ignoring_scroll(&last_seen_local) {
-                    last_seen_local = current_local.clone();
-                    if !current_local.eq_ignoring_scroll(shared_rp) {
-                        // Merge local non-scroll changes with the current shared scroll position
-                        let mut merged = current_local.clone();
-                        merged.markdown_scroll_position = shared_rp.markdown_scroll_position;
-                        last_seen_shared = merged.clone();
-                        if let Some(first) = running_presentations.write().first_mut() {
-                            *first = merged;
-                        }
+                last_seen_shared = shared_rp.clone();
+                if !shared_rp.eq_ignoring_scroll(&current_local) {
+                    last_seen_local = shared_rp.clone();
+                    running_presentation.set(shared_rp.clone());
+                }
+            }
+            // Local signal changed (this window's user action) → push to shared
+            else if !current_local.eq_ignoring_scroll(&last_seen_local) {
+                last_seen_local = current_local.clone();
+                if !current_local.eq_ignoring_scroll(&shared_rp) {
+                    // Merge local non-scroll changes with the current shared scroll position
+                    let mut merged = current_local.clone();
+                    merged.markdown_scroll_position = shared_rp.markdown_scroll_position;
+                    last_seen_shared = merged.clone();
+                    if let Some(shared) = find_presentation_mut(&mut running_presentations.write(), presentation_id) {
+                        *shared = merged;
                     }
                 }
             }
@@ -202,16 +236,14 @@ pub fn PresentationPage() -> Element {
     #[cfg(not(feature = "desktop"))]
     use_effect(move || {
         let current = running_presentations.read();
-        if current.is_empty() {
+        let Some(rp) = find_presentation(&current, presentation_id) else {
             if is_routed {
                 nav.replace(crate::Route::Selection {});
             }
             return;
-        }
-        if let Some(rp) = current.first() {
-            if !rp.eq_ignoring_scroll(&running_presentation.peek()) {
-                running_presentation.set(rp.clone());
-            }
+        };
+        if !rp.eq_ignoring_scroll(&running_presentation.peek()) {
+            running_presentation.set(rp.clone());
         }
     });
 
@@ -222,16 +254,16 @@ pub fn PresentationPage() -> Element {
     use_effect(move || {
         let local = running_presentation.read().clone();
         let shared = running_presentations.peek();
-        if let Some(first) = shared.first() {
-            if !first.eq_ignoring_scroll(&local) {
+        if let Some(shared_rp) = find_presentation(&shared, presentation_id) {
+            if !shared_rp.eq_ignoring_scroll(&local) {
                 drop(shared);
-                if let Some(first) = running_presentations.write().first_mut() {
+                if let Some(shared_rp) = find_presentation_mut(&mut running_presentations.write(), presentation_id) {
                     // Merge local changes into the shared state, but preserve the
                     // shared markdown_scroll_position to avoid overwriting a newer
                     // scroll value with a stale local one.
                     let mut merged = local;
-                    merged.markdown_scroll_position = first.markdown_scroll_position;
-                    *first = merged;
+                    merged.markdown_scroll_position = shared_rp.markdown_scroll_position;
+                    *shared_rp = merged;
                 }
             }
         }
@@ -270,7 +302,7 @@ pub fn PresentationPage() -> Element {
                     .map(|v| v == "true")
                     .unwrap_or(false);
                 if quit {
-                    running_presentations.write().clear();
+                    running_presentations.write().retain(|rp| rp.id != presentation_id);
                     // Close this tab
                     let _ = document::eval("window.close()").await;
                     return;
@@ -294,6 +326,70 @@ pub fn PresentationPage() -> Element {
         });
     }
 
+    // The position to return to when the slide overview is dismissed, `Some` while it is shown
+    let mut overview_return_position: Signal<Option<(usize, usize)>> = use_signal(|| None);
+    let mut open_overview = move || {
+        overview_return_position.set(
+            running_presentation
+                .read()
+                .position
+                .as_ref()
+                .map(|p| (p.chapter(), p.chapter_slide())),
+        );
+    };
+
+    // Whether the strip with the upcoming slide is shown. This is a per-window flag, so that an
+    // operator can turn it on in their copy of the presentation while the projector output stays clean.
+    let mut show_next_slide_strip = use_signal(|| false);
+
+    // Whether the sidebar with the chapters for jumping is shown
+    let mut show_chapter_sidebar = use_signal(|| false);
+
+    // Whether the overview of the keyboard shortcuts is shown
+    let mut show_shortcut_help = use_signal(|| false);
+
+    // Record the songs in the session log when they are shown for the first time.
+    // Only the presentation window records them, so that the presenter console and mirror
+    // windows don't add the same entries again. The songs are remembered by their path, because
+    // the chapter numbers change when chapters are added or removed during the presentation.
+    let mut recorded_songs: Signal<Vec<PathBuf>> = use_signal(Vec::new);
+    use_effect(move || {
+        let rp = running_presentation.read();
+        if !rp.session_log {
+            return;
+        }
+        let Some(chapter) = rp
+            .position
+            .as_ref()
+            .and_then(|position| rp.presentation.get(position.chapter()))
+        else {
+            return;
+        };
+        if recorded_songs.peek().contains(&chapter.source_file.path) {
+            return;
+        }
+        recorded_songs.write().push(chapter.source_file.path.clone());
+        reporting::record_shown_chapter(chapter);
+    });
+
+    // The mouse cursor is hidden after a short time without mouse movement
+    let mut cursor_hidden = use_signal(|| false);
+    let mut cursor_timer: Signal<Option<Task>> = use_signal(|| None);
+    let mut restart_cursor_timer = move || {
+        if *cursor_hidden.peek() {
+            cursor_hidden.set(false);
+        }
+        if let Some(task) = cursor_timer.take() {
+            task.cancel();
+        }
+        let js_sleep = format!("await new Promise(r => setTimeout(r, {CURSOR_HIDE_DELAY_MS}))");
+        cursor_timer.set(Some(spawn(async move {
+            let _ = document::eval(&js_sleep).await;
+            cursor_hidden.set(true);
+        })));
+    };
+    use_effect(move || restart_cursor_timer());
+
     // Context menu state
     let mut show_context_menu = use_signal(|| false);
     let mut context_menu_x = use_signal(|| 0.0f64);
@@ -315,7 +411,7 @@ pub fn PresentationPage() -> Element {
                     let _ = s.remove_item(SYNC_KEY_POSITION_FROM_CONSOLE);
                 });
         }
-        running_presentations.write().clear();
+        running_presentations.write().retain(|rp| rp.id != presentation_id);
         #[cfg(feature = "desktop")]
         dioxus::desktop::window().close();
         #[cfg(not(feature = "desktop"))]
@@ -329,19 +425,45 @@ pub fn PresentationPage() -> Element {
         }
     };
 
+    // The slides keep the aspect ratio of their design, see letterbox_style
+    let letterbox = use_memo(move || {
+        match running_presentation
+            .read()
+            .get_current_presentation_design()
+            .presentation_design_settings
+        {
+            PresentationDesignSettings::Template(template) => letterbox_style(template.aspect_ratio),
+            PresentationDesignSettings::Custom(_) => letterbox_style(AspectRatio::default()),
+        }
+    });
+
+    // A transparent design lets the desktop (or e.g. a chroma key) shine through the window
+    let transparent_background = use_memo(move || {
+        running_presentation
+            .read()
+            .get_current_presentation_design()
+            .has_transparent_background()
+    });
+
     rsx! {
         document::Link { rel: "stylesheet", href: MAIN_CSS }
         document::Link { rel: "stylesheet", href: PRESENTATION_CSS }
         document::Title { { t!("presentation.title").to_string() } }
+        if transparent_background() {
+            style { "html, body {{ background: transparent !important; }}" }
+        }
         // This div is needed for fullscreen mode
         div {
             tabindex: 0,
-            style: "
+            class: if cursor_hidden() { "cursor-hidden" },
+            onmousemove: move |_| restart_cursor_timer(),
+            style: format!("
                     all: initial;
                     margin:0;
                     width:100%;
                     height:100%;
-                ",
+                    background-color: {};
+                ", if transparent_background() { "transparent" } else { "black" }),
             onclick: move |_| {
                 // Close context menu on any click
                 show_context_menu.set(false);
@@ -356,38 +478,82 @@ pub fn PresentationPage() -> Element {
             onkeydown: move |event: Event<KeyboardData>| {
                 // Close context menu on any key press
                 show_context_menu.set(false);
-                match event.key() {
-                    Key::F5 | Key::F11 => {
-                        #[cfg(feature = "desktop")]
-                        {
-                            let desktop = dioxus::desktop::window();
-                            let is_fullscreen = desktop.fullscreen().is_some();
-                            desktop.set_fullscreen(!is_fullscreen);
-                        }
-                        #[cfg(not(feature = "desktop"))]
-                        {
-                            use_future(move || async move {
-                                let _ = document::eval("
-                                    if (document.fullscreenElement) {
-                                        document.exitFullscreen();
-                                    } else {
-                                        document.documentElement.requestFullscreen();
-                                    }
-                                ").await;
+                match presentation_action(&event) {
+                    // Only reached if the renderer isn't focused
+                    Some(PresentationAction::Fullscreen) => toggle_fullscreen(),
+                    Some(PresentationAction::Quit) if show_chapter_sidebar() => {
+                        show_chapter_sidebar.set(false);
+                    }
+                    Some(PresentationAction::Quit) => {
+                        // Ask before ending a presentation which hasn't reached its last slide yet
+                        if running_presentation.read().peek_next_slide().is_none() {
+                            quit_presentation();
+                        } else {
+                            spawn(async move {
+                                let prompt = t!("presenter.quit_confirm").to_string();
+                                let confirmed = document::eval(&js_yes_no_box(prompt))
+                                    .await
+                                    .ok()
+                                    .and_then(|value| value.as_bool())
+                                    .unwrap_or(false);
+                                if confirmed {
+                                    quit_presentation();
+                                }
                             });
                         }
                     }
-                    Key::Escape => {
-                        quit_presentation();
-                    }
-                    Key::Character(ref c) if c == "b" || c == "B" => {
+                    Some(PresentationAction::Overview) => open_overview(),
+                    Some(PresentationAction::NextSlideStrip) => show_next_slide_strip.toggle(),
+                    Some(PresentationAction::ChapterSidebar) => show_chapter_sidebar.toggle(),
+                    Some(PresentationAction::ShortcutHelp) => show_shortcut_help.set(true),
+                    // Only reached if the renderer isn't focused, see PresentationRendererComponent
+                    Some(PresentationAction::BlackScreen) => {
                         running_presentation.write().toggle_black_screen();
                     }
+                    Some(PresentationAction::WhiteScreen) => {
+                        running_presentation.write().toggle_white_screen();
+                    }
+                    Some(PresentationAction::LogoScreen) => {
+                        running_presentation.write().toggle_logo_screen();
+                    }
                     _ => {}
                 }
             },
-            PresentationRendererComponent {
-                running_presentation: running_presentation
+            div {
+                style: letterbox(),
+                PresentationRendererComponent {
+                    running_presentation: running_presentation,
+                    onlongpress: move |_| open_overview(),
+                    autofocus: true,
+                    wheel_navigation: true,
+                }
+            }
+
+            if show_shortcut_help() {
+                ShortcutHelpOverlay {
+                    on_close: move |_| show_shortcut_help.set(false),
+                }
+            }
+
+            if show_chapter_sidebar() {
+                ChapterSidebar {
+                    running_presentation: running_presentation,
+                    on_close: move |_| show_chapter_sidebar.set(false),
+                }
+            }
+
+            if show_next_slide_strip() {
+                NextSlideStrip {
+                    running_presentation: running_presentation
+                }
+            }
+
+            if let Some(return_position) = overview_return_position() {
+                PresentationOverview {
+                    running_presentation: running_presentation,
+                    return_position: return_position,
+                    on_close: move |_| overview_return_position.set(None),
+                }
             }
 
             // Context menu overlay
@@ -409,6 +575,93 @@ pub fn PresentationPage() -> Element {
     }
 }
 
+/// Returns the CSS which places an absolutely positioned overlay in the given corner of the slide.
+fn overlay_corner_style(corner: OverlayCorner) -> &'static str {
+    match corner {
+        OverlayCorner::TopLeft => "top: 0.5em; left: 0.75em;",
+        OverlayCorner::TopRight => "top: 0.5em; right: 0.75em;",
+        OverlayCorner::BottomLeft => "bottom: 0.5em; left: 0.75em;",
+        OverlayCorner::BottomRight => "bottom: 0.5em; right: 0.75em;",
+    }
+}
+
+/// Returns the CSS which places an absolutely positioned overlay into the padding of the given
+/// corner, so that it never overlaps the slide content.
+fn overlay_padding_style(corner: OverlayCorner, padding: &TopBottomLeftRight) -> String {
+    let vertical = match corner {
+        OverlayCorner::TopLeft | OverlayCorner::TopRight => {
+            format!("top: 0; height: {};", padding.top.to_css_string())
+        }
+        OverlayCorner::BottomLeft | OverlayCorner::BottomRight => {
+            format!("bottom: 0; height: {};", padding.bottom.to_css_string())
+        }
+    };
+    let horizontal = match corner {
+        OverlayCorner::TopLeft | OverlayCorner::BottomLeft => {
+            format!("left: {};", padding.left.to_css_string())
+        }
+        OverlayCorner::TopRight | OverlayCorner::BottomRight => {
+            format!("right: {};", padding.right.to_css_string())
+        }
+    };
+    format!("{} {}", vertical, horizontal)
+}
+
+/// Returns the CSS of the meta footer, which uses the meta font and lies within the padding of
+/// the slide. It is placed at the bottom, or at the top if the content is aligned to the bottom,
+/// so that it doesn't collide with the content. A slide layout which orders the meta block
+/// before the main content places it at the top as well.
+fn meta_footer_style(pds: &PresentationDesignTemplate) -> String {
+    let at_top = pds.vertical_alignment == VerticalAlign::Bottom
+        || pds.slide_layout.meta_above_content();
+    let vertical = match at_top {
+        true => format!("top: {};", pds.padding.top.to_css_string()),
+        false => format!("bottom: {};", pds.padding.bottom.to_css_string()),
+    };
+    format!(
+        "{} left: {}; right: {}; {}",
+        vertical,
+        pds.padding.left.to_css_string(),
+        pds.padding.right.to_css_string(),
+        CssHandler::from(pds.get_default_meta_font())
+    )
+}
+
+/// Toggles the fullscreen mode of the current window.
+/// On desktop, the window decorations are shown again when leaving fullscreen, because a window
+/// which was started in fullscreen has none (and Windows and X11 don't restore them on their own).
+fn toggle_fullscreen() {
+    #[cfg(feature = "desktop")]
+    {
+        let desktop = dioxus::desktop::window();
+        let is_fullscreen = desktop.fullscreen().is_some();
+        desktop.set_fullscreen(!is_fullscreen);
+        desktop.set_decorations(is_fullscreen);
+    }
+    #[cfg(not(feature = "desktop"))]
+    spawn(async move {
+        let _ = document::eval("
+            if (document.fullscreenElement) {
+                document.exitFullscreen();
+            } else {
+                document.documentElement.requestFullscreen();
+            }
+        ").await;
+    });
+}
+
+/// Returns the style of the area in the presentation window in which the slides are shown. The
+/// area has the given aspect ratio and is centered in the window, so that the slides are
+/// letterboxed if the screen has another aspect ratio.
+fn letterbox_style(aspect_ratio: AspectRatio) -> String {
+    let (ratio_w, ratio_h) = aspect_ratio.ratio();
+    format!(
+        "position: absolute; inset: 0; margin: auto; \
+         width: min(100vw, calc(100vh * {ratio_w} / {ratio_h})); \
+         height: min(100vh, calc(100vw * {ratio_h} / {ratio_w}));"
+    )
+}
+
 /// The actual presentation rendering component which can be used to render presentations accordingly
 /// It takes a signal and rewrites to it when the presentation position changes
 #[component]
@@ -420,20 +673,48 @@ pub fn PresentationRendererComponent(
     /// example viewer) so only the primary presentation window drives the timer.
     #[props(default = true)]
     fire_timer: bool,
+    /// Whether overlays like the slide counter are shown. Miniature previews of presentation
+    /// designs set this to `false` to keep them clean.
+    #[props(default = true)]
+    show_overlays: bool,
+    /// Called when the slide is long-pressed on a touch screen
+    onlongpress: Option<EventHandler<()>>,
+    /// Whether the slide takes the keyboard focus when it is shown, so that keys (e.g. of a
+    /// presenter remote) work without clicking into the window first
+    #[props(default = false)]
+    autofocus: bool,
+    /// Whether the mouse wheel changes the slide. Only the presentation window sets this, so that
+    /// previews (e.g. in the presenter console) can be scrolled past without changing the slide.
+    #[props(default = false)]
+    wheel_navigation: bool,
 ) -> Element {
     let current_slide: Memo<Option<Slide>> =
         use_memo(move || running_presentation.read().get_current_slide());
 
-    let current_slide_number: Memo<usize> =
-        use_memo(move || match running_presentation.read().clone().position {
-            Some(position) => position.slide_total(),
-            None => 0,
-        });
-
-    let mut presentation_is_visible = use_signal(|| false);
+    // The slide container is keyed with this number, so that it is remounted on every slide
+    // change, which restarts the CSS animation of the transition
+    let current_slide_number: Memo<usize> = use_memo(move || {
+        running_presentation
+            .read()
+            .position
+            .as_ref()
+            .map(|position| position.slide_total())
+            .unwrap_or(0)
+    });
 
     let is_black_screen =
         use_memo(move || running_presentation.read().is_black_screen);
+    let is_white_screen =
+        use_memo(move || running_presentation.read().is_white_screen);
+    // The path of the logo if the logo screen is shown
+    let logo_screen_path = use_memo(move || {
+        let rp = running_presentation.read();
+        rp.logo_image
+            .as_ref()
+            .filter(|_| rp.is_logo_screen)
+            .map(|logo| logo.as_source().path.to_str().unwrap_or_default().to_string())
+    });
+    let hides_slide = use_memo(move || running_presentation.read().hides_slide());
 
     // Derive the CSS transition class for the current chapter.
     let transition_class = use_memo(move || {
@@ -446,18 +727,47 @@ pub fn PresentationRendererComponent(
         }
     });
 
-    let mut go_to_next_slide = move || {
-        running_presentation.write().next_slide();
-        presentation_is_visible.set(false);
-        presentation_is_visible.set(true);
+    let mut go_to_next_slide = move || running_presentation.write().next_slide();
+
+    // The chapter number which is typed in for jumping to a chapter, see `go_to_typed_chapter`
+    let mut chapter_input: Signal<String> = use_signal(String::new);
+    let mut chapter_input_error: Signal<bool> = use_signal(|| false);
+
+    let mut go_to_typed_chapter = move || {
+        let input = chapter_input.peek().clone();
+        chapter_input.set(String::new());
+        // The typed number is one-based: 3 jumps to the third chapter
+        let moved = input
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| number.checked_sub(1))
+            .is_some_and(|chapter| running_presentation.write().go_to_chapter(chapter).is_ok());
+        if !moved {
+            chapter_input_error.set(true);
+            spawn(async move {
+                let _ = document::eval("await new Promise(r => setTimeout(r, 1500))").await;
+                chapter_input_error.set(false);
+            });
+        }
     };
 
-    let mut go_to_previous_slide = move || {
-        running_presentation.write().previous_slide();
-        presentation_is_visible.set(false);
-        presentation_is_visible.set(true);
+    let mut go_to_previous_slide = move || running_presentation.write().previous_slide();
+
+    // Touch gestures: the start of the current touch and whether it has been handled as swipe or
+    // long press, so that the click which follows the touch doesn't advance the slide as well.
+    // Mouse and pen input keep the click and context menu behavior.
+    let mut touch_start: Signal<Option<(f64, f64)>> = use_signal(|| None);
+    let mut touch_handled = use_signal(|| false);
+    let mut long_press_timer: Signal<Option<Task>> = use_signal(|| None);
+    let mut cancel_long_press = move || {
+        if let Some(task) = long_press_timer.take() {
+            task.cancel();
+        }
     };
 
+    // A mouse wheel detent fires several wheel events, so further events are ignored for a short time
+    let mut wheel_locked = use_signal(|| false);
+
     // Auto-advance timer: each time the slide changes, a new `spawn`-ed task
     // is launched via `use_effect`. A generation counter ensures that only the
     // most-recent timer fires – if the user (or a previous timer) navigated to
@@ -473,6 +783,8 @@ pub fn PresentationRendererComponent(
     use_effect(move || {
         // Track slide changes by reading current_slide_number (subscribes to it)
         let _ = current_slide_number();
+        // A black, white or logo screen pauses the timer, ending it starts the timer again
+        let paused = hides_slide();
 
         // Only the primary presentation window should fire the timer.
         if !fire_timer {
@@ -486,11 +798,13 @@ pub fn PresentationRendererComponent(
             *g
         };
 
-        let timer_opt = running_presentation.read().get_current_timer_settings();
-        if let Some(timer) = timer_opt {
-            let after_last = timer.after_last_slide;
-            let seconds = if timer.timer_seconds == 0 { 1 } else { timer.timer_seconds } as u64;
-            let ms = seconds * 1000;
+        if paused {
+            return;
+        }
+
+        let seconds_opt = running_presentation.read().get_auto_advance_seconds();
+        if let Some(seconds) = seconds_opt {
+            let ms = seconds as u64 * 1000;
 
             spawn(async move {
                 // Sleep via JS setTimeout – works on both desktop (WebView) and web.
@@ -504,24 +818,235 @@ pub fn PresentationRendererComponent(
                 if *timer_generation.peek() != generation_id {
                     return;
                 }
+                // Don't leave a deliberate black, white or logo screen automatically
+                if running_presentation.peek().hides_slide() {
+                    return;
+                }
 
-                let is_last = running_presentation.peek().is_last_slide_in_chapter();
-                match (is_last, after_last) {
-                    (true, AfterLastSlide::RestartCurrentChapter) => {
-                        running_presentation.write().restart_current_chapter();
-                    }
-                    _ => {
-                        running_presentation.write().next_slide();
-                    }
+                running_presentation.write().auto_advance();
+            });
+        }
+    });
+
+    let current_design = use_memo(move || {
+        running_presentation
+            .read()
+            .get_current_presentation_design()
+    });
+
+    // The current presentation design settings, which differ on title slides if the design
+    // has overrides for them
+    let current_pds = use_memo(move || {
+        let template = match current_design.read().presentation_design_settings.clone() {
+            PresentationDesignSettings::Template(template) => template,
+            _ => PresentationDesignTemplate::default(),
+        };
+        match *current_slide.read() {
+            Some(ref slide) => design_for_slide(template, &slide.slide_content),
+            None => template,
+        }
+    });
+
+    // The stylesheet of a custom design, which is restricted to this presentation
+    let design_scope = use_hook(next_design_scope);
+    let allow_external_css_resources =
+        use_memo(move || running_presentation.read().allow_external_css_resources);
+    let custom_css: Memo<Option<String>> = use_memo(move || {
+        match current_design.read().presentation_design_settings {
+            PresentationDesignSettings::Custom(ref css) => Some(custom_design_css(
+                css,
+                design_scope,
+                allow_external_css_resources(),
+            )),
+            _ => None,
+        }
+    });
+
+    let total_slides = use_memo(move || running_presentation.read().total_slides());
+    // The CSS of the slide counter, which uses the meta font
+    let slide_counter_css = use_memo(move || {
+        let pds = current_pds();
+        let css = CssHandler::from(pds.get_default_meta_font());
+        format!("{} {}", overlay_corner_style(pds.slide_counter_corner), css)
+    });
+    // The CSS of the clock, which is placed in the padding so that it does not overlap the content
+    let clock_css = use_memo(move || {
+        let pds = current_pds();
+        let css = CssHandler::from(pds.get_default_meta_font());
+        format!("{} {}", overlay_padding_style(pds.clock_corner, &pds.padding), css)
+    });
+    let clock_time = use_clock_time(show_overlays);
+    // The meta information of the current song, if the design shows it on the current slide
+    // The song number is shown on the first slide of a song even if the design has no meta footer
+    let meta_footer_text: Memo<Option<String>> = use_memo(move || {
+        let pds = current_pds();
+        let running_presentation = running_presentation.read();
+        let meta_text = running_presentation
+            .current_meta_footer_text(pds.meta_footer_slides)
+            .filter(|_| pds.show_meta_footer);
+        let texts: Vec<String> = meta_text
+            .into_iter()
+            .chain(running_presentation.current_song_number())
+            .collect();
+        (!texts.is_empty()).then(|| texts.join("\n"))
+    });
+    let meta_footer_css = use_memo(move || meta_footer_style(&current_pds.read()));
+    let progress_bar_color = use_memo(move || {
+        let color = current_pds().get_default_meta_font().color;
+        format!("rgba({}, {}, {}, {})", color.r, color.g, color.b, color.a as f32 / 255.0)
+    });
+
+    // The CSS of the presentation only depends on the design, so it is built once per design
+    // and not on every slide change
+    let presentation_css: Memo<String> = use_memo(move || {
+        let pds = current_pds.read();
+        let main_font = pds.fonts.first().cloned().unwrap_or_default();
+        let mut css = CssHandler::new();
+
+        css.background_color(pds.get_background_color_with_alpha());
+        // The gradient lies below the background picture, which is a separate layer
+        if let Some(ref gradient) = pds.background_gradient {
+            css.background(gradient);
+        }
+        css.padding_left(pds.padding.left.clone());
+        css.padding_right(pds.padding.right.clone());
+        css.padding_top(pds.padding.top.clone());
+        css.padding_bottom(pds.padding.bottom.clone());
+        css.text_align(main_font.horizontal_alignment);
+        css.set_important(true);
+        css.color(main_font.color);
+        css.place_items(match pds.vertical_alignment {
+            VerticalAlign::Top => PlaceItems::StartStretch,
+            VerticalAlign::Middle => PlaceItems::CenterStretch,
+            VerticalAlign::Bottom => PlaceItems::EndStretch,
+        });
+
+        css.to_string()
+    });
+
+    // The background of the current position, which changes if the design rotates its pictures
+    let current_background: Memo<Option<BackgroundMedia>> = use_memo(move || {
+        let (chapter, slide_total) = running_presentation
+            .read()
+            .position
+            .as_ref()
+            .map(|position| (position.chapter(), position.slide_total()))
+            .unwrap_or_default();
+        current_pds.read().background_at(chapter, slide_total)
+    });
+    let background_css: Memo<String> = use_memo(move || {
+        background_style(&current_pds.read(), current_background.read().as_ref())
+    });
+    // The watermark of the design as (path of the picture, style)
+    let watermark: Memo<Option<(String, String)>> = use_memo(move || {
+        let pds = current_pds.read();
+        pds.watermark
+            .as_ref()
+            .map(|watermark| watermark_image(watermark, &pds.padding))
+    });
+
+    // The path of the background video, which is shown inside of the background layer
+    let background_video_path: Memo<Option<String>> = use_memo(move || {
+        match *current_background.read() {
+            Some(BackgroundMedia::Video(ref video)) => {
+                Some(video.as_source().path.to_str().unwrap_or_default().to_string())
+            }
+            _ => None,
+        }
+    });
+    // The background video is paused while it can't be seen or while a still slide is shown
+    let pause_background_video = use_memo(move || {
+        current_pds.read().pause_background_video_on_still_slides
+            && (hides_slide()
+                || current_slide
+                    .read()
+                    .as_ref()
+                    .is_some_and(|slide| is_still_slide(&slide.slide_content)))
+    });
+    let background_video_id = use_hook(|| {
+        format!(
+            "background-video-{}",
+            BACKGROUND_VIDEO_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+        )
+    });
+    let sync_background_video = {
+        let background_video_id = background_video_id.clone();
+        move || {
+            let script = format!(
+                "const video = document.getElementById('{}');
+                if (video) {{
+                    video.muted = true;
+                    if ({}) {{ video.pause(); }} else {{ video.play().catch(() => {{}}); }}
+                }}",
+                background_video_id,
+                pause_background_video()
+            );
+            document::eval(&script);
+        }
+    };
+    use_effect({
+        let sync_background_video = sync_background_video.clone();
+        move || sync_background_video()
+    });
+
+    // The font scale of auto fit together with the slide it has been found for, so that every
+    // slide starts again with the full font size
+    let mut auto_fit_scale: Signal<(usize, f64)> = use_signal(|| (0, 1.0));
+    let font_scale = use_memo(move || {
+        let (slide_number, scale) = auto_fit_scale();
+        if current_pds.read().auto_fit && slide_number == current_slide_number() {
+            scale
+        } else {
+            1.0
+        }
+    });
+    let slide_container_id = use_hook(|| {
+        format!(
+            "slide-container-{}",
+            SLIDE_CONTAINER_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+        )
+    });
+    // After the slide has been rendered, it is measured and the font is reduced by one step
+    // as long as the content overflows the area within the padding
+    use_effect({
+        let slide_container_id = slide_container_id.clone();
+        move || {
+            let scale = font_scale();
+            let slide_number = current_slide_number();
+            let Some(next_scale) = current_pds
+                .read()
+                .next_auto_fit_scale(scale)
+                .filter(|_| current_pds.read().auto_fit)
+            else {
+                return;
+            };
+            let script = format!(
+                "await new Promise(r => requestAnimationFrame(r));
+                const container = document.getElementById('{}');
+                if (!container || !container.parentElement) {{ return false; }}
+                const presentation = container.parentElement;
+                const style = getComputedStyle(presentation);
+                const available = presentation.clientHeight
+                    - parseFloat(style.paddingTop) - parseFloat(style.paddingBottom);
+                return container.scrollHeight > available + 1;",
+                slide_container_id
+            );
+            spawn(async move {
+                let overflows = document::eval(&script)
+                    .await
+                    .ok()
+                    .and_then(|value| value.as_bool())
+                    .unwrap_or(false);
+                // The slide might have changed during the measurement
+                if overflows && *current_slide_number.peek() == slide_number {
+                    auto_fit_scale.set((slide_number, next_scale));
                 }
-                presentation_is_visible.set(false);
-                presentation_is_visible.set(true);
             });
         }
     });
 
     // Stop rendering if no slide can be rendered.
-    if current_slide.read().clone().is_none() {
+    if current_slide.read().is_none() {
         return rsx! {
             div {
                 style: "
@@ -538,148 +1063,726 @@ pub fn PresentationRendererComponent(
         };
     }
 
-    let current_design = use_memo(move || {
-        running_presentation
-            .read()
-            .get_current_presentation_design()
+    rsx! {
+        document::Link { rel: "stylesheet", href: PRESENTATION_CSS }
+        document::Script { src: PRESENTATION_JS }
+        div {
+            class: "presentation",
+            style: presentation_css,
+            "data-design-scope": "{design_scope}",
+
+            tabindex: 0,
+            onkeydown: move |event: Event<KeyboardData>| {
+                // While a chapter number is typed, Enter, Escape and Backspace belong to the input
+                if !chapter_input.read().is_empty() {
+                    match event.key() {
+                        Key::Enter => return go_to_typed_chapter(),
+                        Key::Escape => {
+                            // Only cancel the input and don't end the presentation
+                            event.stop_propagation();
+                            chapter_input.set(String::new());
+                            return;
+                        }
+                        Key::Backspace => {
+                            chapter_input.write().pop();
+                            return;
+                        }
+                        _ => {}
+                    }
+                }
+                // The other actions (e.g. the overview) are handled by the surrounding view
+                match presentation_action(&event) {
+                    Some(PresentationAction::GoToChapterNumber) => {
+                        if let Key::Character(digit) = event.key() {
+                            chapter_input.write().push_str(&digit);
+                        }
+                    }
+                    Some(PresentationAction::NextSlide) => go_to_next_slide(),
+                    Some(PresentationAction::PreviousSlide) => go_to_previous_slide(),
+                    Some(PresentationAction::FirstSlide) => running_presentation.write().first_slide(),
+                    Some(PresentationAction::LastSlide) => running_presentation.write().last_slide(),
+                    Some(PresentationAction::RestartChapter) => {
+                        running_presentation.write().restart_current_chapter();
+                    }
+                    // Start the current song again, e.g. when a chorus is repeated spontaneously
+                    Some(PresentationAction::ChapterStart) => {
+                        running_presentation.write().go_to_chapter_start();
+                    }
+                    Some(PresentationAction::ChapterTitle) => {
+                        let _ = running_presentation.write().go_to_chapter_title();
+                    }
+                    Some(PresentationAction::Fullscreen) => {
+                        event.stop_propagation();
+                        toggle_fullscreen();
+                    }
+                    // Stop the screen toggles here, so that a surrounding view doesn't toggle them back
+                    Some(PresentationAction::BlackScreen) => {
+                        event.stop_propagation();
+                        running_presentation.write().toggle_black_screen();
+                    }
+                    Some(PresentationAction::WhiteScreen) => {
+                        event.stop_propagation();
+                        running_presentation.write().toggle_white_screen();
+                    }
+                    Some(PresentationAction::LogoScreen) => {
+                        event.stop_propagation();
+                        running_presentation.write().toggle_logo_screen();
+                    }
+                    _ => {}
+                }
+            },
+            onpointerdown: move |event: Event<PointerData>| {
+                if event.pointer_type() != "touch" {
+                    return;
+                }
+                let point = event.client_coordinates();
+                touch_start.set(Some((point.x, point.y)));
+                touch_handled.set(false);
+                cancel_long_press();
+                let js_sleep = format!("await new Promise(r => setTimeout(r, {LONG_PRESS_MS}))");
+                long_press_timer.set(Some(spawn(async move {
+                    let _ = document::eval(&js_sleep).await;
+                    long_press_timer.set(None);
+                    if touch_start.take().is_some() {
+                        touch_handled.set(true);
+                        if let Some(onlongpress) = onlongpress {
+                            onlongpress.call(());
+                        }
+                    }
+                })));
+            },
+            onpointermove: move |event: Event<PointerData>| {
+                let Some((start_x, start_y)) = touch_start() else {
+                    return;
+                };
+                // A moving finger is no long press anymore
+                let point = event.client_coordinates();
+                if (point.x - start_x).hypot(point.y - start_y) > TOUCH_MOVE_TOLERANCE_PX {
+                    cancel_long_press();
+                }
+            },
+            onpointerup: move |event: Event<PointerData>| {
+                cancel_long_press();
+                let Some((start_x, start_y)) = touch_start.take() else {
+                    return;
+                };
+                let point = event.client_coordinates();
+                let (distance_x, distance_y) = (point.x - start_x, point.y - start_y);
+                if distance_x.abs() > SWIPE_THRESHOLD_PX && distance_x.abs() > distance_y.abs() {
+                    touch_handled.set(true);
+                    // Swiping to the left brings the next slide in, like turning a page
+                    if distance_x < 0.0 {
+                        go_to_next_slide();
+                    } else {
+                        go_to_previous_slide();
+                    }
+                }
+            },
+            onpointercancel: move |_| {
+                cancel_long_press();
+                touch_start.set(None);
+            },
+            onclick: move |_| {
+                if touch_handled() {
+                    touch_handled.set(false);
+                    return;
+                }
+                go_to_next_slide();
+            },
+            onwheel: move |event: Event<WheelData>| {
+                if !wheel_navigation {
+                    return;
+                }
+                let delta_y = event.delta().strip_units().y;
+                if delta_y == 0.0 || wheel_locked() {
+                    return;
+                }
+                wheel_locked.set(true);
+                if (delta_y > 0.0) != running_presentation.peek().invert_wheel {
+                    go_to_next_slide();
+                } else {
+                    go_to_previous_slide();
+                }
+                spawn(async move {
+                    let js_sleep = format!("await new Promise(r => setTimeout(r, {WHEEL_DEBOUNCE_MS}))");
+                    let _ = document::eval(&js_sleep).await;
+                    wheel_locked.set(false);
+                });
+            },
+            oncontextmenu: move |event: Event<MouseData>| {
+                // Touch screens fire the context menu on a long press, which is handled above
+                if touch_handled() || touch_start().is_some() {
+                    event.prevent_default();
+                    event.stop_propagation();
+                    return;
+                }
+                go_to_previous_slide();
+            },
+            onmounted: move |event| async move {
+                if autofocus {
+                    let _ = event.set_focus(true).await;
+                }
+            },
+            if let Some(css) = custom_css() {
+                style { "{css}" }
+            }
+            // Black screen overlay
+            if is_black_screen() {
+                div {
+                    style: "position: absolute; top: 0; left: 0; width: 100%; height: 100%; background-color: black; z-index: 1000;",
+                }
+            }
+            // White screen overlay
+            if is_white_screen() {
+                div {
+                    style: "position: absolute; top: 0; left: 0; width: 100%; height: 100%; background-color: white; z-index: 1000;",
+                }
+            }
+            // Indicator of the typed chapter number
+            if !chapter_input.read().is_empty() {
+                div {
+                    class: "chapter-input-indicator",
+                    "→ {chapter_input}"
+                }
+            } else if chapter_input_error() {
+                div {
+                    class: "chapter-input-indicator error",
+                    "✕"
+                }
+            }
+            // Logo overlay
+            if let Some(logo_path) = logo_screen_path() {
+                div {
+                    class: "presentation-fade-in",
+                    style: "position: absolute; top: 0; left: 0; width: 100%; height: 100%; background: black url('{logo_path}') center / contain no-repeat; z-index: 1000;",
+                }
+            }
+            div {
+                class: "background",
+                style: background_css(),
+                if let Some(video_path) = background_video_path() {
+                    video {
+                        id: "{background_video_id}",
+                        class: "background-video",
+                        style: background_video_style(&current_pds.read()),
+                        src: "{video_path}",
+                        autoplay: true,
+                        muted: true,
+                        r#loop: true,
+                        playsinline: true,
+                        onmounted: move |_| sync_background_video(),
+                    }
+                }
+            }
+            if let Some((watermark_path, watermark_css)) = watermark() {
+                img {
+                    class: "watermark",
+                    src: "{watermark_path}",
+                    style: "{watermark_css}",
+                }
+            }
+            // Clock
+            if show_overlays && !hides_slide() && current_pds.read().show_clock {
+                if let Some((hours, minutes)) = clock_time() {
+                    div {
+                        class: "slide-clock",
+                        style: "{clock_css}",
+                        { current_pds.read().clock_format.format(hours, minutes) }
+                    }
+                }
+            }
+            // Slide counter and progress bar
+            if show_overlays && !hides_slide() {
+                if current_pds.read().slide_counter.shows_number() {
+                    div {
+                        class: "slide-counter",
+                        style: "{slide_counter_css}",
+                        "{current_slide_number() + 1} / {total_slides}"
+                    }
+                }
+                if current_pds.read().slide_counter.shows_progress_bar() {
+                    div {
+                        class: "slide-progress-bar",
+                        style: format!(
+                            "width: {}%; background-color: {};",
+                            (current_slide_number() + 1) as f64 * 100.0 / total_slides().max(1) as f64,
+                            progress_bar_color()
+                        ),
+                    }
+                }
+            }
+            // The slide is remounted after a black, white or logo screen, so that it fades in again
+            if !hides_slide() {
+                if let Some(slide) = current_slide() {
+                    div {
+                        id: "{slide_container_id}",
+                        class: "slide-container {transition_class}",
+                        style: slide_container_style(&slide.slide_content, &current_pds.read()),
+                        key: "{current_slide_number}",
+                        SlideContentRenderer {
+                            slide_content: slide.slide_content,
+                            pds: current_pds(),
+                            running_presentation: Some(running_presentation),
+                            font_scale: font_scale(),
+                        }
+                    }
+                }
+                if let Some(meta_text) = meta_footer_text() {
+                    div {
+                        class: "meta-footer",
+                        style: "{meta_footer_css}",
+                        { meta_text }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The stage display for musicians and speakers, which runs in its own window next to the
+/// presentation. It shows the current slide in large text, the first line of the next slide
+/// and a clock in a fixed high-contrast layout, independent of the presentation design.
+/// It only reads the running presentation, so it can be closed without ending the presentation.
+#[component]
+pub fn StageDisplayPage() -> Element {
+    let running_presentations: Signal<Vec<RunningPresentation>> = use_context();
+    let presentation_id_context = try_use_context::<RunningPresentationId>();
+    let find_shown_presentation = move || match presentation_id_context {
+        Some(RunningPresentationId(id)) => find_presentation(&running_presentations.peek(), id).cloned(),
+        None => running_presentations.peek().last().cloned(),
+    };
+    let mut running_presentation: Signal<Option<RunningPresentation>> =
+        use_signal(find_shown_presentation);
+
+    // On desktop each window has its own VirtualDom, so the shared signal is polled like in the
+    // presentation window. The stage display never writes back to it.
+    #[cfg(feature = "desktop")]
+    use_future(move || async move {
+        loop {
+            let _ = document::eval("await new Promise(r => setTimeout(r, 50))").await;
+            // Presentation ended → close the stage display as well
+            let Some(shared_rp) = find_shown_presentation() else {
+                dioxus::desktop::window().close();
+                return;
+            };
+            let changed = running_presentation
+                .peek()
+                .as_ref()
+                .is_none_or(|local| !local.eq_ignoring_scroll(&shared_rp));
+            if changed {
+                running_presentation.set(Some(shared_rp));
+            }
+        }
+    });
+
+    #[cfg(not(feature = "desktop"))]
+    use_effect(move || {
+        // Subscribe to the shared signal
+        let _ = running_presentations.read();
+        running_presentation.set(find_shown_presentation());
     });
 
-    // The current presentation design settings
-    let current_pds =
-        use_memo(
-            move || match current_design.read().presentation_design_settings.clone() {
-                PresentationDesignSettings::Template(template) => template,
-                _ => PresentationDesignTemplate::default(),
+    let clock_time = use_clock_time(true);
+
+    let stage_content = use_memo(move || {
+        let rp = running_presentation.read();
+        let rp = rp.as_ref()?;
+        let position = rp.position.as_ref()?;
+        let chapter_name = rp
+            .presentation
+            .get(position.chapter())
+            .map(|chapter| chapter.source_file.name.clone())
+            .unwrap_or_default();
+        let text = rp
+            .get_current_slide()
+            .and_then(|slide| main_content_text(&slide))
+            .unwrap_or_default();
+        let next_line = rp
+            .peek_next_slide()
+            .and_then(|(_, slide)| first_main_content_line(&slide));
+        let counter = format!("{} / {}", position.slide_total() + 1, rp.total_slides());
+        let clock_format = match rp.get_current_presentation_design().presentation_design_settings {
+            PresentationDesignSettings::Template(template) => template.clock_format,
+            _ => ClockFormat::default(),
+        };
+        Some((chapter_name, text, next_line, counter, clock_format, rp.hides_slide()))
+    });
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: PRESENTATION_CSS }
+        document::Title { { t!("stage_display.title").to_string() } }
+        div {
+            class: "stage-display",
+            tabindex: 0,
+            onmounted: move |event| async move {
+                let _ = event.set_focus(true).await;
             },
-        );
+            onkeydown: move |event: Event<KeyboardData>| {
+                if event.key() == Key::Escape {
+                    #[cfg(feature = "desktop")]
+                    dioxus::desktop::window().close();
+                }
+            },
+            match stage_content() {
+                Some((chapter_name, text, next_line, counter, clock_format, hides_slide)) => rsx! {
+                    div {
+                        class: "stage-display-header",
+                        span { "{chapter_name}" }
+                        span { "{counter}" }
+                        if let Some((hours, minutes)) = clock_time() {
+                            span {
+                                class: "stage-display-clock",
+                                { clock_format.format(hours, minutes) }
+                            }
+                        }
+                    }
+                    div {
+                        class: if hides_slide { "stage-display-text hidden-slide" } else { "stage-display-text" },
+                        for line in text.lines() {
+                            p { "{line}" }
+                        }
+                    }
+                    div {
+                        class: "stage-display-next",
+                        match next_line {
+                            Some(line) => rsx! { "→ {line}" },
+                            None => rsx! { { t!("presenter.no_next_slide").to_string() } },
+                        }
+                    }
+                },
+                None => rsx! {
+                    p { { t!("stage_display.no_presentation").to_string() } }
+                },
+            }
+        }
+    }
+}
 
-    let css_presentation_background_color = use_memo(move || current_pds().background_color);
+/// The presentation design of a mirror window, see [MirrorPage].
+/// `None` shows the slides with the design of the presentation.
+#[derive(Clone, PartialEq)]
+pub struct MirrorDesign(pub Option<PresentationDesign>);
 
-    let css_main_content_font_size = use_memo(move || {
-        current_pds
-            .read()
-            .fonts
-            .first()
-            .unwrap_or(&FontRepresentation::default())
-            .font_size
-            .clone()
+/// A mirror of a running presentation which runs in its own window, e.g. for capturing the
+/// slides into a livestream. It ignores all input, so that nobody changes the slides by accident
+/// in it, and it never writes back to the running presentation. The slides can be shown with
+/// another presentation design, which is given as [MirrorDesign] context.
+#[component]
+pub fn MirrorPage() -> Element {
+    let running_presentations: Signal<Vec<RunningPresentation>> = use_context();
+    let presentation_id_context = try_use_context::<RunningPresentationId>();
+    let mirror_design = try_use_context::<MirrorDesign>().and_then(|design| design.0);
+    let find_shown_presentation = move || match presentation_id_context {
+        Some(RunningPresentationId(id)) => find_presentation(&running_presentations.peek(), id).cloned(),
+        None => running_presentations.peek().last().cloned(),
+    };
+    let mut running_presentation: Signal<RunningPresentation> = use_signal({
+        let mirror_design = mirror_design.clone();
+        move || {
+            let rp = find_shown_presentation().unwrap_or_else(|| RunningPresentation::new(Vec::new()));
+            mirrored_presentation(rp, &mirror_design)
+        }
     });
 
-    let css_main_text_color: Memo<RGBA8> =
-        use_memo(move || current_pds.read().clone().fonts.first().unwrap().color);
-    let css_padding_left: Memo<CssSize> = use_memo(move || current_pds().padding.left);
-    let css_padding_right: Memo<CssSize> = use_memo(move || current_pds().padding.right);
-    let css_padding_top: Memo<CssSize> = use_memo(move || current_pds().padding.top);
-    let css_padding_bottom: Memo<CssSize> = use_memo(move || current_pds().padding.bottom);
-    let css_text_align: Memo<HorizontalAlign> = use_memo(move || {
-        current_pds
-            .read()
-            .fonts
-            .first()
-            .unwrap()
-            .horizontal_alignment
+    // On desktop each window has its own VirtualDom, so the shared signal is polled like in the
+    // presentation window. The shared presentation is compared instead of the local one,
+    // because the local one differs by the mirror design.
+    #[cfg(feature = "desktop")]
+    use_future(move || {
+        let mirror_design = mirror_design.clone();
+        async move {
+            let mut last_seen_shared = find_shown_presentation();
+            loop {
+                let _ = document::eval("await new Promise(r => setTimeout(r, 50))").await;
+                // Presentation ended → close the mirror as well
+                let Some(shared_rp) = find_shown_presentation() else {
+                    dioxus::desktop::window().close();
+                    return;
+                };
+                let changed = last_seen_shared
+                    .as_ref()
+                    .is_none_or(|last_seen| !last_seen.eq_ignoring_scroll(&shared_rp));
+                if changed {
+                    running_presentation.set(mirrored_presentation(shared_rp.clone(), &mirror_design));
+                    last_seen_shared = Some(shared_rp);
+                }
+            }
+        }
     });
-    let css_place_items: Memo<PlaceItems> =
-        use_memo(move || match current_pds.read().vertical_alignment {
-            VerticalAlign::Top => PlaceItems::StartStretch,
-            VerticalAlign::Middle => PlaceItems::CenterStretch,
-            VerticalAlign::Bottom => PlaceItems::EndStretch,
-        });
 
-    // The CSS handler ([CssHandler]) takes all CSS arguments and builds the string from it.
-    // We build it in a memo for the sake of consistency.
-    let css_handler: Memo<CssHandler> = use_memo(move || {
-        let mut css = CssHandler::new();
+    #[cfg(not(feature = "desktop"))]
+    use_effect(move || {
+        // Subscribe to the shared signal
+        let _ = running_presentations.read();
+        if let Some(rp) = find_shown_presentation() {
+            running_presentation.set(mirrored_presentation(rp, &mirror_design));
+        }
+    });
 
-        css.background_color(current_pds().background_color);
-        css.padding_left(current_pds().padding.left);
-        css.padding_right(current_pds().padding.right);
-        css.padding_top(current_pds().padding.top);
-        css.padding_bottom(current_pds().padding.bottom);
-        css.text_align(css_text_align());
-        css.set_important(true);
-        css.color(
-            current_pds
-                .read()
-                .clone()
-                .fonts
-                .first()
-                .unwrap_or(&FontRepresentation::default())
-                .color,
-        );
-        css.place_items(css_place_items());
+    rsx! {
+        document::Link { rel: "stylesheet", href: PRESENTATION_CSS }
+        document::Title { { t!("mirror.title").to_string() } }
+        div {
+            class: "presentation-mirror",
+            // Keeps the slide from taking the focus, so that keys don't reach it either
+            "inert": "true",
+            PresentationRendererComponent {
+                running_presentation: running_presentation,
+                fire_timer: false,
+            }
+        }
+    }
+}
 
-        css
+/// Applies the design of a mirror window to all chapters of the presentation.
+fn mirrored_presentation(
+    mut running_presentation: RunningPresentation,
+    mirror_design: &Option<PresentationDesign>,
+) -> RunningPresentation {
+    if let Some(design) = mirror_design {
+        for chapter in running_presentation.presentation.iter_mut() {
+            chapter.presentation_design_option = Some(design.clone());
+        }
+    }
+    running_presentation
+}
+
+/// Provides the time of the day as hours and minutes, refreshed every 30 seconds.
+/// The time is taken from JavaScript, because it knows the local time zone on all platforms.
+/// If `enabled` is false, the time is never read and stays `None`.
+fn use_clock_time(enabled: bool) -> Signal<Option<(u32, u32)>> {
+    let mut clock_time: Signal<Option<(u32, u32)>> = use_signal(|| None);
+    use_future(move || async move {
+        if !enabled {
+            return;
+        }
+        loop {
+            let time = document::eval(
+                "const now = new Date(); return [now.getHours(), now.getMinutes()];",
+            )
+            .await
+            .ok()
+            .and_then(|value| serde_json::from_value::<(u32, u32)>(value).ok());
+            clock_time.set(time);
+            let _ = document::eval("await new Promise(r => setTimeout(r, 30000))").await;
+        }
     });
+    clock_time
+}
 
-    let background_css: Memo<String> = use_memo(move || {
-        let mut css: CssHandler = CssHandler::new();
-        let pds = current_pds();
+/// Returns the action of the presentation window for a pressed key, see
+/// [PRESENTATION_SHORTCUTS](crate::logic::shortcuts::PRESENTATION_SHORTCUTS).
+fn presentation_action(event: &KeyboardData) -> Option<PresentationAction> {
+    let key = match event.key() {
+        Key::Character(character) => ShortcutKey::from_character(&character)?,
+        Key::Enter => ShortcutKey::Enter,
+        Key::Backspace => ShortcutKey::Backspace,
+        Key::Escape => ShortcutKey::Escape,
+        Key::ArrowLeft => ShortcutKey::ArrowLeft,
+        Key::ArrowRight => ShortcutKey::ArrowRight,
+        Key::PageUp => ShortcutKey::PageUp,
+        Key::PageDown => ShortcutKey::PageDown,
+        Key::Home => ShortcutKey::Home,
+        Key::End => ShortcutKey::End,
+        Key::F1 => ShortcutKey::F1,
+        Key::F5 => ShortcutKey::F5,
+        Key::F11 => ShortcutKey::F11,
+        _ => return None,
+    };
+    find_action(key, event.modifiers().ctrl())
+}
+
+/// An overlay which lists the keyboard shortcuts of the presentation window, which is opened
+/// with ? or F1. It is generated from the shortcut registry and closes on any key or click.
+#[component]
+fn ShortcutHelpOverlay(on_close: EventHandler<()>) -> Element {
+    rsx! {
+        div {
+            class: "shortcut-help",
+            tabindex: -1,
+            onmounted: move |event| async move {
+                let _ = event.set_focus(true).await;
+            },
+            onkeydown: move |event: Event<KeyboardData>| {
+                // The key only closes the overlay and doesn't reach the slides
+                event.stop_propagation();
+                event.prevent_default();
+                on_close.call(());
+            },
+            onclick: move |event| {
+                event.stop_propagation();
+                on_close.call(());
+            },
+            h2 { { t!("shortcuts.title").to_string() } }
+            table {
+                for (action, shortcuts) in shortcuts_by_action() {
+                    tr {
+                        td {
+                            class: "shortcut-help-keys",
+                            for shortcut in shortcuts {
+                                kbd { { shortcut.label() } }
+                            }
+                        }
+                        td { { t!(action.description_key()).to_string() } }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A narrow sidebar with all chapters of the presentation, which is toggled with C.
+/// Clicking a chapter jumps to its first slide. The sidebar hides itself after a few seconds
+/// without mouse movement over it.
+#[component]
+fn ChapterSidebar(
+    running_presentation: Signal<RunningPresentation>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let mut hide_timer: Signal<Option<Task>> = use_signal(|| None);
+    let mut restart_hide_timer = move || {
+        if let Some(task) = hide_timer.take() {
+            task.cancel();
+        }
+        let js_sleep =
+            format!("await new Promise(r => setTimeout(r, {CHAPTER_SIDEBAR_HIDE_MS}))");
+        hide_timer.set(Some(spawn(async move {
+            let _ = document::eval(&js_sleep).await;
+            on_close.call(());
+        })));
+    };
+    use_hook(move || restart_hide_timer());
 
-        if let Some(image) = pds.background_image {
-            css.background_image(image.as_source().path.to_str().unwrap_or_default());
-            css.background_size("cover");
-            css.background_position("center");
-            css.background_repeat("no-repeat");
-            css.opacity(1.0 - pds.background_transparency as f32 / 100.0f32);
-        } else {
-            css.background_image_none();
-            css.opacity(0.0);
+    let rp = running_presentation.read();
+    let current_chapter = rp.position.as_ref().map(|p| p.chapter());
+
+    rsx! {
+        nav {
+            class: "chapter-sidebar",
+            onmousemove: move |_| restart_hide_timer(),
+            // Don't advance the slide or close the context menu of the page
+            onclick: move |event| event.stop_propagation(),
+            ol {
+                for (index, chapter) in rp.presentation.iter().enumerate() {
+                    li {
+                        key: "{index}",
+                        class: if current_chapter == Some(index) { "active" },
+                        onclick: move |_| {
+                            if running_presentation.write().go_to_chapter(index).is_ok() {
+                                on_close.call(());
+                            }
+                        },
+                        { chapter.source_file.name.clone() }
+                    }
+                }
+            }
         }
-        css.to_string()
+    }
+}
+
+/// A footer strip for the operator which shows the first line of the upcoming slide
+/// and the chapter it belongs to. It is toggled with N in the presentation window.
+#[component]
+fn NextSlideStrip(running_presentation: Signal<RunningPresentation>) -> Element {
+    let next_slide = use_memo(move || {
+        let rp = running_presentation.read();
+        rp.peek_next_slide().map(|(chapter, slide)| {
+            let chapter_name = rp
+                .presentation
+                .get(chapter)
+                .map(|chapter| chapter.source_file.name.clone())
+                .unwrap_or_default();
+            let line = first_main_content_line(&slide)
+                .unwrap_or_else(|| t!("presenter.empty_slide").to_string());
+            (chapter_name, line)
+        })
     });
 
     rsx! {
-        document::Link { rel: "stylesheet", href: PRESENTATION_CSS }
-        document::Script { src: PRESENTATION_JS }
         div {
-            class: "presentation",
-            style: css_handler.read().to_string(),
+            class: "next-slide-strip",
+            match next_slide() {
+                Some((chapter_name, line)) => rsx! {
+                    span {
+                        class: "next-slide-strip-chapter",
+                        "{chapter_name}"
+                    }
+                    span { "→ {line}" }
+                },
+                None => rsx! {
+                    span { { t!("presenter.no_next_slide").to_string() } }
+                },
+            }
+        }
+    }
+}
 
-            tabindex: 0,
+/// A grid of all slides grouped by chapter, shown above the running presentation.
+/// Clicking a slide jumps there, Escape returns to `return_position` and closes the overview.
+#[component]
+fn PresentationOverview(
+    running_presentation: Signal<RunningPresentation>,
+    /// The chapter and slide which were shown when the overview was opened
+    return_position: (usize, usize),
+    on_close: EventHandler<()>,
+) -> Element {
+    let rp = running_presentation.read();
+    let (current_chapter, current_slide) = return_position;
+
+    // The thumbnails render at the native resolution and are zoomed down to the thumbnail width
+    let thumb_width: u32 = 240;
+    let (native_w, native_h) = rp.presentation_resolution;
+    let zoom_css = format!("zoom: {};", thumb_width as f64 / native_w as f64);
+    let thumb_height = (thumb_width as f64 * native_h as f64 / native_w as f64).round() as u32;
+
+    rsx! {
+        div {
+            class: "presentation-overview",
+            tabindex: -1,
+            onmounted: move |event| async move {
+                let _ = event.set_focus(true).await;
+            },
             onkeydown: move |event: Event<KeyboardData>| {
-                let key = event.key();
-                match key {
-                    Key::ArrowRight | Key::Enter => go_to_next_slide(),
-                    Key::Character(ref c) if c == " " => go_to_next_slide(),
-                    Key::ArrowLeft => go_to_previous_slide(),
-                    _ => {}
+                // The overview takes all keys, so that the slides don't move behind it
+                event.stop_propagation();
+                if event.key() == Key::Escape {
+                    let (chapter, slide) = return_position;
+                    running_presentation.write().jump_to(chapter, slide);
+                    on_close.call(());
                 }
             },
-            onclick: move |_| {
-                go_to_next_slide();
-            },
-            oncontextmenu: move |_| {
-                go_to_previous_slide();
-            },
-            onmounted: move |_| {
-                presentation_is_visible.set(true);
-            },
-            // Black screen overlay
-            if is_black_screen() {
-                div {
-                    style: "position: absolute; top: 0; left: 0; width: 100%; height: 100%; background-color: black; z-index: 1000;",
-                }
-            }
-            div {
-                class: "background",
-                style: background_css()
-            }
-            if presentation_is_visible() {
+            for (ch_idx, chapter) in rp.presentation.iter().enumerate() {
                 {
-                    let slide_content = current_slide.read().clone().unwrap().slide_content.clone();
-                    let container_style = slide_container_style(&slide_content);
-                    let tc = transition_class();
-
+                    let design = chapter
+                        .presentation_design_option
+                        .clone()
+                        .unwrap_or(PresentationDesign::default());
                     rsx! {
                         div {
-                            class: "slide-container {tc}",
-                            style: "{container_style}",
-                            key: "{current_slide_number}",
-                            SlideContentRenderer {
-                                slide_content: slide_content,
-                                pds: current_pds(),
-                                running_presentation: Some(running_presentation),
+                            class: "presentation-overview-chapter",
+                            h4 { { chapter.source_file.name.clone() } }
+                            div {
+                                class: "presentation-overview-slides",
+                                for (sl_idx, slide) in chapter.slides.iter().enumerate() {
+                                    div {
+                                        key: "{ch_idx}-{sl_idx}",
+                                        class: "presentation-overview-slide",
+                                        class: if ch_idx == current_chapter && sl_idx == current_slide { "active" },
+                                        style: "width: {thumb_width}px; height: {thumb_height}px;",
+                                        onclick: move |event| {
+                                            event.stop_propagation();
+                                            running_presentation.write().jump_to(ch_idx, sl_idx);
+                                            on_close.call(());
+                                        },
+                                        div {
+                                            style: "width: {native_w}px; height: {native_h}px; {zoom_css} transform-origin: top left; pointer-events: none;",
+                                            StaticSlideRendererComponent {
+                                                slide: slide.clone(),
+                                                presentation_design: design.clone()
+                                            }
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -693,6 +1796,17 @@ pub fn PresentationRendererComponent(
 fn TitleSlideComponent(
     title_slide: TitleSlide,
     title_font_representation: FontRepresentation,
+
+    /// The slide layout of the design, which may give the headline its own vertical alignment
+    #[props(default)]
+    layout: SlideLayout,
+
+    /// The vertical alignment of the title slide
+    #[props(default)]
+    vertical_alignment: VerticalAlign,
+
+    /// An optional emphasis of the first letter or word of the title
+    title_emphasis: Option<TitleEmphasis>,
 ) -> Element {
     // Build the CSS
     let css_handler: Memo<CssHandler> = use_memo(move || {
@@ -703,17 +1817,91 @@ fn TitleSlideComponent(
         css
     });
     let css_handler_string: Memo<String> = use_memo(move || css_handler.to_string());
+    let headline_has_alignment = layout.vertical_alignment(SlideBlock::Headline).is_some();
 
     rsx! {
-        div {
-            class: "headline",
-            style: css_handler_string(),
-            p {
+        if headline_has_alignment {
+            div {
+                style: slide_layout_style(&vertical_alignment),
+                div {
+                    class: "headline",
+                    style: format!("{}{}", css_handler_string(), slide_block_style(&layout, SlideBlock::Headline)),
+                    p {
+                        style: css_handler_string(),
+                        TitleText {
+                            title_text: title_slide.title_text.clone(),
+                            title_emphasis: title_emphasis.clone(),
+                        }
+                    }
+                }
+            }
+        } else {
+            div {
+                class: "headline",
                 style: css_handler_string(),
-                { title_slide.title_text }
+                p {
+                    style: css_handler_string(),
+                    TitleText {
+                        title_text: title_slide.title_text.clone(),
+                        title_emphasis: title_emphasis.clone(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The text of a title, whose first letter or word may be emphasized. Titles which consist
+/// only of the emphasized part are shown unchanged.
+#[component]
+fn TitleText(title_text: String, title_emphasis: Option<TitleEmphasis>) -> Element {
+    let split_title = title_emphasis
+        .as_ref()
+        .and_then(|emphasis| emphasis.split_title(&title_text));
+    match (split_title, title_emphasis) {
+        (Some((emphasized, rest)), Some(emphasis)) => {
+            let mut css = CssHandler::new();
+            css.font_size(CssSize::Em(emphasis.scale as f32));
+            if let Some(color) = emphasis.color {
+                css.color(color);
+            }
+            rsx! {
+                span {
+                    class: "title-emphasis",
+                    style: css.to_string(),
+                    { emphasized }
+                }
+                { rest }
             }
         }
+        _ => rsx! { { title_text } },
+    }
+}
+
+/// Returns the style of an element which contains the text blocks of a slide in a flex column,
+/// so that the blocks can be ordered and aligned by the slide layout of the design.
+fn slide_layout_style(vertical_alignment: &VerticalAlign) -> String {
+    let mut css = CssHandler::new();
+    css.flex_column(vertical_alignment);
+    css.to_string()
+}
+
+/// Returns the style which places a text block of a slide according to the slide layout.
+/// The blocks get every second position, so that the distance between the main content and the
+/// spoiler fits in between (see [slide_distance_order]).
+fn slide_block_style(layout: &SlideLayout, block: SlideBlock) -> String {
+    let mut css = CssHandler::new();
+    css.order(layout.position(block) as i32 * 2);
+    if let Some(alignment) = layout.vertical_alignment(block) {
+        css.flex_self_alignment(&alignment);
     }
+    css.to_string()
+}
+
+/// Returns the position of the distance between the main content and the spoiler in the
+/// flex column of a slide, which lies between the positions of both blocks.
+fn slide_distance_order(layout: &SlideLayout) -> i32 {
+    (layout.position(SlideBlock::MainContent) + layout.position(SlideBlock::Spoiler)) as i32
 }
 
 #[component]
@@ -729,6 +1917,17 @@ fn SingleLanguageMainContentSlideRenderer(
 
     /// The distance between the main content and the spoiler, default is `4 em`.
     distance: Option<CssSize>,
+
+    /// The maximal number of lines of the spoiler
+    spoiler_max_lines: Option<u8>,
+
+    /// The slide layout of the design, which orders and aligns the main content and the spoiler
+    #[props(default)]
+    layout: SlideLayout,
+
+    /// The vertical alignment of the slide content
+    #[props(default)]
+    vertical_alignment: VerticalAlign,
 ) -> Element {
     let number_of_main_content_lines = {
         let cloned_main_slide = main_slide.clone();
@@ -737,7 +1936,9 @@ fn SingleLanguageMainContentSlideRenderer(
         lines.len()
     };
 
-    let main_css: Memo<CssHandler> = use_memo(move || {
+    // The CSS is built on every render and not memoized, because auto fit changes the font
+    // sizes while the slide stays mounted
+    let main_css: CssHandler = {
         let mut css = CssHandler::new();
 
         css.set_important(true);
@@ -745,18 +1946,18 @@ fn SingleLanguageMainContentSlideRenderer(
         css.z_index(2);
         css.extend(&CssHandler::from(main_content_font.clone()));
         css
-    });
+    };
 
-    let distance_css: Memo<CssHandler> = use_memo(move || {
+    let distance_css: CssHandler = {
         let mut css = CssHandler::new();
 
         css.set_important(true);
         css.min_height(distance.clone().unwrap_or(CssSize::Em(4.0)));
 
         css
-    });
+    };
 
-    let spoiler_css: Memo<CssHandler> = use_memo(move || {
+    let spoiler_css: CssHandler = {
         let mut css = CssHandler::new();
 
         css.set_important(true);
@@ -764,15 +1965,38 @@ fn SingleLanguageMainContentSlideRenderer(
         css.z_index(2);
         css.extend(&CssHandler::from(spoiler_content_font.clone()));
         css
-    });
+    };
+
+    // Long spoilers are cut off after the maximal number of lines
+    let spoiler_text_css: CssHandler = {
+        let mut css = spoiler_css.clone();
+        if let Some(max_lines) = spoiler_max_lines {
+            css.line_clamp(max_lines);
+        }
+        css
+    };
+
+    // The default layout keeps the plain block flow, so that existing designs look the same
+    let (wrapper_style, main_block_style, distance_block_style, spoiler_block_style) =
+        if layout.is_customized() {
+            (
+                slide_layout_style(&vertical_alignment),
+                slide_block_style(&layout, SlideBlock::MainContent),
+                format!("order:{};", slide_distance_order(&layout)),
+                slide_block_style(&layout, SlideBlock::Spoiler),
+            )
+        } else {
+            Default::default()
+        };
 
     rsx! {
         div {
+            style: wrapper_style,
             div {
                 class: "main-content",
-                style: main_css.read().to_string(),
+                style: format!("{}{}", main_css, main_block_style),
                 p {
-                    style: main_css.read().to_string(),
+                    style: main_css.to_string(),
                     for (num, line) in main_slide.clone().main_text().split("\n").enumerate() {
                         { line }
                         if num < number_of_main_content_lines -1 {
@@ -784,13 +2008,13 @@ fn SingleLanguageMainContentSlideRenderer(
             if let Some(spoiler_content) = main_slide.spoiler_text() {
                 div {
                     class: "distance",
-                    style: distance_css.read().to_string(),
+                    style: format!("{}{}", distance_css, distance_block_style),
                 }
                 div {
                     class: "spoiler-content",
-                    style: spoiler_css.read().to_string(),
+                    style: format!("{}{}", spoiler_css, spoiler_block_style),
                     p {
-                        style: spoiler_css.read().to_string(),
+                        style: spoiler_text_css.to_string(),
                         for (num, line) in spoiler_content.split("\n").enumerate() {
                             { line }
                             if num < spoiler_content.split("\n").count() - 1 {
@@ -804,6 +2028,139 @@ fn SingleLanguageMainContentSlideRenderer(
     }
 }
 
+/// Renders a slide of a multi-language song. The first shown language is rendered in the main
+/// content font and the other shown languages beneath it in the font for secondary languages.
+/// The spoiler is shown in the first shown language only.
+#[component]
+fn MultiLanguageMainContentSlideRenderer(
+    /// The texts of the slide in all languages
+    texts: MultiLanguageTexts,
+
+    /// Which languages of the song are shown
+    shown_languages: ShownLanguages,
+
+    /// The [FontRepresentation] for the main content font.
+    main_content_font: FontRepresentation,
+
+    /// The [FontRepresentation] for the secondary languages.
+    secondary_language_font: FontRepresentation,
+
+    /// The [FontRepresentation] for the spoiler content font.
+    spoiler_content_font: FontRepresentation,
+
+    /// The distance between the main content and the spoiler, default is `4 em`.
+    distance: Option<CssSize>,
+
+    /// The maximal number of lines of the spoiler
+    spoiler_max_lines: Option<u8>,
+
+    /// The slide layout of the design, which orders and aligns the main content and the spoiler
+    #[props(default)]
+    layout: SlideLayout,
+
+    /// The vertical alignment of the slide content
+    #[props(default)]
+    vertical_alignment: VerticalAlign,
+) -> Element {
+    let shown_texts: Vec<(usize, Vec<String>)> = texts
+        .main_texts
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| shown_languages.shows(*idx))
+        .map(|(idx, text)| (idx, text.split("\n").map(str::to_string).collect()))
+        .collect();
+    let spoiler_lines: Option<Vec<String>> = shown_texts
+        .first()
+        .and_then(|(idx, _)| texts.spoiler_texts.get(*idx))
+        .filter(|spoiler| !spoiler.trim().is_empty())
+        .map(|spoiler| spoiler.split("\n").map(str::to_string).collect());
+
+    let font_css = |font: &FontRepresentation| {
+        let mut css = CssHandler::new();
+
+        css.set_important(true);
+        css.opacity(1.0);
+        css.z_index(2);
+        css.extend(&CssHandler::from(font.clone()));
+        css
+    };
+    let main_css = font_css(&main_content_font);
+    let secondary_css = font_css(&secondary_language_font);
+    let spoiler_css = font_css(&spoiler_content_font);
+
+    let distance_css: CssHandler = {
+        let mut css = CssHandler::new();
+
+        css.set_important(true);
+        css.min_height(distance.clone().unwrap_or(CssSize::Em(4.0)));
+
+        css
+    };
+
+    // Long spoilers are cut off after the maximal number of lines
+    let spoiler_text_css: CssHandler = {
+        let mut css = spoiler_css.clone();
+        if let Some(max_lines) = spoiler_max_lines {
+            css.line_clamp(max_lines);
+        }
+        css
+    };
+
+    // The default layout keeps the plain block flow, so that existing designs look the same
+    let (wrapper_style, main_block_style, distance_block_style, spoiler_block_style) =
+        if layout.is_customized() {
+            (
+                slide_layout_style(&vertical_alignment),
+                slide_block_style(&layout, SlideBlock::MainContent),
+                format!("order:{};", slide_distance_order(&layout)),
+                slide_block_style(&layout, SlideBlock::Spoiler),
+            )
+        } else {
+            Default::default()
+        };
+
+    rsx! {
+        div {
+            style: wrapper_style,
+            div {
+                class: "main-content",
+                style: format!("{}{}", main_css, main_block_style),
+                for (position, (_, lines)) in shown_texts.into_iter().enumerate() {
+                    p {
+                        class: if position > 0 { "secondary-language" },
+                        style: if position == 0 { main_css.to_string() } else { secondary_css.to_string() },
+                        for (num, line) in lines.into_iter().enumerate() {
+                            if num > 0 {
+                                br { }
+                            }
+                            { line }
+                        }
+                    }
+                }
+            }
+            if let Some(spoiler_lines) = spoiler_lines {
+                div {
+                    class: "distance",
+                    style: format!("{}{}", distance_css, distance_block_style),
+                }
+                div {
+                    class: "spoiler-content",
+                    style: format!("{}{}", spoiler_css, spoiler_block_style),
+                    p {
+                        style: spoiler_text_css.to_string(),
+                        for (num, line) in spoiler_lines.into_iter().enumerate() {
+                            if num > 0 {
+                                br { }
+                            }
+                            { line }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn EmptySlideComponent() -> Element {
     rsx! {
@@ -816,8 +2173,17 @@ fn EmptySlideComponent() -> Element {
 /// Determines the container style for a slide based on its content type.
 /// Picture and markdown slides need `height: 100%` to fill the grid cell,
 /// so that their content can scroll or scale within a constrained area.
-fn slide_container_style(slide_content: &SlideContent) -> &'static str {
-    match slide_content {
+/// Pictures (but not PDF pages) are shown full-bleed, independent of the padding of the design.
+/// Other slides are limited to the maximum content width of the design and centered within the
+/// padding, while the text keeps its horizontal alignment inside of this width.
+fn slide_container_style(
+    slide_content: &SlideContent,
+    pds: &PresentationDesignTemplate,
+) -> String {
+    let content_style = match slide_content {
+        SlideContent::SimplePicture(picture_slide) if !is_pdf_page(&get_picture_path(picture_slide)) => {
+            return "position: absolute; inset: 0;".to_string();
+        }
         SlideContent::SimplePicture(_) => "height: 100%;",
         SlideContent::SingleLanguageMainContent(main_slide) => {
             if get_markdown_html(&main_slide.clone().main_text()).is_some() {
@@ -827,7 +2193,152 @@ fn slide_container_style(slide_content: &SlideContent) -> &'static str {
             }
         }
         _ => "",
+    };
+    // Blocks with an own vertical alignment need the whole height of the slide
+    let content_style = match slide_content {
+        SlideContent::Title(_)
+        | SlideContent::SingleLanguageMainContent(_)
+        | SlideContent::MultiLanguageMainContent(_)
+            if content_style.is_empty() && pds.slide_layout.has_block_alignments() =>
+        {
+            "height: 100%;"
+        }
+        _ => content_style,
+    };
+    match pds.max_content_width {
+        Some(ref max_width) if !max_width.is_null() => format!(
+            "{} max-width: {}; margin-left: auto; margin-right: auto;",
+            content_style,
+            max_width.to_css_string()
+        ),
+        _ => content_style.to_string(),
+    }
+}
+
+/// Returns the design which is used for a slide. Title slides use the design with its
+/// [TitleSlideOverrides](crate::logic::settings::TitleSlideOverrides) applied.
+fn design_for_slide(
+    pds: PresentationDesignTemplate,
+    slide_content: &SlideContent,
+) -> PresentationDesignTemplate {
+    match slide_content {
+        SlideContent::Title(_) => pds.for_title_slide(),
+        _ => pds,
+    }
+}
+
+/// Counts the rendered presentations, so that the stylesheet of a custom design only applies to
+/// its own presentation (e.g. if several previews are shown on one page).
+static DESIGN_SCOPE_COUNTER: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(0);
+
+fn next_design_scope() -> usize {
+    DESIGN_SCOPE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Returns the stylesheet of a custom design, restricted to the presentation element with the
+/// given `data-design-scope`.
+fn custom_design_css(css: &str, design_scope: usize, allow_external_resources: bool) -> String {
+    scope_custom_css(
+        &sanitize_custom_css(css, allow_external_resources),
+        &format!("[data-design-scope=\"{}\"]", design_scope),
+    )
+}
+
+/// Counts the background videos, so that each of them gets its own element id.
+static BACKGROUND_VIDEO_COUNTER: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(0);
+
+/// Counts the slide containers of the renderers, so that auto fit can measure each of them.
+static SLIDE_CONTAINER_COUNTER: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(0);
+
+/// Returns the style of the background layer, which shows the background picture (or contains
+/// the background video) above the background color or gradient with the design's transparency.
+fn background_style(
+    pds: &PresentationDesignTemplate,
+    background: Option<&BackgroundMedia>,
+) -> String {
+    let mut css = CssHandler::new();
+    css.set_important(true);
+    match background {
+        Some(BackgroundMedia::Image(image)) => {
+            css.background_image(image.as_source().path.to_str().unwrap_or_default());
+            css.background_size(pds.background_fit.background_size());
+            css.background_position(pds.background_position.to_css_string());
+            css.background_repeat(pds.background_fit.background_repeat());
+            css.opacity(1.0 - pds.background_transparency as f32 / 100.0f32);
+        }
+        Some(BackgroundMedia::Video(_)) => {
+            css.background_image_none();
+            css.opacity(1.0 - pds.background_transparency as f32 / 100.0f32);
+        }
+        None => {
+            css.background_image_none();
+            css.opacity(0.0);
+        }
+    }
+    if background.is_some() {
+        if let Some(blur) = pds.background_blur.clone().filter(|blur| !blur.is_null()) {
+            css.filter_blur(blur);
+        }
     }
+    css.to_string()
+}
+
+/// Returns the path and the style of the picture of a watermark. The picture is placed in its
+/// corner within the padding of the design, so that it stays in the safe area of the slide.
+fn watermark_image(watermark: &Watermark, padding: &TopBottomLeftRight) -> (String, String) {
+    let vertical = match watermark.corner {
+        OverlayCorner::TopLeft | OverlayCorner::TopRight => {
+            format!("top: {};", padding.top.to_css_string())
+        }
+        OverlayCorner::BottomLeft | OverlayCorner::BottomRight => {
+            format!("bottom: {};", padding.bottom.to_css_string())
+        }
+    };
+    let horizontal = match watermark.corner {
+        OverlayCorner::TopLeft | OverlayCorner::BottomLeft => {
+            format!("left: {};", padding.left.to_css_string())
+        }
+        OverlayCorner::TopRight | OverlayCorner::BottomRight => {
+            format!("right: {};", padding.right.to_css_string())
+        }
+    };
+    let mut css = CssHandler::new();
+    css.opacity(watermark.opacity.min(100) as f32 / 100.0);
+    let path = watermark
+        .image
+        .as_source()
+        .path
+        .to_str()
+        .unwrap_or_default()
+        .to_string();
+    (
+        path,
+        format!(
+            "{} {} width: {}; {}",
+            vertical,
+            horizontal,
+            watermark.size.to_css_string(),
+            css
+        ),
+    )
+}
+
+/// Returns the style of a background video, which is fitted into the background layer like a
+/// background picture.
+fn background_video_style(pds: &PresentationDesignTemplate) -> String {
+    let mut css = CssHandler::new();
+    css.object_fit(pds.background_fit.object_fit());
+    css.object_position(pds.background_position.to_css_string());
+    css.to_string()
+}
+
+/// Returns true if a slide has no text (e.g. a picture or an empty slide), so that a background
+/// video can be paused while it is shown.
+fn is_still_slide(slide_content: &SlideContent) -> bool {
+    matches!(slide_content, SlideContent::SimplePicture(_) | SlideContent::Empty(_))
 }
 
 /// Renders the content of a single slide based on its [SlideContent] type.
@@ -838,12 +2349,18 @@ fn SlideContentRenderer(
     slide_content: SlideContent,
     pds: PresentationDesignTemplate,
     running_presentation: Option<Signal<RunningPresentation>>,
+    /// The factor for the size of the main and spoiler content font, which is reduced by auto fit
+    #[props(default = 1.0)]
+    font_scale: f64,
 ) -> Element {
     match slide_content {
         SlideContent::Title(title_slide) => rsx! {
             TitleSlideComponent {
                 title_slide: title_slide.clone(),
-                title_font_representation: pds.get_default_headline_font()
+                title_font_representation: pds.get_default_headline_font(),
+                layout: pds.slide_layout.clone(),
+                vertical_alignment: pds.vertical_alignment.clone(),
+                title_emphasis: pds.title_emphasis.clone(),
             }
         },
         SlideContent::SingleLanguageMainContent(main_slide) => {
@@ -858,22 +2375,58 @@ fn SlideContentRenderer(
                     }
                 }
             } else {
+                let (main_content_font, spoiler_content_font) = pds.scaled_content_fonts(font_scale);
                 rsx! {
                     SingleLanguageMainContentSlideRenderer {
                         main_slide: main_slide.clone(),
-                        main_content_font: pds.get_default_font(),
-                        spoiler_content_font: pds.get_default_spoiler_font(),
+                        main_content_font: main_content_font,
+                        spoiler_content_font: spoiler_content_font,
                         distance: pds.main_content_spoiler_content_padding.clone(),
+                        spoiler_max_lines: pds.spoiler_max_lines,
+                        layout: pds.slide_layout.clone(),
+                        vertical_alignment: pds.vertical_alignment.clone(),
                     }
                 }
             }
         },
+        SlideContent::MultiLanguageMainContent(multi_language_slide) => {
+            let (main_content_font, spoiler_content_font) = pds.scaled_content_fonts(font_scale);
+            let shown_languages = running_presentation
+                .map(|rp| rp.read().get_current_shown_languages())
+                .unwrap_or_default();
+            rsx! {
+                MultiLanguageMainContentSlideRenderer {
+                    texts: get_multi_language_texts(&multi_language_slide),
+                    shown_languages: shown_languages,
+                    main_content_font: main_content_font,
+                    secondary_language_font: pds.get_default_secondary_language_font().scaled(font_scale),
+                    spoiler_content_font: spoiler_content_font,
+                    distance: pds.main_content_spoiler_content_padding.clone(),
+                    spoiler_max_lines: pds.spoiler_max_lines,
+                    layout: pds.slide_layout.clone(),
+                    vertical_alignment: pds.vertical_alignment.clone(),
+                }
+            }
+        },
         SlideContent::Empty(_) => rsx! {
             EmptySlideComponent {}
         },
-        SlideContent::SimplePicture(picture_slide) => rsx! {
-            SimplePictureSlideComponent {
-                picture_slide: picture_slide.clone()
+        SlideContent::SimplePicture(picture_slide) => {
+            let (picture_settings, name) = running_presentation
+                .map(|rp| {
+                    let rp = rp.read();
+                    let name = rp.current_source_file().map(|file| file.name.clone());
+                    (rp.get_current_picture_settings(), name)
+                })
+                .unwrap_or_default();
+            let caption = name.filter(|_| picture_settings.show_caption);
+            rsx! {
+                SimplePictureSlideComponent {
+                    picture_slide: picture_slide.clone(),
+                    picture_settings: picture_settings,
+                    caption: caption,
+                    caption_font: pds.get_default_meta_font(),
+                }
             }
         },
         _ => rsx! { p { "No content provided" } }
@@ -984,13 +2537,13 @@ fn MarkdownSlideComponent(
     // with slide navigation — slide changes always take priority.
     use_future(move || async move {
         // No sync needed for static thumbnails
-        if running_presentation.is_none() { return; }
+        let Some(local) = running_presentation else { return; };
+        let presentation_id = local.peek().id;
 
         // Capture the slide position when this component was mounted.
         // If the position changes, we must stop immediately — the component will
         // be unmounted/recreated for the new slide anyway.
-        let initial_position = shared.peek()
-            .first()
+        let initial_position = find_presentation(&shared.peek(), presentation_id)
             .and_then(|rp| rp.position.clone());
 
         let mut last_pos: f64 = 0.0;
@@ -1002,8 +2555,7 @@ fn MarkdownSlideComponent(
 
             // Check if the slide position changed — if so, stop this loop immediately.
             // Slide changes must never be interfered with by scroll sync writes.
-            let current_position = shared.peek()
-                .first()
+            let current_position = find_presentation(&shared.peek(), presentation_id)
                 .and_then(|rp| rp.position.clone());
             if current_position != initial_position {
                 break;
@@ -1029,8 +2581,7 @@ fn MarkdownSlideComponent(
             }
 
             // Read the shared signal without subscribing (peek avoids triggering re-renders)
-            let signal_pos = shared.peek()
-                .first()
+            let signal_pos = find_presentation(&shared.peek(), presentation_id)
                 .map(|rp| rp.markdown_scroll_position)
                 .unwrap_or(0.0);
 
@@ -1039,8 +2590,8 @@ fn MarkdownSlideComponent(
                 // so the other window (presenter console or presentation) picks it up
                 last_pos = dom_pos;
                 if (signal_pos - dom_pos).abs() > SCROLL_SYNC_THRESHOLD {
-                    if let Some(first) = shared.write().first_mut() {
-                        first.markdown_scroll_position = dom_pos;
+                    if let Some(shared_rp) = find_presentation_mut(&mut shared.write(), presentation_id) {
+                        shared_rp.markdown_scroll_position = dom_pos;
                     }
                 }
             } else if (signal_pos - last_pos).abs() > SCROLL_SYNC_THRESHOLD {
@@ -1063,20 +2614,35 @@ fn MarkdownSlideComponent(
         div {
             class: "markdown-slide",
             style: format!("overflow-y: auto; max-height: 100%; padding: 1em 2em; box-sizing: border-box; {}", font_css).to_string(),
+            // The wheel scrolls long markdown slides instead of changing the slide
+            onwheel: move |event| event.stop_propagation(),
             dangerous_inner_html: html_content
         }
     }
 }
 
+/// Returns true if the path of a picture slide refers to a page of a PDF file.
+/// The path may contain a `#page=N` fragment.
+fn is_pdf_page(path: &str) -> bool {
+    let base_path = path.split('#').next().unwrap_or(path);
+    base_path.to_lowercase().ends_with(".pdf")
+}
+
+/// Renders a picture, which fills the whole slide, or a page of a PDF file.
 #[component]
-fn SimplePictureSlideComponent(picture_slide: SimplePictureSlide) -> Element {
+fn SimplePictureSlideComponent(
+    picture_slide: SimplePictureSlide,
+    #[props(default)]
+    picture_settings: PictureSettings,
+    /// The caption which is shown below the picture
+    caption: Option<String>,
+    /// The font of the caption
+    caption_font: Option<FontRepresentation>,
+) -> Element {
     let path = get_picture_path(&picture_slide);
-
-    // Check if this is a PDF; the path may contain a #page=N fragment
     let base_path = path.split('#').next().unwrap_or(&path).to_string();
-    let is_pdf = base_path.to_lowercase().ends_with(".pdf");
 
-    if is_pdf {
+    if is_pdf_page(&path) {
         let page_num: u32 = path
             .split("#page=")
             .nth(1)
@@ -1094,12 +2660,22 @@ fn SimplePictureSlideComponent(picture_slide: SimplePictureSlide) -> Element {
         };
     }
 
+    let caption_css = caption_font.map(|font| CssHandler::from(font).to_string()).unwrap_or_default();
+
     rsx! {
         div {
-            style: "width: 100%; height: 100%; display: flex; align-items: center; justify-content: center; z-index: 2;",
+            class: "picture-slide",
             img {
+                class: "picture-slide-image",
                 src: "{path}",
-                style: "max-width: 100%; max-height: 100%; object-fit: contain;",
+                style: "object-fit: {picture_settings.fit.object_fit()};",
+            }
+            if let Some(caption) = caption {
+                div {
+                    class: "picture-slide-caption",
+                    style: "{caption_css}",
+                    "{caption}"
+                }
             }
         }
     }
@@ -1199,6 +2775,15 @@ pub fn StaticSlideRendererComponent(
         PresentationDesignSettings::Template(ref template) => template.clone(),
         _ => PresentationDesignTemplate::default(),
     };
+    let pds = design_for_slide(pds, &slide.slide_content);
+    // Static slides are only previews, so they never load external resources
+    let design_scope = use_hook(next_design_scope);
+    let custom_css = match presentation_design.presentation_design_settings {
+        PresentationDesignSettings::Custom(ref css) => {
+            Some(custom_design_css(css, design_scope, false))
+        }
+        _ => None,
+    };
 
     let css_text_align = pds
         .fonts
@@ -1215,7 +2800,10 @@ pub fn StaticSlideRendererComponent(
     let css_handler = {
         let mut css = CssHandler::new();
         css.set_important(true);
-        css.background_color(pds.background_color);
+        css.background_color(pds.get_background_color_with_alpha());
+        if let Some(ref gradient) = pds.background_gradient {
+            css.background(gradient);
+        }
         css.padding_left(pds.padding.left.clone());
         css.padding_right(pds.padding.right.clone());
         css.padding_top(pds.padding.top.clone());
@@ -1232,24 +2820,23 @@ pub fn StaticSlideRendererComponent(
         css
     };
 
-    let background_css = {
-        let mut css = CssHandler::new();
-        css.set_important(true);
-        if let Some(ref image) = pds.background_image {
-            css.background_image(image.as_source().path.to_str().unwrap_or_default());
-            css.background_size("cover");
-            css.background_position("center");
-            css.background_repeat("no-repeat");
-            css.opacity(1.0 - pds.background_transparency as f32 / 100.0f32);
-        } else {
-            css.background_image_none();
-            css.opacity(0.0);
+    // Static slides have no position and show the first background of a rotation
+    let background = pds.background_at(0, 0);
+    let background_css = background_style(&pds, background.as_ref());
+    // Static slides show the first frame of a background video without playing it
+    let background_video_path = match background {
+        Some(BackgroundMedia::Video(ref video)) => {
+            Some(video.as_source().path.to_str().unwrap_or_default().to_string())
         }
-        css.to_string()
+        _ => None,
     };
 
     let slide_content = slide.slide_content;
-    let container_style = slide_container_style(&slide_content);
+    let container_style = slide_container_style(&slide_content, &pds);
+    let watermark = pds
+        .watermark
+        .as_ref()
+        .map(|watermark| watermark_image(watermark, &pds.padding));
 
     rsx! {
         document::Link { rel: "stylesheet", href: PRESENTATION_CSS }
@@ -1257,9 +2844,29 @@ pub fn StaticSlideRendererComponent(
         div {
             class: "presentation",
             style: css_handler.to_string(),
+            "data-design-scope": "{design_scope}",
+            if let Some(css) = custom_css {
+                style { "{css}" }
+            }
             div {
                 class: "background",
-                style: "{background_css}"
+                style: "{background_css}",
+                if let Some(video_path) = background_video_path {
+                    video {
+                        class: "background-video",
+                        style: background_video_style(&pds),
+                        src: "{video_path}",
+                        muted: true,
+                        preload: "metadata",
+                    }
+                }
+            }
+            if let Some((watermark_path, watermark_css)) = watermark {
+                img {
+                    class: "watermark",
+                    src: "{watermark_path}",
+                    style: "{watermark_css}",
+                }
             }
             div {
                 class: "slide-container",
@@ -1,22 +1,38 @@
 //! This module provides components for adjusting the presentation designs
 
 use crate::components::font_settings::FontRepresentationsComponent;
-use crate::components::shared_components::NumberedValidatedLengthInput;
+use crate::components::shared_components::{
+    DesignPalette, ExamplePresentationViewer, ExampleTitleSlidePreview,
+    NumberedValidatedLengthInput, PaletteSwatches, js_yes_no_box,
+};
+use crate::logic::conversions::{ToHexAlphaString, ToHexString, ToRgb8, ToRgba8};
 use crate::logic::settings::{
-    CssSize, PresentationDesign, PresentationDesignSettings, PresentationDesignTemplate,
-    TopBottomLeftRight, VerticalAlign, use_settings,
+    AspectRatio, BackgroundFit, BackgroundGradient, BackgroundMedia, BackgroundPosition, BackgroundRotation,
+    CUSTOM_CSS_EXAMPLE, ClockFormat, ColorPreset,
+    CssSize, FontRepresentation, GradientKind, MetaFooterSlides, PaletteColor, OverlayCorner, PresentationDesign,
+    PresentationDesignSettings, PresentationDesignTemplate, SlideBlock, SlideCounterStyle,
+    SlideLayout, TitleEmphasis, TitleEmphasisUnit, TitleSlideOverrides, TopBottomLeftRight,
+    VerticalAlign, Watermark, use_settings,
 };
-use crate::logic::sourcefiles::{ImageSourceFile, SourceFile};
+use crate::logic::sourcefiles::{ImageSourceFile, SourceFile, VideoSourceFile};
+use cantara_songlib::slides::SlideSettings;
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
 use dioxus::hooks::use_signal;
 use dioxus::logger::tracing;
 use dioxus::prelude::*;
+use rgb::{RGB8, RGBA8};
 use rust_i18n::t;
 use std::path::PathBuf;
 
 rust_i18n::i18n!("locales", fallback = "en");
 
+/// The time in milliseconds after the last change of a design until its preview is updated
+const PREVIEW_DEBOUNCE_MS: u32 = 250;
+
+/// The number of lines to which the spoiler is limited when the limit is switched on
+const DEFAULT_SPOILER_MAX_LINES: u8 = 2;
+
 /// This page contains the general settings for Cantara
 #[component]
 pub fn PresentationDesignSettingsPage(
@@ -26,7 +42,7 @@ pub fn PresentationDesignSettingsPage(
     let nav = navigator();
     let mut settings = use_settings();
 
-    let selected_presentation_design_option: Signal<Option<PresentationDesign>> =
+    let mut selected_presentation_design_option: Signal<Option<PresentationDesign>> =
         use_signal(|| {
             settings
                 .read()
@@ -47,6 +63,58 @@ pub fn PresentationDesignSettingsPage(
     let selected_presentation_design =
         use_memo(move || selected_presentation_design_option.read().clone().unwrap());
 
+    // The preview follows the changes which are written into the settings. It is updated
+    // after the changes have paused, so that e.g. dragging a slider doesn't render every step.
+    let live_presentation_design = use_memo(move || {
+        settings
+            .read()
+            .presentation_designs
+            .get(index as usize)
+            .cloned()
+    });
+    let mut preview_design = use_signal(|| live_presentation_design().unwrap_or_default());
+
+    // The saved colors of the design are offered next to all color inputs
+    let palette = use_memo(move || {
+        live_presentation_design()
+            .map(|design| design.palette)
+            .unwrap_or_default()
+    });
+    use_context_provider(|| DesignPalette(palette));
+    let mut preview_generation = use_signal(|| 0usize);
+    let mut preview_debouncing = use_signal(|| false);
+    use_effect(move || {
+        let design = live_presentation_design().unwrap_or_default();
+        let generation = *preview_generation.peek() + 1;
+        preview_generation.set(generation);
+        // A single change (e.g. a color preset) is shown at once, further changes which
+        // follow quickly are shown after they have paused
+        if !*preview_debouncing.peek() {
+            preview_design.set(design.clone());
+        }
+        preview_debouncing.set(true);
+        spawn(async move {
+            let js_sleep = format!("await new Promise(r => setTimeout(r, {PREVIEW_DEBOUNCE_MS}))");
+            let _ = document::eval(&js_sleep).await;
+            if *preview_generation.peek() == generation {
+                preview_debouncing.set(false);
+                if *preview_design.peek() != design {
+                    preview_design.set(design);
+                }
+            }
+        });
+    });
+
+    // Switches between a template and a custom stylesheet
+    let mut set_design_settings = move |design_settings: PresentationDesignSettings| {
+        if let Some(design) = settings.write().presentation_designs.get_mut(index as usize) {
+            design.presentation_design_settings = design_settings.clone();
+        }
+        if let Some(design) = selected_presentation_design_option.write().as_mut() {
+            design.presentation_design_settings = design_settings;
+        }
+    };
+
     rsx! {
         div {
             class: "wrapper",
@@ -66,17 +134,53 @@ pub fn PresentationDesignSettingsPage(
                         let origin_pd = settings_write.presentation_designs.get_mut(index as usize).unwrap();
                         origin_pd.name = pd.name;
                         origin_pd.description = pd.description;
+                        origin_pd.palette = pd.palette;
                     }
                 }
 
-                if let PresentationDesignSettings::Template(pd_template) = selected_presentation_design().presentation_design_settings {
+                if let PresentationDesignSettings::Custom(css) = selected_presentation_design().presentation_design_settings {
                     hr { }
-                    DesignTemplateSettings {
-                        presentation_design_template: pd_template,
-                        onchange: move |new_pdt: PresentationDesignTemplate| {
+                    CustomCssSettings {
+                        css,
+                        onchange: move |new_css: String| {
                             let mut settings_write = settings.write();
-                            if let PresentationDesignSettings::Template(pdt) = &mut settings_write.presentation_designs.get_mut(index as usize).unwrap().presentation_design_settings {
-                                *pdt = new_pdt.clone();
+                            if let Some(design) = settings_write.presentation_designs.get_mut(index as usize) {
+                                design.presentation_design_settings = PresentationDesignSettings::Custom(new_css);
+                            }
+                        },
+                        onrevert: move |_| {
+                            set_design_settings(PresentationDesignSettings::default());
+                        }
+                    }
+                }
+
+                if let PresentationDesignSettings::Template(pd_template) = selected_presentation_design().presentation_design_settings {
+                    hr { }
+                    button {
+                        class: "outline secondary",
+                        onclick: move |_| {
+                            set_design_settings(PresentationDesignSettings::Custom(CUSTOM_CSS_EXAMPLE.to_string()));
+                        },
+                        { t!("settings.custom_css.use_custom_css").to_string() }
+                    }
+                    div {
+                        class: "design-editor",
+                        div {
+                            class: "design-editor-settings",
+                            DesignTemplateSettings {
+                                presentation_design_template: pd_template,
+                                onchange: move |new_pdt: PresentationDesignTemplate| {
+                                    let mut settings_write = settings.write();
+                                    if let PresentationDesignSettings::Template(pdt) = &mut settings_write.presentation_designs.get_mut(index as usize).unwrap().presentation_design_settings {
+                                        *pdt = new_pdt.clone();
+                                    }
+                                }
+                            }
+                        }
+                        aside {
+                            class: "design-editor-preview",
+                            DesignPreviewPanel {
+                                presentation_design: preview_design(),
                             }
                         }
                     }
@@ -133,6 +237,78 @@ fn MetaSettings(
                     }
                 }
             }
+
+            // The color palette of the design
+            h4 { { t!("settings.palette.title").to_string() } }
+            small { { t!("settings.palette.hint").to_string() } }
+            for (idx, palette_color) in pd().palette.into_iter().enumerate() {
+                div {
+                    class: "palette-editor-color",
+                    fieldset {
+                        role: "group",
+                        input {
+                            type: "color",
+                            aria_label: t!("settings.color").to_string(),
+                            value: palette_color.color.to_hex(),
+                            onchange: move |event| {
+                                if let (Some(color), Some(palette_color)) = (event.value().to_rgb8(), pd.write().palette.get_mut(idx)) {
+                                    // The color input has no alpha channel, so the opacity is kept
+                                    palette_color.color = RGBA8::new(color.r, color.g, color.b, palette_color.color.a);
+                                }
+                                on_pd_changed.call(pd());
+                            }
+                        }
+                        input {
+                            type: "text",
+                            aria_label: t!("settings.fonts.hex_color").to_string(),
+                            value: palette_color.color.to_hex_with_alpha(),
+                            onchange: move |event| {
+                                match event.value().to_rgba8() {
+                                    Some(color) => {
+                                        if let Some(palette_color) = pd.write().palette.get_mut(idx) {
+                                            palette_color.color = color;
+                                        }
+                                        on_pd_changed.call(pd());
+                                    },
+                                    None => tracing::error!("Invalid color entered, the value is: {}", event.value()),
+                                }
+                            }
+                        }
+                    }
+                    input {
+                        placeholder: t!("settings.palette.name").to_string(),
+                        aria_label: t!("settings.palette.name").to_string(),
+                        value: palette_color.name,
+                        onchange: move |event| {
+                            if let Some(palette_color) = pd.write().palette.get_mut(idx) {
+                                palette_color.name = event.value();
+                            }
+                            on_pd_changed.call(pd());
+                        }
+                    }
+                    button {
+                        class: "outline secondary",
+                        r#type: "button",
+                        onclick: move |_| {
+                            pd.write().palette.remove(idx);
+                            on_pd_changed.call(pd());
+                        },
+                        { t!("settings.palette.remove_color").to_string() }
+                    }
+                }
+            }
+            button {
+                class: "outline secondary smaller-buttons",
+                r#type: "button",
+                onclick: move |_| {
+                    pd.write().palette.push(PaletteColor {
+                        name: String::new(),
+                        color: RGBA8::new(255, 255, 255, 255),
+                    });
+                    on_pd_changed.call(pd());
+                },
+                { t!("settings.palette.add_color").to_string() }
+            }
         }
     }
 }
@@ -150,10 +326,55 @@ fn DesignTemplateSettings(
     onchange: EventHandler<PresentationDesignTemplate>,
 ) -> Element {
     let mut pdt = use_signal(|| presentation_design_template);
-    let mut use_background_image: Signal<bool> = use_signal(|| pdt().background_image.is_some());
+    let mut use_background_image: Signal<bool> = use_signal(|| {
+        pdt().background_image.is_some() || !pdt().background_rotation_images.is_empty()
+    });
+    let mut use_background_rotation: Signal<bool> =
+        use_signal(|| !pdt().background_rotation_images.is_empty());
+
+    // The font components keep their own state, so they are recreated when all colors change
+    let mut colors_version = use_signal(|| 0usize);
+    let mut change_all_colors = move |change: fn(&mut PresentationDesignTemplate)| {
+        change(&mut pdt.write());
+        colors_version += 1;
+        onchange.call(pdt());
+    };
 
     rsx!(
         h3 { { t!("settings.presentation_design_configuration").to_string() } }
+        AspectRatioSelector {
+            aspect_ratio: pdt().aspect_ratio,
+            onchange: move |aspect_ratio| {
+                pdt.write().aspect_ratio = aspect_ratio;
+                onchange.call(pdt());
+            }
+        }
+
+        // Quick color schemes for the background and all fonts
+        h4 { { t!("settings.color_presets.title").to_string() } }
+        div {
+            role: "group",
+            button {
+                class: "outline secondary",
+                r#type: "button",
+                onclick: move |_| change_all_colors(|template| template.apply_color_preset(ColorPreset::Dark)),
+                { t!("settings.color_presets.dark").to_string() }
+            }
+            button {
+                class: "outline secondary",
+                r#type: "button",
+                onclick: move |_| change_all_colors(|template| template.apply_color_preset(ColorPreset::Light)),
+                { t!("settings.color_presets.light").to_string() }
+            }
+            button {
+                class: "outline secondary",
+                r#type: "button",
+                onclick: move |_| change_all_colors(PresentationDesignTemplate::invert_colors),
+                { t!("settings.color_presets.invert").to_string() }
+            }
+        }
+        small { { t!("settings.color_presets.hint").to_string() } }
+
         h4 { { t!("settings.background").to_string() } }
         form {
             fieldset {
@@ -170,6 +391,57 @@ fn DesignTemplateSettings(
                         }
                     }
                 }
+                PaletteSwatches {
+                    onselect: move |color: RGBA8| {
+                        pdt.write().background_color = RGB8::new(color.r, color.g, color.b);
+                        onchange.call(pdt());
+                    }
+                }
+                label {
+                    span { { format!("{}: {}%",
+                        t!("settings.background_color_transparency.title"),
+                        pdt.read().background_color_transparency) } }
+                    input {
+                        type: "range",
+                        min: 0,
+                        max: 100,
+                        value: pdt.read().background_color_transparency,
+                        oninput: move |event| {
+                            pdt.write().background_color_transparency = event.value().parse().unwrap_or(0);
+                            onchange.call(pdt());
+                        }
+                    }
+                }
+                if pdt().background_color_transparency > 0 {
+                    small { { t!("settings.background_color_transparency.hint").to_string() } }
+                }
+
+                // Use a gradient instead of the background color
+                label {
+                    input {
+                        type: "checkbox",
+                        role: "switch",
+                        checked: pdt().background_gradient.is_some(),
+                        onchange: move |event| {
+                            pdt.write().background_gradient =
+                                event.checked().then(BackgroundGradient::default);
+                            onchange.call(pdt());
+                        }
+                    }
+                    { t!("settings.gradient.use_gradient").to_string() }
+                }
+
+                // The editor keeps its own state, so it is recreated when all colors change
+                for gradient in pdt().background_gradient {
+                    GradientEditor {
+                        key: "{colors_version}",
+                        gradient,
+                        onchange: move |gradient| {
+                            pdt.write().background_gradient = Some(gradient);
+                            onchange.call(pdt());
+                        }
+                    }
+                }
 
                 // Use background image
                 label {
@@ -179,7 +451,9 @@ fn DesignTemplateSettings(
                         checked: use_background_image,
                         onchange: move |event| {
                             use_background_image.set(event.checked());
+                            use_background_rotation.set(false);
                             pdt.write().background_image = None;
+                            pdt.write().background_rotation_images.clear();
                             onchange.call(pdt());
                         }
                     }
@@ -187,23 +461,86 @@ fn DesignTemplateSettings(
                 }
 
                 if use_background_image() {
-                    if let Some(background_image) = pdt().background_image {
-                        PictureSelector {
-                            onchange: move |background_image| {
-                                pdt.write().background_image = Some(background_image);
+                    // Several pictures which are shown in turn
+                    label {
+                        input {
+                            type: "checkbox",
+                            role: "switch",
+                            checked: use_background_rotation,
+                            onchange: move |event| {
+                                use_background_rotation.set(event.checked());
+                                pdt.write().background_rotation_images.clear();
                                 onchange.call(pdt());
-                            },
-                            already_selected_image_path: background_image.into_inner().path,
+                            }
                         }
-                    } else {
-                        PictureSelector {
-                            onchange: move |background_image| {
-                                pdt.write().background_image = Some(background_image);
-                                onchange.call(pdt());
+                        { t!("settings.background_rotation.use_rotation").to_string() }
+                    }
+                }
+
+                if use_background_image() && use_background_rotation() {
+                    small { { t!("settings.background_rotation.description").to_string() } }
+                    PictureSelector {
+                        selected_images: pdt().background_rotation_images,
+                        onchange_selection: move |images| {
+                            pdt.write().background_rotation_images = images;
+                            onchange.call(pdt());
+                        },
+                    }
+                    select {
+                        name: "background_rotation",
+                        aria_label: t!("settings.background_rotation.mode").to_string(),
+                        onchange: move |event| {
+                            match event.value().as_str() {
+                                "chapter" => pdt.write().background_rotation = BackgroundRotation::PerChapter,
+                                "slide" => pdt.write().background_rotation = BackgroundRotation::PerSlide,
+                                other => tracing::error!("Invalid option for the background rotation selected, the value is: {}", other)
+                            };
+                            onchange.call(pdt());
+                        },
+                        option {
+                            value: "chapter",
+                            selected: pdt().background_rotation == BackgroundRotation::PerChapter,
+                            { t!("settings.background_rotation.per_chapter").to_string() }
+                        }
+                        option {
+                            value: "slide",
+                            selected: pdt().background_rotation == BackgroundRotation::PerSlide,
+                            { t!("settings.background_rotation.per_slide").to_string() }
+                        }
+                    }
+                } else if use_background_image() {
+                    PictureSelector {
+                        onchange: move |background_image| {
+                            pdt.write().background_image = Some(BackgroundMedia::Image(background_image));
+                            onchange.call(pdt());
+                        },
+                        already_selected_image_path: pdt().background_image.map(|media| media.as_source().path.clone()),
+                    }
+                    VideoSelector {
+                        onchange: move |background_video| {
+                            pdt.write().background_image = Some(BackgroundMedia::Video(background_video));
+                            onchange.call(pdt());
+                        },
+                        already_selected_video_path: pdt().background_image.map(|media| media.as_source().path.clone()),
+                    }
+
+                    if matches!(pdt().background_image, Some(BackgroundMedia::Video(_))) {
+                        label {
+                            input {
+                                type: "checkbox",
+                                role: "switch",
+                                checked: pdt().pause_background_video_on_still_slides,
+                                onchange: move |event| {
+                                    pdt.write().pause_background_video_on_still_slides = event.checked();
+                                    onchange.call(pdt());
+                                }
                             }
+                            { t!("settings.pause_background_video").to_string() }
                         }
                     }
+                }
 
+                if use_background_image() {
                     // Adjust the background image transparency over a range input
                     label {
                         span { { format!("{}: {}%",
@@ -221,6 +558,14 @@ fn DesignTemplateSettings(
                         }
 
                     }
+
+                    BackgroundPlacementSettings {
+                        presentation_design_template: pdt(),
+                        onchange: move |new_pdt: PresentationDesignTemplate| {
+                            pdt.set(new_pdt);
+                            onchange.call(pdt());
+                        }
+                    }
                 }
             }
         }
@@ -235,18 +580,80 @@ fn DesignTemplateSettings(
             }
         }
 
-        // Distance between the main content and spoiler content
-        h4 { { t!("settings.main_spoiler_content_distance").to_string() } }
+        // A logo in a corner of every slide
+        WatermarkSettings {
+            watermark: pdt().watermark,
+            onchange: move |watermark| {
+                pdt.write().watermark = watermark;
+                onchange.call(pdt());
+            }
+        }
+
+        // Distance and font size of the spoiler content
+        SpoilerSettings {
+            presentation_design_template: pdt(),
+            ondistancechange: move |new_value| {
+                pdt.write().main_content_spoiler_content_padding = new_value;
+                onchange.call(pdt());
+            },
+            onfactorchange: move |new_factor| {
+                pdt.write().spoiler_content_fontsize_factor = new_factor;
+                onchange.call(pdt());
+            },
+            onmaxlineschange: move |max_lines| {
+                pdt.write().spoiler_max_lines = max_lines;
+                onchange.call(pdt());
+            }
+        }
+
+        // Maximal width of the content, e.g. for ultra-wide screens
+        h4 { { t!("settings.max_content_width").to_string() } }
         fieldset {
             role: "group",
             NumberedValidatedLengthInput {
-                value: pdt().main_content_spoiler_content_padding,
+                value: pdt().max_content_width.unwrap_or(CssSize::Null),
                 placeholder: "".to_string(),
-                onchange: move |new_value| {
-                    pdt.write().main_content_spoiler_content_padding = new_value;
+                onchange: move |new_value: CssSize| {
+                    // An empty input means that the content uses the whole width
+                    pdt.write().max_content_width = Some(new_value).filter(|width| !width.is_null());
+                    onchange.call(pdt());
+                }
+            }
+        }
+        small { { t!("settings.max_content_width_hint").to_string() } }
+
+        // Reduce the font size of slides which don't fit on the screen
+        h4 { { t!("settings.auto_fit.title").to_string() } }
+        label {
+            input {
+                type: "checkbox",
+                role: "switch",
+                checked: pdt().auto_fit,
+                onchange: move |event| {
+                    pdt.write().auto_fit = event.checked();
                     onchange.call(pdt());
                 }
             }
+            { t!("settings.auto_fit.enable").to_string() }
+        }
+        if pdt().auto_fit {
+            label {
+                span { { format!("{}: {}%",
+                    t!("settings.auto_fit.min_scale"),
+                    (pdt.read().auto_fit_min_scale * 100.0).round()) } }
+                input {
+                    type: "range",
+                    min: 20,
+                    max: 100,
+                    step: 5,
+                    value: (pdt.read().auto_fit_min_scale * 100.0).round(),
+                    oninput: move |event| {
+                        let percent: f64 = event.value().parse().unwrap_or(50.0);
+                        pdt.write().auto_fit_min_scale = percent / 100.0;
+                        onchange.call(pdt());
+                    }
+                }
+            }
         }
 
         // Here the settings for the vertical alignment of the content are included
@@ -259,108 +666,612 @@ fn DesignTemplateSettings(
             }
         }
 
-        // Adjust individual font settings
-        h3 { { t!("settings.fonts.title").to_string() } }
-
-        FontRepresentationsComponent {
-            fonts: pdt().fonts,
-            spoiler_index: pdt().spoiler_index(),
-            meta_index: pdt().meta_index,
-            onchange: move |data| {
-                pdt.write().fonts = data;
+        // The order of the text blocks and their own vertical alignments
+        SlideLayoutSettings {
+            layout: pdt().slide_layout,
+            onchange: move |layout| {
+                pdt.write().slide_layout = layout;
                 onchange.call(pdt());
             }
         }
-    )
-}
 
-/// A component which allows the selection of a picture
-#[component]
-fn PictureSelector(
-    default_selection_index: Option<usize>,
+        // A different look for the title slides
+        TitleSlideSettings {
+            presentation_design_template: pdt(),
+            onchange: move |overrides| {
+                pdt.write().title_slide_overrides = overrides;
+                onchange.call(pdt());
+            },
+            onemphasischange: move |title_emphasis| {
+                pdt.write().title_emphasis = title_emphasis;
+                onchange.call(pdt());
+            }
+        }
 
-    /// This can be given if an image is already set up. It will then be selected as default.
-    already_selected_image_path: Option<PathBuf>,
+        // Slide counter and progress bar
+        h5 { { t!("settings.slide_counter.title").to_string() } }
+        fieldset {
+            role: "group",
+            SlideCounterStyleSelector {
+                default: pdt().slide_counter,
+                onchange: move |data| {
+                    pdt.write().slide_counter = data;
+                    onchange.call(pdt());
+                }
+            }
+            if pdt().slide_counter.shows_number() {
+                OverlayCornerSelector {
+                    default: pdt().slide_counter_corner,
+                    onchange: move |data| {
+                        pdt.write().slide_counter_corner = data;
+                        onchange.call(pdt());
+                    }
+                }
+            }
+        }
 
-    /// The event will be called if a picture has been selected
-    onchange: Option<EventHandler<ImageSourceFile>>,
-) -> Element {
-    let source_files: Signal<Vec<SourceFile>> = use_context();
-    let image_source_files: Memo<Vec<ImageSourceFile>> = use_memo(move || {
-        source_files()
-            .into_iter()
-            .filter_map(ImageSourceFile::new)
-            .collect()
-    });
-    let mut selection_index = use_signal(|| default_selection_index);
+        // Clock
+        h5 { { t!("settings.clock.title").to_string() } }
+        label {
+            input {
+                type: "checkbox",
+                role: "switch",
+                checked: pdt().show_clock,
+                onchange: move |event| {
+                    pdt.write().show_clock = event.checked();
+                    onchange.call(pdt());
+                }
+            }
+            { t!("settings.clock.show").to_string() }
+        }
+        if pdt().show_clock {
+            fieldset {
+                role: "group",
+                select {
+                    name: "clock_format",
+                    required: true,
+                    aria_label: t!("settings.clock.format").to_string(),
+                    onchange: move |event| {
+                        match event.value().as_str() {
+                            "24h" => pdt.write().clock_format = ClockFormat::TwentyFourHours,
+                            "12h" => pdt.write().clock_format = ClockFormat::TwelveHours,
+                            other => tracing::error!("Invalid option for the clock format selected, the value is: {}", other)
+                        };
+                        onchange.call(pdt());
+                    },
+                    option {
+                        value: "24h",
+                        selected: pdt().clock_format == ClockFormat::TwentyFourHours,
+                        { t!("settings.clock.24h").to_string() }
+                    }
+                    option {
+                        value: "12h",
+                        selected: pdt().clock_format == ClockFormat::TwelveHours,
+                        { t!("settings.clock.12h").to_string() }
+                    }
+                }
+                OverlayCornerSelector {
+                    default: pdt().clock_corner,
+                    onchange: move |data| {
+                        pdt.write().clock_corner = data;
+                        onchange.call(pdt());
+                    }
+                }
+            }
+            small { { t!("settings.clock.padding_hint").to_string() } }
+        }
 
-    rsx! {
-        for (idx, source_file) in image_source_files().iter().enumerate() {
-            PictureSelectorItem {
-                source_file: source_file.clone(),
-                height: "130px",
-                max_width: "200px",
-                active: if let Some(selection_index) = selection_index() {
-                    selection_index == idx
-                } else if Some(source_file.clone().into_inner().path) == already_selected_image_path { true }
-                else { false },
-                onclick: move |image_source_file| {
-                    selection_index.set(Some(idx));
-                    if let Some(onchange_event) = onchange {
-                        onchange_event.call(image_source_file);
+        // Meta information of songs, e.g. author and copyright
+        h5 { { t!("settings.meta_footer.title").to_string() } }
+        label {
+            input {
+                type: "checkbox",
+                role: "switch",
+                checked: pdt().show_meta_footer,
+                onchange: move |event| {
+                    pdt.write().show_meta_footer = event.checked();
+                    onchange.call(pdt());
+                }
+            }
+            { t!("settings.meta_footer.show").to_string() }
+        }
+        if pdt().show_meta_footer {
+            fieldset {
+                select {
+                    name: "meta_footer_slides",
+                    required: true,
+                    aria_label: t!("settings.meta_footer.slides").to_string(),
+                    onchange: move |event| {
+                        match event.value().as_str() {
+                            "first" => pdt.write().meta_footer_slides = MetaFooterSlides::FirstSlide,
+                            "every" => pdt.write().meta_footer_slides = MetaFooterSlides::EverySlide,
+                            "last" => pdt.write().meta_footer_slides = MetaFooterSlides::LastSlide,
+                            other => tracing::error!("Invalid option for the meta footer slides selected, the value is: {}", other)
+                        };
+                        onchange.call(pdt());
+                    },
+                    option {
+                        value: "first",
+                        selected: pdt().meta_footer_slides == MetaFooterSlides::FirstSlide,
+                        { t!("settings.meta_footer.first_slide").to_string() }
+                    }
+                    option {
+                        value: "every",
+                        selected: pdt().meta_footer_slides == MetaFooterSlides::EverySlide,
+                        { t!("settings.meta_footer.every_slide").to_string() }
+                    }
+                    option {
+                        value: "last",
+                        selected: pdt().meta_footer_slides == MetaFooterSlides::LastSlide,
+                        { t!("settings.meta_footer.last_slide").to_string() }
                     }
                 }
             }
+            small { { t!("settings.meta_footer.hint").to_string() } }
+            ExamplePresentationViewer {
+                presentation_design: PresentationDesign {
+                    presentation_design_settings: PresentationDesignSettings::Template(pdt()),
+                    ..Default::default()
+                },
+                width: 320,
+            }
         }
-    }
-}
 
-/// A component representing a single item (picture) in the [PictureSelector] component
-#[component]
-fn PictureSelectorItem(
-    max_width: String,
-    height: String,
-    source_file: ImageSourceFile,
-    onclick: EventHandler<ImageSourceFile>,
-    active: bool,
-) -> Element {
-    // We need a source file signal here due to the use in the closure
-    let sourcefile_signal = use_signal(|| source_file);
-    rsx! {
-        button {
-            role: "button",
-            class: if active { "outline" } else { "outline secondary" },
-            "data-tooltip": sourcefile_signal().into_inner().name,
-            onclick: move |event| {
-                onclick.call(sourcefile_signal());
-                event.prevent_default();
+        // Adjust individual font settings
+        h3 { { t!("settings.fonts.title").to_string() } }
+
+        FontRepresentationsComponent {
+            version: colors_version(),
+            fonts: pdt().fonts,
+            headline_index: pdt().headline_index(),
+            spoiler_index: pdt().spoiler_index(),
+            meta_index: pdt().meta_index,
+            secondary_language_index: pdt().secondary_language_index,
+            onchange: move |(idx, font): (usize, FontRepresentation)| {
+                match pdt.write().fonts.get_mut(idx) {
+                    Some(reference) => *reference = font,
+                    None => tracing::error!("Error while overriding font."),
+                }
+                onchange.call(pdt());
             },
-            img {
-                max_width: "180px",
-                height: "100px",
-                src: sourcefile_signal().into_inner().path.to_str().unwrap_or("").to_string(),
+            onfontlistchange: move |change| {
+                pdt.write().change_fonts(change);
+                onchange.call(pdt());
             }
         }
-    }
+    )
 }
 
-/// A component which allows the setting of padding (left, right, top, bottom)
+/// The settings for the fit, focal position and blur of the background picture or video, with
+/// a preview of the background.
 #[component]
-fn PaddingInput(
-    default_padding: TopBottomLeftRight,
-    onchange: EventHandler<TopBottomLeftRight>,
+fn BackgroundPlacementSettings(
+    /// The presentation design template which contains the background settings
+    presentation_design_template: PresentationDesignTemplate,
+
+    /// An event which is called with the changed template
+    onchange: EventHandler<PresentationDesignTemplate>,
 ) -> Element {
-    let mut padding: Signal<TopBottomLeftRight> = use_signal(|| default_padding);
+    let pdt = presentation_design_template;
+    let fit = pdt.background_fit;
+    let position = pdt.background_position;
+    let blur = pdt.background_blur.clone();
+    let preview_design = PresentationDesign {
+        presentation_design_settings: PresentationDesignSettings::Template(pdt.clone()),
+        ..Default::default()
+    };
+    let position_pdt = pdt.clone();
+    let blur_pdt = pdt.clone();
 
     rsx!(
         div {
             class: "grid",
             div {
                 label {
-                    "Left",
-                    fieldset {
-                        role: "group",
-                        NumberedValidatedLengthInput {
+                    { t!("settings.background_fit.title").to_string() }
+                    select {
+                        name: "background_fit",
+                        required: true,
+                        aria_label: t!("settings.background_fit.title").to_string(),
+                        onchange: move |event| {
+                            let mut new_pdt = pdt.clone();
+                            new_pdt.background_fit = match event.value().as_str() {
+                                "cover" => BackgroundFit::Cover,
+                                "contain" => BackgroundFit::Contain,
+                                "stretch" => BackgroundFit::Stretch,
+                                "tile" => BackgroundFit::Tile,
+                                other => {
+                                    tracing::error!("Invalid option for the background fit selected, the value is: {}", other);
+                                    BackgroundFit::Cover
+                                }
+                            };
+                            onchange.call(new_pdt);
+                        },
+                        option {
+                            value: "cover",
+                            selected: fit == BackgroundFit::Cover,
+                            { t!("settings.background_fit.cover").to_string() }
+                        }
+                        option {
+                            value: "contain",
+                            selected: fit == BackgroundFit::Contain,
+                            { t!("settings.background_fit.contain").to_string() }
+                        }
+                        option {
+                            value: "stretch",
+                            selected: fit == BackgroundFit::Stretch,
+                            { t!("settings.background_fit.stretch").to_string() }
+                        }
+                        option {
+                            value: "tile",
+                            selected: fit == BackgroundFit::Tile,
+                            { t!("settings.background_fit.tile").to_string() }
+                        }
+                    }
+                }
+
+                span { { t!("settings.background_position").to_string() } }
+                div {
+                    class: "background-position-grid",
+                    role: "radiogroup",
+                    aria_label: t!("settings.background_position").to_string(),
+                    for new_position in BackgroundPosition::ALL {
+                        button {
+                            key: "{new_position:?}",
+                            class: if new_position == position { "" } else { "outline secondary" },
+                            role: "radio",
+                            aria_checked: new_position == position,
+                            title: new_position.to_css_string(),
+                            onclick: {
+                                let position_pdt = position_pdt.clone();
+                                move |event: MouseEvent| {
+                                    event.prevent_default();
+                                    let mut new_pdt = position_pdt.clone();
+                                    new_pdt.background_position = new_position;
+                                    onchange.call(new_pdt);
+                                }
+                            },
+                            if new_position == position { "●" } else { "○" }
+                        }
+                    }
+                }
+
+                label {
+                    { t!("settings.background_blur").to_string() }
+                    fieldset {
+                        role: "group",
+                        NumberedValidatedLengthInput {
+                            value: blur.unwrap_or(CssSize::Null),
+                            placeholder: "0",
+                            onchange: move |new_blur: CssSize| {
+                                // No blur is stored as no setting
+                                let mut new_pdt = blur_pdt.clone();
+                                new_pdt.background_blur = Some(new_blur).filter(|blur| !blur.is_null());
+                                onchange.call(new_pdt);
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                ExamplePresentationViewer {
+                    presentation_design: preview_design,
+                    width: 320,
+                }
+            }
+        }
+    )
+}
+
+/// An editor for a background gradient with its kind, angle and color stops
+#[component]
+fn GradientEditor(
+    gradient: BackgroundGradient,
+    onchange: EventHandler<BackgroundGradient>,
+) -> Element {
+    let mut gradient = use_signal(|| gradient);
+    let color_stops_count = gradient.read().color_stops.len();
+
+    rsx!(
+        fieldset {
+            role: "group",
+            select {
+                name: "gradient_kind",
+                aria_label: t!("settings.gradient.kind").to_string(),
+                onchange: move |event| {
+                    match event.value().as_str() {
+                        "linear" => gradient.write().kind = GradientKind::Linear,
+                        "radial" => gradient.write().kind = GradientKind::Radial,
+                        other => tracing::error!("Invalid option for the gradient kind selected, the value is: {}", other)
+                    };
+                    onchange.call(gradient());
+                },
+                option {
+                    value: "linear",
+                    selected: gradient().kind == GradientKind::Linear,
+                    { t!("settings.gradient.linear").to_string() }
+                }
+                option {
+                    value: "radial",
+                    selected: gradient().kind == GradientKind::Radial,
+                    { t!("settings.gradient.radial").to_string() }
+                }
+            }
+        }
+
+        // The angle is only used by linear gradients
+        if gradient().kind == GradientKind::Linear {
+            label {
+                span { { format!("{}: {}°", t!("settings.gradient.angle"), gradient.read().angle) } }
+                input {
+                    type: "range",
+                    min: 0,
+                    max: 360,
+                    value: gradient.read().angle,
+                    oninput: move |event| {
+                        gradient.write().angle = event.value().parse().unwrap_or(180);
+                        onchange.call(gradient());
+                    }
+                }
+            }
+        }
+
+        for (index, stop) in gradient().color_stops.into_iter().enumerate() {
+            fieldset {
+                role: "group",
+                input {
+                    type: "color",
+                    aria_label: t!("settings.color").to_string(),
+                    value: stop.color.to_hex(),
+                    onchange: move |event| {
+                        if let Some(color) = event.value().to_rgb8() {
+                            if let Some(stop) = gradient.write().color_stops.get_mut(index) {
+                                stop.color = color;
+                            }
+                            onchange.call(gradient());
+                        }
+                    }
+                }
+                input {
+                    type: "range",
+                    min: 0,
+                    max: 100,
+                    aria_label: t!("settings.gradient.position").to_string(),
+                    value: stop.position,
+                    oninput: move |event| {
+                        if let Some(stop) = gradient.write().color_stops.get_mut(index) {
+                            stop.position = event.value().parse().unwrap_or(0);
+                        }
+                        onchange.call(gradient());
+                    }
+                }
+                if color_stops_count > 2 {
+                    button {
+                        class: "outline secondary",
+                        r#type: "button",
+                        onclick: move |_| {
+                            gradient.write().remove_color_stop(index);
+                            onchange.call(gradient());
+                        },
+                        { t!("settings.gradient.remove_color").to_string() }
+                    }
+                }
+            }
+            PaletteSwatches {
+                onselect: move |color: RGBA8| {
+                    if let Some(stop) = gradient.write().color_stops.get_mut(index) {
+                        stop.color = RGB8::new(color.r, color.g, color.b);
+                    }
+                    onchange.call(gradient());
+                }
+            }
+        }
+
+        button {
+            class: "outline secondary smaller-buttons",
+            r#type: "button",
+            onclick: move |_| {
+                gradient.write().add_color_stop();
+                onchange.call(gradient());
+            },
+            { t!("settings.gradient.add_color").to_string() }
+        }
+    )
+}
+
+/// A component which allows the selection of a picture
+#[component]
+pub fn PictureSelector(
+    default_selection_index: Option<usize>,
+
+    /// This can be given if an image is already set up. It will then be selected as default.
+    already_selected_image_path: Option<PathBuf>,
+
+    /// The event will be called if a picture has been selected
+    onchange: Option<EventHandler<ImageSourceFile>>,
+
+    /// If given, several pictures can be selected. A click adds or removes a picture, and the
+    /// pictures are ordered in the order of selection (they can be moved up afterwards).
+    selected_images: Option<Vec<ImageSourceFile>>,
+
+    /// The event will be called with all selected pictures if several pictures can be selected
+    onchange_selection: Option<EventHandler<Vec<ImageSourceFile>>>,
+) -> Element {
+    let source_files: Signal<Vec<SourceFile>> = use_context();
+    let image_source_files: Memo<Vec<ImageSourceFile>> = use_memo(move || {
+        source_files()
+            .into_iter()
+            .filter_map(ImageSourceFile::new)
+            .collect()
+    });
+    let mut selection_index = use_signal(|| default_selection_index);
+    let is_multi_selection = selected_images.is_some();
+    let mut selected_images = use_signal(|| selected_images.unwrap_or_default());
+
+    let mut change_selection = move |change: &dyn Fn(&mut Vec<ImageSourceFile>)| {
+        change(&mut selected_images.write());
+        if let Some(onchange_selection) = onchange_selection {
+            onchange_selection.call(selected_images());
+        }
+    };
+
+    rsx! {
+        for (idx, source_file) in image_source_files().iter().enumerate() {
+            PictureSelectorItem {
+                source_file: source_file.clone(),
+                height: "130px",
+                max_width: "200px",
+                active: if is_multi_selection {
+                    selected_images.read().contains(source_file)
+                } else if let Some(selection_index) = selection_index() {
+                    selection_index == idx
+                } else if Some(source_file.clone().into_inner().path) == already_selected_image_path { true }
+                else { false },
+                order_number: selected_images
+                    .read()
+                    .iter()
+                    .position(|image| image == source_file)
+                    .map(|position| position + 1),
+                onclick: move |image_source_file: ImageSourceFile| {
+                    if is_multi_selection {
+                        change_selection(&|images| {
+                            if images.contains(&image_source_file) {
+                                images.retain(|image| *image != image_source_file);
+                            } else {
+                                images.push(image_source_file.clone());
+                            }
+                        });
+                        return;
+                    }
+                    selection_index.set(Some(idx));
+                    if let Some(onchange_event) = onchange {
+                        onchange_event.call(image_source_file);
+                    }
+                }
+            }
+        }
+
+        // The order of the selected pictures
+        if is_multi_selection && !selected_images.read().is_empty() {
+            ol {
+                class: "picture-selection-order",
+                for (position, image) in selected_images().into_iter().enumerate() {
+                    li {
+                        { image.as_source().name.clone() }
+                        if position > 0 {
+                            button {
+                                class: "outline secondary smaller-buttons",
+                                r#type: "button",
+                                aria_label: t!("settings.move_up").to_string(),
+                                onclick: move |_| change_selection(&|images| images.swap(position - 1, position)),
+                                "↑"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A component representing a single item (picture) in the [PictureSelector] component
+#[component]
+fn PictureSelectorItem(
+    max_width: String,
+    height: String,
+    source_file: ImageSourceFile,
+    onclick: EventHandler<ImageSourceFile>,
+    active: bool,
+    /// The position of the picture in a selection of several pictures
+    order_number: Option<usize>,
+) -> Element {
+    // We need a source file signal here due to the use in the closure
+    let sourcefile_signal = use_signal(|| source_file);
+    rsx! {
+        button {
+            role: "button",
+            class: if active { "outline picture-selector-item" } else { "outline secondary picture-selector-item" },
+            "data-tooltip": sourcefile_signal().into_inner().name,
+            onclick: move |event| {
+                onclick.call(sourcefile_signal());
+                event.prevent_default();
+            },
+            if let Some(order_number) = order_number {
+                span { class: "picture-order-number", "{order_number}" }
+            }
+            img {
+                max_width: "180px",
+                height: "100px",
+                src: sourcefile_signal().into_inner().path.to_str().unwrap_or("").to_string(),
+            }
+        }
+    }
+}
+
+/// A component which allows the selection of a video, e.g. as background of a presentation
+#[component]
+fn VideoSelector(
+    /// This can be given if a video is already set up. It will then be selected as default.
+    already_selected_video_path: Option<PathBuf>,
+
+    /// The event will be called if a video has been selected
+    onchange: EventHandler<VideoSourceFile>,
+) -> Element {
+    let source_files: Signal<Vec<SourceFile>> = use_context();
+    let video_source_files: Memo<Vec<VideoSourceFile>> = use_memo(move || {
+        source_files()
+            .into_iter()
+            .filter_map(VideoSourceFile::new)
+            .collect()
+    });
+
+    rsx! {
+        for source_file in video_source_files() {
+            button {
+                role: "button",
+                class: if Some(&source_file.as_source().path) == already_selected_video_path.as_ref() {
+                    "outline"
+                } else {
+                    "outline secondary"
+                },
+                "data-tooltip": source_file.as_source().name.clone(),
+                onclick: {
+                    let source_file = source_file.clone();
+                    move |event: Event<MouseData>| {
+                        onchange.call(source_file.clone());
+                        event.prevent_default();
+                    }
+                },
+                // Only the first frame is loaded as preview
+                video {
+                    max_width: "180px",
+                    height: "100px",
+                    muted: true,
+                    preload: "metadata",
+                    src: source_file.as_source().path.to_str().unwrap_or("").to_string(),
+                }
+            }
+        }
+    }
+}
+
+/// A component which allows the setting of padding (left, right, top, bottom)
+#[component]
+fn PaddingInput(
+    default_padding: TopBottomLeftRight,
+    onchange: EventHandler<TopBottomLeftRight>,
+) -> Element {
+    let mut padding: Signal<TopBottomLeftRight> = use_signal(|| default_padding);
+
+    rsx!(
+        div {
+            class: "grid",
+            div {
+                label {
+                    "Left",
+                    fieldset {
+                        role: "group",
+                        NumberedValidatedLengthInput {
                             value: padding().left,
                             placeholder: "left",
                             onchange: move |value| {
@@ -427,6 +1338,650 @@ fn PaddingInput(
     )
 }
 
+/// An editor for the stylesheet of a custom design with a live preview.
+#[component]
+fn CustomCssSettings(
+    /// The CSS of the design
+    css: String,
+
+    /// An event which is called each time when the CSS has been changed
+    onchange: EventHandler<String>,
+
+    /// An event which is called when the design should be reset to a template
+    onrevert: EventHandler<()>,
+) -> Element {
+    let mut settings = use_settings();
+    let mut css = use_signal(|| css);
+    let preview_design = use_memo(move || PresentationDesign {
+        presentation_design_settings: PresentationDesignSettings::Custom(css()),
+        ..Default::default()
+    });
+
+    rsx! {
+        h3 { { t!("settings.custom_css.title").to_string() } }
+        p { { t!("settings.custom_css.description").to_string() } }
+        div {
+            class: "grid",
+            textarea {
+                class: "custom-css-editor",
+                spellcheck: false,
+                rows: 24,
+                value: css(),
+                oninput: move |event| {
+                    css.set(event.value());
+                    onchange.call(css());
+                }
+            }
+            div {
+                ExamplePresentationViewer {
+                    presentation_design: preview_design(),
+                    width: 480,
+                }
+            }
+        }
+        small { { t!("settings.custom_css.important_hint").to_string() } }
+
+        label {
+            input {
+                type: "checkbox",
+                role: "switch",
+                checked: settings.read().allow_external_css_resources,
+                onchange: move |event| {
+                    settings.write().allow_external_css_resources = event.checked();
+                }
+            }
+            { t!("settings.custom_css.allow_external_resources").to_string() }
+        }
+        small { { t!("settings.custom_css.external_resources_hint").to_string() } }
+
+        div {
+            button {
+                class: "outline secondary",
+                onclick: move |event| {
+                    event.prevent_default();
+                    let js = t!("settings.custom_css.confirm_revert").to_string();
+                    async move {
+                        if let Ok(value) = document::eval(&js_yes_no_box(js)).await {
+                            if value.as_bool().unwrap_or(false) {
+                                onrevert.call(());
+                            }
+                        }
+                    }
+                },
+                { t!("settings.custom_css.revert").to_string() }
+            }
+        }
+    }
+}
+
+/// A preview of the design in which the slides of the example can be clicked through. Guides for
+/// the padding and the lower third, the aspect ratio and the zoom can be chosen.
+#[component]
+fn DesignPreviewPanel(presentation_design: PresentationDesign) -> Element {
+    let mut show_safe_area = use_signal(|| true);
+    let mut show_lower_third = use_signal(|| false);
+    // Without a chosen aspect ratio, the preview uses the aspect ratio of the design
+    let mut aspect_ratio: Signal<Option<(u32, u32)>> = use_signal(|| None);
+    let mut zoom_percent = use_signal(|| 100usize);
+
+    rsx!(
+        details {
+            open: true,
+            summary { { t!("settings.design_preview.title").to_string() } }
+            div {
+                label {
+                    input {
+                        type: "checkbox",
+                        role: "switch",
+                        checked: show_safe_area(),
+                        onchange: move |event| show_safe_area.set(event.checked())
+                    }
+                    { t!("settings.design_preview.safe_area").to_string() }
+                }
+                label {
+                    input {
+                        type: "checkbox",
+                        role: "switch",
+                        checked: show_lower_third(),
+                        onchange: move |event| show_lower_third.set(event.checked())
+                    }
+                    { t!("settings.design_preview.lower_third").to_string() }
+                }
+                select {
+                    name: "preview_aspect_ratio",
+                    aria_label: t!("settings.design_preview.aspect_ratio").to_string(),
+                    onchange: move |event| {
+                        match event.value().as_str() {
+                            "design" => aspect_ratio.set(None),
+                            "16:9" => aspect_ratio.set(Some((16, 9))),
+                            "4:3" => aspect_ratio.set(Some((4, 3))),
+                            other => tracing::error!("Invalid option for the aspect ratio selected, the value is: {}", other)
+                        };
+                    },
+                    option {
+                        value: "design",
+                        selected: aspect_ratio().is_none(),
+                        { t!("settings.design_preview.design_aspect_ratio").to_string() }
+                    }
+                    option {
+                        value: "16:9",
+                        selected: aspect_ratio() == Some((16, 9)),
+                        "16:9"
+                    }
+                    option {
+                        value: "4:3",
+                        selected: aspect_ratio() == Some((4, 3)),
+                        "4:3"
+                    }
+                }
+                label {
+                    { format!("{}: {}%", t!("settings.design_preview.zoom"), zoom_percent()) }
+                    input {
+                        type: "range",
+                        min: 50,
+                        max: 200,
+                        step: 10,
+                        value: zoom_percent(),
+                        oninput: move |event| zoom_percent.set(event.value().parse().unwrap_or(100))
+                    }
+                }
+            }
+            ExamplePresentationViewer {
+                presentation_design,
+                width: 480 * zoom_percent() / 100,
+                aspect_ratio: aspect_ratio(),
+                show_safe_area: show_safe_area(),
+                show_lower_third: show_lower_third(),
+            }
+            div { class: "clearBoth" }
+            small { { t!("settings.design_preview.navigation_hint").to_string() } }
+        }
+    )
+}
+
+/// A selector for the aspect ratio of a design. Custom aspect ratios are entered as width and
+/// height.
+#[component]
+fn AspectRatioSelector(aspect_ratio: AspectRatio, onchange: EventHandler<AspectRatio>) -> Element {
+    let (custom_width, custom_height) = aspect_ratio.ratio();
+    rsx!(
+        label {
+            { t!("settings.aspect_ratio.title").to_string() }
+            select {
+                name: "aspect_ratio",
+                onchange: move |event| {
+                    match event.value().as_str() {
+                        "16:9" => onchange.call(AspectRatio::Ratio16x9),
+                        "4:3" => onchange.call(AspectRatio::Ratio4x3),
+                        "16:10" => onchange.call(AspectRatio::Ratio16x10),
+                        "custom" => onchange.call(AspectRatio::Custom {
+                            width: custom_width,
+                            height: custom_height,
+                        }),
+                        other => tracing::error!("Invalid option for the aspect ratio selected, the value is: {}", other)
+                    };
+                },
+                option {
+                    value: "16:9",
+                    selected: aspect_ratio == AspectRatio::Ratio16x9,
+                    "16:9"
+                }
+                option {
+                    value: "4:3",
+                    selected: aspect_ratio == AspectRatio::Ratio4x3,
+                    "4:3"
+                }
+                option {
+                    value: "16:10",
+                    selected: aspect_ratio == AspectRatio::Ratio16x10,
+                    "16:10"
+                }
+                option {
+                    value: "custom",
+                    selected: matches!(aspect_ratio, AspectRatio::Custom { .. }),
+                    { t!("settings.aspect_ratio.custom").to_string() }
+                }
+            }
+        }
+        if matches!(aspect_ratio, AspectRatio::Custom { .. }) {
+            fieldset {
+                role: "group",
+                input {
+                    type: "number",
+                    min: 1,
+                    aria_label: t!("settings.aspect_ratio.width").to_string(),
+                    value: custom_width,
+                    onchange: move |event| {
+                        if let Ok(width) = event.value().parse::<u32>() {
+                            onchange.call(AspectRatio::Custom { width: width.max(1), height: custom_height });
+                        }
+                    }
+                }
+                input {
+                    type: "number",
+                    min: 1,
+                    aria_label: t!("settings.aspect_ratio.height").to_string(),
+                    value: custom_height,
+                    onchange: move |event| {
+                        if let Ok(height) = event.value().parse::<u32>() {
+                            onchange.call(AspectRatio::Custom { width: custom_width, height: height.max(1) });
+                        }
+                    }
+                }
+            }
+        }
+        small { { t!("settings.aspect_ratio.hint").to_string() } }
+    )
+}
+
+/// The settings of the watermark of a design: the picture, its corner, size and opacity.
+#[component]
+fn WatermarkSettings(
+    /// The watermark of the design, if it has one
+    watermark: Option<Watermark>,
+
+    /// An event which is called each time when the watermark has been changed
+    onchange: EventHandler<Option<Watermark>>,
+) -> Element {
+    // The watermark is only created when a picture has been chosen
+    let mut use_watermark: Signal<bool> = use_signal(|| watermark.is_some());
+
+    rsx!(
+        h4 { { t!("settings.watermark.title").to_string() } }
+        label {
+            input {
+                type: "checkbox",
+                role: "switch",
+                checked: use_watermark(),
+                onchange: move |event| {
+                    use_watermark.set(event.checked());
+                    if !event.checked() {
+                        onchange.call(None);
+                    }
+                }
+            }
+            { t!("settings.watermark.show").to_string() }
+        }
+        if use_watermark() {
+            PictureSelector {
+                onchange: {
+                    let watermark = watermark.clone();
+                    move |image: ImageSourceFile| {
+                        let new_watermark = match watermark.clone() {
+                            Some(watermark) => Watermark { image, ..watermark },
+                            None => Watermark::new(image),
+                        };
+                        onchange.call(Some(new_watermark));
+                    }
+                },
+                already_selected_image_path: watermark.as_ref().map(|watermark| watermark.image.as_source().path.clone()),
+            }
+            if let Some(current) = watermark.clone() {
+                fieldset {
+                    role: "group",
+                    OverlayCornerSelector {
+                        default: current.corner,
+                        onchange: {
+                            let current = current.clone();
+                            move |corner: OverlayCorner| onchange.call(Some(Watermark { corner, ..current.clone() }))
+                        }
+                    }
+                    NumberedValidatedLengthInput {
+                        value: current.size.clone(),
+                        placeholder: "".to_string(),
+                        onchange: {
+                            let current = current.clone();
+                            move |size: CssSize| onchange.call(Some(Watermark { size, ..current.clone() }))
+                        }
+                    }
+                }
+                label {
+                    span { { format!("{}: {}%", t!("settings.watermark.opacity"), current.opacity) } }
+                    input {
+                        type: "range",
+                        min: 0,
+                        max: 100,
+                        value: current.opacity,
+                        oninput: move |event| {
+                            let opacity = event.value().parse().unwrap_or(100);
+                            onchange.call(Some(Watermark { opacity, ..current.clone() }));
+                        }
+                    }
+                }
+                small { { t!("settings.watermark.hint").to_string() } }
+            }
+        }
+    )
+}
+
+/// The settings for the distance between the main content and the spoiler and for the size of
+/// the spoiler font, with a preview of a slide which shows a spoiler.
+#[component]
+fn SpoilerSettings(
+    /// The presentation design template which contains the spoiler settings
+    presentation_design_template: PresentationDesignTemplate,
+
+    /// An event which is called when the distance has been changed
+    ondistancechange: EventHandler<CssSize>,
+
+    /// An event which is called when the font size factor has been changed
+    onfactorchange: EventHandler<f64>,
+
+    /// An event which is called when the maximal number of lines has been changed
+    onmaxlineschange: EventHandler<Option<u8>>,
+) -> Element {
+    let preview_slide_settings = use_signal(|| SlideSettings {
+        title_slide: false,
+        show_spoiler: true,
+        ..Default::default()
+    });
+    let distance = presentation_design_template.main_content_spoiler_content_padding.clone();
+    let factor = presentation_design_template.spoiler_content_fontsize_factor;
+    let has_spoiler_font = presentation_design_template.spoiler_index.is_some();
+    let max_lines = presentation_design_template.spoiler_max_lines;
+    let preview_design = PresentationDesign {
+        presentation_design_settings: PresentationDesignSettings::Template(
+            presentation_design_template,
+        ),
+        ..Default::default()
+    };
+
+    rsx!(
+        h4 { { t!("settings.spoiler.title").to_string() } }
+        div {
+            class: "grid",
+            div {
+                label {
+                    { t!("settings.main_spoiler_content_distance").to_string() }
+                    fieldset {
+                        role: "group",
+                        NumberedValidatedLengthInput {
+                            value: distance,
+                            placeholder: "".to_string(),
+                            onchange: move |new_value| ondistancechange.call(new_value)
+                        }
+                    }
+                }
+                label {
+                    { format!("{}: {}%",
+                        t!("settings.spoiler.fontsize_factor"),
+                        (factor * 100.0).round()) }
+                    input {
+                        type: "range",
+                        min: 20,
+                        max: 100,
+                        step: 5,
+                        value: (factor * 100.0).round(),
+                        disabled: has_spoiler_font,
+                        oninput: move |event| {
+                            let percent: f64 = event.value().parse().unwrap_or(60.0);
+                            onfactorchange.call(percent / 100.0);
+                        }
+                    }
+                }
+                small {
+                    if has_spoiler_font {
+                        { t!("settings.spoiler.fontsize_factor_spoiler_font_hint").to_string() }
+                    } else {
+                        { t!("settings.spoiler.fontsize_factor_hint").to_string() }
+                    }
+                }
+                label {
+                    input {
+                        type: "checkbox",
+                        role: "switch",
+                        checked: max_lines.is_some(),
+                        onchange: move |event| {
+                            onmaxlineschange.call(event.checked().then_some(DEFAULT_SPOILER_MAX_LINES));
+                        }
+                    }
+                    { t!("settings.spoiler.limit_lines").to_string() }
+                }
+                if let Some(max_lines) = max_lines {
+                    label {
+                        { t!("settings.spoiler.max_lines").to_string() }
+                        input {
+                            type: "number",
+                            min: 1,
+                            max: 20,
+                            value: max_lines,
+                            onchange: move |event| {
+                                if let Ok(lines) = event.value().parse::<u8>() {
+                                    onmaxlineschange.call(Some(lines.clamp(1, 20)));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                ExamplePresentationViewer {
+                    presentation_design: preview_design,
+                    song_slide_settings: preview_slide_settings,
+                    width: 320,
+                }
+            }
+        }
+    )
+}
+
+/// A collapsible section for the settings in which the title slides differ from the other slides
+/// of a design, together with a preview of a title slide and a normal slide.
+#[component]
+fn TitleSlideSettings(
+    /// The design, which is needed for the fonts and the preview
+    presentation_design_template: PresentationDesignTemplate,
+
+    /// An event which is called each time when the overrides have been changed
+    onchange: EventHandler<Option<TitleSlideOverrides>>,
+
+    /// An event which is called each time when the emphasis of the titles has been changed
+    onemphasischange: EventHandler<Option<TitleEmphasis>>,
+) -> Element {
+    let mut overrides: Signal<Option<TitleSlideOverrides>> =
+        use_signal(|| presentation_design_template.title_slide_overrides.clone());
+    let title_emphasis = presentation_design_template.title_emphasis.clone();
+    let headline_color = presentation_design_template.get_default_headline_font().color;
+    let mut use_own_background: Signal<bool> =
+        use_signal(|| overrides().is_some_and(|overrides| overrides.background_image.is_some()));
+    let font_count = presentation_design_template.fonts.len();
+    let design_vertical_alignment = presentation_design_template.vertical_alignment.clone();
+    let preview_design = PresentationDesign {
+        presentation_design_settings: PresentationDesignSettings::Template(
+            presentation_design_template,
+        ),
+        ..Default::default()
+    };
+
+    rsx!(
+        details {
+            class: "title-slide-settings",
+            summary { { t!("settings.title_slide.title").to_string() } }
+            label {
+                input {
+                    type: "checkbox",
+                    role: "switch",
+                    checked: overrides().is_some(),
+                    onchange: move |event| {
+                        overrides.set(event.checked().then(TitleSlideOverrides::default));
+                        use_own_background.set(false);
+                        onchange.call(overrides());
+                    }
+                }
+                { t!("settings.title_slide.use_overrides").to_string() }
+            }
+
+            if let Some(current) = overrides() {
+                label {
+                    { t!("settings.title_slide.headline_font").to_string() }
+                    select {
+                        name: "title_slide_headline_font",
+                        onchange: move |event| {
+                            // The empty value keeps the headline font of the design
+                            let index = event.value().parse::<u16>().ok();
+                            if let Some(overrides) = overrides.write().as_mut() {
+                                overrides.headline_index = index;
+                            }
+                            onchange.call(overrides());
+                        },
+                        option {
+                            value: "",
+                            selected: current.headline_index.is_none(),
+                            { t!("settings.title_slide.same_as_design").to_string() }
+                        }
+                        for index in 0..font_count {
+                            option {
+                                value: "{index}",
+                                selected: current.headline_index == Some(index as u16),
+                                { t!("settings.title_slide.font", number = index + 1).to_string() }
+                            }
+                        }
+                    }
+                }
+
+                h5 { { t!("settings.vertical_alignment.title").to_string() } }
+                VerticalAlignmentSelector {
+                    default: current.vertical_alignment.clone().unwrap_or(design_vertical_alignment),
+                    onchange: move |data| {
+                        if let Some(overrides) = overrides.write().as_mut() {
+                            overrides.vertical_alignment = Some(data);
+                        }
+                        onchange.call(overrides());
+                    }
+                }
+
+                label {
+                    input {
+                        type: "checkbox",
+                        role: "switch",
+                        checked: use_own_background,
+                        onchange: move |event| {
+                            use_own_background.set(event.checked());
+                            if let Some(overrides) = overrides.write().as_mut() {
+                                overrides.background_image = None;
+                            }
+                            onchange.call(overrides());
+                        }
+                    }
+                    { t!("settings.title_slide.own_background").to_string() }
+                }
+                if use_own_background() {
+                    PictureSelector {
+                        onchange: move |background_image| {
+                            if let Some(overrides) = overrides.write().as_mut() {
+                                overrides.background_image = Some(BackgroundMedia::Image(background_image));
+                            }
+                            onchange.call(overrides());
+                        },
+                        already_selected_image_path: current.background_image.map(|media| media.as_source().path.clone()),
+                    }
+                }
+            }
+
+            // Emphasis of the first letter or word of the titles
+            h5 { { t!("settings.title_emphasis.title").to_string() } }
+            select {
+                name: "title_emphasis",
+                aria_label: t!("settings.title_emphasis.title").to_string(),
+                onchange: {
+                    let title_emphasis = title_emphasis.clone();
+                    move |event: Event<FormData>| {
+                        let unit = match event.value().as_str() {
+                            "none" => None,
+                            "first_letter" => Some(TitleEmphasisUnit::FirstLetter),
+                            "first_word" => Some(TitleEmphasisUnit::FirstWord),
+                            other => {
+                                tracing::error!("Invalid option for the title emphasis selected, the value is: {}", other);
+                                return;
+                            }
+                        };
+                        onemphasischange.call(unit.map(|unit| TitleEmphasis {
+                            unit,
+                            ..title_emphasis.clone().unwrap_or_default()
+                        }));
+                    }
+                },
+                option {
+                    value: "none",
+                    selected: title_emphasis.is_none(),
+                    { t!("settings.title_emphasis.none").to_string() }
+                }
+                option {
+                    value: "first_letter",
+                    selected: title_emphasis.as_ref().is_some_and(|emphasis| emphasis.unit == TitleEmphasisUnit::FirstLetter),
+                    { t!("settings.title_emphasis.first_letter").to_string() }
+                }
+                option {
+                    value: "first_word",
+                    selected: title_emphasis.as_ref().is_some_and(|emphasis| emphasis.unit == TitleEmphasisUnit::FirstWord),
+                    { t!("settings.title_emphasis.first_word").to_string() }
+                }
+            }
+            if let Some(emphasis) = title_emphasis.clone() {
+                label {
+                    { format!("{}: {}%",
+                        t!("settings.title_emphasis.scale"),
+                        (emphasis.scale * 100.0).round()) }
+                    input {
+                        type: "range",
+                        min: 100,
+                        max: 400,
+                        step: 10,
+                        value: (emphasis.scale * 100.0).round(),
+                        oninput: {
+                            let emphasis = emphasis.clone();
+                            move |event: Event<FormData>| {
+                                let percent: f64 = event.value().parse().unwrap_or(150.0);
+                                onemphasischange.call(Some(TitleEmphasis {
+                                    scale: percent / 100.0,
+                                    ..emphasis.clone()
+                                }));
+                            }
+                        }
+                    }
+                }
+                label {
+                    input {
+                        type: "checkbox",
+                        role: "switch",
+                        checked: emphasis.color.is_some(),
+                        onchange: {
+                            let emphasis = emphasis.clone();
+                            move |event: Event<FormData>| {
+                                onemphasischange.call(Some(TitleEmphasis {
+                                    color: event.checked().then_some(headline_color),
+                                    ..emphasis.clone()
+                                }));
+                            }
+                        }
+                    }
+                    { t!("settings.title_emphasis.own_color").to_string() }
+                }
+                if let Some(color) = emphasis.color {
+                    input {
+                        type: "color",
+                        aria_label: t!("settings.color").to_string(),
+                        value: color.to_hex(),
+                        onchange: move |event| {
+                            if let Some(new_color) = event.value().to_rgb8() {
+                                onemphasischange.call(Some(TitleEmphasis {
+                                    color: Some(RGBA8::new(new_color.r, new_color.g, new_color.b, color.a)),
+                                    ..emphasis.clone()
+                                }));
+                            }
+                        }
+                    }
+                }
+            }
+
+            ExampleTitleSlidePreview {
+                presentation_design: preview_design,
+                width: 320,
+            }
+        }
+    )
+}
+
 /// Returns a [CssSize::Null] if the value is `0.0`. Else, the original value is cloned.
 fn get_nullified_css_size(css_size: CssSize) -> CssSize {
     match css_size.get_float() {
@@ -474,3 +2029,205 @@ fn VerticalAlignmentSelector(
         }
     )
 }
+
+/// Returns the translation key of the name of a text block.
+fn slide_block_label(block: SlideBlock) -> &'static str {
+    match block {
+        SlideBlock::Headline => "settings.slide_layout.headline",
+        SlideBlock::MainContent => "settings.slide_layout.main_content",
+        SlideBlock::Spoiler => "settings.slide_layout.spoiler",
+        SlideBlock::Meta => "settings.slide_layout.meta",
+    }
+}
+
+/// A list of the text blocks of the slides which can be moved up and down. Every block except
+/// the meta footer can get its own vertical alignment.
+#[component]
+fn SlideLayoutSettings(layout: SlideLayout, onchange: EventHandler<SlideLayout>) -> Element {
+    let blocks = layout.blocks();
+    let blocks_count = blocks.len();
+    let is_customized = layout.is_customized();
+
+    rsx!(
+        h5 { { t!("settings.slide_layout.title").to_string() } }
+        for (idx, block_layout) in blocks.into_iter().enumerate() {
+            div {
+                key: "{block_layout.block:?}",
+                class: "slide-layout-entry",
+                span {
+                    class: "slide-layout-block",
+                    { t!(slide_block_label(block_layout.block)).to_string() }
+                }
+                if block_layout.block.can_be_aligned() {
+                    select {
+                        name: "slide_block_alignment",
+                        aria_label: t!("settings.slide_layout.alignment").to_string(),
+                        onchange: {
+                            let mut layout = layout.clone();
+                            move |event: Event<FormData>| {
+                                let alignment = match event.value().as_str() {
+                                    "design" => None,
+                                    "top" => Some(VerticalAlign::Top),
+                                    "middle" => Some(VerticalAlign::Middle),
+                                    "bottom" => Some(VerticalAlign::Bottom),
+                                    other => {
+                                        tracing::error!("Invalid option for the block alignment selected, the value is: {}", other);
+                                        return;
+                                    }
+                                };
+                                layout.set_vertical_alignment(block_layout.block, alignment);
+                                onchange.call(layout.clone());
+                            }
+                        },
+                        option {
+                            value: "design",
+                            selected: block_layout.vertical_alignment.is_none(),
+                            { t!("settings.slide_layout.design_alignment").to_string() }
+                        }
+                        option {
+                            value: "top",
+                            selected: block_layout.vertical_alignment == Some(VerticalAlign::Top),
+                            { t!("settings.vertical_alignment.top").to_string() }
+                        }
+                        option {
+                            value: "middle",
+                            selected: block_layout.vertical_alignment == Some(VerticalAlign::Middle),
+                            { t!("settings.vertical_alignment.middle").to_string() }
+                        }
+                        option {
+                            value: "bottom",
+                            selected: block_layout.vertical_alignment == Some(VerticalAlign::Bottom),
+                            { t!("settings.vertical_alignment.bottom").to_string() }
+                        }
+                    }
+                }
+                button {
+                    class: "outline secondary smaller-buttons",
+                    r#type: "button",
+                    disabled: idx == 0,
+                    aria_label: t!("settings.move_up").to_string(),
+                    onclick: {
+                        let mut layout = layout.clone();
+                        move |_| {
+                            layout.move_block(idx, idx.saturating_sub(1));
+                            onchange.call(layout.clone());
+                        }
+                    },
+                    "↑"
+                }
+                button {
+                    class: "outline secondary smaller-buttons",
+                    r#type: "button",
+                    disabled: idx + 1 >= blocks_count,
+                    aria_label: t!("settings.move_down").to_string(),
+                    onclick: {
+                        let mut layout = layout.clone();
+                        move |_| {
+                            layout.move_block(idx, idx + 1);
+                            onchange.call(layout.clone());
+                        }
+                    },
+                    "↓"
+                }
+            }
+        }
+        if is_customized {
+            button {
+                class: "outline secondary smaller-buttons",
+                r#type: "button",
+                onclick: move |_| onchange.call(SlideLayout::default()),
+                { t!("settings.slide_layout.reset").to_string() }
+            }
+        }
+        small { { t!("settings.slide_layout.hint").to_string() } }
+    )
+}
+
+/// A component for selecting whether and how the slide counter is shown
+#[component]
+fn SlideCounterStyleSelector(
+    default: SlideCounterStyle,
+    onchange: EventHandler<SlideCounterStyle>,
+) -> Element {
+    let mut value_signal = use_signal(|| default);
+    rsx!(
+        select {
+            name: "slide_counter",
+            required: true,
+            aria_label: t!("settings.slide_counter.title").to_string(),
+            onchange: move |event| {
+                match event.value().as_str() {
+                    "hidden" => value_signal.set(SlideCounterStyle::Hidden),
+                    "number" => value_signal.set(SlideCounterStyle::Number),
+                    "progress_bar" => value_signal.set(SlideCounterStyle::ProgressBar),
+                    "number_and_progress_bar" => value_signal.set(SlideCounterStyle::NumberAndProgressBar),
+                    other => tracing::error!("Invalid option for the slide counter selected, the value is: {}", other)
+                    };
+                onchange.call(value_signal());
+            },
+            option {
+                value: "hidden",
+                selected: value_signal() == SlideCounterStyle::Hidden,
+                { t!("settings.slide_counter.hidden").to_string() }
+            }
+            option {
+                value: "number",
+                selected: value_signal() == SlideCounterStyle::Number,
+                { t!("settings.slide_counter.number").to_string() }
+            }
+            option {
+                value: "progress_bar",
+                selected: value_signal() == SlideCounterStyle::ProgressBar,
+                { t!("settings.slide_counter.progress_bar").to_string() }
+            }
+            option {
+                value: "number_and_progress_bar",
+                selected: value_signal() == SlideCounterStyle::NumberAndProgressBar,
+                { t!("settings.slide_counter.number_and_progress_bar").to_string() }
+            }
+        }
+    )
+}
+
+/// A component for selecting the corner of the slide in which an overlay is placed
+#[component]
+fn OverlayCornerSelector(default: OverlayCorner, onchange: EventHandler<OverlayCorner>) -> Element {
+    let mut value_signal = use_signal(|| default);
+    rsx!(
+        select {
+            name: "overlay_corner",
+            required: true,
+            aria_label: t!("settings.overlay_corner.title").to_string(),
+            onchange: move |event| {
+                match event.value().as_str() {
+                    "top_left" => value_signal.set(OverlayCorner::TopLeft),
+                    "top_right" => value_signal.set(OverlayCorner::TopRight),
+                    "bottom_left" => value_signal.set(OverlayCorner::BottomLeft),
+                    "bottom_right" => value_signal.set(OverlayCorner::BottomRight),
+                    other => tracing::error!("Invalid option for the overlay corner selected, the value is: {}", other)
+                    };
+                onchange.call(value_signal());
+            },
+            option {
+                value: "top_left",
+                selected: value_signal() == OverlayCorner::TopLeft,
+                { t!("settings.overlay_corner.top_left").to_string() }
+            }
+            option {
+                value: "top_right",
+                selected: value_signal() == OverlayCorner::TopRight,
+                { t!("settings.overlay_corner.top_right").to_string() }
+            }
+            option {
+                value: "bottom_left",
+                selected: value_signal() == OverlayCorner::BottomLeft,
+                { t!("settings.overlay_corner.bottom_left").to_string() }
+            }
+            option {
+                value: "bottom_right",
+                selected: value_signal() == OverlayCorner::BottomRight,
+                { t!("settings.overlay_corner.bottom_right").to_string() }
+            }
+        }
+    )
+}
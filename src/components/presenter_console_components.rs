@@ -1,9 +1,11 @@
 //! This module contains the components for the Presenter Console window.
 //! The presenter console shows the current slide text, a live preview, and navigation controls.
 
-use crate::logic::presentation::{get_markdown_html, get_picture_path, html_to_plain_text};
+use crate::logic::presentation::{
+    get_markdown_html, get_multi_language_texts, get_picture_path, html_to_plain_text,
+};
 use crate::logic::settings::{PresentationDesign, PresenterConsoleView, use_settings};
-use crate::logic::states::RunningPresentation;
+use crate::logic::states::{RunningPresentation, RunningPresentationId, find_presentation, find_presentation_mut};
 #[cfg(target_arch = "wasm32")]
 use crate::logic::sync::{
     SYNC_KEY_ACTIVE, SYNC_KEY_POSITION, SYNC_KEY_POSITION_FROM_CONSOLE, SYNC_KEY_PRESENTATION,
@@ -36,8 +38,19 @@ pub fn PresenterConsolePage() -> Element {
     // Only acquire the navigator if a router is present to avoid panicking.
     let nav = if is_main_window { Some(navigator()) } else { None };
 
+    // A console window gets the id of its presentation as root context, in the main window
+    // the console belongs to the most recently started presentation
+    let presentation_id_context = try_use_context::<RunningPresentationId>();
+    let shown_presentation = match presentation_id_context {
+        Some(RunningPresentationId(id)) => find_presentation(&running_presentations.read(), id).cloned(),
+        None => running_presentations.read().last().cloned(),
+    };
+    let Some(shown_presentation) = shown_presentation else {
+        return rsx! {};
+    };
+    let presentation_id = shown_presentation.id;
     let mut running_presentation: Signal<RunningPresentation> =
-        use_signal(move || running_presentations.get(0).unwrap().clone());
+        use_signal(move || shown_presentation);
 
     // View mode signal, initialized from settings
     let settings = use_settings();
@@ -58,19 +71,21 @@ pub fn PresenterConsolePage() -> Element {
     // All comparisons use `eq_ignoring_scroll` to exclude `markdown_scroll_position`,
     // which is synced independently by `MarkdownSlideComponent`.
     //
-    // Also monitors whether the shared signal was cleared (presentation ended)
-    // and navigates back or closes the window accordingly.
+    // Also monitors whether the presentation was removed from the shared signal
+    // (presentation ended) and navigates back or closes the window accordingly.
     #[cfg(feature = "desktop")]
     use_future(move || async move {
-        let mut last_seen_shared = running_presentations.peek()
-            .first().cloned().unwrap_or_else(|| running_presentation.peek().clone());
+        let mut last_seen_shared = find_presentation(&running_presentations.peek(), presentation_id)
+            .cloned().unwrap_or_else(|| running_presentation.peek().clone());
         let mut last_seen_local = running_presentation.peek().clone();
 
         loop {
             let _ = document::eval("await new Promise(r => setTimeout(r, 50))").await;
 
-            // Presentation ended (signal cleared by PresentationPage's use_drop)
-            if running_presentations.peek().is_empty() {
+            let current_shared = find_presentation(&running_presentations.peek(), presentation_id)
+                .cloned();
+            // Presentation ended (removed by PresentationPage's use_drop)
+            let Some(shared_rp) = current_shared else {
                 if is_main_window {
                     if let Some(nav) = &nav {
                         nav.replace(crate::Route::Selection {});
@@ -79,33 +94,28 @@ pub fn PresenterConsolePage() -> Element {
                     dioxus::desktop::window().close();
                 }
                 return;
-            }
-
-            let current_shared = running_presentations.peek()
-                .first().cloned();
+            };
             let current_local = running_presentation.peek().clone();
 
-            if let Some(ref shared_rp) = current_shared {
-                // Shared changed (presentation window pushed an update) → pull into local
-                if !shared_rp.eq_ignoring_scroll(&last_seen_shared) {
-                    last_seen_shared = shared_rp.clone();
-                    if !shared_rp.eq_ignoring_scroll(&current_local) {
-                        last_seen_local = shared_rp.clone();
-                        running_presentation.set(shared_rp.clone());
-                    }
+            // Shared changed (presentation window pushed an update) → pull into local
+            if !shared_rp.eq_ignoring_scroll(&last_seen_shared) {
+                last_seen_shared = shared_rp.clone();
+                if !shared_rp.eq_ignoring_scroll(&current_local) {
+                    last_seen_local = shared_rp.clone();
+                    running_presentation.set(shared_rp.clone());
                 }
-                // Local changed (user clicked next/prev in console) → push to shared
-                else if !current_local.eq_ignoring_scroll(&last_seen_local) {
-                    last_seen_local = current_local.clone();
-                    if !current_local.eq_ignoring_scroll(shared_rp) {
-                        last_seen_shared = current_local.clone();
-                        if let Some(first) = running_presentations.write().first_mut() {
-                            // Preserve the shared scroll position to avoid clobbering
-                            // scroll-sync updates with potentially stale local state.
-                            let preserved_scroll = first.markdown_scroll_position;
-                            *first = current_local;
-                            first.markdown_scroll_position = preserved_scroll;
-                        }
+            }
+            // Local changed (user clicked next/prev in console) → push to shared
+            else if !current_local.eq_ignoring_scroll(&last_seen_local) {
+                last_seen_local = current_local.clone();
+                if !current_local.eq_ignoring_scroll(&shared_rp) {
+                    last_seen_shared = current_local.clone();
+                    if let Some(shared) = find_presentation_mut(&mut running_presentations.write(), presentation_id) {
+                        // Preserve the shared scroll position to avoid clobbering
+                        // scroll-sync updates with potentially stale local state.
+                        let preserved_scroll = shared.markdown_scroll_position;
+                        *shared = current_local;
+                        shared.markdown_scroll_position = preserved_scroll;
                     }
                 }
             }
@@ -121,18 +131,16 @@ pub fn PresenterConsolePage() -> Element {
     #[cfg(not(feature = "desktop"))]
     use_effect(move || {
         let current = running_presentations.read();
-        if current.is_empty() {
+        let Some(rp) = find_presentation(&current, presentation_id) else {
             if is_main_window {
                 if let Some(nav) = &nav {
                     nav.replace(crate::Route::Selection {});
                 }
             }
             return;
-        }
-        if let Some(rp) = current.first() {
-            if !rp.eq_ignoring_scroll(&running_presentation.peek()) {
-                running_presentation.set(rp.clone());
-            }
+        };
+        if !rp.eq_ignoring_scroll(&running_presentation.peek()) {
+            running_presentation.set(rp.clone());
         }
     });
 
@@ -141,8 +149,8 @@ pub fn PresenterConsolePage() -> Element {
     use_effect(move || {
         let local = running_presentation.read().clone();
         let shared = running_presentations.peek();
-        if let Some(first) = shared.first() {
-            if !first.eq_ignoring_scroll(&local) {
+        if let Some(shared_rp) = find_presentation(&shared, presentation_id) {
+            if !shared_rp.eq_ignoring_scroll(&local) {
                 // We are about to push non-scroll changes from `local` into the
                 // shared signal. However, scroll synchronization writes directly
                 // to the shared signal, and `eq_ignoring_scroll` prevents scroll-
@@ -151,10 +159,10 @@ pub fn PresenterConsolePage() -> Element {
                 // stale local one, preserve the shared `markdown_scroll_position`
                 // when applying the update.
                 drop(shared);
-                if let Some(first) = running_presentations.write().first_mut() {
+                if let Some(shared_rp) = find_presentation_mut(&mut running_presentations.write(), presentation_id) {
                     let mut merged = local.clone();
-                    merged.markdown_scroll_position = first.markdown_scroll_position;
-                    *first = merged;
+                    merged.markdown_scroll_position = shared_rp.markdown_scroll_position;
+                    *shared_rp = merged;
                 }
             }
         }
@@ -209,6 +217,14 @@ pub fn PresenterConsolePage() -> Element {
         });
     }
 
+    // The time (in milliseconds since the UNIX epoch) when the console has been opened
+    let mut started_at: Signal<Option<f64>> = use_signal(|| None);
+    use_future(move || async move {
+        if let Ok(now) = document::eval("return Date.now();").await {
+            started_at.set(now.as_f64());
+        }
+    });
+
     let mut go_to_next_slide = move || {
         running_presentation.write().next_slide();
     };
@@ -231,7 +247,7 @@ pub fn PresenterConsolePage() -> Element {
                     let _ = s.remove_item(SYNC_KEY_POSITION_FROM_CONSOLE);
                 });
         }
-        running_presentations.write().clear();
+        running_presentations.write().retain(|rp| rp.id != presentation_id);
         if is_main_window {
             // nav is Some when is_main_window is true
             nav.as_ref().unwrap().replace(crate::Route::Selection {});
@@ -261,12 +277,19 @@ pub fn PresenterConsolePage() -> Element {
                     Key::Character(ref c) if c == "b" || c == "B" => {
                         running_presentation.write().toggle_black_screen();
                     }
+                    Key::Character(ref c) if c == "w" || c == "W" => {
+                        running_presentation.write().toggle_white_screen();
+                    }
+                    Key::Character(ref c) if c == "l" || c == "L" => {
+                        running_presentation.write().toggle_logo_screen();
+                    }
                     _ => {}
                 }
             },
 
             PresenterHeader {
-                view: view
+                view: view,
+                started_at: started_at
             }
 
             PresenterContent {
@@ -282,16 +305,38 @@ pub fn PresenterConsolePage() -> Element {
     }
 }
 
-/// Status bar at the top of the presenter console with view toggle buttons
+/// Status bar at the top of the presenter console with the elapsed time and view toggle buttons
 #[component]
-fn PresenterHeader(view: Signal<PresenterConsoleView>) -> Element {
+fn PresenterHeader(view: Signal<PresenterConsoleView>, started_at: Signal<Option<f64>>) -> Element {
     let mut settings = use_settings();
     let current_view = *view.read();
+    let mut elapsed_seconds: Signal<u64> = use_signal(|| 0);
+
+    // Update the elapsed time every second
+    use_future(move || async move {
+        loop {
+            let _ = document::eval("await new Promise(r => setTimeout(r, 1000))").await;
+            let Some(start) = *started_at.peek() else {
+                continue;
+            };
+            if let Ok(Some(now)) = document::eval("return Date.now();")
+                .await
+                .map(|value| value.as_f64())
+            {
+                elapsed_seconds.set(((now - start) / 1000.0).max(0.0) as u64);
+            }
+        }
+    });
 
     rsx! {
         header {
             class: "presenter-header",
             h3 { { t!("presenter.status_running").to_string() } }
+            span {
+                class: "presenter-elapsed-time",
+                title: t!("presenter.elapsed_time").to_string(),
+                { format_elapsed_time(elapsed_seconds()) }
+            }
             div {
                 class: "presenter-view-toggle",
                 button {
@@ -317,6 +362,16 @@ fn PresenterHeader(view: Signal<PresenterConsoleView>) -> Element {
     }
 }
 
+/// Formats a duration in seconds as `m:ss` or `h:mm:ss`.
+fn format_elapsed_time(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
 /// Main content area: switches between text+preview layout and grid overview
 #[component]
 fn PresenterContent(
@@ -362,6 +417,17 @@ fn PresenterTextPanel(running_presentation: Signal<RunningPresentation>) -> Elem
                     h4 {
                         class: if ch_idx == current_chapter { "presenter-chapter-title active" } else { "presenter-chapter-title" },
                         { chapter.source_file.name.clone() }
+                        // Chapters which haven't been reached yet can be dropped
+                        if ch_idx > current_chapter {
+                            button {
+                                class: "presenter-remove-chapter",
+                                title: t!("presenter.remove_chapter").to_string(),
+                                onclick: move |_| {
+                                    let _ = running_presentation.write().remove_upcoming_chapter(ch_idx);
+                                },
+                                "×"
+                            }
+                        }
                     }
                     for (sl_idx, slide) in chapter.slides.iter().enumerate() {
                         {
@@ -582,6 +648,24 @@ fn PresenterSlideTextContent(slide_content: SlideContent) -> Element {
                 }
             }
         }
+        SlideContent::MultiLanguageMainContent(multi_language_slide) => {
+            // The presenter sees all languages, independent of the shown languages
+            let texts = get_multi_language_texts(&multi_language_slide);
+            rsx! {
+                div {
+                    class: "slide-text-content",
+                    for text in texts.main_texts {
+                        p { { text } }
+                    }
+                    if let Some(spoiler) = texts.spoiler_texts.into_iter().next().filter(|spoiler| !spoiler.trim().is_empty()) {
+                        p {
+                            class: "slide-text-spoiler",
+                            { spoiler }
+                        }
+                    }
+                }
+            }
+        }
         SlideContent::Empty(_) => {
             rsx! {
                 div {
@@ -645,6 +729,16 @@ fn PresenterPreviewPanel(running_presentation: Signal<RunningPresentation>) -> E
     let current_slide = rp.position.as_ref().map(|p| p.slide_total()).unwrap_or(0);
     let total_slides = rp.total_slides();
 
+    // The next slide is rendered as a smaller static preview with the design of its chapter
+    let next_slide = rp.peek_next_slide().map(|(chapter, slide)| {
+        let design = rp.presentation[chapter]
+            .presentation_design_option
+            .clone()
+            .unwrap_or_default();
+        (slide, design)
+    });
+    let next_zoom_css = format!("zoom: {}%;", (scale_percentage * 0.6).round());
+
     rsx! {
         div {
             class: "presenter-preview-panel",
@@ -672,8 +766,65 @@ fn PresenterPreviewPanel(running_presentation: Signal<RunningPresentation>) -> E
                     { format!("{} / {}", current_slide + 1, total_slides) }
                 }
             }
+            h4 {
+                class: "presenter-next-title",
+                { t!("presenter.next_slide").to_string() }
+            }
+            match next_slide {
+                Some((slide, design)) => rsx! {
+                    div {
+                        class: "presentation-preview",
+                        style: format!("position: relative; width: {}px; height: {}px; border-radius: 4px; overflow: hidden; {}", native_w, native_h, next_zoom_css),
+                        StaticSlideRendererComponent {
+                            slide: slide,
+                            presentation_design: design,
+                        }
+                    }
+                },
+                None => rsx! {
+                    p {
+                        class: "presenter-next-empty",
+                        em { { t!("presenter.no_next_slide").to_string() } }
+                    }
+                },
+            }
+            RemoteControlPanel {}
+        }
+    }
+}
+
+/// Shows the address of the remote control server as QR code, so that a phone can connect to it.
+/// Nothing is shown if the server isn't running.
+#[component]
+fn RemoteControlPanel() -> Element {
+    #[cfg(feature = "desktop")]
+    {
+        let url = use_hook(crate::logic::remote_control::remote_control_url);
+        let Some(url) = url else {
+            return rsx! {};
+        };
+        let qr_code_svg = qrcode::QrCode::new(url.as_bytes())
+            .map(|code| {
+                code.render::<qrcode::render::svg::Color>()
+                    .min_dimensions(180, 180)
+                    .build()
+            })
+            .unwrap_or_default();
+
+        rsx! {
+            details {
+                class: "presenter-remote-control",
+                summary { { t!("presenter.remote_control").to_string() } }
+                div {
+                    class: "presenter-remote-control-qr",
+                    dangerous_inner_html: qr_code_svg,
+                }
+                small { "{url}" }
+            }
         }
     }
+    #[cfg(not(feature = "desktop"))]
+    rsx! {}
 }
 
 /// Bottom control bar with navigation buttons, chapter jump dropdown, and black screen toggle
@@ -690,6 +841,9 @@ fn PresenterControlBar(
         .unwrap_or(0);
     let total_slides = rp.total_slides();
     let is_black = rp.is_black_screen;
+    let is_white = rp.is_white_screen;
+    let is_logo = rp.is_logo_screen;
+    let has_logo = rp.logo_image.is_some();
     let current_chapter = rp.position.as_ref().map(|p| p.chapter()).unwrap_or(0);
     let chapters: Vec<(usize, String)> = rp
         .presentation
@@ -744,6 +898,22 @@ fn PresenterControlBar(
                     },
                     { t!("presenter.black_screen").to_string() }
                 }
+                button {
+                    class: if is_white { "contrast" } else { "outline secondary" },
+                    onclick: move |_| {
+                        running_presentation.write().toggle_white_screen();
+                    },
+                    { t!("presenter.white_screen").to_string() }
+                }
+                if has_logo {
+                    button {
+                        class: if is_logo { "contrast" } else { "outline secondary" },
+                        onclick: move |_| {
+                            running_presentation.write().toggle_logo_screen();
+                        },
+                        { t!("presenter.logo_screen").to_string() }
+                    }
+                }
                 button {
                     class: "outline secondary",
                     onclick: move |_| {
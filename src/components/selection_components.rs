@@ -1,15 +1,32 @@
 //! This module includes the components for song selection
 
-use super::shared_components::{ImageIcon, MarkdownIcon, MusicIcon, PdfIcon, SelectedItemPreview};
+use super::shared_components::{
+    AnnouncementIcon, BlankIcon, ContextMenu, ContextMenuEntry, ImageIcon, MarkdownIcon, MusicIcon, PdfIcon,
+    PresentationIcon, SelectedItemPreview, SelectedItemSlideList,
+    js_alert, js_yes_no_box,
+};
 use crate::TEST_STATE;
+use super::song_slide_settings_components::slide_settings_profile_name;
 use crate::logic::presentation;
-use crate::logic::search::{SearchResult, search_source_files};
+use crate::logic::search::{
+    SEARCH_RESULTS_PAGE_SIZE, SearchGeneration, SearchResult, index_letter, name_matches_query,
+    name_without_song_number, search_source_files_cancellable, song_number,
+};
 use crate::logic::settings::PresentationDesign;
-use crate::logic::settings::SelectionSidebarType;
-use crate::logic::settings::{AfterLastSlide, SlideTimerSettings, SlideTransition};
+use crate::logic::settings::{DuplicateSelectionBehavior, SelectionSidebarType};
+use crate::logic::settings::{
+    AfterLastSlide, PictureFit, ShownLanguages, SlideSettingsProfile, SlideTimerSettings,
+    SlideTransition,
+};
 use crate::logic::sourcefiles::SourceFileType;
-use crate::logic::states::{RunningPresentation, SelectedItemRepresentation};
-use crate::logic::settings::{Settings, default_sidebar_order, use_settings};
+use crate::logic::states::{
+    RunningPresentation, SelectedItemRepresentation, SlideChapter, find_presentation_mut,
+};
+use crate::logic::recently_used::RecentlyUsedSongs;
+use crate::logic::export::{self, HTML_FILE_NAME, PPTX_FILE_EXTENSION};
+use crate::logic::schedule::{SCHEDULE_FILE_EXTENSION, Schedule};
+use crate::logic::verse_order;
+use crate::logic::settings::{Repository, Settings, use_settings};
 use crate::logic::sourcefiles::SourceFile;
 #[cfg(target_arch = "wasm32")]
 use crate::logic::sync::{
@@ -20,28 +37,62 @@ use crate::Route;
 use cantara_songlib::slides::SlideSettings;
 #[cfg(feature = "desktop")]
 use dioxus::desktop::tao;
+#[cfg(feature = "desktop")]
+use rfd::FileDialog;
 use dioxus::html::HasFileData;
 use dioxus::prelude::*;
 use dioxus_free_icons::Icon;
 use dioxus_free_icons::icons::fa_regular_icons::*;
-use dioxus_free_icons::icons::fa_solid_icons::{FaArrowDown, FaArrowUp, FaGear, FaFileImport, FaFileExport, FaPlay};
+use dioxus_free_icons::icons::fa_solid_icons::{FaArrowDown, FaArrowUp, FaBullhorn, FaGear, FaDesktop, FaFileImport, FaFileExport, FaPlay};
 use rust_i18n::t;
+use std::ops::Range;
 use std::rc::Rc;
 
 rust_i18n::i18n!("locales", fallback = "en");
 
+/// The time in milliseconds to wait after the last keystroke before a search is performed
+const SEARCH_DEBOUNCE_MS: u32 = 150;
+
+/// The time in milliseconds between the checks whether the search thread has finished
+#[cfg(not(target_arch = "wasm32"))]
+const SEARCH_POLL_MS: u32 = 20;
+
+/// The time in milliseconds to wait after the last change of the selection before it is autosaved
+const AUTOSAVE_DEBOUNCE_MS: u32 = 1000;
+
+/// The height of a song item in the virtualized song list including its margin (see `.virtual-list` in main.css)
+const SONG_ITEM_HEIGHT_PX: f64 = 48.0;
+
+/// The height of an image item in the virtualized image list including its margin
+const IMAGE_ITEM_HEIGHT_PX: f64 = 360.0;
+
+/// The number of items which are rendered above and below the visible part of a virtualized list
+const VIRTUAL_LIST_BUFFER: usize = 10;
+
 /// Component to display search results
 #[component]
 fn SearchResults(
     search_results: Signal<Vec<SearchResult>>,
     query: Signal<String>,
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
     search_visible: Signal<bool>,
 ) -> Element {
+    let mut settings = use_settings();
+    let mut visible_results_count: Signal<usize> = use_signal(|| SEARCH_RESULTS_PAGE_SIZE);
+
+    // Start with the first page again whenever the results change
+    use_effect(move || {
+        let _ = search_results.read();
+        visible_results_count.set(SEARCH_RESULTS_PAGE_SIZE);
+    });
+
     let results = search_results.read().clone();
     if results.is_empty() {
         return rsx! { div {} };
     }
+    let total_count = results.len();
+    let shown_count = visible_results_count().min(total_count);
 
     let query_str = query.read().clone();
 
@@ -66,8 +117,14 @@ fn SearchResults(
                 }
             },
             h3 { { t!("search.results").to_string() } }
+            if shown_count < total_count {
+                p {
+                    style: "font-size: 0.9em; color: #666;",
+                    { t!("search.showing_results", shown = shown_count, total = total_count).to_string() }
+                }
+            }
 
-            for (index, result) in results.iter().enumerate() {
+            for (index, result) in results.iter().take(shown_count).enumerate() {
                 {
                     let source_file = result.source_file.clone();
                     let matched_content = result.matched_content.clone();
@@ -92,9 +149,13 @@ fn SearchResults(
                                 class: "search-result-title",
                                 style: "font-weight: bold; cursor: pointer;",
                                 onclick: move |_| {
-                                    selected_items.write().push(
-                                        SelectedItemRepresentation::new_with_sourcefile(source_file.clone())
+                                    add_to_selection(
+                                        selected_items,
+                                        active_selected_item_id,
+                                        source_file.clone(),
+                                        &settings.read(),
                                     );
+                                    remember_search_query(&mut settings, &query.read());
                                     // Close search results after selection
                                     search_visible.set(false);
                                 },
@@ -193,6 +254,18 @@ fn SearchResults(
                     }
                 }
             }
+
+            // Further results are only rendered on demand, page by page
+            if shown_count < total_count {
+                div {
+                    class: "search-result",
+                    style: "padding: 5px; cursor: pointer; font-style: italic;",
+                    onclick: move |_| {
+                        visible_results_count += SEARCH_RESULTS_PAGE_SIZE;
+                    },
+                    { t!("search.more_results", count = total_count - shown_count).to_string() }
+                }
+            }
         }
     }
 }
@@ -200,52 +273,139 @@ fn SearchResults(
 #[component]
 pub fn Selection() -> Element {
     let nav = navigator();
-    let settings: Signal<Settings> = use_context();
+    let mut settings: Signal<Settings> = use_context();
 
     let filter_string: Signal<String> = use_signal(|| "".to_string());
     let mut search_results: Signal<Vec<SearchResult>> = use_signal(Vec::new);
     let mut search_visible: Signal<bool> = use_signal(|| false);
 
     let mut source_files: Signal<Vec<SourceFile>> = use_context();
-    let mut selected_items: Signal<Vec<SelectedItemRepresentation>> = use_context();
+    let selected_items: Signal<Vec<SelectedItemRepresentation>> = use_context();
     let active_selected_item_id: Signal<Option<usize>> = use_signal(|| None);
     let active_detailed_item_id: Signal<Option<usize>> = use_signal(|| None);
     let active_selection_filter: Signal<SelectionSidebarType> =
         use_signal(|| SelectionSidebarType::Songs);
     let mut running_presentations: Signal<Vec<RunningPresentation>> = use_context();
+    // Whether the menu for choosing the design of a mirror window is open
+    let mut mirror_menu_open = use_signal(|| false);
+    // Whether the menu for choosing the format of an export is open
+    let mut export_menu_open = use_signal(|| false);
+
+    // The source files which are currently presented, together with the id of their presentation
+    let live_source_files: Memo<Vec<(usize, SourceFile)>> = use_memo(move || {
+        running_presentations
+            .read()
+            .iter()
+            .filter_map(|rp| rp.current_source_file().map(|file| (rp.id, file.clone())))
+            .collect()
+    });
 
     // Track drag-over state for the source files drop zone
     let mut drag_over_source: Signal<bool> = use_signal(|| false);
 
     let input_element_signal: Signal<Option<Rc<MountedData>>> = use_signal(|| None);
 
-    // Update search results when filter_string changes
+    // Every query gets its own token, so that a newer query cancels the older ones
+    let search_generation = use_hook(SearchGeneration::new);
+
+    // Update search results when filter_string changes. The search is debounced, so that
+    // typing quickly in large repositories does not trigger a search on every keystroke.
     use_effect(move || {
         let query = filter_string.read().clone();
-        if !query.is_empty() {
-            let results = search_source_files(&source_files.read(), &query);
-            let has_results = !results.is_empty();
-            search_results.set(results);
-            search_visible.set(has_results);
-        } else {
+        let files = source_files.read().clone();
+        if query.is_empty() {
+            search_generation.cancel_all();
             search_results.set(Vec::new());
             search_visible.set(false);
+            return;
+        }
+
+        let token = search_generation.next_token();
+        spawn(async move {
+            let js_sleep = format!("await new Promise(r => setTimeout(r, {SEARCH_DEBOUNCE_MS}))");
+            let _ = document::eval(&js_sleep).await;
+            if token.is_cancelled() {
+                return;
+            }
+            // The search runs in a background OS thread, so that the UI stays responsive and a
+            // newer query can cancel it while it is still scanning the files
+            #[cfg(not(target_arch = "wasm32"))]
+            let results = {
+                let (sender, receiver) = std::sync::mpsc::channel();
+                let search_token = token.clone();
+                std::thread::spawn(move || {
+                    let _ = sender.send(search_source_files_cancellable(&files, &query, &search_token));
+                });
+                let js_poll = format!("await new Promise(r => setTimeout(r, {SEARCH_POLL_MS}))");
+                loop {
+                    match receiver.try_recv() {
+                        Ok(results) => break results,
+                        Err(std::sync::mpsc::TryRecvError::Empty) => {
+                            let _ = document::eval(&js_poll).await;
+                        }
+                        Err(std::sync::mpsc::TryRecvError::Disconnected) => return,
+                    }
+                }
+            };
+            #[cfg(target_arch = "wasm32")]
+            let results = search_source_files_cancellable(&files, &query, &token);
+            // Results of stale queries are discarded, only the latest query writes its results
+            if let Some(results) = results {
+                if token.is_cancelled() {
+                    return;
+                }
+                let has_results = !results.is_empty();
+                search_results.set(results);
+                search_visible.set(has_results);
+            }
+        });
+    });
+
+    // The selection of the last session is offered for restoring if nothing has been selected yet
+    let restorable_selection: Signal<Option<Schedule>> = use_signal(|| {
+        if selected_items.peek().is_empty() {
+            Schedule::load_last_selection()
+        } else {
+            None
         }
     });
+    let mut autosave_generation: Signal<u64> = use_signal(|| 0);
+    let mut announcement_dialog_visible: Signal<bool> = use_signal(|| false);
 
-    let default_presentation_design_memo =
-        use_memo(move || match settings.read().presentation_designs.first() {
-            Some(design) => design.clone(),
-            None => PresentationDesign::default(),
+    // Autosave the selection (debounced), so that it survives a crash or a restart
+    use_effect(move || {
+        let items = selected_items.read().clone();
+        // Keep the last selection as long as the user has not decided about restoring it
+        if items.is_empty() && restorable_selection.peek().is_some() {
+            return;
+        }
+
+        let generation = *autosave_generation.peek() + 1;
+        autosave_generation.set(generation);
+        spawn(async move {
+            let js_sleep = format!("await new Promise(r => setTimeout(r, {AUTOSAVE_DEBOUNCE_MS}))");
+            let _ = document::eval(&js_sleep).await;
+            if *autosave_generation.peek() != generation {
+                return;
+            }
+            Schedule::from_selected_items(&items, &settings.peek().repositories)
+                .save_as_last_selection();
         });
+    });
 
-    let default_song_slide_settings_memo = use_memo(move || {
-        settings
+    let default_presentation_design_memo =
+        use_memo(move || settings.read().default_presentation_design());
+
+    let default_song_slide_settings_memo = use_memo(move || settings.read().default_slide_settings());
+
+    // The slide counts are cached per item, so this only generates slides for new items
+    let total_slide_count = use_memo(move || {
+        let default_slide_settings = default_song_slide_settings_memo();
+        selected_items
             .read()
-            .song_slide_settings
-            .first()
-            .unwrap_or(&SlideSettings::default())
-            .clone()
+            .iter()
+            .map(|item| presentation::slide_count(item, &default_slide_settings))
+            .sum::<usize>()
     });
 
     use_effect(move || {
@@ -255,6 +415,7 @@ pub fn Selection() -> Element {
 
         spawn(async move {
             let files = settings.read().get_sourcefiles_async().await;
+            presentation::clear_slide_count_cache();
             // Set source files immediately so the UI is responsive without waiting for
             // the (potentially slow) cache pre-population below.
             source_files.set(files.clone());
@@ -312,9 +473,13 @@ pub fn Selection() -> Element {
                             let index = if digit == 0 { 9 } else { (digit as usize) - 1 };
                             let results = search_results.read();
                             if index < results.len() {
-                                selected_items.write().push(
-                                    SelectedItemRepresentation::new_with_sourcefile(results[index].source_file.clone())
+                                add_to_selection(
+                                    selected_items,
+                                    active_selected_item_id,
+                                    results[index].source_file.clone(),
+                                    &settings.read(),
                                 );
+                                remember_search_query(&mut settings, &filter_string.read());
                                 // Close search results after selection
                                 search_visible.set(false);
                                 event.stop_propagation();
@@ -337,6 +502,7 @@ pub fn Selection() -> Element {
                     search_results: search_results,
                     query: filter_string,
                     selected_items: selected_items,
+                    active_selected_item_id: active_selected_item_id,
                     search_visible: search_visible
                 }
             }
@@ -415,28 +581,40 @@ pub fn Selection() -> Element {
                             SongSourceItems {
                                 source_files: source_files,
                                 active_detailed_item_id: active_detailed_item_id,
-                                selected_items: selected_items
+                                selected_items: selected_items,
+                                active_selected_item_id: active_selected_item_id
                             }
                         }
                         if active_selection_filter() == SelectionSidebarType::Pictures {
                             ImageSourceItems {
                                 source_files: source_files,
                                 active_detailed_item_id: active_detailed_item_id,
-                                selected_items: selected_items
+                                selected_items: selected_items,
+                                active_selected_item_id: active_selected_item_id
                             }
                         }
                         if active_selection_filter() == SelectionSidebarType::Pdfs {
                             PdfSourceItems {
                                 source_files: source_files,
                                 active_detailed_item_id: active_detailed_item_id,
-                                selected_items: selected_items
+                                selected_items: selected_items,
+                                active_selected_item_id: active_selected_item_id
+                            }
+                        }
+                        if active_selection_filter() == SelectionSidebarType::Presentations {
+                            PresentationSourceItems {
+                                source_files: source_files,
+                                active_detailed_item_id: active_detailed_item_id,
+                                selected_items: selected_items,
+                                active_selected_item_id: active_selected_item_id
                             }
                         }
                         if active_selection_filter() == SelectionSidebarType::Markdown {
                             MarkdownSourceItems {
                                 source_files: source_files,
                                 active_detailed_item_id: active_detailed_item_id,
-                                selected_items: selected_items
+                                selected_items: selected_items,
+                                active_selected_item_id: active_selected_item_id
                             }
                         }
                         // Drop zone hint shown when dragging over
@@ -456,10 +634,20 @@ pub fn Selection() -> Element {
                     // The area where the selected elements are shown
                     div {
                         class: "height-100 scrollable-container swipe-panel",
+                        RunningPresentationsBanner {
+                            running_presentations: running_presentations,
+                            selected_items: selected_items,
+                        }
+                        RestoreSelectionBanner {
+                            restorable_selection: restorable_selection,
+                            source_files: source_files,
+                            selected_items: selected_items,
+                        }
                         if !selected_items.read().is_empty() {
                             SelectedItems {
                                 selected_items: selected_items,
-                                active_selected_item_id: active_selected_item_id
+                                active_selected_item_id: active_selected_item_id,
+                                live_source_files: live_source_files,
                             }
                         }
                     }
@@ -500,6 +688,9 @@ pub fn Selection() -> Element {
                     },
                     button {
                         class: "outline secondary smaller-buttons",
+                        onclick: move |_| async move {
+                            import_schedule(source_files, selected_items, active_selected_item_id).await;
+                        },
                         span {
                             class: "mobile-only",
                             Icon { icon: FaFileImport }
@@ -509,17 +700,168 @@ pub fn Selection() -> Element {
                             { t!("selection.import").to_string() }
                         }
                     },
+                    details {
+                        class: "dropdown export-dropdown",
+                        open: export_menu_open() && !selected_items.read().is_empty(),
+                        summary {
+                            role: "button",
+                            class: "outline secondary smaller-buttons",
+                            aria_disabled: selected_items.read().is_empty().to_string(),
+                            onclick: move |event| {
+                                event.prevent_default();
+                                if !selected_items.read().is_empty() {
+                                    export_menu_open.toggle();
+                                }
+                            },
+                            span {
+                                class: "mobile-only",
+                                Icon { icon: FaFileExport }
+                            }
+                            span {
+                                class: "desktop-only",
+                                { t!("selection.export").to_string() }
+                            }
+                        }
+                        ul {
+                            li {
+                                a {
+                                    href: "#",
+                                    onclick: move |event| {
+                                        event.prevent_default();
+                                        export_menu_open.set(false);
+                                        let items = selected_items.read().clone();
+                                        let repositories = settings.read().repositories.clone();
+                                        export_schedule(items, repositories)
+                                    },
+                                    { t!("selection.export_schedule").to_string() }
+                                }
+                            }
+                            li {
+                                a {
+                                    href: "#",
+                                    onclick: move |event| {
+                                        event.prevent_default();
+                                        export_menu_open.set(false);
+                                        let (chapters, failed_items) = presentation::create_presentation_chapters(
+                                            &selected_items.read(),
+                                            &default_presentation_design_memo(),
+                                            &default_song_slide_settings_memo(),
+                                            settings.read().blank_slide_between_chapters,
+                                        );
+                                        export_pptx(chapters, failed_items)
+                                    },
+                                    { t!("selection.export_pptx").to_string() }
+                                }
+                            }
+                            li {
+                                a {
+                                    href: "#",
+                                    onclick: move |event| {
+                                        event.prevent_default();
+                                        export_menu_open.set(false);
+                                        let (chapters, failed_items) = presentation::create_presentation_chapters(
+                                            &selected_items.read(),
+                                            &default_presentation_design_memo(),
+                                            &default_song_slide_settings_memo(),
+                                            settings.read().blank_slide_between_chapters,
+                                        );
+                                        export_html(chapters, failed_items)
+                                    },
+                                    { t!("selection.export_html").to_string() }
+                                }
+                            }
+                        }
+                    }
                     button {
                         class: "outline secondary smaller-buttons",
+                        onclick: move |_| announcement_dialog_visible.set(true),
                         span {
                             class: "mobile-only",
-                            Icon { icon: FaFileExport }
+                            Icon { icon: FaBullhorn }
                         }
                         span {
                             class: "desktop-only",
-                            { t!("selection.export").to_string() }
+                            { t!("selection.announcement.add_button").to_string() }
                         }
                     },
+                    if cfg!(feature = "desktop") {
+                        button {
+                            class: "outline secondary smaller-buttons",
+                            disabled: running_presentations.read().is_empty(),
+                            title: t!("selection.stage_display_description").to_string(),
+                            onclick: move |_| {
+                                #[cfg(feature = "desktop")]
+                                if let Some(rp) = running_presentations.read().last() {
+                                    open_stage_display(running_presentations, rp.id);
+                                }
+                            },
+                            span {
+                                class: "mobile-only",
+                                Icon { icon: FaDesktop }
+                            }
+                            span {
+                                class: "desktop-only",
+                                { t!("selection.stage_display").to_string() }
+                            }
+                        }
+                    }
+                    if cfg!(feature = "desktop") && !running_presentations.read().is_empty() {
+                        details {
+                            class: "dropdown mirror-window-dropdown",
+                            open: mirror_menu_open(),
+                            summary {
+                                role: "button",
+                                class: "outline secondary smaller-buttons",
+                                title: t!("selection.mirror_window_description").to_string(),
+                                onclick: move |event| {
+                                    event.prevent_default();
+                                    mirror_menu_open.toggle();
+                                },
+                                span {
+                                    class: "mobile-only",
+                                    Icon { icon: FaClone }
+                                }
+                                span {
+                                    class: "desktop-only",
+                                    { t!("selection.mirror_window").to_string() }
+                                }
+                            }
+                            ul {
+                                li {
+                                    a {
+                                        href: "#",
+                                        onclick: move |event| {
+                                            event.prevent_default();
+                                            mirror_menu_open.set(false);
+                                            #[cfg(feature = "desktop")]
+                                            if let Some(rp) = running_presentations.read().last() {
+                                                open_mirror_window(running_presentations, rp.id, None);
+                                            }
+                                        },
+                                        { t!("selection.mirror_window_presentation_design").to_string() }
+                                    }
+                                }
+                                for (index, design) in settings.read().presentation_designs.iter().enumerate() {
+                                    li {
+                                        key: "{index}",
+                                        a {
+                                            href: "#",
+                                            onclick: move |event| {
+                                                event.prevent_default();
+                                                mirror_menu_open.set(false);
+                                                #[cfg(feature = "desktop")]
+                                                if let Some(rp) = running_presentations.read().last() {
+                                                    let design = settings.read().presentation_designs.get(index).cloned();
+                                                    open_mirror_window(running_presentations, rp.id, design);
+                                                }
+                                            },
+                                            "{design.name}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                     button {
                         class: "primary smaller-buttons",
                         onclick: move |_| start_presentation(&selected_items.read().clone(), &mut running_presentations, &default_presentation_design_memo(), &default_song_slide_settings_memo(), &settings.read()),
@@ -531,6 +873,13 @@ pub fn Selection() -> Element {
                             class: "desktop-only",
                             { t!("selection.start_presentation").to_string() }
                         }
+                        if total_slide_count() > 0 {
+                            span {
+                                class: "slide-count-badge",
+                                title: t!("selection.total_slide_count", count = total_slide_count()).to_string(),
+                                "{total_slide_count}"
+                            }
+                        }
                     }
                 }
             }
@@ -542,6 +891,69 @@ pub fn Selection() -> Element {
                 active_detailed_item_id: active_detailed_item_id,
             }
         }
+
+        if announcement_dialog_visible() {
+            AnnouncementDialog {
+                selected_items: selected_items,
+                active_selected_item_id: active_selected_item_id,
+                visible: announcement_dialog_visible,
+            }
+        }
+    }
+}
+
+/// A dialog for typing in the text of a new announcement
+#[component]
+fn AnnouncementDialog(
+    selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
+    visible: Signal<bool>,
+) -> Element {
+    let settings = use_settings();
+    let mut text: Signal<String> = use_signal(String::new);
+
+    rsx! {
+        dialog {
+            style: "position: fixed",
+            open: true,
+            onkeydown: move |event: Event<KeyboardData>| {
+                if event.key() == Key::Escape {
+                    visible.set(false);
+                }
+            },
+            article {
+                header {
+                    p { { t!("selection.announcement.dialog_title").to_string() } }
+                }
+                textarea {
+                    rows: "6",
+                    autofocus: true,
+                    placeholder: t!("selection.announcement.placeholder").to_string(),
+                    value: text,
+                    oninput: move |event| text.set(event.value()),
+                }
+                footer {
+                    button {
+                        class: "secondary",
+                        onclick: move |_| visible.set(false),
+                        { t!("general.cancel").to_string() }
+                    }
+                    button {
+                        disabled: text.read().trim().is_empty(),
+                        onclick: move |_| {
+                            insert_selected_item(
+                                selected_items,
+                                active_selected_item_id,
+                                SelectedItemRepresentation::new_announcement(text.read().clone()),
+                                settings.read().insert_after_active_item,
+                            );
+                            visible.set(false);
+                        },
+                        { t!("selection.announcement.add").to_string() }
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -550,6 +962,18 @@ fn SearchInput(
     input_signal: Signal<String>,
     element_signal: Signal<Option<Rc<MountedData>>>,
 ) -> Element {
+    let mut settings = use_settings();
+    let mut has_focus: Signal<bool> = use_signal(|| false);
+    let mut highlighted_history_entry: Signal<Option<usize>> = use_signal(|| None);
+
+    // The search history is offered as suggestions while the focused search box is empty
+    let show_history = use_memo(move || {
+        has_focus()
+            && input_signal.read().is_empty()
+            && settings.read().search_history_enabled
+            && !settings.read().search_history.is_empty()
+    });
+
     rsx! {
         div {
             role: "group",
@@ -564,9 +988,85 @@ fn SearchInput(
                 oninput: move |event| {
                     let value = event.value();
                     input_signal.set(value);
+                    highlighted_history_entry.set(None);
+                },
+                onfocus: move |_| has_focus.set(true),
+                onblur: move |_| {
+                    has_focus.set(false);
+                    highlighted_history_entry.set(None);
+                },
+                onkeydown: move |event: Event<KeyboardData>| {
+                    if !show_history() {
+                        return;
+                    }
+                    let history_length = settings.read().search_history.len();
+                    match event.key() {
+                        Key::ArrowDown => {
+                            highlighted_history_entry.set(match highlighted_history_entry() {
+                                Some(index) if index + 1 < history_length => Some(index + 1),
+                                Some(index) => Some(index),
+                                None => Some(0),
+                            });
+                            event.prevent_default();
+                        }
+                        Key::ArrowUp => {
+                            highlighted_history_entry.set(match highlighted_history_entry() {
+                                Some(0) | None => None,
+                                Some(index) => Some(index - 1),
+                            });
+                            event.prevent_default();
+                        }
+                        Key::Enter => {
+                            let query = highlighted_history_entry()
+                                .and_then(|index| settings.read().search_history.get(index).cloned());
+                            if let Some(query) = query {
+                                input_signal.set(query);
+                                highlighted_history_entry.set(None);
+                            }
+                        }
+                        _ => {}
+                    }
                 },
             }
         }
+        if show_history() {
+            div {
+                class: "search-history",
+                for (index, query) in settings.read().search_history.clone().into_iter().enumerate() {
+                    div {
+                        class: if highlighted_history_entry() == Some(index) { "search-history-entry active" } else { "search-history-entry" },
+                        // Use mousedown instead of click, so that the entry is selected before the
+                        // search box loses its focus and the suggestions are hidden.
+                        onmousedown: move |event: Event<MouseData>| {
+                            event.prevent_default();
+                            let query = settings.read().search_history.get(index).cloned();
+                            if let Some(query) = query {
+                                input_signal.set(query);
+                            }
+                            highlighted_history_entry.set(None);
+                        },
+                        { query }
+                    }
+                }
+                div {
+                    class: "search-history-entry search-history-clear",
+                    onmousedown: move |event: Event<MouseData>| {
+                        event.prevent_default();
+                        settings.write().clear_search_history();
+                        settings.read().save();
+                    },
+                    { t!("search.clear_history").to_string() }
+                }
+            }
+        }
+    }
+}
+
+/// Remembers the query in the search history after one of its results has been selected
+fn remember_search_query(settings: &mut Signal<Settings>, query: &str) {
+    if settings.read().search_history_enabled {
+        settings.write().add_to_search_history(query);
+        settings.read().save();
     }
 }
 
@@ -575,20 +1075,159 @@ fn SongSourceItems(
     source_files: Signal<Vec<SourceFile>>,
     active_detailed_item_id: Signal<Option<usize>>,
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
 ) -> Element {
+    let settings = use_settings();
+    let recently_used = use_signal(RecentlyUsedSongs::load);
+    let recently_used_ids = recently_used.read().source_file_indices(&source_files.read());
+    // Songs which have been marked with Ctrl/Shift+click for adding them at once
+    let mut marked_items: Signal<Vec<usize>> = use_signal(Vec::new);
+    let last_marked_item: Signal<Option<usize>> = use_signal(|| None);
+
+    let song_ids: Vec<usize> = source_files
+        .read()
+        .iter()
+        .enumerate()
+        .filter(|(_, sf)| sf.file_type == SourceFileType::Song)
+        .map(|(id, _)| id)
+        .collect();
+    // The first song of every letter, which is the target of the alphabet jump bar
+    let show_jump_bar = song_ids.len() >= settings.read().alphabet_jump_bar_threshold;
+    // The position of the first song of every letter, which is the target of the alphabet jump bar
+    let mut letter_targets: Vec<(char, usize)> = Vec::new();
+    if show_jump_bar {
+        for (position, id) in song_ids.iter().enumerate() {
+            if let Some(letter) = index_letter(&source_files.read()[*id].name) {
+                if !letter_targets.iter().any(|(l, _)| *l == letter) {
+                    letter_targets.push((letter, position));
+                }
+            }
+        }
+    }
+
+    let mut virtual_list = use_virtual_list();
+    let visible_range = virtual_list.visible_range(song_ids.len(), SONG_ITEM_HEIGHT_PX);
+    let top_spacer = visible_range.start as f64 * SONG_ITEM_HEIGHT_PX;
+    let bottom_spacer = (song_ids.len() - visible_range.end) as f64 * SONG_ITEM_HEIGHT_PX;
+
     rsx! {
         div {
             class: "scrollable-container",
-            onmounted: move |_| async move {
+            onmounted: move |event| async move {
+                virtual_list.container.set(Some(event.data()));
                 let _ = document::eval("initSelectionLayout();").await;
+                virtual_list.measure().await;
             },
-            for (id, _) in source_files.read().iter().enumerate().filter(|(_, sf)| sf.file_type == SourceFileType::Song) {
-                SongSourceItem {
-                    id: id,
-                    source_files: source_files,
-                    active_detailed_item_id: active_detailed_item_id,
-                    selected_items: selected_items
+            onscroll: move |_| async move {
+                virtual_list.measure().await;
+            },
+            onkeydown: move |event: Event<KeyboardData>| {
+                if event.key() == Key::Escape && !marked_items.read().is_empty() {
+                    marked_items.write().clear();
+                    event.stop_propagation();
+                }
+            },
+            if marked_items.read().len() > 1 {
+                div {
+                    class: "source-action-bar",
+                    button {
+                        class: "smaller-buttons",
+                        onclick: move |_| {
+                            // Add the songs in the order of the list
+                            let mut ids = marked_items.read().clone();
+                            ids.sort_unstable();
+                            let insert_after_active = settings.read().insert_after_active_item;
+                            for id in ids {
+                                let source_file = source_files.read().get(id).cloned();
+                                if let Some(source_file) = source_file {
+                                    insert_selected_item(
+                                        selected_items,
+                                        active_selected_item_id,
+                                        SelectedItemRepresentation::new_with_sourcefile(source_file),
+                                        insert_after_active,
+                                    );
+                                }
+                            }
+                            marked_items.write().clear();
+                        },
+                        { t!("selection.add_marked_songs", count = marked_items.read().len()).to_string() }
+                    }
+                    button {
+                        class: "smaller-buttons outline secondary",
+                        onclick: move |_| marked_items.write().clear(),
+                        { t!("selection.clear_marks").to_string() }
+                    }
+                }
+            }
+            if show_jump_bar {
+                nav {
+                    class: "alphabet-jump-bar",
+                    for letter in 'A'..='Z' {
+                        {
+                            let target = letter_targets
+                                .iter()
+                                .find(|(l, _)| *l == letter)
+                                .map(|(_, position)| *position);
+                            rsx! {
+                                button {
+                                    class: "alphabet-jump-letter",
+                                    disabled: target.is_none(),
+                                    aria_label: t!("selection.jump_to_letter", letter = letter.to_string()).to_string(),
+                                    onclick: move |_| {
+                                        if let Some(position) = target {
+                                            scroll_to_virtual_list_item(
+                                                "song-source-list",
+                                                position as f64 * SONG_ITEM_HEIGHT_PX,
+                                            );
+                                        }
+                                    },
+                                    { letter.to_string() }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if !recently_used_ids.is_empty() {
+                details {
+                    class: "recently-used",
+                    open: true,
+                    summary { { t!("selection.recently_used").to_string() } }
+                    for id in recently_used_ids {
+                        SongSourceItem {
+                            key: "recent-{id}",
+                            id: id,
+                            source_files: source_files,
+                            active_detailed_item_id: active_detailed_item_id,
+                            selected_items: selected_items,
+                            active_selected_item_id: active_selected_item_id,
+                            marked_items: marked_items,
+                            last_marked_item: last_marked_item
+                        }
+                    }
+                }
+            }
+            div {
+                id: "song-source-list",
+                class: "virtual-list",
+                onmounted: move |event| async move {
+                    virtual_list.list.set(Some(event.data()));
+                    virtual_list.measure().await;
+                },
+                div { style: "height: {top_spacer}px" }
+                for id in song_ids[visible_range].iter().copied() {
+                    SongSourceItem {
+                        key: "{id}",
+                        id: id,
+                        source_files: source_files,
+                        active_detailed_item_id: active_detailed_item_id,
+                        selected_items: selected_items,
+                        active_selected_item_id: active_selected_item_id,
+                        marked_items: marked_items,
+                        last_marked_item: last_marked_item
+                    }
                 }
+                div { style: "height: {bottom_spacer}px" }
             }
         }
     }
@@ -600,45 +1239,258 @@ fn SongSourceItem(
     source_files: Signal<Vec<SourceFile>>,
     id: usize,
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
     active_detailed_item_id: Signal<Option<usize>>,
+    marked_items: Signal<Vec<usize>>,
+    last_marked_item: Signal<Option<usize>>,
 ) -> Element {
+    let settings = use_settings();
+    let is_selected = selected_items
+        .read()
+        .iter()
+        .any(|item| item.refers_to(&source_files.read()[id]));
+    let mut menu_position: Signal<Option<(f64, f64)>> = use_signal(|| None);
+    let preview_visible: Signal<bool> = use_signal(|| false);
+    let menu_entries = source_item_menu_entries(
+        source_files,
+        id,
+        selected_items,
+        active_selected_item_id,
+        active_detailed_item_id,
+        preview_visible,
+        settings,
+    );
+
     rsx! {
         div {
             role: "button",
             class: "outline secondary selection_item",
+            class: if is_selected { "already-selected" },
+            class: if marked_items.read().contains(&id) { "checked" },
             tabindex: 0,
-            onclick: move |_| { selected_items.write().push(
-                SelectedItemRepresentation::new_with_sourcefile(source_files.get(id).unwrap().clone())
-            ); },
-            oncontextmenu: move |_| {
-                active_detailed_item_id.set(Some(id));
+            onclick: move |event| {
+                let modifiers = event.modifiers();
+                if modifiers.shift() {
+                    mark_song_range(source_files, marked_items, last_marked_item(), id);
+                    last_marked_item.set(Some(id));
+                } else if modifiers.ctrl() || modifiers.meta() {
+                    let mut marked = marked_items.write();
+                    match marked.iter().position(|marked_id| *marked_id == id) {
+                        Some(position) => {
+                            marked.remove(position);
+                        }
+                        None => marked.push(id),
+                    }
+                    last_marked_item.set(Some(id));
+                } else {
+                    add_to_selection(
+                        selected_items,
+                        active_selected_item_id,
+                        source_files.get(id).unwrap().clone(),
+                        &settings.read(),
+                    )
+                }
             },
-            { source_files.get(id).unwrap().clone().name }
+            oncontextmenu: move |event| {
+                event.prevent_default();
+                let point = event.client_coordinates();
+                menu_position.set(Some((point.x, point.y)));
+            },
+            {
+                let source_file = source_files.get(id).unwrap().clone();
+                match song_number(&source_file) {
+                    Some(number) => rsx! {
+                        span {
+                            class: "song-number",
+                            { number.to_string() }
+                        }
+                        { name_without_song_number(&source_file.name).to_string() }
+                    },
+                    None => rsx! { { source_file.name } },
+                }
+            }
+        }
+        ContextMenu {
+            position: menu_position,
+            entries: menu_entries,
+        }
+        SourcePreviewDialog {
+            source_file: source_files.read()[id].clone(),
+            visible: preview_visible,
         }
     }
 }
 
-/// The component renders the list of available pictures
-#[component]
-fn ImageSourceItems(
+/// Creates the entries of the context menu of a source item.
+fn source_item_menu_entries(
     source_files: Signal<Vec<SourceFile>>,
-    active_detailed_item_id: Signal<Option<usize>>,
+    id: usize,
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
-) -> Element {
-    rsx! {
-        div {
-            class: "scrollable-container",
-            onmounted: move |_| async move {
-                let _ = document::eval("initSelectionLayout();").await;
-            },
-            for (id, _) in source_files.read().iter().enumerate().filter(|(_, sf)| sf.file_type == SourceFileType::Image) {
-                ImageSourceItem {
-                    id: id,
-                    source_files: source_files,
-                    active_detailed_item_id: active_detailed_item_id,
-                    selected_items: selected_items
+    active_selected_item_id: Signal<Option<usize>>,
+    mut active_detailed_item_id: Signal<Option<usize>>,
+    mut preview_visible: Signal<bool>,
+    settings: Signal<Settings>,
+) -> Vec<ContextMenuEntry> {
+    let mut entries = vec![
+        ContextMenuEntry {
+            label: t!("selection.context_menu.add").to_string(),
+            on_select: EventHandler::new(move |_| {
+                let source_file = source_files.read()[id].clone();
+                add_to_selection(selected_items, active_selected_item_id, source_file, &settings.read());
+            }),
+        },
+        ContextMenuEntry {
+            label: t!("selection.context_menu.preview").to_string(),
+            on_select: EventHandler::new(move |_| preview_visible.set(true)),
+        },
+        ContextMenuEntry {
+            label: t!("selection.context_menu.details").to_string(),
+            on_select: EventHandler::new(move |_| active_detailed_item_id.set(Some(id))),
+        },
+    ];
+
+    let running_presentations: Signal<Vec<RunningPresentation>> = consume_context();
+    if !running_presentations.read().is_empty() {
+        entries.push(ContextMenuEntry {
+            label: t!("selection.send_to_presentation").to_string(),
+            on_select: EventHandler::new(move |_| {
+                let item = SelectedItemRepresentation::new_with_sourcefile(source_files.read()[id].clone());
+                send_to_running_presentation(running_presentations, &item, None, &settings.read());
+            }),
+        });
+    }
+
+    #[cfg(feature = "desktop")]
+    entries.push(ContextMenuEntry {
+        label: t!("selection.context_menu.reveal").to_string(),
+        on_select: EventHandler::new(move |_| {
+            if let Some(source_file) = source_files.read().get(id) {
+                if let Err(err) = source_file.reveal_in_file_manager() {
+                    log::warn!("Could not reveal {} in the file manager: {}", source_file.path.display(), err);
                 }
             }
+        }),
+    });
+
+    entries
+}
+
+/// Shows the slides which would be generated for a source file in a modal dialog.
+#[component]
+fn SourcePreviewDialog(source_file: SourceFile, visible: Signal<bool>) -> Element {
+    let settings = use_settings();
+
+    if !visible() {
+        return rsx! {};
+    }
+
+    rsx! {
+        dialog {
+            style: "position: fixed",
+            open: true,
+            article {
+                header {
+                    p { { source_file.name.clone() } }
+                }
+                SelectedItemSlideList {
+                    selected_item: SelectedItemRepresentation::new_with_sourcefile(source_file.clone()),
+                    default_presentation_design: settings.read().default_presentation_design(),
+                    default_slide_settings: settings.read().default_slide_settings(),
+                    width: 320,
+                }
+                footer {
+                    button {
+                        onclick: move |_| visible.set(false),
+                        { t!("general.close").to_string() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Marks all songs between the last marked song and the given song (inclusive) in list order.
+/// Without a previously marked song, only the given song is marked.
+fn mark_song_range(
+    source_files: Signal<Vec<SourceFile>>,
+    mut marked_items: Signal<Vec<usize>>,
+    anchor: Option<usize>,
+    id: usize,
+) {
+    let song_ids: Vec<usize> = source_files
+        .read()
+        .iter()
+        .enumerate()
+        .filter(|(_, sf)| sf.file_type == SourceFileType::Song)
+        .map(|(index, _)| index)
+        .collect();
+    let end = song_ids.iter().position(|song_id| *song_id == id);
+    let start = anchor
+        .and_then(|anchor| song_ids.iter().position(|song_id| *song_id == anchor))
+        .or(end);
+
+    let (Some(start), Some(end)) = (start, end) else {
+        return;
+    };
+    let mut marked = marked_items.write();
+    for song_id in &song_ids[start.min(end)..=start.max(end)] {
+        if !marked.contains(song_id) {
+            marked.push(*song_id);
+        }
+    }
+}
+
+/// The component renders the list of available pictures
+#[component]
+fn ImageSourceItems(
+    source_files: Signal<Vec<SourceFile>>,
+    active_detailed_item_id: Signal<Option<usize>>,
+    selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
+) -> Element {
+    let image_ids: Vec<usize> = source_files
+        .read()
+        .iter()
+        .enumerate()
+        .filter(|(_, sf)| sf.file_type == SourceFileType::Image)
+        .map(|(id, _)| id)
+        .collect();
+
+    let mut virtual_list = use_virtual_list();
+    let visible_range = virtual_list.visible_range(image_ids.len(), IMAGE_ITEM_HEIGHT_PX);
+    let top_spacer = visible_range.start as f64 * IMAGE_ITEM_HEIGHT_PX;
+    let bottom_spacer = (image_ids.len() - visible_range.end) as f64 * IMAGE_ITEM_HEIGHT_PX;
+
+    rsx! {
+        div {
+            class: "scrollable-container",
+            onmounted: move |event| async move {
+                virtual_list.container.set(Some(event.data()));
+                let _ = document::eval("initSelectionLayout();").await;
+                virtual_list.measure().await;
+            },
+            onscroll: move |_| async move {
+                virtual_list.measure().await;
+            },
+            div {
+                class: "virtual-list image-list",
+                onmounted: move |event| async move {
+                    virtual_list.list.set(Some(event.data()));
+                    virtual_list.measure().await;
+                },
+                div { style: "height: {top_spacer}px" }
+                for id in image_ids[visible_range].iter().copied() {
+                    ImageSourceItem {
+                        key: "{id}",
+                        id: id,
+                        source_files: source_files,
+                        active_detailed_item_id: active_detailed_item_id,
+                        selected_items: selected_items,
+                        active_selected_item_id: active_selected_item_id
+                    }
+                }
+                div { style: "height: {bottom_spacer}px" }
+            }
         }
     }
 }
@@ -648,18 +1500,42 @@ fn ImageSourceItem(
     source_files: Signal<Vec<SourceFile>>,
     id: usize,
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
     active_detailed_item_id: Signal<Option<usize>>,
 ) -> Element {
+    let settings = use_settings();
+    let is_selected = selected_items
+        .read()
+        .iter()
+        .any(|item| item.refers_to(&source_files.read()[id]));
+    let mut menu_position: Signal<Option<(f64, f64)>> = use_signal(|| None);
+    let preview_visible: Signal<bool> = use_signal(|| false);
+    let menu_entries = source_item_menu_entries(
+        source_files,
+        id,
+        selected_items,
+        active_selected_item_id,
+        active_detailed_item_id,
+        preview_visible,
+        settings,
+    );
+
     rsx! {
         div {
             role: "button",
             class: "outline secondary selection_item",
+            class: if is_selected { "already-selected" },
             tabindex: 0,
-            onclick: move |_| { selected_items.write().push(
-                SelectedItemRepresentation::new_with_sourcefile(source_files.get(id).unwrap().clone())
-            ); },
-            oncontextmenu: move |_| {
-                active_detailed_item_id.set(Some(id));
+            onclick: move |_| add_to_selection(
+                selected_items,
+                active_selected_item_id,
+                source_files.get(id).unwrap().clone(),
+                &settings.read(),
+            ),
+            oncontextmenu: move |event| {
+                event.prevent_default();
+                let point = event.client_coordinates();
+                menu_position.set(Some((point.x, point.y)));
             },
             { source_files.get(id).unwrap().clone().name },
             br { },
@@ -668,15 +1544,96 @@ fn ImageSourceItem(
                 src: source_files.get(id).unwrap().clone().path.to_str().unwrap_or("")
             }
         }
+        ContextMenu {
+            position: menu_position,
+            entries: menu_entries,
+        }
+        SourcePreviewDialog {
+            source_file: source_files.read()[id].clone(),
+            visible: preview_visible,
+        }
+    }
+}
+
+/// The scroll state of a list which only renders its visible items, see [use_virtual_list].
+/// All items of the list are required to have the same height.
+#[derive(Clone, Copy)]
+struct VirtualList {
+    /// The scrollable container which contains the list
+    container: Signal<Option<Rc<MountedData>>>,
+    /// The element which contains the items
+    list: Signal<Option<Rc<MountedData>>>,
+    /// The distance between the top of the list and the top of the visible area
+    scroll_offset: Signal<f64>,
+    /// The height of the visible area
+    viewport_height: Signal<f64>,
+}
+
+/// Creates the scroll state of a virtualized list.
+fn use_virtual_list() -> VirtualList {
+    VirtualList {
+        container: use_signal(|| None),
+        list: use_signal(|| None),
+        scroll_offset: use_signal(|| 0.0),
+        // Assume a large viewport until the container has been measured
+        viewport_height: use_signal(|| 1000.0),
+    }
+}
+
+impl VirtualList {
+    /// Measures the position of the list relative to the visible area of its container.
+    async fn measure(mut self) {
+        let container = self.container.peek().clone();
+        let list = self.list.peek().clone();
+        let (Some(container), Some(list)) = (container, list) else {
+            return;
+        };
+        if let (Ok(container_rect), Ok(list_rect)) =
+            (container.get_client_rect().await, list.get_client_rect().await)
+        {
+            let scroll_offset = container_rect.origin.y - list_rect.origin.y;
+            if *self.scroll_offset.peek() != scroll_offset {
+                self.scroll_offset.set(scroll_offset);
+            }
+            if *self.viewport_height.peek() != container_rect.size.height {
+                self.viewport_height.set(container_rect.size.height);
+            }
+        }
+    }
+
+    /// Returns the range of the items which have to be rendered, including a buffer of
+    /// [VIRTUAL_LIST_BUFFER] items above and below the visible area.
+    fn visible_range(&self, item_count: usize, item_height: f64) -> Range<usize> {
+        let first_visible = ((self.scroll_offset)().max(0.0) / item_height) as usize;
+        let visible_count = ((self.viewport_height)() / item_height).ceil() as usize + 1;
+        let start = first_visible.saturating_sub(VIRTUAL_LIST_BUFFER).min(item_count);
+        let end = (first_visible + visible_count + VIRTUAL_LIST_BUFFER).min(item_count);
+        start..end
     }
 }
 
+/// Scrolls the container of a virtualized list, so that the given offset within the list is at the top.
+fn scroll_to_virtual_list_item(list_id: &str, offset: f64) {
+    let _ = document::eval(&format!(
+        r#"
+        const list = document.getElementById('{}');
+        const container = list ? list.closest('.scrollable-container') : null;
+        if (list && container) {{
+            container.scrollTop += list.getBoundingClientRect().top
+                - container.getBoundingClientRect().top + {};
+        }}
+        "#,
+        list_id, offset
+    ));
+}
+
 /// The component renders the list of available PDF files
 #[component]
 fn PdfSourceItems(
     source_files: Signal<Vec<SourceFile>>,
     active_detailed_item_id: Signal<Option<usize>>,
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
 ) -> Element {
     rsx! {
         div {
@@ -689,7 +1646,8 @@ fn PdfSourceItems(
                     id: id,
                     source_files: source_files,
                     active_detailed_item_id: active_detailed_item_id,
-                    selected_items: selected_items
+                    selected_items: selected_items,
+                    active_selected_item_id: active_selected_item_id
                 }
             }
         }
@@ -701,16 +1659,99 @@ fn PdfSourceItem(
     source_files: Signal<Vec<SourceFile>>,
     id: usize,
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
+    active_detailed_item_id: Signal<Option<usize>>,
+) -> Element {
+    let settings = use_settings();
+    let is_selected = selected_items
+        .read()
+        .iter()
+        .any(|item| item.refers_to(&source_files.read()[id]));
+
+    rsx! {
+        div {
+            role: "button",
+            class: "outline secondary selection_item",
+            class: if is_selected { "already-selected" },
+            tabindex: 0,
+            onclick: move |_| add_to_selection(
+                selected_items,
+                active_selected_item_id,
+                source_files.get(id).unwrap().clone(),
+                &settings.read(),
+            ),
+            oncontextmenu: move |_| {
+                active_detailed_item_id.set(Some(id));
+            },
+            { source_files.get(id).unwrap().clone().name }
+        }
+    }
+}
+
+/// The component renders the list of available presentation files
+#[component]
+fn PresentationSourceItems(
+    source_files: Signal<Vec<SourceFile>>,
+    active_detailed_item_id: Signal<Option<usize>>,
+    selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
+) -> Element {
+    let has_presentations = source_files
+        .read()
+        .iter()
+        .any(|sf| sf.file_type == SourceFileType::Presentation);
+
+    rsx! {
+        div {
+            class: "scrollable-container",
+            onmounted: move |_| async move {
+                let _ = document::eval("initSelectionLayout();").await;
+            },
+            if !has_presentations {
+                p {
+                    class: "drop-zone-hint",
+                    { t!("selection.no_presentations_hint").to_string() }
+                }
+            }
+            for (id, _) in source_files.read().iter().enumerate().filter(|(_, sf)| sf.file_type == SourceFileType::Presentation) {
+                PresentationSourceItem {
+                    id: id,
+                    source_files: source_files,
+                    active_detailed_item_id: active_detailed_item_id,
+                    selected_items: selected_items,
+                    active_selected_item_id: active_selected_item_id
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn PresentationSourceItem(
+    source_files: Signal<Vec<SourceFile>>,
+    id: usize,
+    selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
     active_detailed_item_id: Signal<Option<usize>>,
 ) -> Element {
+    let settings = use_settings();
+    let is_selected = selected_items
+        .read()
+        .iter()
+        .any(|item| item.refers_to(&source_files.read()[id]));
+
     rsx! {
         div {
             role: "button",
             class: "outline secondary selection_item",
+            class: if is_selected { "already-selected" },
             tabindex: 0,
-            onclick: move |_| { selected_items.write().push(
-                SelectedItemRepresentation::new_with_sourcefile(source_files.get(id).unwrap().clone())
-            ); },
+            onclick: move |_| add_to_selection(
+                selected_items,
+                active_selected_item_id,
+                source_files.get(id).unwrap().clone(),
+                &settings.read(),
+            ),
             oncontextmenu: move |_| {
                 active_detailed_item_id.set(Some(id));
             },
@@ -725,7 +1766,9 @@ fn MarkdownSourceItems(
     source_files: Signal<Vec<SourceFile>>,
     active_detailed_item_id: Signal<Option<usize>>,
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
 ) -> Element {
+    let settings = use_settings();
     let mut spontaneous_text: Signal<String> = use_signal(|| String::new());
 
     rsx! {
@@ -739,7 +1782,8 @@ fn MarkdownSourceItems(
                     id: id,
                     source_files: source_files,
                     active_detailed_item_id: active_detailed_item_id,
-                    selected_items: selected_items
+                    selected_items: selected_items,
+                    active_selected_item_id: active_selected_item_id
                 }
             }
             // Spontaneous markdown text input form
@@ -767,7 +1811,12 @@ fn MarkdownSourceItems(
                             };
                             let mut item = SelectedItemRepresentation::new_with_sourcefile(source_file);
                             item.inline_markdown = Some(text.clone());
-                            selected_items.write().push(item);
+                            insert_selected_item(
+                                selected_items,
+                                active_selected_item_id,
+                                item,
+                                settings.read().insert_after_active_item,
+                            );
                             spontaneous_text.set(String::new());
                         }
                     },
@@ -783,16 +1832,27 @@ fn MarkdownSourceItem(
     source_files: Signal<Vec<SourceFile>>,
     id: usize,
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
     active_detailed_item_id: Signal<Option<usize>>,
 ) -> Element {
+    let settings = use_settings();
+    let is_selected = selected_items
+        .read()
+        .iter()
+        .any(|item| item.refers_to(&source_files.read()[id]));
+
     rsx! {
         div {
             role: "button",
             class: "outline secondary selection_item",
+            class: if is_selected { "already-selected" },
             tabindex: 0,
-            onclick: move |_| { selected_items.write().push(
-                SelectedItemRepresentation::new_with_sourcefile(source_files.get(id).unwrap().clone())
-            ); },
+            onclick: move |_| add_to_selection(
+                selected_items,
+                active_selected_item_id,
+                source_files.get(id).unwrap().clone(),
+                &settings.read(),
+            ),
             oncontextmenu: move |_| {
                 active_detailed_item_id.set(Some(id));
             },
@@ -805,6 +1865,7 @@ fn MarkdownSourceItem(
 fn SelectedItems(
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
     active_selected_item_id: Signal<Option<usize>>,
+    live_source_files: Memo<Vec<(usize, SourceFile)>>,
 ) -> Element {
     // Track drag state for custom mouse-based reordering
     let mut dragging_from: Signal<Option<usize>> = use_signal(|| None);
@@ -812,8 +1873,97 @@ fn SelectedItems(
     // Animation signals: target index to animate and a flip to retrigger animation
     let mut anim_target: Signal<Option<usize>> = use_signal(|| None);
     let mut anim_flip: Signal<bool> = use_signal(|| false);
+    // Filter for the rendered rows; the indices of the rows stay the ones of `selected_items`
+    let mut filter: Signal<String> = use_signal(String::new);
+    // The item which can be reached with the tab key (roving tabindex)
+    let mut focused_item: Signal<Option<usize>> = use_signal(|| None);
+    // The items which have been marked with Ctrl+click for removal
+    let mut checked_items: Signal<Vec<usize>> = use_signal(Vec::new);
+    let mut settings = use_settings();
+
+    // The marked indices are not valid anymore as soon as the selection changes
+    use_effect(move || {
+        selected_items.read();
+        checked_items.write().clear();
+    });
 
     rsx! {
+        div {
+            class: "selected-items-toolbar",
+            input {
+                type: "search",
+                class: "selected-items-filter",
+                placeholder: t!("selection.filter_selected_items").to_string(),
+                aria_label: t!("selection.filter_selected_items").to_string(),
+                value: filter,
+                oninput: move |event| filter.set(event.value()),
+            }
+            span {
+                class: "selected-items-help",
+                "data-tooltip": t!("selection.selected_items_shortcuts").to_string(),
+                "data-placement": "left",
+                "?"
+            }
+        }
+        if !selected_items.read().is_empty() {
+            div {
+                class: "selected-items-toolbar",
+                label {
+                    class: "selected-items-insert-toggle",
+                    "data-tooltip": t!("selection.insert_after_active_description").to_string(),
+                    input {
+                        r#type: "checkbox",
+                        role: "switch",
+                        checked: settings.read().insert_after_active_item,
+                        onchange: move |event| {
+                            settings.write().insert_after_active_item = event.checked();
+                        }
+                    }
+                    { t!("selection.insert_after_active").to_string() }
+                }
+                if !checked_items.read().is_empty() {
+                    button {
+                        class: "smaller-buttons secondary",
+                        onclick: move |_| {
+                            remove_selected_items(selected_items, active_selected_item_id, &checked_items.read());
+                            focused_item.set(None);
+                        },
+                        { t!("selection.remove_checked_items", count = checked_items.read().len()).to_string() }
+                    }
+                }
+                button {
+                    class: "smaller-buttons outline secondary",
+                    onclick: move |_| {
+                        spawn(async move {
+                            let prompt = t!("selection.clear_selection_confirm").to_string();
+                            let confirmed = document::eval(&js_yes_no_box(prompt))
+                                .await
+                                .ok()
+                                .and_then(|value| value.as_bool())
+                                .unwrap_or(false);
+                            if confirmed {
+                                active_selected_item_id.set(None);
+                                focused_item.set(None);
+                                selected_items.write().clear();
+                            }
+                        });
+                    },
+                    { t!("selection.clear_selection").to_string() }
+                }
+                button {
+                    class: "smaller-buttons outline secondary",
+                    onclick: move |_| {
+                        insert_selected_item(
+                            selected_items,
+                            active_selected_item_id,
+                            SelectedItemRepresentation::new_blank(),
+                            settings.read().insert_after_active_item,
+                        );
+                    },
+                    { t!("selection.insert_blank").to_string() }
+                }
+            }
+        }
         div {
             class: "selected-container",
             onmouseup: move |_| {
@@ -840,11 +1990,18 @@ fn SelectedItems(
                 dragging_from.set(None);
                 hover_over.set(None);
             },
-            for (number, _) in selected_items.read().iter().enumerate() {
+            for (number, item) in selected_items.read().iter().enumerate().filter(|(_, item)| name_matches_query(&item.source_file.name, &filter.read())) {
                 SelectedItem {
                     selected_items: selected_items,
                     id: number,
+                    live_presentation_id: live_source_files
+                        .read()
+                        .iter()
+                        .find(|(_, file)| item.refers_to(file))
+                        .map(|(presentation_id, _)| *presentation_id),
                     active_selected_item_id: active_selected_item_id,
+                    focused_item: focused_item,
+                    checked_items: checked_items,
                     dragging_from: dragging_from,
                     hover_over: hover_over,
                     anim_target: anim_target,
@@ -869,91 +2026,583 @@ fn SelectedItems(
     }
 }
 
-/// This component renders a selected item
+/// Offers to restore the selection of the last session and lists the items of it which could
+/// not be found anymore.
+/// Lists the running presentations, so that each of them can be brought to the front or closed.
+#[component]
+fn RunningPresentationsBanner(
+    running_presentations: Signal<Vec<RunningPresentation>>,
+    selected_items: Signal<Vec<SelectedItemRepresentation>>,
+) -> Element {
+    let settings = use_settings();
+
+    rsx! {
+        if !running_presentations.read().is_empty() {
+            article {
+                class: "restore-banner running-presentations",
+                p { { t!("selection.running_presentations").to_string() } }
+                ul {
+                    for rp in running_presentations.read().iter() {
+                        {
+                            let id = rp.id;
+                            let first_name = rp
+                                .presentation
+                                .first()
+                                .map(|chapter| chapter.source_file.name.clone())
+                                .unwrap_or_default();
+                            rsx! {
+                                li {
+                                    key: "{id}",
+                                    span {
+                                        { t!("selection.running_presentation", number = id + 1, first = first_name, count = rp.presentation.len()).to_string() }
+                                    }
+                                    div {
+                                        role: "group",
+                                        if cfg!(feature = "desktop") {
+                                            button {
+                                                class: "smaller-buttons outline secondary",
+                                                onclick: move |_| {
+                                                    if let Some(rp) = find_presentation_mut(&mut running_presentations.write(), id) {
+                                                        rp.focus_requests += 1;
+                                                    }
+                                                },
+                                                { t!("selection.focus_presentation").to_string() }
+                                            }
+                                        }
+                                        button {
+                                            class: "smaller-buttons outline secondary",
+                                            title: t!("selection.apply_design_changes_description").to_string(),
+                                            onclick: move |_| {
+                                                let settings = settings.read();
+                                                let default_presentation_design = settings.default_presentation_design();
+                                                let default_slide_settings = settings.default_slide_settings();
+                                                if let Some(rp) = find_presentation_mut(&mut running_presentations.write(), id) {
+                                                    presentation::apply_design_changes(
+                                                        rp,
+                                                        &selected_items.read(),
+                                                        &default_presentation_design,
+                                                        &default_slide_settings,
+                                                    );
+                                                }
+                                            },
+                                            { t!("selection.apply_design_changes").to_string() }
+                                        }
+                                        button {
+                                            class: "smaller-buttons outline secondary",
+                                            // The windows of the presentation close themselves when it has been removed
+                                            onclick: move |_| running_presentations.write().retain(|rp| rp.id != id),
+                                            { t!("selection.close_presentation").to_string() }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RestoreSelectionBanner(
+    restorable_selection: Signal<Option<Schedule>>,
+    source_files: Signal<Vec<SourceFile>>,
+    selected_items: Signal<Vec<SelectedItemRepresentation>>,
+) -> Element {
+    let settings = use_settings();
+    let mut unresolved_items: Signal<Vec<String>> = use_signal(Vec::new);
+
+    rsx! {
+        if let Some(schedule) = restorable_selection.read().as_ref() {
+            article {
+                class: "restore-banner",
+                p { { t!("selection.restore_last_selection", count = schedule.items.len()).to_string() } }
+                div {
+                    role: "group",
+                    button {
+                        class: "smaller-buttons",
+                        onclick: move |_| {
+                            let Some(schedule) = restorable_selection.take() else {
+                                return;
+                            };
+                            let resolved = schedule.resolve(&source_files.read());
+                            selected_items.write().extend(resolved.items);
+                            unresolved_items.set(resolved.missing.into_iter().map(|item| item.name).collect());
+                        },
+                        { t!("selection.restore").to_string() }
+                    }
+                    button {
+                        class: "smaller-buttons outline secondary",
+                        onclick: move |_| {
+                            restorable_selection.set(None);
+                            // Replace the stored selection, the user doesn't want it anymore
+                            Schedule::from_selected_items(&selected_items.read(), &settings.read().repositories)
+                                .save_as_last_selection();
+                        },
+                        { t!("selection.dismiss").to_string() }
+                    }
+                }
+            }
+        }
+        if !unresolved_items.read().is_empty() {
+            article {
+                class: "restore-banner",
+                p { { t!("selection.import_missing_items").to_string() } }
+                ul {
+                    for name in unresolved_items.read().iter() {
+                        li { { name.clone() } }
+                    }
+                }
+                button {
+                    class: "smaller-buttons outline secondary",
+                    onclick: move |_| unresolved_items.set(Vec::new()),
+                    { t!("selection.dismiss").to_string() }
+                }
+            }
+        }
+    }
+}
+
+/// This component renders a selected item
+#[component]
+fn SelectedItem(
+    selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    id: usize,
+    active_selected_item_id: Signal<Option<usize>>,
+    focused_item: Signal<Option<usize>>,
+    checked_items: Signal<Vec<usize>>,
+    dragging_from: Signal<Option<usize>>,
+    hover_over: Signal<Option<usize>>,
+    anim_target: Signal<Option<usize>>,
+    anim_flip: Signal<bool>,
+    /// The id of the running presentation which currently shows this item
+    live_presentation_id: Option<usize>,
+) -> Element {
+    let settings = use_settings();
+    let mut running_presentations: Signal<Vec<RunningPresentation>> = use_context();
+    let slide_count = selected_items
+        .read()
+        .get(id)
+        .map(|item| {
+            let default_slide_settings = settings
+                .read()
+                .song_slide_settings
+                .first()
+                .cloned()
+                .unwrap_or_default();
+            presentation::slide_count(item, &default_slide_settings)
+        })
+        .unwrap_or(0);
+
+    rsx! {
+        div {
+            id: format!("selected-item-{}", id),
+            role: "button",
+            class: "outline secondary selection_item",
+            class: if checked_items.read().contains(&id) { "checked" },
+            aria_selected: checked_items.read().contains(&id),
+            style: {
+                let mut s = String::from("display: flex; align-items: left; cursor: grab; transition: background-color 300ms ease-out;");
+                if dragging_from().is_some() && hover_over() == Some(id) {
+                    s.push_str(" outline: 2px dashed #888; background-color: rgba(0,0,0,0.03);");
+                }
+                if anim_target() == Some(id) {
+                    s.push_str(" background-color: rgba(255,230,150,0.8);");
+                }
+                s
+            },
+            // Only one item can be reached with the tab key, the others with the arrow keys
+            tabindex: if focused_item().unwrap_or(0) == id { 0 } else { -1 },
+            onfocus: move |_| focused_item.set(Some(id)),
+            onkeydown: move |event: Event<KeyboardData>| {
+                let ctrl = event.modifiers().ctrl();
+                let len = selected_items.len();
+                let handled = match event.key() {
+                    Key::Delete => {
+                        remove_selected_item(selected_items, active_selected_item_id, id);
+                        // Focus the item which has taken the place of the removed one
+                        if len > 1 {
+                            focus_selected_item(focused_item, id.min(len - 2));
+                        } else {
+                            focused_item.set(None);
+                        }
+                        true
+                    }
+                    Key::ArrowUp if ctrl && id > 0 => {
+                        move_selected_item(selected_items, active_selected_item_id, id, id - 1);
+                        focus_selected_item(focused_item, id - 1);
+                        true
+                    }
+                    Key::ArrowDown if ctrl && id + 1 < len => {
+                        move_selected_item(selected_items, active_selected_item_id, id, id + 1);
+                        focus_selected_item(focused_item, id + 1);
+                        true
+                    }
+                    Key::ArrowUp if !ctrl && id > 0 => {
+                        focus_selected_item(focused_item, id - 1);
+                        true
+                    }
+                    Key::ArrowDown if !ctrl && id + 1 < len => {
+                        focus_selected_item(focused_item, id + 1);
+                        true
+                    }
+                    Key::Enter => {
+                        active_selected_item_id.set(Some(id));
+                        true
+                    }
+                    Key::Character(character) if ctrl && character.eq_ignore_ascii_case("d") => {
+                        duplicate_selected_item(selected_items, active_selected_item_id, id);
+                        focus_selected_item(focused_item, id + 1);
+                        true
+                    }
+                    _ => false,
+                };
+                if handled {
+                    // Don't let the selection page move the focus into the search input
+                    event.prevent_default();
+                    event.stop_propagation();
+                }
+            },
+            onmouseenter: move |_| {
+                if dragging_from.read().is_some() {
+                    hover_over.set(Some(id));
+                }
+            },
+            onmouseup: move |_| {
+                // If mouse is released over the same item, the container onmouseup will also handle it
+            },
+            span {
+                style: "flex-grow: 1; display: flex; align-items: center; gap: 0.5em;",
+                onmousedown: move |_| {
+                    anim_target.set(None);
+                    dragging_from.set(Some(id));
+                    hover_over.set(Some(id));
+                },
+                onclick: move |event| {
+                    if event.modifiers().ctrl() {
+                        // Ctrl+click marks several items for removal
+                        let mut checked = checked_items.write();
+                        match checked.iter().position(|checked_id| *checked_id == id) {
+                            Some(position) => {
+                                checked.remove(position);
+                            }
+                            None => checked.push(id),
+                        }
+                    } else {
+                        active_selected_item_id.set(Some(id))
+                    }
+                },
+                match selected_items.read().get(id).unwrap().source_file.file_type {
+                    SourceFileType::Song => rsx! { MusicIcon {} },
+                    SourceFileType::Image => rsx! { ImageIcon {} },
+                    SourceFileType::Pdf => rsx! { PdfIcon {} },
+                    SourceFileType::Markdown => rsx! { MarkdownIcon {} },
+                    SourceFileType::Text => rsx! { AnnouncementIcon {} },
+                    SourceFileType::Blank => rsx! { BlankIcon {} },
+                    _ => rsx! {},
+                },
+                if selected_items.read().get(id).unwrap().source_file.file_type == SourceFileType::Blank {
+                    { t!("selection.blank_item").to_string() }
+                } else {
+                    { selected_items.read().get(id).unwrap().source_file.name.clone() }
+                },
+                span {
+                    class: "slide-count-badge",
+                    title: t!("selection.slide_count", count = slide_count).to_string(),
+                    "{slide_count}"
+                }
+                if let Some(presentation_id) = live_presentation_id {
+                    span {
+                        class: "badge live-badge",
+                        title: t!("selection.live_description").to_string(),
+                        // Don't start dragging the item
+                        onmousedown: move |event| event.stop_propagation(),
+                        onclick: move |event| {
+                            event.stop_propagation();
+                            if let Some(rp) = find_presentation_mut(&mut running_presentations.write(), presentation_id) {
+                                rp.focus_requests += 1;
+                            }
+                        },
+                        { t!("selection.live").to_string() }
+                    }
+                }
+            }
+
+            // Delete a selected item
+            span {
+                class: "right-justified",
+                // Move Item Up
+                if id > 0 {
+                    span {
+                        onclick: move |_| move_selected_item(selected_items, active_selected_item_id, id, id - 1),
+                        Icon {
+                            icon: FaArrowUp,
+                        }
+                    }
+                }
+                if id < selected_items.len() - 1 {
+                    span {
+                        onclick: move |_| move_selected_item(selected_items, active_selected_item_id, id, id + 1),
+                        Icon {
+                            icon: FaArrowDown,
+                        }
+                    }
+                }
+                // Append the item to the running presentation
+                if !running_presentations.read().is_empty() {
+                    span {
+                        title: t!("selection.send_to_presentation").to_string(),
+                        onclick: move |_| {
+                            if let Some(item) = selected_items.read().get(id) {
+                                send_to_running_presentation(running_presentations, item, Some(id), &settings.read());
+                            }
+                        },
+                        PresentationIcon {}
+                    }
+                }
+                // Delete a selected item
+                span {
+                    onclick: move |_| remove_selected_item(selected_items, active_selected_item_id, id),
+                    Icon {
+                        icon: FaTrashCan,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Appends an item as a new chapter to the most recently started presentation while it is running.
+/// `selected_item_index` is the index of the item in the selection, if it is sent from there.
+fn send_to_running_presentation(
+    mut running_presentations: Signal<Vec<RunningPresentation>>,
+    item: &SelectedItemRepresentation,
+    selected_item_index: Option<usize>,
+    settings: &Settings,
+) {
+    let default_presentation_design = settings.default_presentation_design();
+    let default_slide_settings = settings.default_slide_settings();
+    match presentation::create_slide_chapter(item, &default_presentation_design, &default_slide_settings) {
+        Ok(mut chapter) => {
+            chapter.selected_item_index = selected_item_index;
+            if let Some(rp) = running_presentations.write().last_mut() {
+                rp.append_chapter(chapter, settings.blank_slide_between_chapters);
+            }
+        }
+        Err(err) => log::warn!("Could not send {} to the presentation: {}", item.source_file.name, err),
+    }
+}
+
+/// Adds a source file to the selected items. If the file is already part of the selection,
+/// the given [DuplicateSelectionBehavior] decides whether it is added again.
+fn add_to_selection(
+    selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    active_selected_item_id: Signal<Option<usize>>,
+    source_file: SourceFile,
+    settings: &Settings,
+) {
+    let existing_index = selected_items
+        .read()
+        .iter()
+        .position(|item| item.refers_to(&source_file));
+    let insert_after_active = settings.insert_after_active_item;
+
+    match (existing_index, settings.duplicate_selection_behavior) {
+        (Some(index), DuplicateSelectionBehavior::Highlight) => highlight_selected_item(index),
+        (Some(_), DuplicateSelectionBehavior::Confirm) => {
+            spawn(async move {
+                let prompt = t!("selection.duplicate_confirm", name = source_file.name).to_string();
+                let confirmed = document::eval(&js_yes_no_box(prompt))
+                    .await
+                    .ok()
+                    .and_then(|value| value.as_bool())
+                    .unwrap_or(false);
+                if confirmed {
+                    insert_selected_item(
+                        selected_items,
+                        active_selected_item_id,
+                        SelectedItemRepresentation::new_with_sourcefile(source_file),
+                        insert_after_active,
+                    );
+                }
+            });
+        }
+        _ => insert_selected_item(
+            selected_items,
+            active_selected_item_id,
+            SelectedItemRepresentation::new_with_sourcefile(source_file),
+            insert_after_active,
+        ),
+    }
+}
+
+/// Inserts a new item into the selection. If `insert_after_active` is set and an item is active,
+/// the new item is inserted after the active one and becomes active itself, so that consecutive
+/// insertions keep their order. Otherwise the item is appended.
+fn insert_selected_item(
+    mut selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    mut active_selected_item_id: Signal<Option<usize>>,
+    item: SelectedItemRepresentation,
+    insert_after_active: bool,
+) {
+    let len = selected_items.len();
+    match active_selected_item_id() {
+        Some(active) if insert_after_active && active < len => {
+            selected_items.write().insert(active + 1, item);
+            active_selected_item_id.set(Some(active + 1));
+        }
+        _ => selected_items.write().push(item),
+    }
+}
+
+/// Scrolls to the selected item with the given index and lets it flash shortly.
+fn highlight_selected_item(id: usize) {
+    let _ = document::eval(&format!(
+        r#"
+        const element = document.getElementById('selected-item-{}');
+        if (element) {{
+            element.scrollIntoView({{ block: 'nearest', behavior: 'smooth' }});
+            element.classList.remove('duplicate-highlight');
+            void element.offsetWidth;
+            element.classList.add('duplicate-highlight');
+        }}
+        "#,
+        id
+    ));
+}
+
+/// Removes a selected item. The active item is adjusted so that it still refers to the same
+/// element, or reset if the active item has been removed.
+fn remove_selected_item(
+    mut selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    mut active_selected_item_id: Signal<Option<usize>>,
+    id: usize,
+) {
+    if id >= selected_items.len() {
+        return;
+    }
+    selected_items.write().remove(id);
+    match active_selected_item_id() {
+        Some(active) if active == id => active_selected_item_id.set(None),
+        Some(active) if active > id => active_selected_item_id.set(Some(active - 1)),
+        _ => {}
+    }
+}
+
+/// Removes several selected items at once. The active item is adjusted in the same way as in
+/// [`remove_selected_item`].
+fn remove_selected_items(
+    mut selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    mut active_selected_item_id: Signal<Option<usize>>,
+    ids: &[usize],
+) {
+    let mut ids = ids.to_vec();
+    ids.sort_unstable();
+    ids.dedup();
+    {
+        let mut items = selected_items.write();
+        for id in ids.iter().rev() {
+            if *id < items.len() {
+                items.remove(*id);
+            }
+        }
+    }
+    if let Some(active) = active_selected_item_id() {
+        if ids.contains(&active) {
+            active_selected_item_id.set(None);
+        } else {
+            let removed_before = ids.iter().filter(|id| **id < active).count();
+            active_selected_item_id.set(Some(active - removed_before));
+        }
+    }
+}
+
+/// Swaps a selected item with its neighbor. The active item follows the moved element.
+fn move_selected_item(
+    mut selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    mut active_selected_item_id: Signal<Option<usize>>,
+    from: usize,
+    to: usize,
+) {
+    if from >= selected_items.len() || to >= selected_items.len() {
+        return;
+    }
+    selected_items.write().swap(from, to);
+    match active_selected_item_id() {
+        Some(active) if active == from => active_selected_item_id.set(Some(to)),
+        Some(active) if active == to => active_selected_item_id.set(Some(from)),
+        _ => {}
+    }
+}
+
+/// Inserts a copy of a selected item (including its individual settings) right after it.
+fn duplicate_selected_item(
+    mut selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    mut active_selected_item_id: Signal<Option<usize>>,
+    id: usize,
+) {
+    let Some(item) = selected_items.read().get(id).cloned() else {
+        return;
+    };
+    selected_items.write().insert(id + 1, item);
+    if let Some(active) = active_selected_item_id() {
+        if active > id {
+            active_selected_item_id.set(Some(active + 1));
+        }
+    }
+}
+
+/// Moves the keyboard focus to the selected item with the given index.
+fn focus_selected_item(mut focused_item: Signal<Option<usize>>, id: usize) {
+    focused_item.set(Some(id));
+    let _ = document::eval(&format!(
+        "document.getElementById('selected-item-{}')?.focus();",
+        id
+    ));
+}
+
+/// The input of the verse order of a song. It shows the parts of the song and warns if the
+/// order names parts which don't exist.
 #[component]
-fn SelectedItem(
+fn VerseOrderSettings(
     selected_items: Signal<Vec<SelectedItemRepresentation>>,
-    id: usize,
-    active_selected_item_id: Signal<Option<usize>>,
-    dragging_from: Signal<Option<usize>>,
-    hover_over: Signal<Option<usize>>,
-    anim_target: Signal<Option<usize>>,
-    anim_flip: Signal<bool>,
+    item_index: usize,
+    song_content: String,
 ) -> Element {
+    let verse_order_override = selected_items
+        .read()
+        .get(item_index)
+        .and_then(|item| item.verse_order.clone());
+    let part_labels = verse_order::part_labels(&song_content);
+    let unknown_parts = verse_order::effective_order(&song_content, verse_order_override.as_deref())
+        .map(|order| verse_order::apply_verse_order(&song_content, &order).unknown_parts)
+        .unwrap_or_default();
+    // Without an override, the order of the song file is shown
+    let placeholder = verse_order::order_directive(&song_content).unwrap_or_else(|| part_labels.join(" "));
+
     rsx! {
-        div {
-            role: "button",
-            class: "outline secondary selection_item",
-            style: {
-                let mut s = String::from("display: flex; align-items: left; cursor: grab; transition: background-color 300ms ease-out;");
-                if dragging_from().is_some() && hover_over() == Some(id) {
-                    s.push_str(" outline: 2px dashed #888; background-color: rgba(0,0,0,0.03);");
-                }
-                if anim_target() == Some(id) {
-                    s.push_str(" background-color: rgba(255,230,150,0.8);");
-                }
-                s
-            },
-            tabindex: 0,
-            onmouseenter: move |_| {
-                if dragging_from.read().is_some() {
-                    hover_over.set(Some(id));
+        label {
+            { t!("selection.presentation_options.verse_order.label").to_string() }
+            input {
+                r#type: "text",
+                value: verse_order_override.unwrap_or_default(),
+                placeholder: placeholder,
+                aria_invalid: if !unknown_parts.is_empty() { "true" },
+                onchange: move |event: Event<FormData>| {
+                    let value = event.value();
+                    let value = value.trim();
+                    selected_items.write()[item_index].verse_order =
+                        (!value.is_empty()).then(|| value.to_string());
                 }
-            },
-            onmouseup: move |_| {
-                // If mouse is released over the same item, the container onmouseup will also handle it
-            },
-            span {
-                style: "flex-grow: 1; display: flex; align-items: center; gap: 0.5em;",
-                onmousedown: move |_| {
-                    anim_target.set(None);
-                    dragging_from.set(Some(id));
-                    hover_over.set(Some(id));
-                },
-                onclick: move |_| {
-                    active_selected_item_id.set(Some(id))
-                },
-                match selected_items.read().get(id).unwrap().source_file.file_type {
-                    SourceFileType::Song => rsx! { MusicIcon {} },
-                    SourceFileType::Image => rsx! { ImageIcon {} },
-                    SourceFileType::Pdf => rsx! { PdfIcon {} },
-                    SourceFileType::Markdown => rsx! { MarkdownIcon {} },
-                    _ => rsx! {},
-                },
-                { selected_items.read().get(id).unwrap().source_file.name.clone() },
             }
-
-            // Delete a selected item
-            span {
-                class: "right-justified",
-                // Move Item Up
-                if id > 0 {
-                    span {
-                        onclick: move |_| { selected_items.write().swap(id, id-1); },
-                        Icon {
-                            icon: FaArrowUp,
-                        }
-                    }
-                }
-                if id < selected_items.len() - 1 {
-                    span {
-                        onclick: move |_| { selected_items.write().swap(id, id+1); },
-                        Icon {
-                            icon: FaArrowDown,
-                        }
-                    }
-                }
-                // Delete a selected item
-                span {
-                    onclick: move |_| {
-                        if *active_selected_item_id.read() == Some(id) {
-                            active_selected_item_id.set(None);
-                        }
-                        selected_items.write().remove(id);
-                    },
-                    Icon {
-                        icon: FaTrashCan,
-                    }
+            small {
+                { t!("selection.presentation_options.verse_order.parts", parts = part_labels.join(", ")).to_string() }
+            }
+            if !unknown_parts.is_empty() {
+                small {
+                    style: "color: var(--pico-del-color);",
+                    { t!("selection.presentation_options.verse_order.unknown_parts", parts = unknown_parts.join(", ")).to_string() }
                 }
             }
         }
@@ -1033,61 +2682,123 @@ fn PresentationOptions(
                     .map(|t| t.after_last_slide)
                     .unwrap_or_default();
                 let current_transition = item.transition_effect;
+                let song_content = if item.source_file.file_type == SourceFileType::Song {
+                    presentation::read_song_content(&item.source_file)
+                } else {
+                    None
+                };
 
                 rsx! {
+                    if let Some(text) = item.inline_text.clone() {
+                        label {
+                            { t!("selection.announcement.text").to_string() }
+                            textarea {
+                                rows: "4",
+                                value: text,
+                                oninput: move |event| {
+                                    selected_items.write()[item_index].set_inline_text(event.value());
+                                },
+                            }
+                        }
+                    }
+                    ItemPresentationDesignSettings {
+                        key: "{item_index}",
+                        selected_items: selected_items,
+                        item_index: item_index,
+                    }
                     div {
                         class: "grid",
                         div {
-                            label { { t!("selection.presentation_options.design").to_string() } }
+                            label { { t!("selection.presentation_options.slide_settings").to_string() } }
                             select {
                                 onchange: move |evt| {
                                     let val = evt.value();
                                     let mut items = selected_items.write();
                                     if val == "default" {
-                                        items[item_index].presentation_design_option = None;
+                                        items[item_index].slide_settings_option = None;
                                     } else if let Ok(idx) = val.parse::<usize>() {
-                                        items[item_index].presentation_design_option = Some(settings.read().presentation_designs[idx].clone());
+                                        items[item_index].slide_settings_option = Some(settings.read().song_slide_settings[idx].clone());
                                     }
                                 },
                                 option {
                                     value: "default",
-                                    selected: item.presentation_design_option.is_none(),
+                                    selected: item.slide_settings_option.is_none(),
                                     { t!("selection.presentation_options.default").to_string() }
                                 }
-                                for (idx, pd) in settings.read().presentation_designs.iter().enumerate() {
+                                for (idx, profile) in settings.read().song_slide_settings.iter().enumerate() {
                                     option {
                                         value: "{idx}",
-                                        selected: item.presentation_design_option.as_ref().map_or(false, |p| p.name == pd.name),
-                                        "{pd.name}"
+                                        // The profile stays selected if only max lines have been changed for this item
+                                        selected: item.slide_settings_option.as_ref().is_some_and(|s| *s == SlideSettingsProfile {
+                                            slide_settings: SlideSettings { max_lines: s.slide_settings.max_lines, ..profile.slide_settings.clone() },
+                                            ..profile.clone()
+                                        }),
+                                        { slide_settings_profile_name(profile, idx) }
                                     }
                                 }
                             }
                         }
                         div {
-                            label { { t!("selection.presentation_options.slide_settings").to_string() } }
-                            select {
+                            label { { t!("selection.presentation_options.max_lines").to_string() } }
+                            input {
+                                r#type: "number",
+                                min: "1",
+                                max: "20",
+                                value: item.slide_settings_option.as_ref().and_then(|s| s.slide_settings.max_lines).map(|lines| lines.to_string()).unwrap_or_default(),
+                                placeholder: t!("selection.presentation_options.max_lines_placeholder").to_string(),
                                 onchange: move |evt| {
-                                    let val = evt.value();
+                                    let value = evt.value();
                                     let mut items = selected_items.write();
-                                    if val == "default" {
-                                        items[item_index].slide_settings_option = None;
-                                    } else if let Ok(idx) = val.parse::<usize>() {
-                                        items[item_index].slide_settings_option = Some(settings.read().song_slide_settings[idx].clone());
-                                    }
+                                    let mut slide_settings = items[item_index]
+                                        .slide_settings_option
+                                        .clone()
+                                        .unwrap_or_else(|| settings.read().default_slide_settings());
+                                    slide_settings.slide_settings.max_lines = value.trim().parse().ok();
+                                    items[item_index].slide_settings_option = Some(slide_settings);
+                                }
+                            }
+                        }
+                    }
+                    if let Some(song_content) = song_content {
+                        VerseOrderSettings {
+                            selected_items: selected_items,
+                            item_index: item_index,
+                            song_content: song_content,
+                        }
+                    }
+                    if item.source_file.file_type == SourceFileType::Song {
+                        label {
+                            { t!("selection.presentation_options.languages.label").to_string() }
+                            select {
+                                onchange: move |evt: Event<FormData>| {
+                                    let shown_languages = match evt.value().as_str() {
+                                        "all" => ShownLanguages::All,
+                                        "primary" => ShownLanguages::Primary,
+                                        "secondary" => ShownLanguages::Secondary,
+                                        other => {
+                                            log::error!("Invalid option for the shown languages selected, the value is: {}", other);
+                                            return;
+                                        }
+                                    };
+                                    selected_items.write()[item_index].shown_languages = shown_languages;
                                 },
                                 option {
-                                    value: "default",
-                                    selected: item.slide_settings_option.is_none(),
-                                    { t!("selection.presentation_options.default").to_string() }
+                                    value: "all",
+                                    selected: item.shown_languages == ShownLanguages::All,
+                                    { t!("selection.presentation_options.languages.all").to_string() }
                                 }
-                                for (idx, _) in settings.read().song_slide_settings.iter().enumerate() {
-                                    option {
-                                        value: "{idx}",
-                                        selected: item.slide_settings_option.as_ref().map_or(false, |s| s == &settings.read().song_slide_settings[idx]),
-                                        { format!("{} {}", t!("selection.presentation_options.slide_settings").to_string(), idx + 1) }
-                                    }
+                                option {
+                                    value: "primary",
+                                    selected: item.shown_languages == ShownLanguages::Primary,
+                                    { t!("selection.presentation_options.languages.primary").to_string() }
+                                }
+                                option {
+                                    value: "secondary",
+                                    selected: item.shown_languages == ShownLanguages::Secondary,
+                                    { t!("selection.presentation_options.languages.secondary").to_string() }
                                 }
                             }
+                            small { { t!("selection.presentation_options.languages.hint").to_string() } }
                         }
                     }
                     div {
@@ -1135,6 +2846,42 @@ fn PresentationOptions(
                                 }
                             }
                         }
+                        // Picture settings
+                        if item.source_file.file_type == SourceFileType::Image {
+                            div {
+                                label { { t!("selection.presentation_options.picture.fit").to_string() } }
+                                select {
+                                    onchange: move |evt| {
+                                        let fit = match evt.value().as_str() {
+                                            "cover" => PictureFit::Cover,
+                                            _ => PictureFit::Contain,
+                                        };
+                                        selected_items.write()[item_index].picture_settings.fit = fit;
+                                    },
+                                    option {
+                                        value: "contain",
+                                        selected: item.picture_settings.fit == PictureFit::Contain,
+                                        { t!("selection.presentation_options.picture.contain").to_string() }
+                                    }
+                                    option {
+                                        value: "cover",
+                                        selected: item.picture_settings.fit == PictureFit::Cover,
+                                        { t!("selection.presentation_options.picture.cover").to_string() }
+                                    }
+                                }
+                                label {
+                                    input {
+                                        r#type: "checkbox",
+                                        role: "switch",
+                                        checked: item.picture_settings.show_caption,
+                                        onchange: move |evt| {
+                                            selected_items.write()[item_index].picture_settings.show_caption = evt.checked();
+                                        }
+                                    }
+                                    { t!("selection.presentation_options.picture.show_caption").to_string() }
+                                }
+                            }
+                        }
                         // Timer settings
                         div {
                             label { { t!("selection.presentation_options.timer.label").to_string() } }
@@ -1213,11 +2960,77 @@ fn PresentationOptions(
                         style: "margin-top: 20px; display: flex; flex-direction: column; align-items: center;",
                         SelectedItemPreview {
                             selected_item: item.clone(),
-                            default_presentation_design: settings.read().presentation_designs[0].clone(),
-                            default_slide_settings: settings.read().song_slide_settings[0].clone(),
+                            default_presentation_design: settings.read().default_presentation_design(),
+                            default_slide_settings: settings.read().default_slide_settings(),
                             width: 400,
                         }
                     }
+                    details {
+                        summary { { t!("selection.presentation_options.all_slides").to_string() } }
+                        SelectedItemSlideList {
+                            selected_item: item.clone(),
+                            default_presentation_design: settings.read().default_presentation_design(),
+                            default_slide_settings: settings.read().default_slide_settings(),
+                            width: 240,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Lets the user choose an individual presentation design for a single selected item.
+/// If no individual design is chosen, the item uses the default presentation design.
+/// A design of the item which is not one of the designs in the settings (e.g. an imported or
+/// adjusted one) is kept as a custom design until another design is chosen.
+#[component]
+fn ItemPresentationDesignSettings(
+    selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    item_index: usize,
+) -> Element {
+    let settings = use_settings();
+    let item_design = selected_items
+        .read()
+        .get(item_index)
+        .and_then(|item| item.presentation_design_option.clone());
+    let designs = settings.read().presentation_designs.clone();
+    let active_design = item_design
+        .as_ref()
+        .and_then(|item_design| designs.iter().position(|design| design == item_design));
+    let custom_design = item_design.filter(|_| active_design.is_none());
+
+    rsx! {
+        label { { t!("selection.presentation_options.design").to_string() } }
+        select {
+            onchange: move |evt| {
+                let val = evt.value();
+                if val == "default" {
+                    selected_items.write()[item_index].presentation_design_option = None;
+                } else if let Ok(idx) = val.parse::<usize>() {
+                    let design = settings.read().presentation_designs.get(idx).cloned();
+                    if design.is_some() {
+                        selected_items.write()[item_index].presentation_design_option = design;
+                    }
+                }
+            },
+            option {
+                value: "default",
+                selected: active_design.is_none() && custom_design.is_none(),
+                { t!("selection.presentation_options.default").to_string() }
+            }
+            if let Some(custom_design) = custom_design {
+                option {
+                    value: "custom",
+                    selected: true,
+                    { t!("selection.presentation_options.custom_design", name = custom_design.name).to_string() }
+                }
+            }
+            for (idx, design) in designs.iter().enumerate() {
+                option {
+                    value: "{idx}",
+                    selected: active_design == Some(idx),
+                    { design.name.clone() }
                 }
             }
         }
@@ -1259,7 +3072,9 @@ fn SourceDetailView(
                                     SourceFileType::Presentation => t!("general.presentation").to_string(),
                                     SourceFileType::Video => t!("general.video").to_string(),
                                     SourceFileType::Pdf => t!("general.pdf").to_string(),
-                                    SourceFileType::Markdown => t!("general.markdown").to_string()
+                                    SourceFileType::Markdown => t!("general.markdown").to_string(),
+                                    SourceFileType::Text => t!("general.announcement").to_string(),
+                                    SourceFileType::Blank => t!("general.blank").to_string()
                                 }
                             }
                         }
@@ -1267,6 +3082,12 @@ fn SourceDetailView(
                             td { strong { { t!("general.title").to_string() } } }
                             td { { item.read().name.clone() } }
                         }
+                        if let Some(number) = song_number(&item.read()) {
+                            tr {
+                                td { strong { { t!("general.song_number").to_string() } } }
+                                td { { number.to_string() } }
+                            }
+                        }
                         tr {
                             td { strong { { t!("general.file_path").to_string() } } }
                             td { { path_string } }
@@ -1371,6 +3192,253 @@ async fn process_dropped_files(
     }
 }
 
+/// Saves the selected items as a schedule file.
+/// On desktop, the user chooses the target file in a save dialog, on other platforms
+/// the schedule is offered as a download.
+async fn export_schedule(
+    selected_items: Vec<SelectedItemRepresentation>,
+    repositories: Vec<Repository>,
+) {
+    let schedule = Schedule::from_selected_items(&selected_items, &repositories);
+
+    #[cfg(feature = "desktop")]
+    {
+        let file_name = format!("{}.{}", t!("selection.schedule_file_name"), SCHEDULE_FILE_EXTENSION);
+        if let Some(path) = FileDialog::new()
+            .add_filter(t!("selection.schedule_file_type").to_string(), &[SCHEDULE_FILE_EXTENSION])
+            .set_file_name(file_name)
+            .save_file()
+        {
+            if let Err(e) = schedule.save_to_file(&path) {
+                log::error!("{}", e);
+                js_alert(t!("selection.export_failed").to_string()).await;
+            }
+        }
+    }
+
+    #[cfg(not(feature = "desktop"))]
+    {
+        match schedule.to_json() {
+            Ok(json) => {
+                let file_name = format!("{}.{}", t!("selection.schedule_file_name"), SCHEDULE_FILE_EXTENSION);
+                let js = format!(
+                    r#"
+                    (function() {{
+                        const blob = new Blob([{}], {{ type: 'application/json' }});
+                        const link = document.createElement('a');
+                        link.href = URL.createObjectURL(blob);
+                        link.download = {};
+                        link.click();
+                        URL.revokeObjectURL(link.href);
+                    }})();
+                    "#,
+                    serde_json::to_string(&json).unwrap_or_default(),
+                    serde_json::to_string(&file_name).unwrap_or_default(),
+                );
+                let _ = document::eval(&js).await;
+            }
+            Err(e) => {
+                log::error!("{}", e);
+                js_alert(t!("selection.export_failed").to_string()).await;
+            }
+        }
+    }
+}
+
+/// Exports the slides of the chapters into a PowerPoint presentation, see [export::pptx_from_chapters].
+/// The user is told about items which could not be exported.
+async fn export_pptx(chapters: Vec<SlideChapter>, failed_items: Vec<SourceFile>) {
+    alert_failed_export_items(&failed_items).await;
+    match export::pptx_from_chapters(&chapters) {
+        Ok(pptx) => {
+            save_export_file(
+                format!("{}.{}", t!("selection.schedule_file_name"), PPTX_FILE_EXTENSION),
+                t!("selection.pptx_file_type").to_string(),
+                PPTX_FILE_EXTENSION,
+                "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+                pptx,
+            )
+            .await
+        }
+        Err(e) => {
+            log::error!("{}", e);
+            js_alert(t!("selection.export_slides_failed").to_string()).await;
+        }
+    }
+}
+
+/// Exports the chapters as a web page, see [export::html_from_chapters].
+/// The user is told about items which could not be exported.
+async fn export_html(chapters: Vec<SlideChapter>, failed_items: Vec<SourceFile>) {
+    alert_failed_export_items(&failed_items).await;
+    let html = export::html_from_chapters(&chapters, &t!("selection.schedule_file_name"));
+    save_export_file(
+        HTML_FILE_NAME.to_string(),
+        t!("selection.html_file_type").to_string(),
+        "html",
+        "text/html",
+        html.into_bytes(),
+    )
+    .await
+}
+
+/// Tells the user which items are missing in an export, because their slides could not be created.
+async fn alert_failed_export_items(failed_items: &[SourceFile]) {
+    if failed_items.is_empty() {
+        return;
+    }
+    let names: Vec<&str> = failed_items.iter().map(|item| item.name.as_str()).collect();
+    js_alert(t!("selection.export_items_failed", items = names.join(", ")).to_string()).await;
+}
+
+/// Saves an exported file.
+/// On desktop, the user chooses the target file in a save dialog, on other platforms
+/// the file is offered as a download.
+async fn save_export_file(
+    file_name: String,
+    file_type: String,
+    extension: &str,
+    mime_type: &str,
+    content: Vec<u8>,
+) {
+    #[cfg(feature = "desktop")]
+    {
+        let _ = mime_type;
+        if let Some(path) = FileDialog::new()
+            .add_filter(file_type, &[extension])
+            .set_file_name(file_name)
+            .save_file()
+        {
+            if let Err(e) = std::fs::write(&path, content) {
+                log::error!("{}", e);
+                js_alert(t!("selection.export_slides_failed").to_string()).await;
+            }
+        }
+    }
+
+    #[cfg(not(feature = "desktop"))]
+    {
+        use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+
+        let _ = (file_type, extension);
+        // The file is passed as base64, which is much shorter than a JSON array of the bytes
+        let js = format!(
+            r#"
+            (function() {{
+                const bytes = Uint8Array.from(atob({}), c => c.charCodeAt(0));
+                const blob = new Blob([bytes], {{ type: {} }});
+                const link = document.createElement('a');
+                link.href = URL.createObjectURL(blob);
+                link.download = {};
+                link.click();
+                URL.revokeObjectURL(link.href);
+            }})();
+            "#,
+            serde_json::to_string(&BASE64.encode(&content)).unwrap_or_default(),
+            serde_json::to_string(mime_type).unwrap_or_default(),
+            serde_json::to_string(&file_name).unwrap_or_default(),
+        );
+        let _ = document::eval(&js).await;
+    }
+}
+
+/// Lets the user choose a schedule file and returns its content.
+/// On desktop, a native file dialog is used, on other platforms a file input of the browser.
+async fn pick_schedule_file_content() -> Option<String> {
+    #[cfg(feature = "desktop")]
+    {
+        let path = FileDialog::new()
+            .add_filter(t!("selection.schedule_file_type").to_string(), &[SCHEDULE_FILE_EXTENSION])
+            .pick_file()?;
+        return match std::fs::read_to_string(&path) {
+            Ok(content) => Some(content),
+            Err(e) => {
+                log::error!("Could not read schedule file {}: {}", path.display(), e);
+                None
+            }
+        };
+    }
+
+    #[cfg(not(feature = "desktop"))]
+    {
+        let js = format!(
+            r#"
+            return await new Promise(resolve => {{
+                const input = document.createElement('input');
+                input.type = 'file';
+                input.accept = '.{}';
+                input.onchange = () => {{
+                    const file = input.files[0];
+                    if (!file) {{ resolve(null); return; }}
+                    file.text().then(resolve, () => resolve(null));
+                }};
+                input.click();
+            }});
+            "#,
+            SCHEDULE_FILE_EXTENSION
+        );
+        document::eval(&js)
+            .await
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+    }
+}
+
+/// Loads a schedule file chosen by the user into the selected items.
+/// If there are already selected items, the user is asked whether they should be replaced.
+/// Entries whose source file can't be found are reported to the user.
+async fn import_schedule(
+    source_files: Signal<Vec<SourceFile>>,
+    mut selected_items: Signal<Vec<SelectedItemRepresentation>>,
+    mut active_selected_item_id: Signal<Option<usize>>,
+) {
+    let Some(content) = pick_schedule_file_content().await else {
+        return;
+    };
+
+    let schedule = match Schedule::from_json(&content) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            log::error!("{}", e);
+            js_alert(t!("selection.import_failed").to_string()).await;
+            return;
+        }
+    };
+    let resolved = schedule.resolve(&source_files.read());
+
+    let replace = if selected_items.read().is_empty() {
+        false
+    } else {
+        let prompt = t!("selection.import_replace_selection").to_string();
+        document::eval(&js_yes_no_box(prompt))
+            .await
+            .ok()
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false)
+    };
+
+    if replace {
+        active_selected_item_id.set(None);
+        selected_items.set(resolved.items);
+    } else {
+        selected_items.write().extend(resolved.items);
+    }
+
+    if !resolved.missing.is_empty() {
+        let missing_names: Vec<String> = resolved
+            .missing
+            .iter()
+            .map(|item| format!("- {}", item.name))
+            .collect();
+        js_alert(format!(
+            "{}\n\n{}",
+            t!("selection.import_missing_items"),
+            missing_names.join("\n")
+        ))
+        .await;
+    }
+}
+
 /// Helper function to start a presentation from the selection page.
 /// Supports multi-screen placement and optional presenter console.
 #[cfg(feature = "desktop")]
@@ -1378,22 +3446,40 @@ fn start_presentation(
     selected_items: &Vec<SelectedItemRepresentation>,
     running_presentations: &mut Signal<Vec<RunningPresentation>>,
     default_presentation_design: &PresentationDesign,
-    default_slide_settings: &SlideSettings,
+    default_slide_settings: &SlideSettingsProfile,
     settings_read: &Settings,
 ) {
     use super::presentation_components::PresentationPage;
     use super::presenter_console_components::PresenterConsolePage;
-    use crate::logic::screens::{enumerate_monitors, resolve_monitor};
+    use crate::logic::screens::{enumerate_monitors, find_monitor_handle, resolve_monitor};
+    use crate::logic::remote_control;
+    use crate::logic::states::RunningPresentationId;
     use dioxus::desktop::Config;
 
-    if presentation::add_presentation(
+    if let Some(presentation_id) = presentation::add_presentation(
         selected_items,
         running_presentations,
         default_presentation_design,
         default_slide_settings,
-    )
-    .is_some()
-    {
+        settings_read.blank_slide_between_chapters,
+    ) {
+        if let Some(rp) = running_presentations.write().last_mut() {
+            rp.apply_presentation_settings(settings_read);
+        }
+
+        if settings_read.remote_control_enabled {
+            let was_running = remote_control::remote_control_running();
+            match remote_control::start_remote_control(settings_read.remote_control_port) {
+                Ok(_) if !was_running => {
+                    forward_remote_commands(*running_presentations, consume_context())
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    log::warn!("The remote control server could not be started: {}", error)
+                }
+            }
+        }
+
         let desktop = dioxus::desktop::window();
         let monitors = enumerate_monitors(&desktop);
 
@@ -1415,14 +3501,19 @@ fn start_presentation(
         let show_presenter_console = settings_read.show_presenter_console;
         let always_fullscreen = settings_read.always_start_fullscreen;
 
+        // A design with a transparent background needs a transparent window and webview
+        let transparent_window = default_presentation_design.has_transparent_background();
+
         // Build the presentation window
         let mut presentation_window_builder = tao::window::WindowBuilder::new()
             .with_resizable(true)
-            .with_visible(true);
+            .with_visible(true)
+            .with_transparent(transparent_window);
 
         if let Some(ref monitor) = presentation_monitor {
-            // Position on the target monitor and use true fullscreen (borderless)
+            // Position on the target monitor and use true fullscreen (borderless) on it
             // to ensure the taskbar is hidden
+            let monitor_handle = find_monitor_handle(&desktop, monitor);
             presentation_window_builder = presentation_window_builder
                 .with_position(tao::dpi::PhysicalPosition::new(
                     monitor.position.0,
@@ -1433,7 +3524,7 @@ fn start_presentation(
                     monitor.size.1,
                 ))
                 .with_decorations(false)
-                .with_fullscreen(Some(tao::window::Fullscreen::Borderless(None)));
+                .with_fullscreen(Some(tao::window::Fullscreen::Borderless(monitor_handle)));
         } else if always_fullscreen {
             presentation_window_builder = presentation_window_builder
                 .with_decorations(false)
@@ -1444,21 +3535,24 @@ fn start_presentation(
                 .with_maximized(true);
         }
 
-        let presentation_dom =
-            VirtualDom::new(PresentationPage).with_root_context(*running_presentations);
-
-        dioxus::desktop::window().new_window(
-            presentation_dom,
-            Config::new()
-                .with_menu(None)
-                // Disable the OS-level drag-drop handler for the presentation window.
-                // Files should only be dropped onto the selection window, not the
-                // presentation window. On Windows, receiving drag-drop events in the
-                // presentation window can cause unexpected VirtualDom teardown and
-                // signal access errors.
-                .with_disable_drag_drop_handler(true)
-                .with_window(presentation_window_builder),
-        );
+        let presentation_dom = VirtualDom::new(PresentationPage)
+            .with_root_context(*running_presentations)
+            .with_root_context(RunningPresentationId(presentation_id));
+
+        let mut presentation_config = Config::new()
+            .with_menu(None)
+            // Disable the OS-level drag-drop handler for the presentation window.
+            // Files should only be dropped onto the selection window, not the
+            // presentation window. On Windows, receiving drag-drop events in the
+            // presentation window can cause unexpected VirtualDom teardown and
+            // signal access errors.
+            .with_disable_drag_drop_handler(true)
+            .with_window(presentation_window_builder);
+        if transparent_window {
+            presentation_config = presentation_config.with_background_color((0, 0, 0, 0));
+        }
+
+        dioxus::desktop::window().new_window(presentation_dom, presentation_config);
 
         // Open presenter console if enabled
         if show_presenter_console {
@@ -1491,8 +3585,9 @@ fn start_presentation(
                         .with_maximized(true);
                 }
 
-                let console_dom =
-                    VirtualDom::new(PresenterConsolePage).with_root_context(*running_presentations);
+                let console_dom = VirtualDom::new(PresenterConsolePage)
+                    .with_root_context(*running_presentations)
+                    .with_root_context(RunningPresentationId(presentation_id));
 
                 dioxus::desktop::window().new_window(
                     console_dom,
@@ -1508,12 +3603,105 @@ fn start_presentation(
     }
 }
 
+/// Opens a stage display window for the running presentation with the given id.
+#[cfg(feature = "desktop")]
+fn open_stage_display(running_presentations: Signal<Vec<RunningPresentation>>, presentation_id: usize) {
+    use super::presentation_components::StageDisplayPage;
+    use crate::logic::states::RunningPresentationId;
+    use dioxus::desktop::Config;
+
+    let window_builder = tao::window::WindowBuilder::new()
+        .with_resizable(true)
+        .with_decorations(true)
+        .with_visible(true)
+        .with_title("Cantara - Stage Display")
+        .with_inner_size(tao::dpi::LogicalSize::new(1024.0, 640.0));
+
+    let stage_display_dom = VirtualDom::new(StageDisplayPage)
+        .with_root_context(running_presentations)
+        .with_root_context(RunningPresentationId(presentation_id));
+
+    dioxus::desktop::window().new_window(
+        stage_display_dom,
+        Config::new()
+            .with_menu(None)
+            .with_disable_drag_drop_handler(true)
+            .with_window(window_builder),
+    );
+}
+
+/// Opens a window which mirrors the running presentation without reacting to input, e.g. for
+/// capturing it into a livestream. The slides are shown with the given design if there is one.
+#[cfg(feature = "desktop")]
+fn open_mirror_window(
+    running_presentations: Signal<Vec<RunningPresentation>>,
+    presentation_id: usize,
+    design: Option<PresentationDesign>,
+) {
+    use super::presentation_components::{MirrorDesign, MirrorPage};
+    use crate::logic::states::RunningPresentationId;
+    use dioxus::desktop::Config;
+
+    let window_builder = tao::window::WindowBuilder::new()
+        .with_resizable(true)
+        .with_decorations(true)
+        .with_visible(true)
+        .with_title("Cantara - Mirror")
+        .with_inner_size(tao::dpi::LogicalSize::new(1280.0, 720.0));
+
+    let mirror_dom = VirtualDom::new(MirrorPage)
+        .with_root_context(running_presentations)
+        .with_root_context(RunningPresentationId(presentation_id))
+        .with_root_context(MirrorDesign(design));
+
+    dioxus::desktop::window().new_window(
+        mirror_dom,
+        Config::new()
+            .with_menu(None)
+            .with_disable_drag_drop_handler(true)
+            .with_window(window_builder),
+    );
+}
+
+/// Forwards the commands of the remote control server to the most recently started presentation
+/// and publishes its state. The server is stopped and the forwarding ends when no presentation is
+/// running anymore or the remote control has been switched off in the settings.
+#[cfg(feature = "desktop")]
+fn forward_remote_commands(
+    mut running_presentations: Signal<Vec<RunningPresentation>>,
+    settings: Signal<Settings>,
+) {
+    use crate::logic::remote_control::{self, RemoteState};
+
+    // The forwarding outlives the selection page, e.g. when the settings are opened
+    dioxus::dioxus_core::spawn_forever(async move {
+        while remote_control::remote_control_running() {
+            let commands = remote_control::take_remote_commands();
+            if !commands.is_empty() {
+                if let Some(rp) = running_presentations.write().last_mut() {
+                    for command in commands {
+                        remote_control::apply_remote_command(rp, command);
+                    }
+                }
+            }
+            match running_presentations.peek().last() {
+                Some(rp) if settings.peek().remote_control_enabled => {
+                    remote_control::publish_remote_state(RemoteState::from(rp))
+                }
+                _ => remote_control::stop_remote_control(),
+            }
+            // Sleep via JS setTimeout, a Rust-side sleep would not pump the WebView
+            let _ = document::eval("await new Promise(r => setTimeout(r, 200))").await;
+        }
+    });
+}
+
 #[cfg(not(feature = "desktop"))]
 fn start_presentation(
     selected_items: &Vec<SelectedItemRepresentation>,
     running_presentations: &mut Signal<Vec<RunningPresentation>>,
     default_presentation_design: &PresentationDesign,
-    default_slide_settings: &SlideSettings,
+    default_slide_settings: &SlideSettingsProfile,
     settings_read: &Settings,
 ) {
     if presentation::add_presentation(
@@ -1521,15 +3709,20 @@ fn start_presentation(
         running_presentations,
         default_presentation_design,
         default_slide_settings,
+        settings_read.blank_slide_between_chapters,
     )
         .is_some()
     {
+        if let Some(rp) = running_presentations.write().last_mut() {
+            rp.apply_presentation_settings(settings_read);
+        }
+
         let nav = navigator();
         if settings_read.show_presenter_console {
             // Store the presentation data in localStorage for the new-tab presentation
             #[cfg(target_arch = "wasm32")]
             {
-                if let Some(rp) = running_presentations.read().first() {
+                if let Some(rp) = running_presentations.read().last() {
                     if let Ok(json) = serde_json::to_string(rp) {
                         let _ = web_sys::window()
                             .and_then(|w| w.local_storage().ok().flatten())
@@ -1594,14 +3787,8 @@ fn SelectionFilterSideBar(active_selection: Signal<SelectionSidebarType>) -> Ele
     let mut settings = use_settings();
 
     // Effective order: use settings value if non-empty, otherwise fall back to the default.
-    let mut order: Signal<Vec<SelectionSidebarType>> = use_signal(|| {
-        let s = settings.read();
-        if s.sidebar_order.is_empty() {
-            default_sidebar_order()
-        } else {
-            s.sidebar_order.clone()
-        }
-    });
+    let mut order: Signal<Vec<SelectionSidebarType>> =
+        use_signal(|| settings.read().effective_sidebar_order());
 
     // Drag-and-drop state
     let mut dragging_from: Signal<Option<usize>> = use_signal(|| None);
@@ -1680,6 +3867,7 @@ fn SelectionFilterSideBar(active_selection: Signal<SelectionSidebarType>) -> Ele
                                 SelectionSidebarType::Pictures => rsx! { ImageIcon {} },
                                 SelectionSidebarType::Pdfs => rsx! { PdfIcon {} },
                                 SelectionSidebarType::Markdown => rsx! { MarkdownIcon {} },
+                                SelectionSidebarType::Presentations => rsx! { PresentationIcon {} },
                             }
                         }
                     }
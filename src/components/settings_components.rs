@@ -1,12 +1,21 @@
 //! This module contains components for displaying and manipulating the program and presentation settings
 
 use super::directory_browser::DirectoryBrowserModal;
-use super::shared_components::{DeleteIcon, EditIcon, PresentationDesignSelector, js_yes_no_box};
+use super::presentation_design_settings_components::PictureSelector;
+use super::shared_components::{
+    DeleteIcon, EditIcon, ExamplePresentationViewer, PresentationDesignSelector, js_alert,
+    js_yes_no_box,
+};
 use super::song_slide_settings_components::SongSlideSettings;
 #[cfg(feature = "desktop")]
 use crate::logic::screens::{MonitorInfo, enumerate_monitors};
+use crate::logic::recently_used::RecentlyUsedSongs;
+use crate::logic::reporting::{self, SessionLogEntry};
+#[cfg(feature = "desktop")]
+use crate::logic::design_files::DESIGN_FILE_EXTENSION;
+#[cfg(feature = "desktop")]
+use crate::logic::sourcefiles::SourceFile;
 use crate::{Route, logic::settings::*};
-use cantara_songlib::slides::SlideSettings;
 use dioxus::logger::tracing;
 use dioxus::prelude::*;
 #[cfg(feature = "desktop")]
@@ -60,22 +69,25 @@ pub fn SettingsPage() -> Element {
 /// Middleware component between SettingsPage and its children.
 #[component]
 fn SettingsContent(presentation_designs: Signal<Vec<PresentationDesign>>) -> Element {
-    let mut settings = use_settings();
-    let song_slide_settings: Signal<Vec<SlideSettings>> =
-        use_signal(|| settings.read().song_slide_settings.clone());
-
     rsx! {
         RepositorySettings {}
         hr {}
+        SearchSettings {}
+        hr {}
+        SelectionSettings {}
+        hr {}
         ScreenSettings {}
         hr {}
         PresentationSettings {
             presentation_designs
         }
         hr {}
-        SongSlideSettings {
-            song_slide_settings
+        // The web version has no settings folder for the session log
+        if !cfg!(target_arch = "wasm32") {
+            SessionLogSettings {}
+            hr {}
         }
+        SongSlideSettings {}
     }
 }
 
@@ -108,6 +120,8 @@ fn RepositorySettings() -> Element {
         if let Some(path) = FileDialog::new().pick_folder() {
             if path.is_dir() && path.exists() {
                 let chosen_directory = path.to_str().unwrap_or_default().to_string();
+                // Drop outdated content in case the folder has been added before
+                crate::logic::search::invalidate_path(&path);
                 settings.write().add_repository_folder(chosen_directory);
 
                 // Trigger a refresh of the file counts
@@ -161,6 +175,7 @@ fn RepositorySettings() -> Element {
                                 onclick: move |_| {
                                     // Clean up the repository before removing it
                                     let repo = settings.read().repositories[index].clone();
+                                    repo.invalidate_search_cache();
                                     repo.cleanup();
 
                                     settings.write().repositories.remove(index);
@@ -262,6 +277,8 @@ fn RepositorySettings() -> Element {
                 DirectoryBrowserModal {
                     show: show_dir_browser,
                     on_select: move |path: String| {
+                        // Drop outdated content in case the folder has been added before
+                        crate::logic::search::invalidate_path(std::path::Path::new(&path));
                         settings.write().add_repository_folder(path);
 
                         // Trigger a refresh of the file counts
@@ -381,12 +398,202 @@ fn RepositorySettings() -> Element {
     }
 }
 
+/// Component for configuring the search, e.g. the search history.
+#[component]
+fn SearchSettings() -> Element {
+    let mut settings = use_settings();
+
+    rsx! {
+        hgroup {
+            h3 { { t!("settings.search_headline").to_string() } }
+        }
+
+        // Remember search history switch
+        article {
+            class: "listed-article",
+            div {
+                div {
+                    h6 { { t!("settings.search_history_title").to_string() } }
+                    p { { t!("settings.search_history_description").to_string() } }
+                }
+                div {
+                    label {
+                        class: "switch",
+                        input {
+                            r#type: "checkbox",
+                            role: "switch",
+                            checked: settings.read().search_history_enabled,
+                            onchange: move |event| {
+                                let enabled = event.value().parse().unwrap_or(true);
+                                settings.write().search_history_enabled = enabled;
+                                // Forget the history when the user opts out
+                                if !enabled {
+                                    settings.write().clear_search_history();
+                                }
+                            }
+                        }
+                        span { class: "slider" }
+                    }
+                }
+            }
+            if !settings.read().search_history.is_empty() {
+                button {
+                    class: "outline secondary smaller-buttons",
+                    onclick: move |_| settings.write().clear_search_history(),
+                    { t!("settings.clear_search_history").to_string() }
+                }
+            }
+        }
+    }
+}
+
+/// Component for the session log of the presented songs (e.g. for the CCLI reporting) with a
+/// list of the entries of the current month.
+#[component]
+fn SessionLogSettings() -> Element {
+    let mut settings = use_settings();
+    let entries: Signal<Vec<SessionLogEntry>> = use_signal(reporting::load_current_month);
+
+    rsx! {
+        hgroup {
+            h3 { { t!("settings.session_log_headline").to_string() } }
+        }
+
+        article {
+            class: "listed-article",
+            div {
+                div {
+                    h6 { { t!("settings.session_log_title").to_string() } }
+                    p { { t!("settings.session_log_description").to_string() } }
+                }
+                div {
+                    label {
+                        class: "switch",
+                        input {
+                            r#type: "checkbox",
+                            role: "switch",
+                            checked: settings.read().session_log_enabled,
+                            onchange: move |event| {
+                                settings.write().session_log_enabled = event.value().parse().unwrap_or(false);
+                            }
+                        }
+                        span { class: "slider" }
+                    }
+                }
+            }
+            h6 { { t!("settings.session_log_current_month").to_string() } }
+            if entries.read().is_empty() {
+                p { { t!("settings.session_log_empty").to_string() } }
+            } else {
+                table {
+                    class: "session-log",
+                    for entry in entries.read().iter() {
+                        tr {
+                            td { { reporting::format_timestamp(entry.timestamp) } }
+                            td { { entry.title.clone() } }
+                        }
+                    }
+                }
+                button {
+                    class: "outline secondary smaller-buttons",
+                    onclick: move |_| {
+                        // Quote the text as a JSON string, so that it can't break the script
+                        let text = serde_json::to_string(&reporting::format_entries(&entries.read()))
+                            .unwrap_or_default();
+                        document::eval(&format!("navigator.clipboard.writeText({});", text));
+                    },
+                    { t!("settings.session_log_copy").to_string() }
+                }
+            }
+        }
+    }
+}
+
+/// Component for modifying how the selection of songs and other items behaves.
+#[component]
+fn SelectionSettings() -> Element {
+    let mut settings = use_settings();
+    let behavior = settings.read().duplicate_selection_behavior;
+    let mut has_recently_used = use_signal(|| !RecentlyUsedSongs::load().entries.is_empty());
+
+    rsx! {
+        hgroup {
+            h3 { { t!("settings.selection_headline").to_string() } }
+        }
+
+        article {
+            class: "listed-article",
+            h6 { { t!("settings.duplicate_selection_title").to_string() } }
+            p { { t!("settings.duplicate_selection_description").to_string() } }
+            select {
+                onchange: move |evt| {
+                    settings.write().duplicate_selection_behavior = match evt.value().as_str() {
+                        "highlight" => DuplicateSelectionBehavior::Highlight,
+                        "allow" => DuplicateSelectionBehavior::Allow,
+                        _ => DuplicateSelectionBehavior::Confirm,
+                    };
+                },
+                option {
+                    value: "highlight",
+                    selected: behavior == DuplicateSelectionBehavior::Highlight,
+                    { t!("settings.duplicate_selection_highlight").to_string() }
+                }
+                option {
+                    value: "confirm",
+                    selected: behavior == DuplicateSelectionBehavior::Confirm,
+                    { t!("settings.duplicate_selection_confirm").to_string() }
+                }
+                option {
+                    value: "allow",
+                    selected: behavior == DuplicateSelectionBehavior::Allow,
+                    { t!("settings.duplicate_selection_allow").to_string() }
+                }
+            }
+        }
+
+        // Alphabet jump bar
+        article {
+            class: "listed-article",
+            h6 { { t!("settings.alphabet_jump_bar_title").to_string() } }
+            p { { t!("settings.alphabet_jump_bar_description").to_string() } }
+            input {
+                r#type: "number",
+                min: "0",
+                value: settings.read().alphabet_jump_bar_threshold,
+                onchange: move |event| {
+                    if let Ok(threshold) = event.value().trim().parse::<usize>() {
+                        settings.write().alphabet_jump_bar_threshold = threshold;
+                    }
+                }
+            }
+        }
+
+        // Recently used songs
+        article {
+            class: "listed-article",
+            h6 { { t!("settings.recently_used_title").to_string() } }
+            p { { t!("settings.recently_used_description").to_string() } }
+            button {
+                class: "outline secondary smaller-buttons",
+                disabled: !has_recently_used(),
+                onclick: move |_| {
+                    RecentlyUsedSongs::default().save();
+                    has_recently_used.set(false);
+                },
+                { t!("settings.clear_recently_used").to_string() }
+            }
+        }
+    }
+}
+
 /// Component for modifying presentation design settings.
 #[component]
 fn PresentationSettings(presentation_designs: Signal<Vec<PresentationDesign>>) -> Element {
+    let nav = use_navigator();
     let mut selected_presentation_design_index = use_signal(|| Some(0));
     let mut selected_presentation_design = use_signal(|| None::<PresentationDesign>);
     let mut settings = use_settings();
+    let mut show_design_gallery = use_signal(|| false);
 
     use_effect(move || {
         let new_value = selected_presentation_design_index()
@@ -425,6 +632,169 @@ fn PresentationSettings(presentation_designs: Signal<Vec<PresentationDesign>>) -
             }
         }
 
+        // Automatic advance of the slides
+        article {
+            class: "listed-article",
+            h6 { { t!("settings.auto_advance_title").to_string() } }
+            p { { t!("settings.auto_advance_description").to_string() } }
+            input {
+                r#type: "number",
+                min: "0",
+                value: settings.read().auto_advance_seconds,
+                onchange: move |event| {
+                    if let Ok(seconds) = event.value().trim().parse::<u32>() {
+                        settings.write().auto_advance_seconds = seconds;
+                    }
+                }
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    role: "switch",
+                    checked: settings.read().auto_advance_loop,
+                    onchange: move |event| {
+                        settings.write().auto_advance_loop = event.value().parse().unwrap_or(false);
+                    }
+                }
+                { t!("settings.auto_advance_loop").to_string() }
+            }
+        }
+
+        // Behavior after the last slide
+        article {
+            class: "listed-article",
+            h6 { { t!("settings.end_of_presentation_title").to_string() } }
+            p { { t!("settings.end_of_presentation_description").to_string() } }
+            select {
+                onchange: move |evt| {
+                    settings.write().end_of_presentation = match evt.value().as_str() {
+                        "blank" => EndOfPresentationBehavior::Blank,
+                        "wrap_around" => EndOfPresentationBehavior::WrapAround,
+                        _ => EndOfPresentationBehavior::Hold,
+                    };
+                },
+                option {
+                    value: "hold",
+                    selected: settings.read().end_of_presentation == EndOfPresentationBehavior::Hold,
+                    { t!("settings.end_of_presentation_hold").to_string() }
+                }
+                option {
+                    value: "blank",
+                    selected: settings.read().end_of_presentation == EndOfPresentationBehavior::Blank,
+                    { t!("settings.end_of_presentation_blank").to_string() }
+                }
+                option {
+                    value: "wrap_around",
+                    selected: settings.read().end_of_presentation == EndOfPresentationBehavior::WrapAround,
+                    { t!("settings.end_of_presentation_wrap_around").to_string() }
+                }
+            }
+        }
+
+        // Blank slide between the songs
+        article {
+            class: "listed-article",
+            div {
+                div {
+                    h6 { { t!("settings.blank_slide_between_chapters_title").to_string() } }
+                    p { { t!("settings.blank_slide_between_chapters_description").to_string() } }
+                }
+                div {
+                    label {
+                        class: "switch",
+                        input {
+                            r#type: "checkbox",
+                            role: "switch",
+                            checked: settings.read().blank_slide_between_chapters,
+                            onchange: move |event| {
+                                settings.write().blank_slide_between_chapters = event.value().parse().unwrap_or(false);
+                            }
+                        }
+                        span { class: "slider" }
+                    }
+                }
+            }
+        }
+
+        // Navigation with the mouse wheel or a presenter remote
+        article {
+            class: "listed-article",
+            h6 { { t!("settings.wheel_navigation_title").to_string() } }
+            p { { t!("settings.wheel_navigation_description").to_string() } }
+            label {
+                input {
+                    r#type: "checkbox",
+                    role: "switch",
+                    checked: settings.read().invert_wheel_direction,
+                    onchange: move |event| {
+                        settings.write().invert_wheel_direction = event.value().parse().unwrap_or(false);
+                    }
+                }
+                { t!("settings.invert_wheel_direction").to_string() }
+            }
+        }
+
+        // Remote control over the local network (not available in the browser)
+        if cfg!(feature = "desktop") {
+            article {
+                class: "listed-article",
+                h6 { { t!("settings.remote_control_title").to_string() } }
+                p { { t!("settings.remote_control_description").to_string() } }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        role: "switch",
+                        checked: settings.read().remote_control_enabled,
+                        onchange: move |event| {
+                            settings.write().remote_control_enabled = event.value().parse().unwrap_or(false);
+                        }
+                    }
+                    { t!("settings.remote_control_enabled").to_string() }
+                }
+                if settings.read().remote_control_enabled {
+                    label {
+                        { t!("settings.remote_control_port").to_string() }
+                        input {
+                            r#type: "number",
+                            min: "1024",
+                            max: "65535",
+                            value: settings.read().remote_control_port,
+                            onchange: move |event| {
+                                if let Ok(port) = event.value().trim().parse::<u16>() {
+                                    settings.write().remote_control_port = port;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Logo which can be shown instead of the slides
+        article {
+            class: "listed-article",
+            h6 { { t!("settings.logo_title").to_string() } }
+            p { { t!("settings.logo_description").to_string() } }
+            div {
+                class: "logo-picker",
+                PictureSelector {
+                    already_selected_image_path: settings.read().logo_image.clone().map(|logo| logo.into_inner().path),
+                    onchange: move |logo| {
+                        settings.write().logo_image = Some(logo);
+                    }
+                }
+            }
+            if settings.read().logo_image.is_some() {
+                button {
+                    class: "outline secondary smaller-buttons",
+                    onclick: move |_| {
+                        settings.write().logo_image = None;
+                    },
+                    { t!("settings.remove_logo").to_string() }
+                }
+            }
+        }
+
         div {
             class: "grid",
             div {
@@ -433,37 +803,69 @@ fn PresentationSettings(presentation_designs: Signal<Vec<PresentationDesign>>) -
                     viewer_width: 400,
                     active_item: selected_presentation_design_index
                 }
+                button {
+                    class: "outline smaller-buttons",
+                    onclick: move |_| show_design_gallery.set(true),
+                    { t!("settings.design_gallery.open").to_string() }
+                }
+                DesignGalleryDialog {
+                    show: show_design_gallery,
+                    onadd: move |design: PresentationDesign| {
+                        presentation_designs.write().push(design);
+                        selected_presentation_design_index.set(Some(presentation_designs.read().len() - 1));
+                        settings.write().ensure_slide_settings_for_designs();
+                    }
+                }
             }
             div {
                 if let Some(selected_presentation) = selected_presentation_design() {
                     PresentationDesignCard {
                         presentation_design: selected_presentation,
                         index: selected_presentation_design_index(),
+                        is_default: selected_presentation_design_index() == Some(settings.read().default_presentation_design_index),
+                        onsetdefault: move |_| {
+                            if let Some(index) = selected_presentation_design_index() {
+                                // The index refers to the designs of this page, so they are saved with it
+                                let mut settings_write = settings.write();
+                                settings_write.presentation_designs = presentation_designs.read().clone();
+                                settings_write.default_presentation_design_index = index;
+                                settings_write.save();
+                            }
+                        },
                         onclone: move |_| {
-                            if let Some(design) = selected_presentation_design() {
+                            if let Some(mut design) = selected_presentation_design() {
+                                let copy_name = t!("settings.design_copy_name", name = design.name).to_string();
+                                design.name = unique_design_name(&presentation_designs.read(), &copy_name, None);
                                 presentation_designs.write().push(design);
                                 let new_len = presentation_designs.read().len();
                                 tracing::debug!("Cloned design. New length: {}", new_len);
-                                
+
+                                // The settings page of the copy reads the designs from the settings
+                                let mut settings_write = settings.write();
+                                settings_write.presentation_designs = presentation_designs.read().clone();
                                 // Ensure there are enough slide settings for all presentation designs
-                                settings.write().ensure_slide_settings_for_designs();
+                                settings_write.ensure_slide_settings_for_designs();
+                                settings_write.save();
+                                drop(settings_write);
+
+                                nav.push(Route::PresentationDesignSettingsPage { index: (new_len - 1) as u16 });
                             }
                         },
                         ondelete: move |_| {
                             if let Some(index) = selected_presentation_design_index() {
                                 if index < presentation_designs.read().len() {
-                                    // Also remove the corresponding slide setting if it exists
-                                    if index < settings.read().song_slide_settings.len() {
-                                        settings.write().song_slide_settings.remove(index);
-                                    }
-                                    
                                     presentation_designs.write().remove(index);
+                                    if settings.write().presentation_design_removed(index) {
+                                        spawn(js_alert(t!("settings.default_design_deleted").to_string()));
+                                    }
                                     selected_presentation_design_index.set(Some(0).filter(|_| !presentation_designs.read().is_empty()));
-                                    
-                                    // Ensure slide settings and presentation designs stay in sync
-                                    settings.write().ensure_slide_settings_for_designs();
                                 }
                             }
+                        },
+                        onimport: move |design: PresentationDesign| {
+                            presentation_designs.write().push(design);
+                            selected_presentation_design_index.set(Some(presentation_designs.read().len() - 1));
+                            settings.write().ensure_slide_settings_for_designs();
                         }
                     }
                 }
@@ -706,14 +1108,25 @@ fn ScreenSettings() -> Element {
 fn PresentationDesignCard(
     presentation_design: PresentationDesign,
     index: Option<usize>,
+    /// Whether the design is used for items without a design of their own
+    is_default: bool,
+    onsetdefault: EventHandler<()>,
     onclone: EventHandler<()>,
     ondelete: EventHandler<()>,
+    /// Called with a design which has been imported from a design file
+    onimport: EventHandler<PresentationDesign>,
 ) -> Element {
     let nav = use_navigator();
     rsx! {
         article {
-            h6 { { presentation_design.name } }
-            p { { presentation_design.description } }
+            h6 {
+                { presentation_design.name.clone() }
+                if is_default {
+                    " "
+                    mark { { t!("settings.default_mark").to_string() } }
+                }
+            }
+            p { { presentation_design.description.clone() } }
             if let Some(index) = index {
                 button {
                     onclick: move |_| {
@@ -721,6 +1134,13 @@ fn PresentationDesignCard(
                     },
                     { t!("general.edit").to_string() }
                 }
+                if !is_default {
+                    button {
+                        class: "secondary",
+                        onclick: move |_| onsetdefault.call(()),
+                        { t!("settings.set_as_default").to_string() }
+                    }
+                }
                 button {
                     class: "secondary",
                     onclick: move |_| onclone.call(()),
@@ -744,6 +1164,167 @@ fn PresentationDesignCard(
                     { t!("general.delete").to_string() }
                 }
             }
+            DesignFileButtons {
+                presentation_design,
+                onimport,
+            }
+        }
+    }
+}
+
+/// Returns the translated name and description of a design of the gallery.
+fn gallery_design_texts(design: GalleryDesign) -> (String, String) {
+    let (name, description) = match design {
+        GalleryDesign::Dark => (
+            t!("settings.design_gallery.dark.name"),
+            t!("settings.design_gallery.dark.description"),
+        ),
+        GalleryDesign::Light => (
+            t!("settings.design_gallery.light.name"),
+            t!("settings.design_gallery.light.description"),
+        ),
+        GalleryDesign::WarmGradient => (
+            t!("settings.design_gallery.warm_gradient.name"),
+            t!("settings.design_gallery.warm_gradient.description"),
+        ),
+        GalleryDesign::Photo => (
+            t!("settings.design_gallery.photo.name"),
+            t!("settings.design_gallery.photo.description"),
+        ),
+    };
+    (name.to_string(), description.to_string())
+}
+
+/// A dialog which shows the ready-made designs of the gallery with a preview. A chosen design
+/// is added as a copy to the designs of the user.
+#[component]
+fn DesignGalleryDialog(show: Signal<bool>, onadd: EventHandler<PresentationDesign>) -> Element {
+    let gallery = use_hook(|| design_gallery(gallery_design_texts));
+
+    if !show() {
+        return rsx! {};
+    }
+
+    rsx! {
+        dialog {
+            open: true,
+            article {
+                class: "design-gallery",
+                header {
+                    h3 { { t!("settings.design_gallery.title").to_string() } }
+                }
+                for (index, design) in gallery.iter().cloned().enumerate() {
+                    div {
+                        key: "{index}",
+                        class: "design-gallery-item",
+                        ExamplePresentationViewer {
+                            presentation_design: design.clone(),
+                            width: 300,
+                        }
+                        div {
+                            h6 { { design.name.clone() } }
+                            p { { design.description.clone() } }
+                            button {
+                                onclick: {
+                                    let design = design.clone();
+                                    move |_| {
+                                        // The gallery keeps its own design, the user gets a copy
+                                        onadd.call(design.clone());
+                                        show.set(false);
+                                    }
+                                },
+                                { t!("settings.design_gallery.add").to_string() }
+                            }
+                        }
+                    }
+                }
+                footer {
+                    button {
+                        class: "secondary",
+                        onclick: move |_| show.set(false),
+                        { t!("settings.close").to_string() }
+                    }
+                }
+            }
         }
     }
 }
+
+/// Buttons for exporting a design into a design file and for importing a design from one.
+/// Only available on desktop platforms, where the files are chosen with file dialogs.
+#[cfg(feature = "desktop")]
+#[component]
+fn DesignFileButtons(
+    presentation_design: PresentationDesign,
+    onimport: EventHandler<PresentationDesign>,
+) -> Element {
+    let source_files: Signal<Vec<SourceFile>> = use_context();
+
+    let export_design = move |_| {
+        let design = presentation_design.clone();
+        async move {
+            let file_name = format!("{}.{}", design.name, DESIGN_FILE_EXTENSION);
+            if let Some(path) = FileDialog::new()
+                .add_filter(t!("settings.design_file_type").to_string(), &[DESIGN_FILE_EXTENSION])
+                .set_file_name(file_name)
+                .save_file()
+            {
+                if let Err(e) = design.export_to_file(&path) {
+                    tracing::error!("{}", e);
+                    js_alert(t!("settings.design_export_failed").to_string()).await;
+                }
+            }
+        }
+    };
+
+    let import_design = move |_| async move {
+        let Some(path) = FileDialog::new()
+            .add_filter(t!("settings.design_file_type").to_string(), &[DESIGN_FILE_EXTENSION])
+            .pick_file()
+        else {
+            return;
+        };
+        let imported = PresentationDesign::import_from_file(&path, &source_files.read());
+        match imported {
+            Ok(imported) => {
+                onimport.call(imported.design);
+                if !imported.missing_backgrounds.is_empty() {
+                    let names = imported.missing_backgrounds.join(", ");
+                    js_alert(t!("settings.design_import_missing_backgrounds", names = names).to_string())
+                        .await;
+                }
+            }
+            Err(e) => {
+                tracing::error!("{}", e);
+                js_alert(t!("settings.design_import_failed").to_string()).await;
+            }
+        }
+    };
+
+    rsx! {
+        div {
+            class: "design-file-buttons",
+            button {
+                class: "outline secondary",
+                onclick: export_design,
+                { t!("settings.export_design").to_string() }
+            }
+            button {
+                class: "outline secondary",
+                onclick: import_design,
+                { t!("settings.import_design").to_string() }
+            }
+        }
+    }
+}
+
+/// Design files are not available on the web.
+#[cfg(not(feature = "desktop"))]
+#[component]
+fn DesignFileButtons(
+    presentation_design: PresentationDesign,
+    onimport: EventHandler<PresentationDesign>,
+) -> Element {
+    let _ = (presentation_design, onimport);
+    rsx! {}
+}
@@ -1,15 +1,28 @@
 //! Shared components reusable across different parts of the program.
 
-use crate::components::presentation_components::PresentationRendererComponent;
-use crate::logic::presentation::{create_amazing_grace_presentation, create_single_item_presentation};
-use crate::logic::settings::{CssSize, PresentationDesign};
+use crate::components::presentation_components::{
+    PresentationRendererComponent, StaticSlideRendererComponent,
+};
+use crate::logic::presentation::{
+    create_amazing_grace_presentation, create_amazing_grace_presentation_with_profile,
+    create_single_item_presentation, example_presentation,
+};
+use crate::logic::css::CssString;
+use crate::logic::settings::{
+    CssSize, PaletteColor, PresentationDesign, PresentationDesignSettings, SlideSettingsProfile,
+    TopBottomLeftRight, unique_design_name, use_settings,
+};
 use crate::logic::states::{RunningPresentation, SelectedItemRepresentation};
+use crate::logic::thumbnails::{cached_thumbnail, design_thumbnail_key, store_thumbnail};
 use cantara_songlib::slides::SlideSettings;
 use dioxus::logger::tracing;
 use dioxus::prelude::*;
 use dioxus_free_icons::Icon;
 use dioxus_free_icons::icons::fa_regular_icons::FaTrashCan;
-use dioxus_free_icons::icons::fa_solid_icons::{FaFilePdf, FaFileCode, FaImage, FaMusic, FaPenToSquare};
+use dioxus_free_icons::icons::fa_solid_icons::{
+    FaBullhorn, FaDisplay, FaFileCode, FaFilePdf, FaImage, FaMusic, FaPenToSquare, FaSquare,
+};
+use rgb::RGBA8;
 
 #[component]
 pub fn DeleteIcon() -> Element {
@@ -41,7 +54,23 @@ pub fn MarkdownIcon(width: Option<u32>) -> Element {
     rsx! { Icon { icon: FaFileCode, width: width.unwrap_or(20) } }
 }
 
+#[component]
+pub fn PresentationIcon(width: Option<u32>) -> Element {
+    rsx! { Icon { icon: FaDisplay, width: width.unwrap_or(20) } }
+}
+
+#[component]
+pub fn AnnouncementIcon(width: Option<u32>) -> Element {
+    rsx! { Icon { icon: FaBullhorn, width: width.unwrap_or(20) } }
+}
+
+#[component]
+pub fn BlankIcon(width: Option<u32>) -> Element {
+    rsx! { Icon { icon: FaSquare, width: width.unwrap_or(20) } }
+}
+
 /// A component displaying multiple presentation designs in an "Amazing Grace" presentation.
+/// A design is renamed by double-clicking its title. The new name is written into the settings.
 #[component]
 pub fn PresentationDesignSelector(
     presentation_designs: Signal<Vec<PresentationDesign>>,
@@ -49,7 +78,24 @@ pub fn PresentationDesignSelector(
     viewer_width: usize,
     active_item: Signal<Option<usize>>,
 ) -> Element {
+    let mut settings = use_settings();
     let song_slide_settings = use_signal(|| song_slide_settings.unwrap_or_default());
+    let mut renamed_design = use_signal(|| None::<usize>);
+
+    let mut rename_design = move |index: usize, name: String| {
+        renamed_design.set(None);
+        if name.trim().is_empty() {
+            return;
+        }
+        let name = unique_design_name(&presentation_designs.read(), &name, Some(index));
+        if let Some(design) = presentation_designs.write().get_mut(index) {
+            design.name = name.clone();
+        }
+        if let Some(design) = settings.write().presentation_designs.get_mut(index) {
+            design.name = name;
+        }
+        settings.read().save();
+    };
 
     rsx! {
         div {
@@ -60,11 +106,31 @@ pub fn PresentationDesignSelector(
                     tabindex: index,
                     key: "{index}",
                     SelectablePresentationViewer {
-                        presentation: create_amazing_grace_presentation(design, &song_slide_settings()),
+                        presentation_design: design.clone(),
+                        song_slide_settings: song_slide_settings(),
                         width: viewer_width,
                         title: design.name.clone(),
                         index,
-                        current_selection: active_item
+                        current_selection: active_item,
+                        ontitledoubleclick: move |_| renamed_design.set(Some(index)),
+                    }
+                    if renamed_design() == Some(index) {
+                        input {
+                            class: "presentation-design-rename",
+                            r#type: "text",
+                            aria_label: t!("general.name").to_string(),
+                            value: design.name.clone(),
+                            onmounted: move |event| async move {
+                                let _ = event.set_focus(true).await;
+                            },
+                            onchange: move |event| rename_design(index, event.value()),
+                            onkeydown: move |event| {
+                                if event.key() == Key::Escape {
+                                    renamed_design.set(None);
+                                }
+                            },
+                            onblur: move |_| renamed_design.set(None),
+                        }
                     }
                 }
             }
@@ -72,25 +138,124 @@ pub fn PresentationDesignSelector(
     }
 }
 
+/// The time in milliseconds which a live preview is shown before its thumbnail is taken, so
+/// that fonts and pictures have been loaded
+const THUMBNAIL_CAPTURE_DELAY_MS: u32 = 500;
+
+/// Takes a thumbnail of the slide in the element with the id `ELEMENT_ID`. The slide is copied
+/// with its computed styles and pictures as data URLs into an SVG image, which is returned as
+/// data URL. Slides with videos are not captured, because a video can't be part of the image.
+const THUMBNAIL_CAPTURE_JS: &str = r#"
+    const slide = document.getElementById(ELEMENT_ID)?.querySelector('.presentation');
+    if (!slide || slide.querySelector('video')) {
+        return null;
+    }
+    const toDataUrl = async (url) => {
+        const blob = await (await fetch(url)).blob();
+        return await new Promise((resolve, reject) => {
+            const reader = new FileReader();
+            reader.onload = () => resolve(reader.result);
+            reader.onerror = reject;
+            reader.readAsDataURL(blob);
+        });
+    };
+    const clone = slide.cloneNode(true);
+    const originals = [slide, ...slide.querySelectorAll('*')];
+    const copies = [clone, ...clone.querySelectorAll('*')];
+    for (let i = 0; i < originals.length; i++) {
+        const computed = getComputedStyle(originals[i]);
+        let style = '';
+        for (const property of computed) {
+            let value = computed.getPropertyValue(property);
+            const url = value.match(/url\(["']?([^"')]+)["']?\)/);
+            if (url && !url[1].startsWith('data:')) {
+                try {
+                    value = value.replace(url[1], await toDataUrl(url[1]));
+                } catch (e) {
+                    value = 'none';
+                }
+            }
+            style += property + ':' + value + ';';
+        }
+        copies[i].setAttribute('style', style);
+        if (copies[i].tagName === 'IMG' && !originals[i].src.startsWith('data:')) {
+            try {
+                copies[i].src = await toDataUrl(originals[i].src);
+            } catch (e) {}
+        }
+    }
+    const xhtml = new XMLSerializer().serializeToString(clone);
+    const svg = '<svg xmlns="http://www.w3.org/2000/svg" width="WIDTH" height="HEIGHT">'
+        + '<foreignObject width="100%" height="100%">' + xhtml + '</foreignObject></svg>';
+    return 'data:image/svg+xml;charset=utf-8,' + encodeURIComponent(svg);
+"#;
+
 /// A wrapper component around PresentationViewer that allows selecting it.
+/// The design is shown as a cached thumbnail. While there is none, the design is rendered live
+/// and a thumbnail is taken from it.
 #[component]
 fn SelectablePresentationViewer(
-    presentation: RunningPresentation,
+    presentation_design: PresentationDesign,
+    song_slide_settings: SlideSettings,
     width: usize,
     title: String,
     index: usize,
     current_selection: Signal<Option<usize>>,
+    ontitledoubleclick: Option<EventHandler<MouseEvent>>,
 ) -> Element {
+    let thumbnail_key = design_thumbnail_key(&presentation_design, &song_slide_settings);
+    let element_id = format!("design-thumbnail-{}-{}", index, thumbnail_key);
+
+    // Reading the signal re-renders the viewer when a thumbnail has been taken
+    let mut captured_key = use_signal(|| None::<u64>);
+    let _ = captured_key();
+    let thumbnail = cached_thumbnail(thumbnail_key);
+    // The example song is only needed as long as there is no thumbnail to show instead
+    let presentation = match thumbnail {
+        Some(_) => example_presentation(&presentation_design, vec![]),
+        None => create_amazing_grace_presentation(&presentation_design, &song_slide_settings),
+    };
+    let needs_thumbnail = thumbnail.is_none();
+    let (native_w, native_h) = presentation.presentation_resolution;
+
+    // The thumbnail is taken after the live preview has been rendered
+    use_effect(use_reactive!(|thumbnail_key, needs_thumbnail, element_id, native_w, native_h| {
+        if !needs_thumbnail || *captured_key.peek() == Some(thumbnail_key) {
+            return;
+        }
+        let js = THUMBNAIL_CAPTURE_JS
+            .replace("ELEMENT_ID", &serde_json::to_string(&element_id).unwrap_or_default())
+            .replace("WIDTH", &native_w.to_string())
+            .replace("HEIGHT", &native_h.to_string());
+        spawn(async move {
+            let js_sleep = format!("await new Promise(r => setTimeout(r, {THUMBNAIL_CAPTURE_DELAY_MS}))");
+            let _ = document::eval(&js_sleep).await;
+            match document::eval(&js).await {
+                Ok(value) => {
+                    if let Some(image) = value.as_str().filter(|image| image.starts_with("data:image")) {
+                        store_thumbnail(thumbnail_key, image.to_string());
+                        // Trigger a re-render which shows the thumbnail
+                        captured_key.set(Some(thumbnail_key));
+                    }
+                }
+                Err(error) => tracing::debug!("Could not take a thumbnail of the design: {}", error),
+            }
+        });
+    }));
+
     rsx! {
         PresentationViewer {
             presentation,
             width,
+            id: element_id,
+            thumbnail,
             title: Some(title),
             selected: Some(index == current_selection().unwrap_or(usize::MAX)),
             onclick: move |_| {
                 tracing::debug!("Selected Presentation: {}", index);
                 current_selection.set(Some(index));
-            }
+            },
+            ontitledoubleclick,
         }
     }
 }
@@ -102,6 +267,16 @@ pub fn PresentationViewer(
     title: Option<String>,
     selected: Option<bool>,
     onclick: Option<EventHandler<MouseEvent>>,
+    /// If set, the boundaries of this padding are drawn above the slide as a guide
+    safe_area: Option<TopBottomLeftRight>,
+    /// Whether the lower third of the slide is marked, where e.g. a camera overlay may be shown
+    show_lower_third: Option<bool>,
+    /// Called when the title is double-clicked, e.g. to rename the shown design
+    ontitledoubleclick: Option<EventHandler<MouseEvent>>,
+    /// An image of the slide which is shown instead of rendering the presentation
+    thumbnail: Option<String>,
+    /// The id of the viewer element
+    id: Option<String>,
 ) -> Element {
     // Render at native presentation resolution and scale down to desired width
     let (native_w, native_h) = presentation.presentation_resolution;
@@ -115,24 +290,74 @@ pub fn PresentationViewer(
         }
     });
 
+    // The presentation is replaced when it changes. If only its design has changed, the slide
+    // which has been navigated to in the preview is kept.
     let mut presentation_signal = use_signal(|| presentation.clone());
-    if *presentation_signal.peek() != presentation {
-        presentation_signal.set(presentation.clone());
+    let mut given_presentation = use_signal(|| presentation.clone());
+    if *given_presentation.peek() != presentation {
+        given_presentation.set(presentation.clone());
+        let mut new_presentation = presentation.clone();
+        let shown_presentation = presentation_signal.peek();
+        let same_sources = shown_presentation
+            .presentation
+            .iter()
+            .map(|chapter| &chapter.source_file)
+            .eq(presentation.presentation.iter().map(|chapter| &chapter.source_file));
+        if let Some(position) = shown_presentation.position.as_ref().filter(|_| same_sources) {
+            new_presentation.jump_to(position.chapter(), position.chapter_slide());
+        }
+        drop(shown_presentation);
+        presentation_signal.set(new_presentation);
     }
 
     rsx! {
         div {
             class: format!("{} presentation-preview inline-div", css_class),
             style: format!("position: relative; width: {}px; height: {}px; {}", native_w, native_h, zoom_css),
+            id,
             onclick: move |event| if let Some(onclick_event) = onclick { onclick_event.call(event) },
-            PresentationRendererComponent {
-                running_presentation: presentation_signal,
-                fire_timer: false,
+            if let Some(thumbnail) = thumbnail {
+                img {
+                    src: thumbnail,
+                    width: native_w,
+                    height: native_h,
+                    draggable: false,
+                }
+            } else {
+                PresentationRendererComponent {
+                    running_presentation: presentation_signal,
+                    fire_timer: false,
+                    show_overlays: false,
+                }
+            }
+            // The guides are drawn next to the renderer, so that they never show up in a
+            // real presentation
+            if let Some(padding) = safe_area {
+                // The padding is applied like in the presentation, so that relative sizes match
+                div {
+                    class: "preview-guide",
+                    style: format!(
+                        "padding: {} {} {} {};",
+                        padding.top.to_css_string(),
+                        padding.right.to_css_string(),
+                        padding.bottom.to_css_string(),
+                        padding.left.to_css_string()
+                    ),
+                    div { class: "preview-safe-area" }
+                }
+            }
+            if show_lower_third.unwrap_or(false) {
+                div {
+                    class: "preview-guide preview-lower-third",
+                }
             }
             if let Some(title) = title {
                 div {
                     class: "presentation-title",
                     style: "position: absolute; top: 0; right: 0; display: flex; align-items: center; justify-content: center; font-size: 30pt; background-color: black; color: white; z-index: 99;",
+                    ondoubleclick: move |event| if let Some(ontitledoubleclick) = ontitledoubleclick {
+                        ontitledoubleclick.call(event)
+                    },
                     { title }
                 }
             }
@@ -140,6 +365,48 @@ pub fn PresentationViewer(
     }
 }
 
+/// The color palette of the design which is currently edited. It is provided as context by
+/// the design settings page, so that every color input can offer the saved colors.
+#[derive(Clone, Copy, PartialEq)]
+pub struct DesignPalette(pub Memo<Vec<PaletteColor>>);
+
+/// Shows the colors of the [DesignPalette] as small buttons. A click applies the color.
+/// Nothing is shown outside of the design settings or if the palette is empty.
+#[component]
+pub fn PaletteSwatches(onselect: EventHandler<RGBA8>) -> Element {
+    let palette = try_use_context::<DesignPalette>()
+        .map(|palette| palette.0())
+        .unwrap_or_default();
+    if palette.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "palette-swatches",
+            for (label, color) in palette.iter().map(|palette_color| (palette_color.label(), palette_color.color)) {
+                button {
+                    class: "palette-swatch",
+                    r#type: "button",
+                    title: label.clone(),
+                    aria_label: label,
+                    style: format!(
+                        "background-color: rgba({}, {}, {}, {});",
+                        color.r,
+                        color.g,
+                        color.b,
+                        color.a as f32 / 255.0
+                    ),
+                    onclick: move |event| {
+                        event.prevent_default();
+                        onselect.call(color);
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Displays an example presentation in 16:9 format scaled to a fixed width.
 #[component]
 pub fn ExamplePresentationViewer(
@@ -147,16 +414,79 @@ pub fn ExamplePresentationViewer(
     song_slide_settings: Option<Signal<SlideSettings>>,
     width: usize,
     increase_font_size_in_percent: Option<usize>,
+    /// A different aspect ratio (width, height) of the example, e.g. `(4, 3)`
+    aspect_ratio: Option<(u32, u32)>,
+    /// Whether the padding of the design is drawn as a guide above the slide
+    show_safe_area: Option<bool>,
+    /// Whether the lower third of the slide is marked
+    show_lower_third: Option<bool>,
+    /// A whole slide settings profile for the example, which is used instead of the song slide
+    /// settings, so that e.g. the end slide and the song number are shown as well
+    slide_settings_profile: Option<SlideSettingsProfile>,
 ) -> Element {
-    let presentation = create_amazing_grace_presentation(
-        &presentation_design,
-        &song_slide_settings.map_or(SlideSettings::default(), |s| s()),
-    );
+    let mut presentation = match slide_settings_profile {
+        Some(profile) => create_amazing_grace_presentation_with_profile(&presentation_design, &profile),
+        None => create_amazing_grace_presentation(
+            &presentation_design,
+            &song_slide_settings.map_or(SlideSettings::default(), |s| s()),
+        ),
+    };
+    if let Some((ratio_w, ratio_h)) = aspect_ratio.filter(|(w, h)| *w > 0 && *h > 0) {
+        let (native_w, _) = presentation.presentation_resolution;
+        presentation.presentation_resolution = (native_w, native_w * ratio_h / ratio_w);
+    }
+    let safe_area = match presentation_design.presentation_design_settings {
+        PresentationDesignSettings::Template(ref template) if show_safe_area.unwrap_or(false) => {
+            Some(template.padding.clone())
+        }
+        _ => None,
+    };
 
     rsx! {
         PresentationViewer {
             presentation,
             width,
+            safe_area,
+            show_lower_third,
+        }
+    }
+}
+
+/// Displays the title slide and the first verse of the "Amazing Grace" example next to each
+/// other, so that a different look of the title slides can be compared with the other slides.
+#[component]
+pub fn ExampleTitleSlidePreview(presentation_design: PresentationDesign, width: usize) -> Element {
+    let mut slide_settings = SlideSettings::default();
+    slide_settings.title_slide = true;
+    let presentation = create_amazing_grace_presentation(&presentation_design, &slide_settings);
+
+    let (native_w, native_h) = presentation.presentation_resolution;
+    let zoom_css = format!("zoom: {};", width as f64 / native_w as f64);
+    let thumb_height = (width as f64 * native_h as f64 / native_w as f64).round() as u32;
+    let slides: Vec<_> = presentation
+        .presentation
+        .into_iter()
+        .next()
+        .map(|chapter| chapter.slides.into_iter().take(2).collect())
+        .unwrap_or_default();
+
+    rsx! {
+        div {
+            class: "title-slide-preview",
+            for (index, slide) in slides.into_iter().enumerate() {
+                div {
+                    key: "{index}",
+                    class: "slide-preview-item",
+                    style: "width: {width}px; height: {thumb_height}px;",
+                    div {
+                        style: "width: {native_w}px; height: {native_h}px; {zoom_css} transform-origin: top left;",
+                        StaticSlideRendererComponent {
+                            slide,
+                            presentation_design: presentation_design.clone(),
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -167,7 +497,7 @@ pub fn ExamplePresentationViewer(
 pub fn SelectedItemPreview(
     selected_item: SelectedItemRepresentation,
     default_presentation_design: PresentationDesign,
-    default_slide_settings: SlideSettings,
+    default_slide_settings: SlideSettingsProfile,
     width: usize,
 ) -> Element {
     let timer_seconds = selected_item
@@ -240,9 +570,144 @@ pub fn SelectedItemPreview(
     }
 }
 
+/// Displays all slides which will be generated for a selected item as miniatures below each other.
+/// The item's individual design and slide settings are taken into account, so the slides look
+/// exactly like in the presentation.
+#[component]
+pub fn SelectedItemSlideList(
+    selected_item: SelectedItemRepresentation,
+    default_presentation_design: PresentationDesign,
+    default_slide_settings: SlideSettingsProfile,
+    width: usize,
+) -> Element {
+    // The props change whenever an individual setting of the item changes, so the slides are
+    // generated again on each render.
+    let presentation = create_single_item_presentation(
+        &selected_item,
+        &default_presentation_design,
+        &default_slide_settings,
+    );
+
+    let (native_w, native_h) = presentation.presentation_resolution;
+    let zoom_factor = width as f64 / native_w as f64;
+    let zoom_css = format!("zoom: {};", zoom_factor);
+    let thumb_height = (width as f64 * native_h as f64 / native_w as f64).round() as u32;
+
+    let Some(chapter) = presentation.presentation.into_iter().next() else {
+        return rsx! {};
+    };
+    let design = chapter
+        .presentation_design_option
+        .unwrap_or(default_presentation_design);
+
+    rsx! {
+        div {
+            class: "slide-preview-list",
+            for (index, slide) in chapter.slides.into_iter().enumerate() {
+                div {
+                    key: "{index}",
+                    class: "slide-preview-item",
+                    style: "width: {width}px; height: {thumb_height}px;",
+                    div {
+                        style: "width: {native_w}px; height: {native_h}px; {zoom_css} transform-origin: top left;",
+                        StaticSlideRendererComponent {
+                            slide,
+                            presentation_design: design.clone(),
+                        }
+                    }
+                    span {
+                        class: "slide-preview-number",
+                        { (index + 1).to_string() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// An entry of a [ContextMenu].
+#[derive(Clone, PartialEq)]
+pub struct ContextMenuEntry {
+    /// The text of the entry
+    pub label: String,
+
+    /// Called when the entry has been chosen
+    pub on_select: EventHandler<()>,
+}
+
+/// A small context menu which is shown at the given position (in client coordinates) as long as
+/// the position is set. The menu closes when an entry is chosen, when the user clicks elsewhere
+/// or when Escape is pressed.
+#[component]
+pub fn ContextMenu(
+    position: Signal<Option<(f64, f64)>>,
+    entries: Vec<ContextMenuEntry>,
+) -> Element {
+    let Some((x, y)) = position() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        // Catches clicks outside of the menu
+        div {
+            class: "context-menu-backdrop",
+            onclick: move |event| {
+                event.stop_propagation();
+                position.set(None);
+            },
+            oncontextmenu: move |event| {
+                event.prevent_default();
+                event.stop_propagation();
+                position.set(None);
+            },
+        }
+        div {
+            class: "context-menu",
+            role: "menu",
+            tabindex: -1,
+            style: "left: {x}px; top: {y}px;",
+            onmounted: move |event| async move {
+                let _ = event.set_focus(true).await;
+            },
+            onkeydown: move |event: Event<KeyboardData>| {
+                if event.key() == Key::Escape {
+                    event.stop_propagation();
+                    position.set(None);
+                }
+            },
+            for (index, entry) in entries.into_iter().enumerate() {
+                {
+                    let ContextMenuEntry { label, on_select } = entry;
+                    rsx! {
+                        div {
+                            key: "{index}",
+                            class: "context-menu-entry",
+                            role: "menuitem",
+                            onclick: move |event| {
+                                event.stop_propagation();
+                                position.set(None);
+                                on_select.call(());
+                            },
+                            { label }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Generates JavaScript for a yes/no dialog box.
 pub fn js_yes_no_box(prompt: String) -> String {
-    format!("return confirm('{}');", prompt)
+    // Quote the prompt as a JSON string, so that quotes in names can't break the script
+    let prompt = serde_json::to_string(&prompt).unwrap_or_default();
+    format!("return confirm({});", prompt)
+}
+
+/// Shows a message to the user in a simple alert box.
+pub async fn js_alert(message: String) {
+    let message = serde_json::to_string(&message).unwrap_or_default();
+    let _ = document::eval(&format!("alert({});", message)).await;
 }
 
 #[component]
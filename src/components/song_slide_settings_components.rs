@@ -1,8 +1,9 @@
 //! This module provides components for adjusting the song slide settings
 
-use crate::components::shared_components::{DeleteIcon, EditIcon, NumberedValidatedLengthInput};
-use crate::logic::settings::{use_settings};
-use cantara_songlib::slides::SlideSettings;
+use crate::components::shared_components::{
+    DeleteIcon, EditIcon, ExamplePresentationViewer, NumberedValidatedLengthInput, js_alert,
+};
+use crate::logic::settings::{EndSlide, SlideSettingsProfile, SpoilerMode, use_settings};
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
 use dioxus::hooks::use_signal;
@@ -12,6 +13,15 @@ use rust_i18n::t;
 
 rust_i18n::i18n!("locales", fallback = "en");
 
+/// Returns the name of a song slide settings profile which is shown to the user.
+/// Profiles without a name are numbered by their position in the settings.
+pub fn slide_settings_profile_name(profile: &SlideSettingsProfile, index: usize) -> String {
+    match profile.name() {
+        Some(name) => name.to_string(),
+        None => t!("settings.song_slide_settings.unnamed", number = index + 1).to_string(),
+    }
+}
+
 /// This page contains the settings for song slides
 #[component]
 pub fn SongSlideSettingsPage(
@@ -21,7 +31,7 @@ pub fn SongSlideSettingsPage(
     let nav = navigator();
     let mut settings = use_settings();
 
-    let selected_slide_settings_option: Signal<Option<SlideSettings>> =
+    let selected_slide_settings_option: Signal<Option<SlideSettingsProfile>> =
         use_signal(|| {
             settings
                 .read()
@@ -41,22 +51,36 @@ pub fn SongSlideSettingsPage(
     let selected_slide_settings =
         use_memo(move || selected_slide_settings_option.read().clone().unwrap());
 
+    // The example preview follows the changes of the settings
+    let mut preview_slide_settings = use_signal(move || selected_slide_settings.read().clone());
+
     rsx! {
         div {
             class: "wrapper",
             header {
                 class: "top-bar",
-                h2 { { t!("settings.song_slide_settings_edit_header", title = index + 1).to_string() } }
+                h2 { { t!("settings.song_slide_settings_edit_header", title = slide_settings_profile_name(&selected_slide_settings(), index as usize)).to_string() } }
             }
             main {
                 class: "container-fluid content height-100",
 
-                MetaSettings {
-                    slide_settings: selected_slide_settings(),
-                    on_settings_changed: move |updated_settings: SlideSettings| {
-                        let mut settings_write = settings.write();
-                        let origin_settings = settings_write.song_slide_settings.get_mut(index as usize).unwrap();
-                        *origin_settings = updated_settings;
+                div {
+                    class: "grid",
+                    MetaSettings {
+                        slide_settings: selected_slide_settings(),
+                        on_settings_changed: move |updated_settings: SlideSettingsProfile| {
+                            preview_slide_settings.set(updated_settings.clone());
+                            let mut settings_write = settings.write();
+                            let origin_settings = settings_write.song_slide_settings.get_mut(index as usize).unwrap();
+                            *origin_settings = updated_settings;
+                        }
+                    }
+                    div {
+                        ExamplePresentationViewer {
+                            presentation_design: settings.read().default_presentation_design(),
+                            width: 480,
+                            slide_settings_profile: preview_slide_settings(),
+                        }
                     }
                 }
             }
@@ -73,19 +97,31 @@ pub fn SongSlideSettingsPage(
     }
 }
 
-/// Component for modifying song slide settings.
+/// Component for creating, duplicating and deleting the song slide settings profiles. They are
+/// edited on their own page. The profiles are changed in the settings directly, which are saved
+/// before the page of a profile is opened.
 #[component]
-pub fn SongSlideSettings(song_slide_settings: Signal<Vec<SlideSettings>>) -> Element {
+pub fn SongSlideSettings() -> Element {
+    let nav = use_navigator();
     let mut selected_slide_settings_index = use_signal(|| Some(0));
-    let mut selected_slide_settings = use_signal(|| None::<SlideSettings>);
     let mut settings = use_settings();
-
-    use_effect(move || {
-        let new_value = selected_slide_settings_index()
-            .and_then(|index| song_slide_settings.read().get(index).cloned());
-        selected_slide_settings.set(new_value);
+    let selected_slide_settings = use_memo(move || {
+        selected_slide_settings_index()
+            .and_then(|index| settings.read().song_slide_settings.get(index).cloned())
     });
 
+    // Adds a profile and opens its page
+    let mut add_profile = move |profile: SlideSettingsProfile| {
+        let mut settings_write = settings.write();
+        settings_write.song_slide_settings.push(profile);
+        let new_len = settings_write.song_slide_settings.len();
+        settings_write.save();
+        drop(settings_write);
+        tracing::debug!("Added slide settings. New length: {}", new_len);
+        selected_slide_settings_index.set(Some(new_len - 1));
+        nav.push(crate::Route::SongSlideSettingsPage { index: (new_len - 1) as u16 });
+    };
+
     rsx! {
         hgroup {
             h4 { { t!("settings.song_slide_headline").to_string() } }
@@ -102,11 +138,11 @@ pub fn SongSlideSettings(song_slide_settings: Signal<Vec<SlideSettings>>) -> Ele
                         let index = event.value().parse::<usize>().unwrap_or(0);
                         selected_slide_settings_index.set(Some(index));
                     },
-                    for (index, _) in song_slide_settings.read().iter().enumerate() {
+                    for (index, profile) in settings.read().song_slide_settings.iter().enumerate() {
                         option {
                             value: index.to_string(),
                             selected: selected_slide_settings_index() == Some(index),
-                            { format!("Slide Setting {}", index + 1) }
+                            { slide_settings_profile_name(profile, index) }
                         }
                     }
                 }
@@ -116,23 +152,45 @@ pub fn SongSlideSettings(song_slide_settings: Signal<Vec<SlideSettings>>) -> Ele
                     SongSlideSettingsCard {
                         slide_settings: selected_settings,
                         index: selected_slide_settings_index(),
+                        is_default: selected_slide_settings_index() == Some(settings.read().default_slide_settings_index),
+                        onsetdefault: move |_| {
+                            if let Some(index) = selected_slide_settings_index() {
+                                let mut settings_write = settings.write();
+                                settings_write.default_slide_settings_index = index;
+                                settings_write.save();
+                            }
+                        },
                         onclone: move |_| {
-                            if let Some(settings) = selected_slide_settings() {
-                                song_slide_settings.write().push(settings);
-                                let new_len = song_slide_settings.read().len();
-                                tracing::debug!("Cloned slide settings. New length: {}", new_len);
+                            if let Some(mut profile) = selected_slide_settings() {
+                                if let Some(name) = profile.name().map(str::to_string) {
+                                    profile.name = t!("settings.song_slide_settings.copy_name", name = name).to_string();
+                                }
+                                add_profile(profile);
                             }
                         },
                         ondelete: move |_| {
                             if let Some(index) = selected_slide_settings_index() {
-                                if index < song_slide_settings.read().len() {
-                                    song_slide_settings.write().remove(index);
-                                    selected_slide_settings_index.set(Some(0).filter(|_| !song_slide_settings.read().is_empty()));
+                                let mut settings_write = settings.write();
+                                if index < settings_write.song_slide_settings.len() {
+                                    settings_write.song_slide_settings.remove(index);
+                                    let default_reassigned = settings_write.slide_settings_removed(index);
+                                    let is_empty = settings_write.song_slide_settings.is_empty();
+                                    settings_write.save();
+                                    drop(settings_write);
+                                    selected_slide_settings_index.set(Some(0).filter(|_| !is_empty));
+                                    if default_reassigned {
+                                        spawn(js_alert(t!("settings.default_slide_settings_deleted").to_string()));
+                                    }
                                 }
                             }
                         }
                     }
                 }
+                button {
+                    class: "outline smaller-buttons",
+                    onclick: move |_| add_profile(SlideSettingsProfile::default()),
+                    { t!("settings.song_slide_settings.new").to_string() }
+                }
             }
         }
     }
@@ -141,16 +199,36 @@ pub fn SongSlideSettings(song_slide_settings: Signal<Vec<SlideSettings>>) -> Ele
 /// Displays an article with details and actions for song slide settings.
 #[component]
 fn SongSlideSettingsCard(
-    slide_settings: SlideSettings,
+    slide_settings: SlideSettingsProfile,
     index: Option<usize>,
+    /// Whether the profile is used for items without slide settings of their own
+    is_default: bool,
+    onsetdefault: EventHandler<()>,
     onclone: EventHandler<()>,
     ondelete: EventHandler<()>,
 ) -> Element {
     let nav = use_navigator();
     rsx! {
         article {
-            h6 { { format!("Slide Setting {}", index.map_or(0, |i| i + 1)) } }
-            p { { format!("{:?}", slide_settings) } }
+            h6 {
+                { slide_settings_profile_name(&slide_settings, index.unwrap_or_default()) }
+                if is_default {
+                    " "
+                    mark { { t!("settings.default_mark").to_string() } }
+                }
+            }
+            if !slide_settings.description.trim().is_empty() {
+                p { { slide_settings.description.clone() } }
+            }
+            p {
+                small {
+                    {
+                        let max_lines = slide_settings.slide_settings.max_lines.map(|lines| lines.to_string())
+                            .unwrap_or_else(|| t!("settings.song_slide_settings.unlimited").to_string());
+                        t!("settings.song_slide_settings.summary", max_lines = max_lines).to_string()
+                    }
+                }
+            }
             if let Some(index) = index {
                 button {
                     onclick: move |_| {
@@ -158,6 +236,13 @@ fn SongSlideSettingsCard(
                     },
                     { t!("general.edit").to_string() }
                 }
+                if !is_default {
+                    button {
+                        class: "secondary",
+                        onclick: move |_| onsetdefault.call(()),
+                        { t!("settings.set_as_default").to_string() }
+                    }
+                }
                 button {
                     class: "secondary",
                     onclick: move |_| onclone.call(()),
@@ -189,16 +274,16 @@ fn SongSlideSettingsCard(
 #[component]
 fn MetaSettings(
     /// The slide settings which should be edited
-    slide_settings: SlideSettings,
+    slide_settings: SlideSettingsProfile,
 
     /// A closure which is called each time when the slide settings have been changed
-    on_settings_changed: EventHandler<SlideSettings>,
+    on_settings_changed: EventHandler<SlideSettingsProfile>,
 ) -> Element {
     let mut settings = use_signal(|| slide_settings);
 
     // Helper function to display the max_lines value
     let max_lines_display = move || {
-        match settings().max_lines {
+        match settings().slide_settings.max_lines {
             Some(lines) => lines.to_string(),
             None => "".to_string(),
         }
@@ -208,16 +293,40 @@ fn MetaSettings(
         h3 { { t!("general.meta_information").to_string() } }
         form {
             fieldset {
+                // Name and description of the profile
+                label {
+                    { t!("settings.song_slide_settings.name").to_string() }
+                    input {
+                        type: "text",
+                        value: settings().name.clone(),
+                        placeholder: t!("settings.song_slide_settings.name_placeholder").to_string(),
+                        onchange: move |event| {
+                            settings.write().name = event.value();
+                            on_settings_changed.call(settings());
+                        }
+                    }
+                }
+                label {
+                    { t!("settings.song_slide_settings.description").to_string() }
+                    textarea {
+                        value: settings().description.clone(),
+                        onchange: move |event| {
+                            settings.write().description = event.value();
+                            on_settings_changed.call(settings());
+                        }
+                    }
+                }
+
                 // Title Slide setting
                 label {
                     input {
                         type: "checkbox",
                         role: "switch",
-                        checked: settings().title_slide,
+                        checked: settings().slide_settings.title_slide,
                         onchange: move |event| {
                             {
                                 let mut settings_write = settings.write();
-                                settings_write.title_slide = event.checked();
+                                settings_write.slide_settings.title_slide = event.checked();
                             } // Drop the mutable borrow
                             on_settings_changed.call(settings());
                         }
@@ -225,38 +334,75 @@ fn MetaSettings(
                     { "Show Title Slide" }
                 }
 
-                // Show Spoiler setting
+                // Spoiler Mode setting, the example shows the effect of the selected mode
                 label {
-                    input {
-                        type: "checkbox",
-                        role: "switch",
-                        checked: settings().show_spoiler,
+                    { t!("settings.song_slide_settings.spoiler_mode.label").to_string() }
+                    select {
                         onchange: move |event| {
-                            {
-                                let mut settings_write = settings.write();
-                                settings_write.show_spoiler = event.checked();
-                            } // Drop the mutable borrow
+                            let spoiler_mode = match event.value().as_str() {
+                                "next_lines" => SpoilerMode::NextLines,
+                                "chorus" => SpoilerMode::Chorus,
+                                "none" => SpoilerMode::None,
+                                value => {
+                                    tracing::error!("Invalid option for the spoiler mode selected, the value is: {}", value);
+                                    return;
+                                }
+                            };
+                            settings.write().set_spoiler_mode(spoiler_mode);
                             on_settings_changed.call(settings());
+                        },
+                        option {
+                            value: "none",
+                            selected: settings().spoiler_mode == SpoilerMode::None,
+                            { t!("settings.song_slide_settings.spoiler_mode.none").to_string() }
+                        }
+                        option {
+                            value: "next_lines",
+                            selected: settings().spoiler_mode == SpoilerMode::NextLines,
+                            { t!("settings.song_slide_settings.spoiler_mode.next_lines").to_string() }
+                        }
+                        option {
+                            value: "chorus",
+                            selected: settings().spoiler_mode == SpoilerMode::Chorus,
+                            { t!("settings.song_slide_settings.spoiler_mode.chorus").to_string() }
                         }
                     }
-                    { "Show Spoiler" }
+                    small { { t!("settings.song_slide_settings.spoiler_mode.hint").to_string() } }
                 }
 
-                // Empty Last Slide setting
+                // End Slide setting, which replaces the empty last slide of cantara_songlib
                 label {
-                    input {
-                        type: "checkbox",
-                        role: "switch",
-                        checked: settings().empty_last_slide,
+                    { t!("settings.song_slide_settings.end_slide.label").to_string() }
+                    select {
                         onchange: move |event| {
-                            {
-                                let mut settings_write = settings.write();
-                                settings_write.empty_last_slide = event.checked();
-                            } // Drop the mutable borrow
+                            let end_slide = match event.value().as_str() {
+                                "repeat_title" => EndSlide::RepeatTitle,
+                                "empty" => EndSlide::Empty,
+                                "none" => EndSlide::None,
+                                value => {
+                                    tracing::error!("Invalid option for the end slide selected, the value is: {}", value);
+                                    return;
+                                }
+                            };
+                            settings.write().end_slide = end_slide;
                             on_settings_changed.call(settings());
+                        },
+                        option {
+                            value: "none",
+                            selected: settings().end_slide == EndSlide::None,
+                            { t!("settings.song_slide_settings.end_slide.none").to_string() }
+                        }
+                        option {
+                            value: "repeat_title",
+                            selected: settings().end_slide == EndSlide::RepeatTitle,
+                            { t!("settings.song_slide_settings.end_slide.repeat_title").to_string() }
+                        }
+                        option {
+                            value: "empty",
+                            selected: settings().end_slide == EndSlide::Empty,
+                            { t!("settings.song_slide_settings.end_slide.empty").to_string() }
                         }
                     }
-                    { "Empty Last Slide" }
                 }
 
                 // Meta Syntax setting
@@ -264,11 +410,11 @@ fn MetaSettings(
                     { "Meta Syntax" }
                     input {
                         type: "text",
-                        value: settings().meta_syntax.clone(),
+                        value: settings().slide_settings.meta_syntax.clone(),
                         onchange: move |event| {
                             {
                                 let mut settings_write = settings.write();
-                                settings_write.meta_syntax = event.value().clone();
+                                settings_write.slide_settings.meta_syntax = event.value().clone();
                             } // Drop the mutable borrow
                             on_settings_changed.call(settings());
                         }
@@ -290,15 +436,66 @@ fn MetaSettings(
                             {
                                 let mut settings_write = settings.write();
                                 if value.is_empty() {
-                                    settings_write.max_lines = None;
+                                    settings_write.slide_settings.max_lines = None;
                                 } else {
-                                    settings_write.max_lines = Some(value.parse().unwrap_or(4));
+                                    settings_write.slide_settings.max_lines = Some(value.parse().unwrap_or(4));
                                 }
                             } // Drop the mutable borrow
                             on_settings_changed.call(settings());
                         }
                     }
                 }
+
+                // Max Characters Per Line setting
+                label {
+                    { t!("settings.song_slide_settings.max_chars_per_line").to_string() }
+                    input {
+                        type: "number",
+                        min: "10",
+                        max: "200",
+                        value: settings().max_chars_per_line.map(|chars| chars.to_string()).unwrap_or_default(),
+                        placeholder: t!("settings.song_slide_settings.max_chars_per_line_placeholder").to_string(),
+                        onchange: move |event| {
+                            let value = event.value();
+                            settings.write().max_chars_per_line = value.trim().parse().ok().filter(|chars| *chars > 0);
+                            on_settings_changed.call(settings());
+                        }
+                    }
+                    small { { t!("settings.song_slide_settings.max_chars_per_line_hint").to_string() } }
+                }
+
+                // Combine Stanzas setting
+                label {
+                    { t!("settings.song_slide_settings.combine_stanzas_up_to").to_string() }
+                    input {
+                        type: "number",
+                        min: "2",
+                        max: "20",
+                        value: settings().combine_stanzas_up_to.map(|lines| lines.to_string()).unwrap_or_default(),
+                        placeholder: t!("settings.song_slide_settings.combine_stanzas_placeholder").to_string(),
+                        onchange: move |event| {
+                            let value = event.value();
+                            settings.write().combine_stanzas_up_to = value.trim().parse().ok().filter(|lines| *lines > 0);
+                            on_settings_changed.call(settings());
+                        }
+                    }
+                    small { { t!("settings.song_slide_settings.combine_stanzas_hint").to_string() } }
+                }
+
+                // Show Song Number setting
+                label {
+                    input {
+                        type: "checkbox",
+                        role: "switch",
+                        checked: settings().show_song_number,
+                        onchange: move |event| {
+                            settings.write().show_song_number = event.checked();
+                            on_settings_changed.call(settings());
+                        }
+                    }
+                    { t!("settings.song_slide_settings.show_song_number").to_string() }
+                }
+                small { { t!("settings.song_slide_settings.show_song_number_hint").to_string() } }
             }
         }
     }
@@ -11,12 +11,25 @@ impl ToHexString for RGB8 {
     }
 }
 
+/// The alpha channel is left out, because color inputs only accept `#RRGGBB`.
+/// Use [ToHexAlphaString] to keep it.
 impl ToHexString for RGBA8 {
     fn to_hex(&self) -> String {
         format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
     }
 }
 
+/// Converts a Color with alpha channel to a Hex string in the form `#RRGGBBAA`
+pub trait ToHexAlphaString {
+    fn to_hex_with_alpha(&self) -> String;
+}
+
+impl ToHexAlphaString for RGBA8 {
+    fn to_hex_with_alpha(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+    }
+}
+
 pub trait ToRgb8 {
     fn to_rgb8(&self) -> Option<RGB8>;
 }
@@ -44,6 +57,56 @@ impl ToRgb8 for String {
     }
 }
 
+pub trait ToRgba8 {
+    fn to_rgba8(&self) -> Option<RGBA8>;
+}
+
+impl ToRgba8 for String {
+    /// Parses colors in the form `#RRGGBBAA`. Colors without alpha channel (`#RRGGBB`) are opaque.
+    fn to_rgba8(&self) -> Option<RGBA8> {
+        let hex = self.trim().trim_start_matches('#');
+
+        // Verify all characters are valid hexadecimal digits before slicing the string
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let alpha = match hex.len() {
+            6 => 255,
+            8 => u8::from_str_radix(&hex[6..8], 16).ok()?,
+            _ => return None,
+        };
+        let rgb = hex[0..6].to_string().to_rgb8()?;
+        Some(RGBA8::new(rgb.r, rgb.g, rgb.b, alpha))
+    }
+}
+
+/// Inverts the lightness of a color while its hue and saturation stay the same, so that dark
+/// colors become light and vice versa (e.g. dark blue becomes light blue).
+pub trait InvertLightness {
+    fn invert_lightness(&self) -> Self;
+}
+
+impl InvertLightness for RGB8 {
+    fn invert_lightness(&self) -> Self {
+        // With the same hue and saturation, the lightness `(max + min) / 2` is mirrored by
+        // shifting all channels by the same amount
+        let max = self.r.max(self.g).max(self.b) as i16;
+        let min = self.r.min(self.g).min(self.b) as i16;
+        let shift = 255 - max - min;
+        let shifted = |channel: u8| (channel as i16 + shift).clamp(0, 255) as u8;
+        RGB8::new(shifted(self.r), shifted(self.g), shifted(self.b))
+    }
+}
+
+/// The alpha channel is kept.
+impl InvertLightness for RGBA8 {
+    fn invert_lightness(&self) -> Self {
+        let rgb = RGB8::new(self.r, self.g, self.b).invert_lightness();
+        RGBA8::new(rgb.r, rgb.g, rgb.b, self.a)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,6 +132,64 @@ mod tests {
         assert_eq!(rgba.to_hex(), "#000000");
     }
 
+    #[test]
+    fn test_rgba8_to_hex_with_alpha() {
+        let rgba = RGBA8::new(255, 0, 128, 255);
+        assert_eq!(rgba.to_hex_with_alpha(), "#FF0080FF");
+
+        let rgba = RGBA8::new(0, 0, 0, 128);
+        assert_eq!(rgba.to_hex_with_alpha(), "#00000080");
+    }
+
+    #[test]
+    fn test_string_to_rgba8() {
+        assert_eq!(
+            "#FF008080".to_string().to_rgba8(),
+            Some(RGBA8::new(255, 0, 128, 128))
+        );
+        assert_eq!(
+            "ff0080".to_string().to_rgba8(),
+            Some(RGBA8::new(255, 0, 128, 255))
+        );
+
+        // Invalid length or characters
+        assert!("#FF00800".to_string().to_rgba8().is_none());
+        assert!("#FF0080ZZ".to_string().to_rgba8().is_none());
+        assert!("#+F0080FF".to_string().to_rgba8().is_none());
+        assert!("".to_string().to_rgba8().is_none());
+    }
+
+    #[test]
+    fn test_rgba_roundtrip_conversion() {
+        for original in [
+            RGBA8::new(255, 0, 128, 0),
+            RGBA8::new(12, 34, 56, 78),
+            RGBA8::new(255, 255, 255, 255),
+        ] {
+            assert_eq!(original.to_hex_with_alpha().to_rgba8(), Some(original));
+        }
+
+        let original_hex = "#00FF0033".to_string();
+        let rgba = original_hex.to_rgba8().unwrap();
+        assert_eq!(rgba.to_hex_with_alpha(), original_hex);
+    }
+
+    #[test]
+    fn test_invert_lightness() {
+        assert_eq!(RGB8::new(0, 0, 0).invert_lightness(), RGB8::new(255, 255, 255));
+        assert_eq!(RGB8::new(255, 255, 255).invert_lightness(), RGB8::new(0, 0, 0));
+        assert_eq!(RGB8::new(200, 200, 200).invert_lightness(), RGB8::new(55, 55, 55));
+
+        // Pure colors keep their lightness, dark blue becomes light blue
+        assert_eq!(RGB8::new(255, 0, 0).invert_lightness(), RGB8::new(255, 0, 0));
+        assert_eq!(RGB8::new(0, 0, 128).invert_lightness(), RGB8::new(127, 127, 255));
+
+        // Inverting twice gives the original color and the alpha channel is kept
+        let color = RGBA8::new(12, 34, 56, 78);
+        assert_eq!(color.invert_lightness().a, 78);
+        assert_eq!(color.invert_lightness().invert_lightness(), color);
+    }
+
     #[test]
     fn test_string_to_rgb8_valid() {
         let hex = "#FF0080".to_string();
@@ -1,6 +1,8 @@
 //! This module contains structures for building CSS rules which can be used to build a CSS string.
 
-use crate::logic::settings::{CssSize, FontRepresentation, HorizontalAlign};
+use crate::logic::settings::{
+    BackgroundGradient, CssSize, FontRepresentation, HorizontalAlign, TextTransform, VerticalAlign,
+};
 use rgb::{RGB8, RGBA8};
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
@@ -41,8 +43,14 @@ impl CssHandler {
         self.important = important;
     }
 
-    pub fn background_color(&mut self, color: RGB8) {
-        self.push("background-color".to_string(), CssValue::Rgb(color))
+    /// Sets the background color. Opaque colors are given as `rgb(...)`, transparent ones as
+    /// `rgba(...)`.
+    pub fn background_color(&mut self, color: RGBA8) {
+        let value = match color.a {
+            255 => CssValue::Rgb(RGB8::new(color.r, color.g, color.b)),
+            _ => CssValue::Rgba(color),
+        };
+        self.push("background-color".to_string(), value)
     }
 
     pub fn padding_left(&mut self, size: CssSize) {
@@ -80,6 +88,14 @@ impl CssHandler {
         )
     }
 
+    /// Sets a gradient as background, which replaces the background color.
+    pub fn background(&mut self, gradient: &BackgroundGradient) {
+        self.push(
+            "background".to_string(),
+            CssValue::String(gradient.to_css_string()),
+        )
+    }
+
     pub fn background_image_none(&mut self) {
         self.push(
             "background-image".to_string(),
@@ -108,6 +124,28 @@ impl CssHandler {
         )
     }
 
+    /// Blurs the element with the given radius, e.g. a background picture.
+    pub fn filter_blur(&mut self, radius: CssSize) {
+        self.push(
+            "filter".to_string(),
+            CssValue::String(format!("blur({})", radius.to_css_string())),
+        )
+    }
+
+    pub fn object_fit(&mut self, content: &str) {
+        self.push(
+            "object-fit".to_string(),
+            CssValue::String(content.to_string()),
+        )
+    }
+
+    pub fn object_position(&mut self, content: &str) {
+        self.push(
+            "object-position".to_string(),
+            CssValue::String(content.to_string()),
+        )
+    }
+
     pub fn opacity(&mut self, opacity: f32) {
         let opacity = opacity.clamp(0.0, 1.0);
 
@@ -140,8 +178,110 @@ impl CssHandler {
             CssValue::String(value.to_string()),
         )
     }
+
+    pub fn text_shadow(
+        &mut self,
+        offset_x: CssSize,
+        offset_y: CssSize,
+        blur: CssSize,
+        color: RGBA8,
+    ) {
+        self.push(
+            "text-shadow".to_string(),
+            CssValue::String(format!(
+                "{} {} {} {}",
+                offset_x.to_css_string(),
+                offset_y.to_css_string(),
+                blur.to_css_string(),
+                CssValue::Rgba(color)
+            )),
+        )
+    }
+
+    pub fn text_stroke(&mut self, width: CssSize, color: RGBA8) {
+        self.push(
+            "-webkit-text-stroke".to_string(),
+            CssValue::String(format!("{} {}", width.to_css_string(), CssValue::Rgba(color))),
+        )
+    }
+
+    pub fn letter_spacing(&mut self, size: CssSize) {
+        self.push("letter-spacing".to_string(), CssValue::CssSize(size))
+    }
+
+    pub fn text_transform(&mut self, text_transform: TextTransform) {
+        self.push(
+            "text-transform".to_string(),
+            CssValue::String(text_transform.to_css_string()),
+        )
+    }
+
+    pub fn paint_order(&mut self, value: &str) {
+        self.push(
+            "paint-order".to_string(),
+            CssValue::String(value.to_string()),
+        )
+    }
+
+    /// Limits the text of the element to the given number of lines. The text which doesn't fit
+    /// is cut off with an ellipsis.
+    pub fn line_clamp(&mut self, lines: u8) {
+        self.push(
+            "display".to_string(),
+            CssValue::String("-webkit-box".to_string()),
+        );
+        self.push(
+            "-webkit-box-orient".to_string(),
+            CssValue::String("vertical".to_string()),
+        );
+        self.push("-webkit-line-clamp".to_string(), CssValue::Int(lines.max(1) as i32));
+        self.push("line-clamp".to_string(), CssValue::Int(lines.max(1) as i32));
+        self.push("overflow".to_string(), CssValue::String("hidden".to_string()))
+    }
+
+    /// Lays out the children of the element in a column which fills the height of its parent.
+    /// The children are aligned vertically as given, unless they have an own alignment
+    /// (see [CssHandler::flex_self_alignment]).
+    pub fn flex_column(&mut self, align: &VerticalAlign) {
+        self.push("display".to_string(), CssValue::String("flex".to_string()));
+        self.push(
+            "flex-direction".to_string(),
+            CssValue::String("column".to_string()),
+        );
+        self.push("height".to_string(), CssValue::CssSize(CssSize::Percentage(100.0)));
+        self.push(
+            "justify-content".to_string(),
+            CssValue::String(
+                match align {
+                    VerticalAlign::Top => "flex-start",
+                    VerticalAlign::Middle => "center",
+                    VerticalAlign::Bottom => "flex-end",
+                }
+                .to_string(),
+            ),
+        )
+    }
+
+    /// Sets the position of the element within a flex layout of its parent.
+    pub fn order(&mut self, order: i32) {
+        self.push("order".to_string(), CssValue::Int(order))
+    }
+
+    /// Aligns the element vertically within a flex column (see [CssHandler::flex_column]) by
+    /// automatic margins, which push the element away from the other elements.
+    pub fn flex_self_alignment(&mut self, align: &VerticalAlign) {
+        if *align != VerticalAlign::Top {
+            self.push("margin-top".to_string(), CssValue::String("auto".to_string()));
+        }
+        if *align != VerticalAlign::Bottom {
+            self.push("margin-bottom".to_string(), CssValue::String("auto".to_string()));
+        }
+    }
 }
 
+/// The offset of a text shadow. It is given in `em`, so that it grows with the font size.
+const TEXT_SHADOW_OFFSET: CssSize = CssSize::Em(0.05);
+
 impl Display for CssHandler {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for entry in &self.declarations {
@@ -195,7 +335,9 @@ impl Display for CssValue {
             CssValue::String(s) => write!(f, "{}", s),
             CssValue::Rgb(rgb) => write!(f, "rgb({}, {}, {})", rgb.r, rgb.g, rgb.b),
             CssValue::Rgba(rgba) => {
-                write!(f, "rgba({}, {}, {}, {})", rgba.r, rgba.g, rgba.b, rgba.a)
+                // CSS expects the alpha channel as a number between 0 and 1
+                let alpha = (rgba.a as f64 / 255.0 * 1000.0).round() / 1000.0;
+                write!(f, "rgba({}, {}, {}, {})", rgba.r, rgba.g, rgba.b, alpha)
             }
             CssValue::Url(s) => write!(f, "url('{}')", s),
             CssValue::Int(i) => write!(f, "{}", i),
@@ -220,6 +362,25 @@ impl From<FontRepresentation> for CssHandler {
         if font.horizontal_alignment == HorizontalAlign::JustifyWithHyphenation {
             css_handler.hyphens("auto");
         }
+        if font.shadow {
+            css_handler.text_shadow(
+                TEXT_SHADOW_OFFSET,
+                TEXT_SHADOW_OFFSET,
+                font.shadow_blur,
+                font.shadow_color,
+            );
+        }
+        if let Some(letter_spacing) = font.letter_spacing {
+            css_handler.letter_spacing(letter_spacing);
+        }
+        if let Some(text_transform) = font.text_transform {
+            css_handler.text_transform(text_transform);
+        }
+        if let Some(outline) = font.outline {
+            css_handler.text_stroke(outline.width, outline.color);
+            // The stroke is painted below the letters, so that it doesn't make them thinner
+            css_handler.paint_order("stroke fill");
+        }
 
         css_handler
     }
@@ -293,7 +454,10 @@ impl CssString for CssFontFamily {
     fn to_css_string(&self) -> String {
         match &self.family {
             Some(family_name) => {
-                format!("{}, {}", family_name, self.genereric_family.to_css_string())
+                // The name is quoted, because family names may contain spaces or digits. Single
+                // quotes are used, so that the CSS can be placed into HTML style attributes.
+                let escaped_name = family_name.replace('\\', "\\\\").replace('\'', "\\'");
+                format!("'{}', {}", escaped_name, self.genereric_family.to_css_string())
             }
             None => self.genereric_family.to_css_string(),
         }
@@ -326,14 +490,309 @@ impl CssString for GenericFontFamily {
     }
 }
 
+/// Removes `@import` statements, `url()` references and `image-set()` images of external
+/// resources (e.g. `http://`) from custom CSS, unless external resources are allowed. External
+/// references are replaced by `none`, local files and data URLs are kept. Comments and escaped
+/// letters (e.g. `ur/**/l(` or `u\72l(`) are resolved before, so that they can't hide a reference.
+pub fn sanitize_custom_css(css: &str, allow_external_resources: bool) -> String {
+    if allow_external_resources {
+        return css.to_string();
+    }
+    let css = resolve_escaped_letters(&strip_css_comments(css));
+    // The lower case copy has the same byte positions, because only ASCII letters are changed
+    let lower_css = css.to_ascii_lowercase();
+    let mut result = String::with_capacity(css.len());
+    let mut position = 0;
+    while let Some(character) = css[position..].chars().next() {
+        let rest = &lower_css[position..];
+        if rest.starts_with("@import") {
+            position += rest.find(';').map_or(rest.len(), |end| end + 1);
+        } else if rest.starts_with("url(")
+            || rest.starts_with("image-set(")
+            || rest.starts_with("-webkit-image-set(")
+        {
+            let end = matching_parenthesis(rest).map_or(rest.len(), |end| end + 1);
+            if references_external_resource(&rest[..end]) {
+                result.push_str("none");
+            } else {
+                result.push_str(&css[position..position + end]);
+            }
+            position += end;
+        } else {
+            result.push(character);
+            position += character.len_utf8();
+        }
+    }
+    result
+}
+
+/// Whether the given lower case `url()` or `image-set()` function refers to an external
+/// resource, either as `url()` or as string.
+fn references_external_resource(function: &str) -> bool {
+    let is_external = |target: &str| {
+        ["http:", "https:", "//"]
+            .iter()
+            .any(|prefix| target.trim().starts_with(prefix))
+    };
+    if let Some(target) = function.strip_prefix("url(") {
+        return is_external(target.trim_end_matches(')').trim().trim_matches(['"', '\'']));
+    }
+    let mut rest = function;
+    while let Some(start) = rest.find(['"', '\'', '(']) {
+        let (quote, after) = rest[start..].split_at(1);
+        if quote == "(" {
+            if rest[..start].ends_with("url")
+                && is_external(after.trim_start().trim_start_matches(['"', '\'']))
+            {
+                return true;
+            }
+            rest = after;
+            continue;
+        }
+        let end = after.find(quote).unwrap_or(after.len());
+        if is_external(&after[..end]) {
+            return true;
+        }
+        rest = after.get(end + 1..).unwrap_or_default();
+    }
+    false
+}
+
+/// Returns the position of the parenthesis which closes the first opening parenthesis.
+/// Parentheses in quoted strings are skipped.
+fn matching_parenthesis(css: &str) -> Option<usize> {
+    let open = css.find('(')?;
+    matching_bracket(css, open, '(', ')')
+}
+
+/// Resolves escape sequences in CSS which stand for ASCII letters, digits or `-`, e.g. `\72`
+/// or `\r` for `r`. These have the same meaning escaped and unescaped, everywhere in CSS.
+fn resolve_escaped_letters(css: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find('\\') {
+        result.push_str(&rest[..start]);
+        let escape = &rest[start + 1..];
+        let hex_length = escape
+            .char_indices()
+            .take_while(|(index, character)| *index < 6 && character.is_ascii_hexdigit())
+            .count();
+        let (resolved, mut length) = match hex_length {
+            0 => match escape.chars().next() {
+                Some(character) => (Some(character), character.len_utf8()),
+                None => (None, 0),
+            },
+            _ => (
+                u32::from_str_radix(&escape[..hex_length], 16)
+                    .ok()
+                    .and_then(char::from_u32),
+                hex_length,
+            ),
+        };
+        match resolved.filter(|character| character.is_ascii_alphanumeric() || *character == '-') {
+            Some(character) => {
+                // A single white space ends a hexadecimal escape and belongs to it
+                if hex_length > 0 && escape[length..].starts_with([' ', '\t', '\n']) {
+                    length += 1;
+                }
+                result.push(character);
+            }
+            None => result.push_str(&rest[start..start + 1 + length]),
+        }
+        rest = &escape[length..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Restricts custom CSS to the element which is selected by `scope` (e.g. an attribute
+/// selector like `[data-design-scope="1"]`) and its descendants, so that a custom design can't
+/// change the rest of the window. Selectors starting with `.presentation` select the scope
+/// element itself. Rules in nested at-rules like `@media`, `@supports` or `@layer` are scoped
+/// as well, only the bodies of `@font-face`, `@keyframes` and `@page` are kept as they are.
+pub fn scope_custom_css(css: &str, scope: &str) -> String {
+    let css = strip_css_comments(css);
+    let mut result = String::new();
+    let mut rest = css.as_str();
+    while let Some(open) = rest.find('{') {
+        let Some(close) = matching_brace(rest, open) else {
+            break;
+        };
+        // Statements like `@import` end with a semicolon before the next rule
+        let (statements, prelude) = match rest[..open].rfind(';') {
+            Some(end) => (rest[..=end].trim(), rest[end + 1..open].trim()),
+            None => ("", rest[..open].trim()),
+        };
+        if !statements.is_empty() {
+            result.push_str(statements);
+            result.push('\n');
+        }
+        let body = &rest[open + 1..close];
+        let at_rule = prelude
+            .strip_prefix('@')
+            .map(|rule| rule.split(|c: char| c.is_whitespace() || c == '(').next().unwrap_or_default());
+        if let Some(at_rule) = at_rule {
+            if ["font-face", "keyframes", "-webkit-keyframes", "page"]
+                .contains(&at_rule.to_ascii_lowercase().as_str())
+            {
+                // These don't contain selectors
+                result.push_str(&format!("{} {{{}}}\n", prelude, body));
+            } else {
+                result.push_str(&format!(
+                    "{} {{\n{}}}\n",
+                    prelude,
+                    scope_custom_css(body, scope)
+                ));
+            }
+        } else {
+            let selectors: Vec<String> = prelude
+                .split(',')
+                .map(|selector| scope_selector(selector.trim(), scope))
+                .collect();
+            result.push_str(&format!("{} {{{}}}\n", selectors.join(", "), body));
+        }
+        rest = &rest[close + 1..];
+    }
+    result
+}
+
+/// Scopes a single selector, see [scope_custom_css].
+fn scope_selector(selector: &str, scope: &str) -> String {
+    match selector.strip_prefix(".presentation") {
+        Some(rest)
+            if !rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+        {
+            format!(".presentation{}{}", scope, rest)
+        }
+        _ => format!("{} {}", scope, selector),
+    }
+}
+
+/// Returns the position of the brace which closes the brace at `open`.
+fn matching_brace(css: &str, open: usize) -> Option<usize> {
+    matching_bracket(css, open, '{', '}')
+}
+
+/// Returns the position of the bracket which closes the bracket at `open`.
+/// Brackets in quoted strings are skipped.
+fn matching_bracket(css: &str, open: usize, opening: char, closing: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, character) in css[open..].char_indices() {
+        if let Some(quote_character) = quote {
+            match character {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                _ if character == quote_character => quote = None,
+                _ => {}
+            }
+            continue;
+        }
+        match character {
+            '"' | '\'' => quote = Some(character),
+            _ if character == opening => depth += 1,
+            _ if character == closing => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Removes the `/* ... */` comments from CSS.
+fn strip_css_comments(css: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        rest = rest[start + 2..]
+            .find("*/")
+            .map_or("", |end| &rest[start + 2 + end + 2..]);
+    }
+    result.push_str(rest);
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logic::settings::FontOutline;
+    use crate::logic::test_helpers::without_fields;
+
+    #[test]
+    fn test_custom_css_is_scoped() {
+        let css = "/* The whole slide */
+            .presentation { background: navy; }
+            .main-content p, .headline { color: gold; }
+            @media (max-width: 800px) { .spoiler-content { display: none; } }
+            @keyframes glow { from { opacity: 0; } to { opacity: 1; } }";
+        let scoped = scope_custom_css(css, "[data-design-scope=\"1\"]");
+        assert!(scoped.contains(".presentation[data-design-scope=\"1\"] { background: navy; }"));
+        assert!(scoped.contains(
+            "[data-design-scope=\"1\"] .main-content p, [data-design-scope=\"1\"] .headline {"
+        ));
+        assert!(scoped.contains("@media (max-width: 800px) {\n[data-design-scope=\"1\"] .spoiler-content"));
+        assert!(scoped.contains("@keyframes glow { from { opacity: 0; } to { opacity: 1; } }"));
+        assert!(!scoped.contains("The whole slide"));
+    }
+
+    #[test]
+    fn test_nested_at_rules_and_strings_are_scoped() {
+        let css = "@layer theme { .headline { color: gold; } }
+            @container (min-width: 400px) { .main-content { font-size: 2em; } }
+            .main-content::before { content: \"}\"; }
+            body { background: red; }";
+        let scoped = scope_custom_css(css, "[data-design-scope=\"1\"]");
+        assert!(scoped.contains("@layer theme {\n[data-design-scope=\"1\"] .headline"));
+        assert!(scoped.contains(
+            "@container (min-width: 400px) {\n[data-design-scope=\"1\"] .main-content"
+        ));
+        assert!(scoped.contains("[data-design-scope=\"1\"] .main-content::before { content: \"}\"; }"));
+        assert!(scoped.contains("[data-design-scope=\"1\"] body {"));
+    }
+
+    #[test]
+    fn test_external_resources_are_removed_from_custom_css() {
+        let css = "@import url('https://example.com/theme.css');
+            .background { background-image: URL( \"https://example.com/a.jpg\" ); }
+            .headline { background: url(data:image/png;base64,AAAA); }";
+        let sanitized = sanitize_custom_css(css, false);
+        assert!(!sanitized.contains("example.com"));
+        assert!(sanitized.contains("background-image: none;"));
+        assert!(sanitized.contains("url(data:image/png;base64,AAAA)"));
+        assert_eq!(sanitize_custom_css(css, true), css);
+    }
+
+    #[test]
+    fn test_hidden_external_resources_are_removed_from_custom_css() {
+        let css = ".a { background-image: image-set(\"https://example.com/a.jpg\" 1x); }
+            .b { background-image: -webkit-image-set(url(//example.com/b.jpg) 1x); }
+            .c { background-image: u\\72l(https://example.com/c.jpg); }
+            .d { background-image: \\75 \\72\\6c(https://example.com/d.jpg); }
+            .e { background-image: image-set(\"local.jpg\" 1x); content: \"\\201C\"; }
+            .f { background-image: ur/**/l(https://example.com/f.jpg); }
+            @im/**/port \"https://example.com/t.css\";";
+        let sanitized = sanitize_custom_css(css, false);
+        assert!(!sanitized.contains("example.com"));
+        assert!(sanitized.contains(".a { background-image: none; }"));
+        assert!(sanitized.contains(".b { background-image: none; }"));
+        assert!(sanitized.contains(".c { background-image: none; }"));
+        assert!(sanitized.contains(".d { background-image: none; }"));
+        assert!(sanitized.contains(".f { background-image: none; }"));
+        assert!(!sanitized.contains("@import"));
+        assert!(!scope_custom_css(&sanitized, ".scope").contains("example.com"));
+        assert!(sanitized.contains("image-set(\"local.jpg\" 1x); content: \"\\201C\";"));
+    }
 
     #[test]
     fn test_css_handler() {
         let mut handler = CssHandler::new();
-        handler.background_color(RGB8::new(100, 100, 100));
+        handler.background_color(RGBA8::new(100, 100, 100, 255));
         handler.set_important(true);
         handler.color(RGBA8::new(255, 255, 255, 255));
         handler.set_important(false);
@@ -344,7 +803,7 @@ mod tests {
 
         assert_eq!(
             handler.to_string().as_str(),
-            "background-color:rgb(100, 100, 100);color:rgba(255, 255, 255, 255)!important;padding-left:20px;padding-right:20px;padding-top:20px;padding-bottom:20px;"
+            "background-color:rgb(100, 100, 100);color:rgba(255, 255, 255, 1)!important;padding-left:20px;padding-right:20px;padding-top:20px;padding-bottom:20px;"
         );
     }
 
@@ -353,4 +812,136 @@ mod tests {
         let handler = CssHandler::new();
         assert_eq!(handler.to_string().as_str(), "");
     }
+
+    #[test]
+    fn test_letter_spacing_and_text_transform_css() {
+        let mut handler = CssHandler::new();
+        handler.letter_spacing(CssSize::Em(0.2));
+        handler.text_transform(TextTransform::Uppercase);
+        assert_eq!(
+            handler.to_string().as_str(),
+            "letter-spacing:0.2em;text-transform:uppercase;"
+        );
+    }
+
+    #[test]
+    fn test_font_letter_spacing_and_text_transform_css() {
+        let mut font = FontRepresentation::default();
+        let css = CssHandler::from(font.clone()).to_string();
+        assert!(!css.contains("letter-spacing") && !css.contains("text-transform"));
+
+        font.letter_spacing = Some(CssSize::Px(4.0));
+        font.text_transform = Some(TextTransform::Capitalize);
+        let css = CssHandler::from(font).to_string();
+        assert!(css.contains("letter-spacing:4px;"));
+        assert!(css.contains("text-transform:capitalize;"));
+    }
+
+    #[test]
+    fn test_font_outline_css() {
+        let mut font = FontRepresentation::default();
+        assert!(!CssHandler::from(font.clone()).to_string().contains("-webkit-text-stroke"));
+
+        font.outline = Some(FontOutline {
+            width: CssSize::Px(3.0),
+            color: RGBA8::new(0, 0, 0, 255),
+        });
+        let css = CssHandler::from(font).to_string();
+        assert!(css.contains("-webkit-text-stroke:3px rgba(0, 0, 0, 1);"));
+        assert!(css.contains("paint-order:stroke fill;"));
+    }
+
+    #[test]
+    fn test_font_outline_is_optional_in_old_settings() {
+        let font = without_fields(&FontRepresentation::default(), &["outline"]);
+        assert!(font.outline.is_none());
+    }
+
+    #[test]
+    fn test_font_family_css() {
+        let mut font = FontRepresentation::default();
+        assert!(CssHandler::from(font.clone()).to_string().contains("font-family:sans-serif;"));
+
+        font.font_family = Some(CssFontFamily {
+            family: Some("Liberation Serif".to_string()),
+            genereric_family: GenericFontFamily::Serif,
+        });
+        let json = serde_json::to_string(&font).unwrap();
+        let font: FontRepresentation = serde_json::from_str(&json).unwrap();
+        assert!(
+            CssHandler::from(font)
+                .to_string()
+                .contains("font-family:'Liberation Serif', serif;")
+        );
+        assert_eq!(
+            CssFontFamily::with_family("Joe's Hand".to_string()).to_css_string(),
+            "'Joe\\'s Hand', sans-serif"
+        );
+    }
+
+    #[test]
+    fn test_font_shadow_css() {
+        let mut font = FontRepresentation::default();
+        assert!(!CssHandler::from(font.clone()).to_string().contains("text-shadow"));
+
+        font.shadow = true;
+        font.shadow_color = RGBA8::new(10, 20, 30, 255);
+        assert!(
+            CssHandler::from(font)
+                .to_string()
+                .contains("text-shadow:0.05em 0.05em 0.1em rgba(10, 20, 30, 1);")
+        );
+    }
+
+    #[test]
+    fn test_semi_transparent_colors_css() {
+        let mut handler = CssHandler::new();
+        handler.color(RGBA8::new(255, 255, 255, 128));
+        assert_eq!(handler.to_string(), "color:rgba(255, 255, 255, 0.502);");
+
+        let mut handler = CssHandler::new();
+        handler.color(RGBA8::new(0, 0, 0, 0));
+        assert_eq!(handler.to_string(), "color:rgba(0, 0, 0, 0);");
+    }
+
+    #[test]
+    fn test_transparent_background_color_css() {
+        let mut handler = CssHandler::new();
+        handler.background_color(RGBA8::new(0, 255, 0, 0));
+        assert_eq!(handler.to_string(), "background-color:rgba(0, 255, 0, 0);");
+    }
+
+    #[test]
+    fn test_line_clamp_css() {
+        let mut handler = CssHandler::new();
+        handler.line_clamp(2);
+        assert_eq!(
+            handler.to_string(),
+            "display:-webkit-box;-webkit-box-orient:vertical;-webkit-line-clamp:2;line-clamp:2;overflow:hidden;"
+        );
+
+        // At least one line is shown
+        let mut handler = CssHandler::new();
+        handler.line_clamp(0);
+        assert!(handler.to_string().contains("-webkit-line-clamp:1;"));
+    }
+
+    #[test]
+    fn test_flex_layout_css() {
+        let mut handler = CssHandler::new();
+        handler.flex_column(&VerticalAlign::Bottom);
+        assert_eq!(
+            handler.to_string(),
+            "display:flex;flex-direction:column;height:100%;justify-content:flex-end;"
+        );
+
+        let mut handler = CssHandler::new();
+        handler.order(2);
+        handler.flex_self_alignment(&VerticalAlign::Top);
+        assert_eq!(handler.to_string(), "order:2;margin-bottom:auto;");
+
+        let mut handler = CssHandler::new();
+        handler.flex_self_alignment(&VerticalAlign::Middle);
+        assert_eq!(handler.to_string(), "margin-top:auto;margin-bottom:auto;");
+    }
 }
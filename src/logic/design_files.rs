@@ -0,0 +1,317 @@
+//! This module provides the file format for sharing presentation designs.
+//!
+//! A design file contains a single [PresentationDesign] as JSON. Background pictures and videos
+//! are referenced by their path relative to the design file, so that a design can be passed on
+//! together with its pictures in one folder. Backgrounds outside of this folder are referenced
+//! by their file name and looked up in the repositories when the design is imported.
+
+use crate::logic::settings::{
+    BackgroundMedia, PresentationDesign, PresentationDesignSettings, Watermark,
+};
+use crate::logic::sourcefiles::{ImageSourceFile, SourceFile};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The file extension of design files (without the leading dot).
+pub const DESIGN_FILE_EXTENSION: &str = "cantara-design";
+
+/// The version of the design file format which is written by this version of Cantara.
+pub const DESIGN_FORMAT_VERSION: u32 = 1;
+
+/// The content of a design file.
+#[derive(Serialize, Deserialize)]
+struct DesignFile {
+    /// The version of the file format, see [DESIGN_FORMAT_VERSION].
+    version: u32,
+
+    /// The design with the paths of its backgrounds relative to the design file
+    design: PresentationDesign,
+}
+
+/// A design which has been read from a design file.
+pub struct ImportedDesign {
+    /// The design with the backgrounds which could be found on this machine
+    pub design: PresentationDesign,
+
+    /// The names of the backgrounds which could not be found and have been removed from the design
+    pub missing_backgrounds: Vec<String>,
+}
+
+impl PresentationDesign {
+    /// Serializes the design into the JSON representation used in design files. The paths of
+    /// the backgrounds are stored relative to `base_folder`, the folder of the design file.
+    pub fn to_design_json(&self, base_folder: &Path) -> Result<String, String> {
+        let mut design = self.clone();
+        map_backgrounds(&mut design, |mut source_file| {
+            source_file.path = match source_file.path.strip_prefix(base_folder) {
+                Ok(relative_path) => relative_path.to_path_buf(),
+                Err(_) => source_file
+                    .path
+                    .file_name()
+                    .map(PathBuf::from)
+                    .unwrap_or_default(),
+            };
+            Some(source_file)
+        });
+        serde_json::to_string_pretty(&DesignFile {
+            version: DESIGN_FORMAT_VERSION,
+            design,
+        })
+        .map_err(|e| format!("Could not serialize the design: {}", e))
+    }
+
+    /// Parses a design from the JSON representation used in design files. Backgrounds are
+    /// resolved against `base_folder` (the folder of the design file) and afterwards against the
+    /// given source files. Designs written by a newer version of Cantara are rejected.
+    pub fn from_design_json(
+        json: &str,
+        base_folder: &Path,
+        source_files: &[SourceFile],
+    ) -> Result<ImportedDesign, String> {
+        let design_file: DesignFile =
+            serde_json::from_str(json).map_err(|e| format!("Could not parse the design: {}", e))?;
+        if design_file.version > DESIGN_FORMAT_VERSION {
+            return Err(format!(
+                "The design has the unsupported format version {}",
+                design_file.version
+            ));
+        }
+
+        let mut design = design_file.design;
+        let mut missing_backgrounds = Vec::new();
+        map_backgrounds(&mut design, |source_file| {
+            let resolved = resolve_background(&source_file, base_folder, source_files);
+            if resolved.is_none() {
+                missing_backgrounds.push(source_file.name.clone());
+            }
+            resolved
+        });
+        Ok(ImportedDesign {
+            design,
+            missing_backgrounds,
+        })
+    }
+
+    /// Saves the design into the given design file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn export_to_file(&self, path: &Path) -> Result<(), String> {
+        let json = self.to_design_json(path.parent().unwrap_or(Path::new("")))?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Could not write design file {}: {}", path.display(), e))
+    }
+
+    /// Loads a design from the given design file, see [PresentationDesign::from_design_json].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn import_from_file(
+        path: &Path,
+        source_files: &[SourceFile],
+    ) -> Result<ImportedDesign, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read design file {}: {}", path.display(), e))?;
+        Self::from_design_json(&json, path.parent().unwrap_or(Path::new("")), source_files)
+    }
+}
+
+/// Applies `f` to the source files of all backgrounds and the watermark of a design template.
+/// Backgrounds for which `f` returns [None] are removed.
+fn map_backgrounds(
+    design: &mut PresentationDesign,
+    mut f: impl FnMut(SourceFile) -> Option<SourceFile>,
+) {
+    let PresentationDesignSettings::Template(ref mut template) =
+        design.presentation_design_settings
+    else {
+        return;
+    };
+    let mut map_media = |media: Option<BackgroundMedia>| {
+        media
+            .and_then(|media| f(media.into()))
+            .and_then(|source_file| BackgroundMedia::try_from(source_file).ok())
+    };
+    template.background_image = map_media(template.background_image.take());
+    if let Some(ref mut overrides) = template.title_slide_overrides {
+        overrides.background_image = map_media(overrides.background_image.take());
+    }
+    template.background_rotation_images = std::mem::take(&mut template.background_rotation_images)
+        .into_iter()
+        .filter_map(|image| f(image.into_inner()).and_then(ImageSourceFile::new))
+        .collect();
+    template.watermark = template.watermark.take().and_then(|watermark| {
+        let image = f(watermark.image.clone().into_inner()).and_then(ImageSourceFile::new)?;
+        Some(Watermark { image, ..watermark })
+    });
+}
+
+/// Finds a background of an imported design on this machine. A file next to the design file is
+/// preferred, otherwise a source file with the same MD5 hash or file name is used.
+fn resolve_background(
+    source_file: &SourceFile,
+    base_folder: &Path,
+    source_files: &[SourceFile],
+) -> Option<SourceFile> {
+    let path = base_folder.join(&source_file.path);
+    if path.is_file() {
+        return Some(SourceFile {
+            path,
+            ..source_file.clone()
+        });
+    }
+    let file_name = source_file.path.file_name();
+    source_files
+        .iter()
+        .filter(|candidate| candidate.file_type == source_file.file_type)
+        .find(
+            |candidate| match (&candidate.md5_hash, &source_file.md5_hash) {
+                (Some(candidate_hash), Some(hash)) => candidate_hash == hash,
+                _ => false,
+            },
+        )
+        .or_else(|| {
+            source_files.iter().find(|candidate| {
+                candidate.file_type == source_file.file_type
+                    && file_name.is_some()
+                    && candidate.path.file_name() == file_name
+            })
+        })
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::settings::{PaletteColor, PresentationDesignTemplate};
+    use crate::logic::sourcefiles::SourceFileType;
+    use rgb::RGBA8;
+
+    fn picture(path: &str) -> SourceFile {
+        SourceFile {
+            name: "Sunrise".to_string(),
+            path: PathBuf::from(path),
+            file_type: SourceFileType::Image,
+            md5_hash: None,
+        }
+    }
+
+    fn design_with_background(path: &str) -> PresentationDesign {
+        let mut template = PresentationDesignTemplate::default();
+        template.background_image = BackgroundMedia::try_from(picture(path)).ok();
+        PresentationDesign {
+            name: "Shared".to_string(),
+            presentation_design_settings: PresentationDesignSettings::Template(template),
+            ..Default::default()
+        }
+    }
+
+    fn background_path(design: &PresentationDesign) -> Option<PathBuf> {
+        match design.presentation_design_settings {
+            PresentationDesignSettings::Template(ref template) => template
+                .background_image
+                .as_ref()
+                .map(|media| media.as_source().path.clone()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn backgrounds_are_stored_relative_to_the_design_file() {
+        let design = design_with_background("/exports/pictures/sunrise.jpg");
+        let json = design.to_design_json(Path::new("/exports")).unwrap();
+        assert!(json.contains("\"version\": 1"));
+        assert!(json.contains("pictures/sunrise.jpg"));
+        assert!(!json.contains("/exports"));
+
+        // Backgrounds outside of the folder are only referenced by their file name
+        let json = design.to_design_json(Path::new("/elsewhere")).unwrap();
+        assert!(!json.contains("/exports"));
+        assert!(json.contains("sunrise.jpg"));
+    }
+
+    #[test]
+    fn missing_backgrounds_are_looked_up_or_reported() {
+        let json = design_with_background("/exports/pictures/sunrise.jpg")
+            .to_design_json(Path::new("/exports"))
+            .unwrap();
+        let base_folder = Path::new("/nonexistent-design-folder");
+
+        let repository_picture = picture("/songs/backgrounds/sunrise.jpg");
+        let imported =
+            PresentationDesign::from_design_json(&json, base_folder, &[repository_picture])
+                .unwrap();
+        assert_eq!(
+            background_path(&imported.design),
+            Some(PathBuf::from("/songs/backgrounds/sunrise.jpg"))
+        );
+        assert!(imported.missing_backgrounds.is_empty());
+
+        let imported = PresentationDesign::from_design_json(&json, base_folder, &[]).unwrap();
+        assert_eq!(background_path(&imported.design), None);
+        assert_eq!(imported.missing_backgrounds, vec!["Sunrise".to_string()]);
+        assert_eq!(imported.design.name, "Shared");
+    }
+
+    #[test]
+    fn watermarks_are_exported_and_imported_like_backgrounds() {
+        let mut design = design_with_background("/exports/pictures/sunrise.jpg");
+        if let PresentationDesignSettings::Template(ref mut template) =
+            design.presentation_design_settings
+        {
+            let logo = SourceFile {
+                name: "Logo".to_string(),
+                ..picture("/exports/pictures/logo.png")
+            };
+            template.watermark = Some(Watermark::new(ImageSourceFile::new(logo).unwrap()));
+        }
+        let watermark_path = |design: &PresentationDesign| match design.presentation_design_settings {
+            PresentationDesignSettings::Template(ref template) => template
+                .watermark
+                .as_ref()
+                .map(|watermark| watermark.image.as_source().path.clone()),
+            _ => None,
+        };
+
+        let json = design.to_design_json(Path::new("/exports")).unwrap();
+        assert!(json.contains("pictures/logo.png"));
+        assert!(!json.contains("/exports"));
+
+        let base_folder = Path::new("/nonexistent-design-folder");
+        let repository_logo = picture("/songs/backgrounds/logo.png");
+        let imported =
+            PresentationDesign::from_design_json(&json, base_folder, &[repository_logo]).unwrap();
+        assert_eq!(
+            watermark_path(&imported.design),
+            Some(PathBuf::from("/songs/backgrounds/logo.png"))
+        );
+
+        let imported = PresentationDesign::from_design_json(&json, base_folder, &[]).unwrap();
+        assert_eq!(watermark_path(&imported.design), None);
+        assert!(imported.missing_backgrounds.contains(&"Logo".to_string()));
+    }
+
+    #[test]
+    fn the_palette_is_exported_with_the_design() {
+        let mut design = design_with_background("sunrise.jpg");
+        design.palette = vec![PaletteColor {
+            name: "Brand blue".to_string(),
+            color: RGBA8::new(0, 82, 155, 200),
+        }];
+        let json = design.to_design_json(Path::new("")).unwrap();
+        let imported = PresentationDesign::from_design_json(&json, Path::new(""), &[]).unwrap();
+        assert_eq!(imported.design.palette, design.palette);
+
+        // Designs without a palette can still be imported
+        let json = json.replace("\"palette\"", "\"unknown\"");
+        let imported = PresentationDesign::from_design_json(&json, Path::new(""), &[]).unwrap();
+        assert!(imported.design.palette.is_empty());
+    }
+
+    #[test]
+    fn invalid_or_newer_design_files_are_rejected() {
+        assert!(PresentationDesign::from_design_json("{}", Path::new(""), &[]).is_err());
+
+        let json = design_with_background("sunrise.jpg")
+            .to_design_json(Path::new(""))
+            .unwrap()
+            .replace("\"version\": 1", "\"version\": 99");
+        assert!(PresentationDesign::from_design_json(&json, Path::new(""), &[]).is_err());
+    }
+}
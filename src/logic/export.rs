@@ -0,0 +1,851 @@
+//! This module exports the slides of the selected items into files which can be used without
+//! Cantara, e.g. a PowerPoint presentation for venues which run everything through PowerPoint
+//! or a static web page with the lyrics.
+//!
+//! An export contains the same slides as the presentation which is started from the selection,
+//! see [create_presentation_chapters](crate::logic::presentation::create_presentation_chapters).
+//! The designs are only mapped approximately: the texts and their order are complete, while
+//! fonts, colors and backgrounds get the closest equivalent of the target format.
+
+use crate::logic::conversions::ToHexString;
+use crate::logic::css::CssHandler;
+use crate::logic::presentation::{get_multi_language_texts, get_picture_path, main_content_text};
+use crate::logic::settings::{
+    BackgroundMedia, CssSize, FontRepresentation, HorizontalAlign, PresentationDesignSettings,
+    PresentationDesignTemplate, TextTransform, VerticalAlign,
+};
+use crate::logic::states::SlideChapter;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use cantara_songlib::slides::{Slide, SlideContent};
+use rgb::{RGB8, RGBA8};
+use std::collections::HashMap;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+/// The file extension of PowerPoint presentations (without the leading dot).
+pub const PPTX_FILE_EXTENSION: &str = "pptx";
+
+/// The file name of exported web pages, so that they can be put on a web server as they are.
+pub const HTML_FILE_NAME: &str = "index.html";
+
+/// The width of a 16:9 PowerPoint slide in EMU (English Metric Units, 914400 per inch)
+const SLIDE_WIDTH: i64 = 12_192_000;
+
+/// The height of a 16:9 PowerPoint slide in EMU
+const SLIDE_HEIGHT: i64 = 6_858_000;
+
+/// The font sizes of the designs refer to a presentation window which is 1920 pixels wide,
+/// while a 16:9 slide is 960 points wide.
+const POINTS_PER_PIXEL: f64 = 960.0 / 1920.0;
+
+/// The size of a CSS `em` in pixels, to which relative font sizes refer
+const EM_IN_PIXELS: f64 = 16.0;
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+const NAMESPACES: &str = r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main""#;
+
+const RELATIONSHIP_TYPES: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// The picture formats which can be embedded into PowerPoint files with their content types
+const PICTURE_CONTENT_TYPES: [(&str, &str); 5] = [
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("bmp", "image/bmp"),
+];
+
+/// Escapes a text for the content or an attribute of an XML or HTML element.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Reads a picture which is embedded into an export. On web, it is read from the in-memory VFS.
+fn read_picture(path: &Path) -> Option<Vec<u8>> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        crate::logic::settings::RepositoryType::web_read_file(path.to_str()?)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::fs::read(path).ok()
+    }
+}
+
+/// Returns the template of a design which is exported. Custom designs can't be mapped and are
+/// exported with the default template.
+fn exported_template(chapter: &SlideChapter) -> PresentationDesignTemplate {
+    match chapter
+        .presentation_design_option
+        .as_ref()
+        .map(|design| &design.presentation_design_settings)
+    {
+        Some(PresentationDesignSettings::Template(template)) => template.clone(),
+        _ => PresentationDesignTemplate::default(),
+    }
+}
+
+/// The pictures which are embedded into a PowerPoint file, each picture once.
+#[derive(Default)]
+struct PptxMedia {
+    /// The file names in the `ppt/media` folder with the data of the pictures
+    files: Vec<(String, Vec<u8>)>,
+
+    /// The file names of the pictures which have already been embedded by their path
+    names: HashMap<PathBuf, String>,
+}
+
+impl PptxMedia {
+    /// Embeds a picture and returns its file name in the `ppt/media` folder. Returns `None` if
+    /// the picture can't be read or has a format which PowerPoint doesn't support (e.g. PDF pages).
+    fn embed(&mut self, path: &Path) -> Option<String> {
+        if let Some(name) = self.names.get(path) {
+            return Some(name.clone());
+        }
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if !PICTURE_CONTENT_TYPES.iter().any(|(known, _)| *known == extension) {
+            log::debug!("The picture {} can't be embedded into PowerPoint", path.display());
+            return None;
+        }
+        let Some(data) = read_picture(path) else {
+            log::warn!("The picture {} could not be read for the export", path.display());
+            return None;
+        };
+        let name = format!("image{}.{}", self.files.len() + 1, extension);
+        self.files.push((name.clone(), data));
+        self.names.insert(path.to_path_buf(), name.clone());
+        Some(name)
+    }
+}
+
+/// The shapes and pictures of a single PowerPoint slide.
+#[derive(Default)]
+struct PptxSlide {
+    /// The background fill of the slide
+    background: String,
+
+    /// The shapes of the slide in their order from back to front
+    shapes: Vec<String>,
+
+    /// The pictures which are used on the slide, referenced as `rId2`, `rId3`, …
+    /// (`rId1` is the slide layout)
+    pictures: Vec<String>,
+}
+
+impl PptxSlide {
+    /// Adds a picture to the relationships of the slide and returns its relationship id.
+    fn picture_relationship(&mut self, media_name: String) -> String {
+        let id = match self.pictures.iter().position(|name| *name == media_name) {
+            Some(index) => index,
+            None => {
+                self.pictures.push(media_name);
+                self.pictures.len() - 1
+            }
+        };
+        format!("rId{}", id + 2)
+    }
+
+    /// Returns the id of the next shape. The group of the slide has the id 1.
+    fn next_shape_id(&self) -> usize {
+        self.shapes.len() + 2
+    }
+
+    /// Adds a text box with the given text, one paragraph per line.
+    fn add_text_box(
+        &mut self,
+        name: &str,
+        text: &str,
+        font: &FontRepresentation,
+        (x, y, width, height): (i64, i64, i64, i64),
+        anchor: &VerticalAlign,
+    ) {
+        let anchor = match anchor {
+            VerticalAlign::Top => "t",
+            VerticalAlign::Middle => "ctr",
+            VerticalAlign::Bottom => "b",
+        };
+        // A text body needs at least one paragraph
+        let paragraphs: String = match text.lines().next() {
+            Some(_) => text.lines().map(|line| paragraph_xml(line, font)).collect(),
+            None => paragraph_xml("", font),
+        };
+        self.shapes.push(format!(
+            r#"<p:sp><p:nvSpPr><p:cNvPr id="{}" name="{}"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="{}" y="{}"/><a:ext cx="{}" cy="{}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom><a:noFill/></p:spPr><p:txBody><a:bodyPr wrap="square" anchor="{}"><a:normAutofit/></a:bodyPr><a:lstStyle/>{}</p:txBody></p:sp>"#,
+            self.next_shape_id(),
+            xml_escape(name),
+            x,
+            y,
+            width,
+            height,
+            anchor,
+            paragraphs,
+        ));
+    }
+
+    /// Adds a picture which is stretched over the whole slide.
+    fn add_picture(&mut self, media_name: String) {
+        let id = self.next_shape_id();
+        let relationship = self.picture_relationship(media_name);
+        self.shapes.push(format!(
+            r#"<p:pic><p:nvPicPr><p:cNvPr id="{}" name="Picture"/><p:cNvPicPr><a:picLocks noChangeAspect="1"/></p:cNvPicPr><p:nvPr/></p:nvPicPr><p:blipFill><a:blip r:embed="{}"/><a:stretch><a:fillRect/></a:stretch></p:blipFill><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="{}" cy="{}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr></p:pic>"#,
+            id, relationship, SLIDE_WIDTH, SLIDE_HEIGHT,
+        ));
+    }
+
+    fn to_xml(&self) -> String {
+        format!(
+            r#"{}<p:sld {}><p:cSld><p:bg><p:bgPr>{}<a:effectLst/></p:bgPr></p:bg><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/>{}</p:spTree></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sld>"#,
+            XML_DECLARATION,
+            NAMESPACES,
+            self.background,
+            self.shapes.concat(),
+        )
+    }
+
+    fn relationships_xml(&self) -> String {
+        let pictures: String = self
+            .pictures
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                format!(
+                    r#"<Relationship Id="rId{}" Type="{}/image" Target="../media/{}"/>"#,
+                    index + 2,
+                    RELATIONSHIP_TYPES,
+                    name
+                )
+            })
+            .collect();
+        relationships_xml(&format!(
+            r#"<Relationship Id="rId1" Type="{}/slideLayout" Target="../slideLayouts/slideLayout1.xml"/>{}"#,
+            RELATIONSHIP_TYPES, pictures
+        ))
+    }
+}
+
+fn relationships_xml(relationships: &str) -> String {
+    format!(
+        r#"{}<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{}</Relationships>"#,
+        XML_DECLARATION, relationships
+    )
+}
+
+/// Converts the font size of a design into points on a PowerPoint slide, see [POINTS_PER_PIXEL].
+fn font_size_in_points(font_size: &CssSize) -> f64 {
+    let pixels = match font_size {
+        CssSize::Px(size) => *size as f64,
+        CssSize::Pt(size) => *size as f64 * 4.0 / 3.0,
+        CssSize::Em(size) => *size as f64 * EM_IN_PIXELS,
+        CssSize::Percentage(size) => *size as f64 / 100.0 * EM_IN_PIXELS,
+        CssSize::Null => return font_size_in_points(&FontRepresentation::default().font_size),
+    };
+    pixels * POINTS_PER_PIXEL
+}
+
+/// Returns a solid fill with the color, including its transparency.
+fn solid_fill_xml(color: RGBA8) -> String {
+    let hex = color.to_hex();
+    match color.a {
+        255 => format!(r#"<a:solidFill><a:srgbClr val="{}"/></a:solidFill>"#, &hex[1..]),
+        alpha => format!(
+            r#"<a:solidFill><a:srgbClr val="{}"><a:alpha val="{}"/></a:srgbClr></a:solidFill>"#,
+            &hex[1..],
+            alpha as u32 * 100_000 / 255
+        ),
+    }
+}
+
+/// Returns a paragraph with a single line of text in the given font.
+fn paragraph_xml(line: &str, font: &FontRepresentation) -> String {
+    let alignment = match font.horizontal_alignment {
+        HorizontalAlign::Left => "l",
+        HorizontalAlign::Centered => "ctr",
+        HorizontalAlign::Right => "r",
+        HorizontalAlign::Justify | HorizontalAlign::JustifyWithHyphenation => "just",
+    };
+    let size = (font_size_in_points(&font.font_size) * 100.0).round().clamp(100.0, 400_000.0) as u32;
+    let capitals = match font.text_transform {
+        Some(TextTransform::Uppercase) => r#" cap="all""#,
+        _ => "",
+    };
+    let typeface = font
+        .font_family
+        .as_ref()
+        .and_then(|font_family| font_family.family.as_ref())
+        .map(|family| format!(r#"<a:latin typeface="{}"/>"#, xml_escape(family)))
+        .unwrap_or_default();
+    let properties = format!(
+        r#"sz="{}"{}>{}{}"#,
+        size,
+        capitals,
+        solid_fill_xml(font.color),
+        typeface
+    );
+    match line.trim().is_empty() {
+        true => format!(
+            r#"<a:p><a:pPr algn="{}"/><a:endParaRPr {}</a:endParaRPr></a:p>"#,
+            alignment, properties
+        ),
+        false => format!(
+            r#"<a:p><a:pPr algn="{}"/><a:r><a:rPr {}</a:rPr><a:t>{}</a:t></a:r></a:p>"#,
+            alignment,
+            properties,
+            xml_escape(line.trim_end())
+        ),
+    }
+}
+
+/// Returns the background fill of the slides of a design: its background picture if it can be
+/// embedded, otherwise its background color. Gradients and videos are replaced by the color.
+fn background_xml(template: &PresentationDesignTemplate, slide: &mut PptxSlide, media: &mut PptxMedia) -> String {
+    let picture = match &template.background_image {
+        Some(BackgroundMedia::Image(image)) => media.embed(&image.as_source().path),
+        _ => None,
+    };
+    match picture {
+        Some(media_name) => format!(
+            r#"<a:blipFill dpi="0" rotWithShape="1"><a:blip r:embed="{}"/><a:srcRect/><a:stretch><a:fillRect/></a:stretch></a:blipFill>"#,
+            slide.picture_relationship(media_name)
+        ),
+        None => {
+            let RGB8 { r, g, b } = template.background_color;
+            solid_fill_xml(RGBA8::new(r, g, b, 255))
+        }
+    }
+}
+
+/// Converts a slide into a PowerPoint slide. Title slides get a title and a meta text box, song
+/// slides a main text box and a spoiler text box below it, picture slides their picture.
+fn pptx_slide(slide: &Slide, template: &PresentationDesignTemplate, media: &mut PptxMedia) -> PptxSlide {
+    let template = match slide.slide_content {
+        SlideContent::Title(_) => template.for_title_slide(),
+        _ => template.clone(),
+    };
+    let mut pptx_slide = PptxSlide::default();
+    pptx_slide.background = background_xml(&template, &mut pptx_slide, media);
+
+    let margin = SLIDE_WIDTH / 20;
+    let width = SLIDE_WIDTH - 2 * margin;
+    match &slide.slide_content {
+        SlideContent::Title(title_slide) => {
+            pptx_slide.add_text_box(
+                "Title",
+                &title_slide.title_text,
+                &template.get_default_headline_font(),
+                (margin, SLIDE_HEIGHT / 5, width, SLIDE_HEIGHT * 2 / 5),
+                &VerticalAlign::Bottom,
+            );
+            if !title_slide.meta_text.trim().is_empty() {
+                pptx_slide.add_text_box(
+                    "Meta",
+                    &title_slide.meta_text,
+                    &template.get_default_meta_font(),
+                    (margin, SLIDE_HEIGHT * 3 / 5, width, SLIDE_HEIGHT / 4),
+                    &VerticalAlign::Top,
+                );
+            }
+        }
+        SlideContent::SingleLanguageMainContent(_) | SlideContent::MultiLanguageMainContent(_) => {
+            let spoiler = spoiler_text(slide).filter(|spoiler| !spoiler.trim().is_empty());
+            let main_height = match spoiler {
+                Some(_) => SLIDE_HEIGHT * 7 / 10,
+                None => SLIDE_HEIGHT * 9 / 10,
+            };
+            pptx_slide.add_text_box(
+                "Main Content",
+                &main_content_text(slide).unwrap_or_default(),
+                &template.get_default_font(),
+                (margin, SLIDE_HEIGHT / 20, width, main_height),
+                &template.vertical_alignment,
+            );
+            if let Some(spoiler) = spoiler {
+                pptx_slide.add_text_box(
+                    "Spoiler",
+                    &spoiler,
+                    &template.get_default_spoiler_font(),
+                    (margin, SLIDE_HEIGHT / 20 + main_height, width, SLIDE_HEIGHT * 2 / 10),
+                    &VerticalAlign::Top,
+                );
+            }
+        }
+        SlideContent::SimplePicture(picture_slide) => {
+            let path = get_picture_path(picture_slide);
+            if let Some(media_name) = media.embed(Path::new(&path)) {
+                pptx_slide.add_picture(media_name);
+            }
+        }
+        _ => {}
+    }
+    pptx_slide
+}
+
+/// Returns the spoiler of a song slide. Multi-language slides return the spoiler of their
+/// primary language.
+fn spoiler_text(slide: &Slide) -> Option<String> {
+    match &slide.slide_content {
+        SlideContent::SingleLanguageMainContent(main_slide) => main_slide.spoiler_text(),
+        SlideContent::MultiLanguageMainContent(multi_language_slide) => {
+            get_multi_language_texts(multi_language_slide).spoiler_texts.into_iter().next()
+        }
+        _ => None,
+    }
+}
+
+fn content_types_xml(slide_count: usize) -> String {
+    let defaults: String = PICTURE_CONTENT_TYPES
+        .iter()
+        .map(|(extension, content_type)| {
+            format!(r#"<Default Extension="{}" ContentType="{}"/>"#, extension, content_type)
+        })
+        .collect();
+    let slides: String = (1..=slide_count)
+        .map(|number| {
+            format!(
+                r#"<Override PartName="/ppt/slides/slide{}.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slide+xml"/>"#,
+                number
+            )
+        })
+        .collect();
+    format!(
+        r#"{}<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/>{}<Override PartName="/ppt/presentation.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml"/><Override PartName="/ppt/slideMasters/slideMaster1.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slideMaster+xml"/><Override PartName="/ppt/slideLayouts/slideLayout1.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slideLayout+xml"/><Override PartName="/ppt/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/>{}</Types>"#,
+        XML_DECLARATION, defaults, slides
+    )
+}
+
+fn presentation_xml(slide_count: usize) -> String {
+    let slide_ids: String = (0..slide_count)
+        .map(|index| format!(r#"<p:sldId id="{}" r:id="rId{}"/>"#, 256 + index, index + 2))
+        .collect();
+    format!(
+        r#"{}<p:presentation {}><p:sldMasterIdLst><p:sldMasterId id="2147483648" r:id="rId1"/></p:sldMasterIdLst><p:sldIdLst>{}</p:sldIdLst><p:sldSz cx="{}" cy="{}"/><p:notesSz cx="6858000" cy="9144000"/></p:presentation>"#,
+        XML_DECLARATION, NAMESPACES, slide_ids, SLIDE_WIDTH, SLIDE_HEIGHT
+    )
+}
+
+fn presentation_relationships_xml(slide_count: usize) -> String {
+    let slides: String = (1..=slide_count)
+        .map(|number| {
+            format!(
+                r#"<Relationship Id="rId{}" Type="{}/slide" Target="slides/slide{}.xml"/>"#,
+                number + 1,
+                RELATIONSHIP_TYPES,
+                number
+            )
+        })
+        .collect();
+    relationships_xml(&format!(
+        r#"<Relationship Id="rId1" Type="{0}/slideMaster" Target="slideMasters/slideMaster1.xml"/>{1}<Relationship Id="rId{2}" Type="{0}/theme" Target="theme/theme1.xml"/>"#,
+        RELATIONSHIP_TYPES,
+        slides,
+        slide_count + 2
+    ))
+}
+
+const EMPTY_SHAPE_TREE: &str = r#"<p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/></p:spTree>"#;
+
+fn slide_master_xml() -> String {
+    format!(
+        r#"{}<p:sldMaster {}><p:cSld>{}</p:cSld><p:clrMap bg1="lt1" tx1="dk1" bg2="lt2" tx2="dk2" accent1="accent1" accent2="accent2" accent3="accent3" accent4="accent4" accent5="accent5" accent6="accent6" hlink="hlink" folHlink="folHlink"/><p:sldLayoutIdLst><p:sldLayoutId id="2147483649" r:id="rId1"/></p:sldLayoutIdLst></p:sldMaster>"#,
+        XML_DECLARATION, NAMESPACES, EMPTY_SHAPE_TREE
+    )
+}
+
+fn slide_layout_xml() -> String {
+    format!(
+        r#"{}<p:sldLayout {} type="blank" preserve="1"><p:cSld name="Blank">{}</p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sldLayout>"#,
+        XML_DECLARATION, NAMESPACES, EMPTY_SHAPE_TREE
+    )
+}
+
+/// A minimal theme, which PowerPoint requires for the slide master. The slides don't use it.
+fn theme_xml() -> String {
+    let line = |width: u32| {
+        format!(
+            r#"<a:ln w="{}"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln>"#,
+            width
+        )
+    };
+    let fill = r#"<a:solidFill><a:schemeClr val="phClr"/></a:solidFill>"#;
+    let effect = "<a:effectStyle><a:effectLst/></a:effectStyle>";
+    format!(
+        r#"{}<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="Cantara"><a:themeElements><a:clrScheme name="Cantara"><a:dk1><a:srgbClr val="000000"/></a:dk1><a:lt1><a:srgbClr val="FFFFFF"/></a:lt1><a:dk2><a:srgbClr val="44546A"/></a:dk2><a:lt2><a:srgbClr val="E7E6E6"/></a:lt2><a:accent1><a:srgbClr val="4472C4"/></a:accent1><a:accent2><a:srgbClr val="ED7D31"/></a:accent2><a:accent3><a:srgbClr val="A5A5A5"/></a:accent3><a:accent4><a:srgbClr val="FFC000"/></a:accent4><a:accent5><a:srgbClr val="5B9BD5"/></a:accent5><a:accent6><a:srgbClr val="70AD47"/></a:accent6><a:hlink><a:srgbClr val="0563C1"/></a:hlink><a:folHlink><a:srgbClr val="954F72"/></a:folHlink></a:clrScheme><a:fontScheme name="Cantara"><a:majorFont><a:latin typeface="Calibri"/><a:ea typeface=""/><a:cs typeface=""/></a:majorFont><a:minorFont><a:latin typeface="Calibri"/><a:ea typeface=""/><a:cs typeface=""/></a:minorFont></a:fontScheme><a:fmtScheme name="Cantara"><a:fillStyleLst>{1}{1}{1}</a:fillStyleLst><a:lnStyleLst>{2}{3}{4}</a:lnStyleLst><a:effectStyleLst>{5}{5}{5}</a:effectStyleLst><a:bgFillStyleLst>{1}{1}{1}</a:bgFillStyleLst></a:fmtScheme></a:themeElements></a:theme>"#,
+        XML_DECLARATION,
+        fill,
+        line(6350),
+        line(12700),
+        line(19050),
+        effect
+    )
+}
+
+/// Creates a PowerPoint presentation (`.pptx`) with one slide per slide of the chapters.
+///
+/// The slides are 16:9 and use the background, the fonts and the colors of the design of their
+/// chapter. Custom designs are exported with the default template, see [exported_template].
+pub fn pptx_from_chapters(chapters: &[SlideChapter]) -> Result<Vec<u8>, String> {
+    let mut media = PptxMedia::default();
+    let mut slides: Vec<PptxSlide> = vec![];
+    for chapter in chapters {
+        let template = exported_template(chapter);
+        for slide in &chapter.slides {
+            slides.push(pptx_slide(slide, &template, &mut media));
+        }
+    }
+
+    let mut files: Vec<(String, Vec<u8>)> = vec![
+        ("[Content_Types].xml".to_string(), content_types_xml(slides.len()).into_bytes()),
+        (
+            "_rels/.rels".to_string(),
+            relationships_xml(&format!(
+                r#"<Relationship Id="rId1" Type="{}/officeDocument" Target="ppt/presentation.xml"/>"#,
+                RELATIONSHIP_TYPES
+            ))
+            .into_bytes(),
+        ),
+        ("ppt/presentation.xml".to_string(), presentation_xml(slides.len()).into_bytes()),
+        (
+            "ppt/_rels/presentation.xml.rels".to_string(),
+            presentation_relationships_xml(slides.len()).into_bytes(),
+        ),
+        ("ppt/slideMasters/slideMaster1.xml".to_string(), slide_master_xml().into_bytes()),
+        (
+            "ppt/slideMasters/_rels/slideMaster1.xml.rels".to_string(),
+            relationships_xml(&format!(
+                r#"<Relationship Id="rId1" Type="{0}/slideLayout" Target="../slideLayouts/slideLayout1.xml"/><Relationship Id="rId2" Type="{0}/theme" Target="../theme/theme1.xml"/>"#,
+                RELATIONSHIP_TYPES
+            ))
+            .into_bytes(),
+        ),
+        ("ppt/slideLayouts/slideLayout1.xml".to_string(), slide_layout_xml().into_bytes()),
+        (
+            "ppt/slideLayouts/_rels/slideLayout1.xml.rels".to_string(),
+            relationships_xml(&format!(
+                r#"<Relationship Id="rId1" Type="{}/slideMaster" Target="../slideMasters/slideMaster1.xml"/>"#,
+                RELATIONSHIP_TYPES
+            ))
+            .into_bytes(),
+        ),
+        ("ppt/theme/theme1.xml".to_string(), theme_xml().into_bytes()),
+    ];
+    for (index, slide) in slides.iter().enumerate() {
+        files.push((format!("ppt/slides/slide{}.xml", index + 1), slide.to_xml().into_bytes()));
+        files.push((
+            format!("ppt/slides/_rels/slide{}.xml.rels", index + 1),
+            slide.relationships_xml().into_bytes(),
+        ));
+    }
+    for (name, data) in media.files {
+        files.push((format!("ppt/media/{}", name), data));
+    }
+
+    zip_files(files).map_err(|e| format!("Could not write the PowerPoint file: {}", e))
+}
+
+/// Packs the files with their paths into a zip archive.
+fn zip_files(files: Vec<(String, Vec<u8>)>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, data) in files {
+        writer.start_file(name, SimpleFileOptions::default())?;
+        writer.write_all(&data)?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+/// The stylesheet of exported web pages, which the stylesheets of the designs are added to.
+/// Every slide fills the window, only the current one is visible.
+const HTML_STYLESHEET: &str = "html, body { margin: 0; height: 100%; overflow: hidden; background: black; }
+.slide { position: absolute; inset: 0; box-sizing: border-box; background-size: cover; background-position: center; }
+.slide[hidden] { display: none; }
+.slide p { margin: 0; white-space: pre-line; }
+.slide img { width: 100%; height: 100%; object-fit: contain; }";
+
+/// The script of exported web pages, which goes through the slides with the arrow keys, the
+/// page keys of presenter remotes, the space bar or a click.
+const HTML_SCRIPT: &str = "const slides = document.querySelectorAll('.slide');
+let current = 0;
+function show(index) {
+    if (index < 0 || index >= slides.length) return;
+    slides[current].hidden = true;
+    current = index;
+    slides[current].hidden = false;
+}
+document.addEventListener('keydown', (event) => {
+    switch (event.key) {
+        case 'ArrowRight': case 'ArrowDown': case 'PageDown': case ' ': show(current + 1); break;
+        case 'ArrowLeft': case 'ArrowUp': case 'PageUp': show(current - 1); break;
+        case 'Home': show(0); break;
+        case 'End': show(slides.length - 1); break;
+        default: return;
+    }
+    event.preventDefault();
+});
+document.addEventListener('click', () => show(current + 1));";
+
+/// Returns a picture as a data URL, so that it can be embedded into a web page. Returns `None`
+/// if the picture can't be read or is no picture (e.g. a PDF page).
+fn picture_data_url(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    let (_, content_type) = PICTURE_CONTENT_TYPES
+        .iter()
+        .find(|(known, _)| *known == extension)?;
+    let data = read_picture(path)?;
+    Some(format!("data:{};base64,{}", content_type, BASE64.encode(data)))
+}
+
+/// Escapes `<` in CSS which is embedded into a `<style>` element, so that values like font
+/// names can't end the element. `\3C ` stands for `<` in CSS strings and identifiers.
+fn escape_embedded_css(css: &str) -> String {
+    css.replace('<', "\\3C ")
+}
+
+/// Returns the style rules of a design for the slides with the given class. The rules are
+/// built like the ones of the presentation, but without its animations and overlays.
+fn design_css(template: &PresentationDesignTemplate, class: &str) -> String {
+    let mut slide_css = CssHandler::new();
+    let RGB8 { r, g, b } = template.background_color;
+    slide_css.background_color(RGBA8::new(r, g, b, 255));
+    if let Some(gradient) = &template.background_gradient {
+        slide_css.background(gradient);
+    }
+    let picture = match &template.background_image {
+        Some(BackgroundMedia::Image(image)) => picture_data_url(&image.as_source().path),
+        _ => None,
+    };
+    if let Some(picture) = picture {
+        slide_css.background_image(&picture);
+    }
+    slide_css.padding_top(template.padding.top.clone());
+    slide_css.padding_bottom(template.padding.bottom.clone());
+    slide_css.padding_left(template.padding.left.clone());
+    slide_css.padding_right(template.padding.right.clone());
+    slide_css.flex_column(&template.vertical_alignment);
+
+    let font_rule = |element: &str, font: FontRepresentation| {
+        format!(".{} .{} {{ {} }}\n", class, element, CssHandler::from(font))
+    };
+    format!(
+        ".{} {{ {} }}\n{}{}{}{}",
+        class,
+        slide_css,
+        font_rule("main", template.get_default_font()),
+        font_rule("spoiler", template.get_default_spoiler_font()),
+        font_rule("title", template.get_default_headline_font()),
+        font_rule("meta", template.get_default_meta_font()),
+    )
+}
+
+/// Returns the content of a slide in an exported web page.
+fn html_slide_content(slide: &Slide) -> String {
+    let paragraph = |class: &str, text: &str| format!(r#"<p class="{}">{}</p>"#, class, xml_escape(text));
+    match &slide.slide_content {
+        SlideContent::Title(title_slide) => format!(
+            "{}{}",
+            paragraph("title", &title_slide.title_text),
+            paragraph("meta", &title_slide.meta_text)
+        ),
+        SlideContent::SingleLanguageMainContent(_) | SlideContent::MultiLanguageMainContent(_) => {
+            let spoiler = spoiler_text(slide)
+                .filter(|spoiler| !spoiler.trim().is_empty())
+                .map(|spoiler| paragraph("spoiler", &spoiler))
+                .unwrap_or_default();
+            format!(
+                "{}{}",
+                paragraph("main", &main_content_text(slide).unwrap_or_default()),
+                spoiler
+            )
+        }
+        SlideContent::SimplePicture(picture_slide) => {
+            match picture_data_url(Path::new(&get_picture_path(picture_slide))) {
+                Some(picture) => format!(r#"<img src="{}" alt="">"#, picture),
+                None => String::new(),
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+/// Creates a self-contained web page (see [HTML_FILE_NAME]) with the slides of the chapters.
+///
+/// The stylesheets of the designs and the pictures are embedded into the page, so that it can
+/// be hosted or opened without further files. The slides are shown one by one and can be
+/// changed with the arrow keys or a click. Custom designs are exported with the default
+/// template, see [exported_template].
+pub fn html_from_chapters(chapters: &[SlideChapter], title: &str) -> String {
+    // The designs of the slides, each design once. Title slides have their own design.
+    let mut templates: Vec<PresentationDesignTemplate> = vec![];
+    let mut sections: Vec<String> = vec![];
+    for chapter in chapters {
+        let chapter_template = exported_template(chapter);
+        for slide in &chapter.slides {
+            let template = match slide.slide_content {
+                SlideContent::Title(_) => chapter_template.for_title_slide(),
+                _ => chapter_template.clone(),
+            };
+            let design_index = match templates.iter().position(|known| *known == template) {
+                Some(index) => index,
+                None => {
+                    templates.push(template);
+                    templates.len() - 1
+                }
+            };
+            // Only the first slide is visible at the beginning
+            let hidden = match sections.is_empty() {
+                true => "",
+                false => " hidden",
+            };
+            sections.push(format!(
+                r#"<section class="slide design-{}"{}>{}</section>"#,
+                design_index,
+                hidden,
+                html_slide_content(slide)
+            ));
+        }
+    }
+
+    let designs_css: String = templates
+        .iter()
+        .enumerate()
+        .map(|(index, template)| escape_embedded_css(&design_css(template, &format!("design-{}", index))))
+        .collect();
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<style>\n{}\n{}</style>\n</head>\n<body>\n{}\n<script>\n{}\n</script>\n</body>\n</html>\n",
+        xml_escape(title),
+        HTML_STYLESHEET,
+        designs_css,
+        sections.join("\n"),
+        HTML_SCRIPT
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::css::CssFontFamily;
+    use crate::logic::settings::PresentationDesign;
+    use crate::logic::test_helpers::{chapter, empty_slide, stanza, title_slide};
+    use std::io::Read;
+    use zip::ZipArchive;
+
+    fn read_file(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> String {
+        let mut content = String::new();
+        archive.by_name(name).unwrap().read_to_string(&mut content).unwrap();
+        content
+    }
+
+    #[test]
+    fn every_slide_is_exported_in_its_order() {
+        let chapters = vec![
+            chapter(
+                "Amazing Grace",
+                vec![
+                    title_slide("Amazing Grace", "John Newton"),
+                    stanza("Amazing grace\nhow sweet the sound", "That saved a wretch"),
+                ],
+            ),
+            chapter("Amazing Grace", vec![empty_slide(), stanza("Grace & <peace>", "")]),
+        ];
+
+        let pptx = pptx_from_chapters(&chapters).unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(pptx)).unwrap();
+        let slide_files = archive
+            .file_names()
+            .filter(|name| name.starts_with("ppt/slides/slide"))
+            .count();
+        assert_eq!(slide_files, 4);
+        assert_eq!(read_file(&mut archive, "ppt/presentation.xml").matches("<p:sldId ").count(), 4);
+        assert_eq!(read_file(&mut archive, "[Content_Types].xml").matches("slide+xml").count(), 4);
+
+        let title = read_file(&mut archive, "ppt/slides/slide1.xml");
+        assert!(title.contains("<a:t>Amazing Grace</a:t>"));
+        assert!(title.contains("<a:t>John Newton</a:t>"));
+
+        let song = read_file(&mut archive, "ppt/slides/slide2.xml");
+        let first_line = song.find("<a:t>Amazing grace</a:t>").unwrap();
+        let second_line = song.find("<a:t>how sweet the sound</a:t>").unwrap();
+        let spoiler = song.find("<a:t>That saved a wretch</a:t>").unwrap();
+        assert!(first_line < second_line && second_line < spoiler);
+
+        assert!(!read_file(&mut archive, "ppt/slides/slide3.xml").contains("<a:t>"));
+        assert!(read_file(&mut archive, "ppt/slides/slide4.xml").contains("<a:t>Grace &amp; &lt;peace&gt;</a:t>"));
+    }
+
+    #[test]
+    fn fonts_and_backgrounds_are_mapped() {
+        let mut template = PresentationDesignTemplate::default();
+        template.background_color = RGB8::new(0x10, 0x20, 0x40);
+        let mut font = FontRepresentation::default();
+        font.font_size = CssSize::Px(64.0);
+        font.color = RGBA8::new(255, 255, 0, 255);
+        font.horizontal_alignment = HorizontalAlign::Left;
+        template.fonts = vec![font];
+
+        let pptx_slide = pptx_slide(&stanza("Verse", ""), &template, &mut PptxMedia::default());
+        let xml = pptx_slide.to_xml();
+        assert!(xml.contains(r#"<p:bgPr><a:solidFill><a:srgbClr val="102040"/></a:solidFill>"#));
+        assert!(xml.contains(r#"<a:pPr algn="l"/><a:r><a:rPr sz="3200"><a:solidFill><a:srgbClr val="FFFF00"/>"#));
+        assert_eq!(font_size_in_points(&CssSize::Pt(48.0)), 32.0);
+    }
+
+    #[test]
+    fn web_pages_contain_every_slide_with_its_design() {
+        let chapters = vec![
+            chapter(
+                "Amazing Grace",
+                vec![
+                    title_slide("Amazing Grace", "John Newton"),
+                    stanza("Amazing grace\nhow sweet the sound", "That saved a wretch"),
+                ],
+            ),
+            chapter("Amazing Grace", vec![empty_slide(), stanza("Grace & <peace>", "")]),
+        ];
+
+        let html = html_from_chapters(&chapters, "Sunday");
+        assert!(html.contains("<title>Sunday</title>"));
+        assert_eq!(html.matches(r#"<section class="slide"#).count(), 4);
+        assert_eq!(html.matches("</section>").count(), 4);
+        // Only the first slide is visible at the beginning
+        assert_eq!(html.matches(" hidden>").count(), 3);
+
+        let texts = [
+            r#"<p class="title">Amazing Grace</p>"#,
+            r#"<p class="meta">John Newton</p>"#,
+            "<p class=\"main\">Amazing grace\nhow sweet the sound</p>",
+            r#"<p class="spoiler">That saved a wretch</p>"#,
+            r#"<p class="main">Grace &amp; &lt;peace&gt;</p>"#,
+        ];
+        let positions: Vec<usize> = texts.iter().map(|text| html.find(text).unwrap()).collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // The design is added once, as it has no deviations for title slides
+        assert!(html.contains(r#"<section class="slide design-0">"#));
+        assert!(html.contains(".design-0 .title {"));
+        assert!(html.contains(".design-0 .main {"));
+        assert!(!html.contains("design-1"));
+    }
+
+    #[test]
+    fn fonts_can_not_end_the_stylesheet_of_web_pages() {
+        let mut template = PresentationDesignTemplate::default();
+        let mut font = FontRepresentation::default();
+        font.font_family = Some(CssFontFamily::with_family("</style><script>alert(1)</script>".to_string()));
+        template.fonts = vec![font];
+        let mut hostile_chapter = chapter("Amazing Grace", vec![stanza("Verse", "")]);
+        hostile_chapter.presentation_design_option = Some(PresentationDesign {
+            presentation_design_settings: PresentationDesignSettings::Template(template),
+            ..PresentationDesign::default()
+        });
+
+        let html = html_from_chapters(&[hostile_chapter], "Sunday");
+        assert!(html.contains("\\3C /style>\\3C script>"));
+        assert_eq!(html.matches("</style>").count(), 1);
+        assert_eq!(html.matches("<script>").count(), 1);
+    }
+}
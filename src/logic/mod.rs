@@ -19,6 +19,17 @@
 //! - [`conversions`]: Provides utilities for data conversion and transformation
 //! - [`css`]: Handles CSS generation and styling
 //! - [`search`]: Implements search functionality for finding songs and other content
+//! - [`schedule`]: Saves and loads the selected items as schedule files
+//! - [`design_files`]: Exports and imports presentation designs as design files
+//! - [`recently_used`]: Remembers the songs which have recently been presented
+//! - [`reporting`]: Records the songs which have been projected in a session log
+//! - [`shortcuts`]: Lists the keyboard shortcuts of the presentation window
+//! - [`system_fonts`]: Lists the font families which are installed on the system
+//! - [`thumbnails`]: Caches the thumbnails of presentation designs
+//! - [`verse_order`]: Brings the parts of songs into a verse order
+//! - [`slide_directives`]: Reads the slide settings which song files override for themselves
+//! - [`export`]: Exports the slides of the selected items, e.g. into PowerPoint presentations
+//! - [`remote_control`]: Controls the running presentation over a local HTTP server (desktop only)
 //!
 //! ## Separation of Concerns
 //!
@@ -65,9 +76,26 @@ pub mod presentation;
 pub mod conversions;
 pub mod css;
 pub mod search;
+pub mod schedule;
+pub mod design_files;
+pub mod recently_used;
+pub mod reporting;
+pub mod shortcuts;
+pub mod system_fonts;
+pub mod thumbnails;
+pub mod verse_order;
+pub mod slide_directives;
+pub mod export;
+
+// Slides and chapters which are shared by the tests
+#[cfg(test)]
+pub mod test_helpers;
 
 #[cfg(target_arch = "wasm32")]
 pub mod sync;
 
 #[cfg(feature = "desktop")]
 pub mod screens;
+
+#[cfg(feature = "desktop")]
+pub mod remote_control;
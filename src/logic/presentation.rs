@@ -1,16 +1,30 @@
 //! This module contains functions for creating presentations
 
 use super::{
-    settings::PresentationDesign,
+    recently_used, search,
+    settings::{
+        EndSlide, PresentationDesign, PresentationDesignSettings, SlideSettingsProfile, SpoilerMode,
+    },
+    slide_directives,
     sourcefiles::{SourceFile, SourceFileType},
-    states::{RunningPresentation, SelectedItemRepresentation, SlideChapter},
+    states::{RunningPresentation, SelectedItemRepresentation, SlideChapter, next_presentation_id},
+    verse_order,
 };
 
 use cantara_songlib::importer::classic_song::slides_from_classic_song;
-use cantara_songlib::slides::{Slide, SlideContent, SimplePictureSlide, SingleLanguageMainContentSlide, SlideSettings};
+use cantara_songlib::slides::{
+    EmptySlide, MultiLanguageMainContentSlide, Slide, SlideContent, SimplePictureSlide,
+    SingleLanguageMainContentSlide, SlideSettings,
+};
 use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
 use std::{error::Error, path::{Path, PathBuf}};
 
+/// The song number of the "Amazing Grace" example, see [create_amazing_grace_presentation].
+pub const EXAMPLE_SONG_NUMBER: &str = "CCLI 22025";
+
 /// Prefix marker used to identify slides containing rendered Markdown HTML
 /// in the `main_text` field of a `SingleLanguageMainContentSlide`.
 pub const MARKDOWN_HTML_PREFIX: &str = "<!--md-->";
@@ -39,6 +53,46 @@ pub fn get_picture_path(picture_slide: &SimplePictureSlide) -> String {
     }
 }
 
+/// The texts of a multi-language slide with one entry per language. The first language is the
+/// primary language of the song.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MultiLanguageTexts {
+    pub main_texts: Vec<String>,
+    pub spoiler_texts: Vec<String>,
+}
+
+/// Extracts the texts of a [MultiLanguageMainContentSlide] using serde,
+/// since its fields are private in the external crate.
+pub fn get_multi_language_texts(slide: &MultiLanguageMainContentSlide) -> MultiLanguageTexts {
+    let value = match serde_json::to_value(slide) {
+        Ok(value) => value,
+        Err(err) => {
+            log::warn!(
+                "get_multi_language_texts: failed to serialize MultiLanguageMainContentSlide: {}",
+                err
+            );
+            return MultiLanguageTexts::default();
+        }
+    };
+    let texts = |field: &str| -> Vec<String> {
+        value
+            .get(field)
+            .and_then(|texts| texts.as_array())
+            .map(|texts| {
+                texts
+                    .iter()
+                    .filter_map(|text| text.as_str())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    MultiLanguageTexts {
+        main_texts: texts("main_text_list"),
+        spoiler_texts: texts("spoiler_text_list"),
+    }
+}
+
 /// Returns the number of pages in a PDF file using lopdf (desktop only).
 #[cfg(not(target_arch = "wasm32"))]
 fn get_pdf_page_count(path: &Path) -> Result<usize, Box<dyn Error>> {
@@ -113,6 +167,281 @@ pub fn slides_from_markdown(markdown_content: &str) -> Vec<Slide> {
     slides
 }
 
+/// Creates a single main content slide which shows the given plain text, e.g. an announcement.
+pub fn slide_from_text(text: &str) -> Result<Slide, Box<dyn Error>> {
+    // Construct SingleLanguageMainContentSlide via serde since the fields are private
+    let slide_content: SingleLanguageMainContentSlide =
+        serde_json::from_value(serde_json::json!({"main_text": text.trim()}))?;
+    Ok(Slide {
+        slide_content: SlideContent::SingleLanguageMainContent(slide_content),
+        linked_file: None,
+    })
+}
+
+/// Wraps the lines of a text at word boundaries, so that no line is longer than the given number
+/// of characters. Existing line breaks are kept, and lines which fit are left as they are.
+/// Words which are longer than a line are not split.
+pub fn wrap_lines(text: &str, max_chars_per_line: usize) -> String {
+    text.split('\n')
+        .map(|line| wrap_line(line, max_chars_per_line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Wraps a single line, see [wrap_lines].
+fn wrap_line(line: &str, max_chars_per_line: usize) -> String {
+    if max_chars_per_line == 0 || line.chars().count() <= max_chars_per_line {
+        return line.to_string();
+    }
+    let mut wrapped_lines: Vec<String> = vec![];
+    let mut current_line = String::new();
+    let mut current_length = 0;
+    for word in line.split_whitespace() {
+        let word_length = word.chars().count();
+        if current_length > 0 && current_length + 1 + word_length > max_chars_per_line {
+            wrapped_lines.push(std::mem::take(&mut current_line));
+            current_length = 0;
+        }
+        if current_length > 0 {
+            current_line.push(' ');
+            current_length += 1;
+        }
+        current_line.push_str(word);
+        current_length += word_length;
+    }
+    if !current_line.is_empty() {
+        wrapped_lines.push(current_line);
+    }
+    wrapped_lines.join("\n")
+}
+
+/// Wraps the given text fields of a slide content, see [wrap_lines]. The fields are changed via
+/// serde, since they are private in the external crate. Fields can be strings or lists of strings.
+fn wrap_text_fields<T: serde::Serialize + serde::de::DeserializeOwned>(
+    content: &T,
+    fields: &[&str],
+    max_chars_per_line: usize,
+) -> Option<T> {
+    let mut value = serde_json::to_value(content).ok()?;
+    for field in fields {
+        match value.get_mut(*field) {
+            Some(serde_json::Value::String(text)) => *text = wrap_lines(text, max_chars_per_line),
+            Some(serde_json::Value::Array(texts)) => {
+                for text in texts.iter_mut() {
+                    if let serde_json::Value::String(text) = text {
+                        *text = wrap_lines(text, max_chars_per_line);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    serde_json::from_value(value).ok()
+}
+
+/// Wraps the lines of the main content and the spoiler of a slide, see [wrap_lines].
+/// Title, picture, empty and markdown slides are returned unchanged.
+pub fn wrap_slide_lines(slide: Slide, max_chars_per_line: usize) -> Slide {
+    let slide_content = match &slide.slide_content {
+        SlideContent::SingleLanguageMainContent(main_slide)
+            if get_markdown_html(&main_slide.clone().main_text()).is_none() =>
+        {
+            wrap_text_fields(main_slide, &["main_text", "spoiler_text"], max_chars_per_line)
+                .map(SlideContent::SingleLanguageMainContent)
+        }
+        SlideContent::MultiLanguageMainContent(multi_language_slide) => wrap_text_fields(
+            multi_language_slide,
+            &["main_text_list", "spoiler_text_list"],
+            max_chars_per_line,
+        )
+        .map(SlideContent::MultiLanguageMainContent),
+        _ => None,
+    };
+    match slide_content {
+        Some(slide_content) => Slide {
+            slide_content,
+            ..slide
+        },
+        None => slide,
+    }
+}
+
+/// Applies the slide settings which Cantara (and not cantara_songlib) takes care of to the slides
+/// of a song: the chorus spoilers, the combined stanzas and the wrapped lines.
+///
+/// # Arguments
+/// * `slides` - The slides which cantara_songlib has created
+/// * `content` - The content of the song file, in which the choruses are found
+/// * `slide_settings` - The slide settings of the song
+fn apply_cantara_slide_settings(
+    mut slides: Vec<Slide>,
+    content: Option<&str>,
+    slide_settings: &SlideSettingsProfile,
+) -> Vec<Slide> {
+    let chorus_texts = content.map(verse_order::chorus_texts).unwrap_or_default();
+    let is_chorus = |text: &str| is_chorus_text(&chorus_texts, text);
+
+    if slide_settings.spoiler_mode == SpoilerMode::Chorus {
+        if let Some(chorus_text) = chorus_texts.first() {
+            slides = show_chorus_spoilers(slides, chorus_text, is_chorus);
+        }
+    }
+    if let Some(max_lines) = slide_settings.combine_stanzas_up_to.filter(|max_lines| *max_lines > 0) {
+        slides = combine_stanzas(slides, max_lines as usize, is_chorus);
+    }
+    match slide_settings.max_chars_per_line.filter(|max_chars| *max_chars > 0) {
+        Some(max_chars) => slides
+            .into_iter()
+            .map(|slide| wrap_slide_lines(slide, max_chars as usize))
+            .collect(),
+        None => slides,
+    }
+}
+
+/// Replaces the spoilers of the slides with the chorus, see [SpoilerMode::Chorus]. The slides
+/// of the chorus and slides without a spoiler (e.g. the last slide) keep their spoiler.
+pub fn show_chorus_spoilers(
+    slides: Vec<Slide>,
+    chorus_text: &str,
+    is_chorus: impl Fn(&str) -> bool,
+) -> Vec<Slide> {
+    slides
+        .into_iter()
+        .map(|slide| {
+            let SlideContent::SingleLanguageMainContent(main_slide) = &slide.slide_content else {
+                return slide;
+            };
+            let Ok(mut value) = serde_json::to_value(main_slide) else {
+                return slide;
+            };
+            let has_spoiler = value
+                .get("spoiler_text")
+                .and_then(|spoiler| spoiler.as_str())
+                .is_some_and(|spoiler| !spoiler.trim().is_empty());
+            if !has_spoiler || is_chorus(&main_slide.clone().main_text()) {
+                return slide;
+            }
+            value["spoiler_text"] = serde_json::Value::String(chorus_text.to_string());
+            match serde_json::from_value(value) {
+                Ok(main_slide) => Slide {
+                    slide_content: SlideContent::SingleLanguageMainContent(main_slide),
+                    ..slide
+                },
+                Err(_) => slide,
+            }
+        })
+        .collect()
+}
+
+/// Returns true if all lines of a text are lines of one of the choruses of a song, so that the
+/// text is (a part of) a chorus.
+fn is_chorus_text(chorus_texts: &[String], text: &str) -> bool {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty()).peekable();
+    lines.peek().is_some()
+        && lines.all(|line| {
+            chorus_texts
+                .iter()
+                .any(|chorus| chorus.lines().any(|chorus_line| chorus_line.trim() == line))
+        })
+}
+
+/// Returns the main text of a slide if it can be combined with other slides, i.e. it is a
+/// single-language slide without markdown.
+fn combinable_main_text(slide: &Slide) -> Option<String> {
+    match &slide.slide_content {
+        SlideContent::SingleLanguageMainContent(main_slide) => {
+            Some(main_slide.clone().main_text()).filter(|text| get_markdown_html(text).is_none())
+        }
+        _ => None,
+    }
+}
+
+/// Combines two single-language slides into one. The combined slide gets the spoiler of the
+/// second slide, which shows the beginning of the slide after both.
+fn combine_slides(first: &Slide, second: &Slide) -> Option<Slide> {
+    let (
+        SlideContent::SingleLanguageMainContent(first_content),
+        SlideContent::SingleLanguageMainContent(second_content),
+    ) = (&first.slide_content, &second.slide_content)
+    else {
+        return None;
+    };
+    let mut value = serde_json::to_value(first_content).ok()?;
+    let second_value = serde_json::to_value(second_content).ok()?;
+    value["main_text"] = serde_json::Value::String(format!(
+        "{}\n{}",
+        first_content.clone().main_text(),
+        second_content.clone().main_text()
+    ));
+    value["spoiler_text"] = second_value.get("spoiler_text").cloned().unwrap_or_default();
+    Some(Slide {
+        slide_content: SlideContent::SingleLanguageMainContent(serde_json::from_value(value).ok()?),
+        linked_file: first.linked_file.clone(),
+    })
+}
+
+/// Combines consecutive slides of short stanzas on a single slide, as long as the combined slide
+/// has not more than `max_lines` lines. A chorus is never combined with a verse, the given
+/// closure tells whether the text of a slide is a chorus. Title, empty, multi-language and
+/// markdown slides are kept as they are.
+pub fn combine_stanzas(
+    slides: Vec<Slide>,
+    max_lines: usize,
+    is_chorus: impl Fn(&str) -> bool,
+) -> Vec<Slide> {
+    let mut combined_slides: Vec<Slide> = vec![];
+    for slide in slides {
+        let combined = combined_slides.last().and_then(|previous| {
+            let previous_text = combinable_main_text(previous)?;
+            let text = combinable_main_text(&slide)?;
+            let fits = previous_text.lines().count() + text.lines().count() <= max_lines;
+            (fits && is_chorus(&previous_text) == is_chorus(&text))
+                .then(|| combine_slides(previous, &slide))
+                .flatten()
+        });
+        match (combined, combined_slides.last_mut()) {
+            (Some(combined), Some(previous)) => *previous = combined,
+            _ => combined_slides.push(slide),
+        }
+    }
+    combined_slides
+}
+
+/// Returns true if the slide is a title slide.
+fn is_title_slide(slide: &Slide) -> bool {
+    matches!(slide.slide_content, SlideContent::Title(_))
+}
+
+/// Returns the slide which is shown at the end of a song, see [EndSlide]. The title slide is
+/// repeated if the song has one.
+pub fn end_slide(end_slide: EndSlide, title_slide: Option<&Slide>) -> Option<Slide> {
+    match end_slide {
+        EndSlide::None => None,
+        EndSlide::RepeatTitle => title_slide.cloned(),
+        EndSlide::Empty => Some(blank_slide()),
+    }
+}
+
+/// Returns an empty slide, which only shows the background of the design.
+fn blank_slide() -> Slide {
+    Slide {
+        slide_content: SlideContent::Empty(EmptySlide {
+            black_background: false,
+        }),
+        linked_file: None,
+    }
+}
+
+/// Appends a blank separator slide to every chapter but the last one, so that there is a blank
+/// moment between the songs, see [crate::logic::settings::Settings::blank_slide_between_chapters].
+/// The separator is added independently of the end slide of the slide settings.
+pub fn insert_separator_slides(presentation: &mut [SlideChapter]) {
+    let chapter_count = presentation.len();
+    for chapter in presentation.iter_mut().take(chapter_count.saturating_sub(1)) {
+        chapter.add_separator_slide();
+    }
+}
+
 /// Checks whether a slide's main text contains rendered Markdown HTML.
 /// Returns the HTML content (without the prefix) if it does.
 pub fn get_markdown_html(main_text: &str) -> Option<&str> {
@@ -172,42 +501,151 @@ pub fn html_to_plain_text(html: &str) -> String {
         .to_string()
 }
 
+/// Returns the main content of a slide as plain text. Title slides return their title,
+/// multi-language slides the texts of all languages, empty and picture slides `None`.
+pub fn main_content_text(slide: &Slide) -> Option<String> {
+    match &slide.slide_content {
+        SlideContent::Title(title_slide) => Some(title_slide.title_text.clone()),
+        SlideContent::SingleLanguageMainContent(main_slide) => {
+            let main_text = main_slide.clone().main_text();
+            match get_markdown_html(&main_text) {
+                Some(html) => Some(html_to_plain_text(html)),
+                None => Some(main_text),
+            }
+        }
+        SlideContent::MultiLanguageMainContent(multi_language_slide) => {
+            Some(get_multi_language_texts(multi_language_slide).main_texts.join("\n"))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the first non-empty line of the main content of a slide, e.g. for a short preview
+/// of the upcoming slide. See [main_content_text].
+pub fn first_main_content_line(slide: &Slide) -> Option<String> {
+    main_content_text(slide)?
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Reads the content of a song file. On web, the song is read from the in-memory VFS.
+pub fn read_song_content(source_file: &SourceFile) -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let path_str = source_file.path.to_str()?;
+        crate::logic::settings::RepositoryType::web_read_file(path_str)
+            .map(|content_bytes| String::from_utf8_lossy(&content_bytes).into_owned())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::fs::read_to_string(&source_file.path).ok()
+    }
+}
+
+/// Creates the slides of a song with cantara_songlib. If a verse order applies to the song,
+/// its parts are brought into this order before. The slide directives of the song file are
+/// removed, they have to be applied to the slide settings before, see [slide_directives].
+fn song_slides(
+    selected_item: &SelectedItemRepresentation,
+    slide_settings: &SlideSettings,
+) -> Result<Vec<Slide>, Box<dyn Error>> {
+    let content = read_song_content(&selected_item.source_file);
+    #[cfg(not(target_arch = "wasm32"))]
+    let has_directives = content
+        .as_deref()
+        .is_some_and(|content| !slide_directives::slide_directives(content).is_empty());
+    let content = content.map(|content| slide_directives::without_slide_directives(&content));
+    let ordered_song = content.as_deref().and_then(|content| {
+        verse_order::effective_order(content, selected_item.verse_order.as_deref())
+            .map(|order| verse_order::apply_verse_order(content, &order))
+    });
+    if let Some(ordered_song) = ordered_song {
+        if !ordered_song.unknown_parts.is_empty() {
+            log::warn!(
+                "The verse order of {} names unknown parts: {}",
+                selected_item.source_file.name,
+                ordered_song.unknown_parts.join(", ")
+            );
+        }
+        return Ok(slides_from_classic_song(
+            &ordered_song.content,
+            slide_settings,
+            selected_item.source_file.name.clone(),
+        ));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        Ok(content
+            .map(|content| {
+                slides_from_classic_song(
+                    &content,
+                    slide_settings,
+                    selected_item.source_file.name.clone(),
+                )
+            })
+            .unwrap_or_default())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // Songs with slide directives are created from their content without the directives
+        match content.filter(|_| has_directives) {
+            Some(content) => Ok(slides_from_classic_song(
+                &content,
+                slide_settings,
+                selected_item.source_file.name.clone(),
+            )),
+            None => cantara_songlib::create_presentation_from_file(
+                selected_item.source_file.path.clone(),
+                slide_settings.clone(),
+            ),
+        }
+    }
+}
+
 /// Creates a presentation from a selected_item_representation and a presentation_design
 fn create_presentation_slides(
     selected_item: &SelectedItemRepresentation,
-    default_song_slide_settings: &SlideSettings,
+    default_song_slide_settings: &SlideSettingsProfile,
 ) -> Result<Vec<Slide>, Box<dyn Error>> {
     let mut presentation: Vec<Slide> = vec![];
 
-    let slide_settings = selected_item
-        .slide_settings_option
-        .clone()
-        .unwrap_or(default_song_slide_settings.clone());
-
     if selected_item.source_file.file_type == SourceFileType::Song {
-        #[cfg(target_arch = "wasm32")]
-        {
-            // On web, read song content from the in-memory VFS
-            let path_str = selected_item.source_file.path.to_str().unwrap_or("");
-            if let Some(content_bytes) = crate::logic::settings::RepositoryType::web_read_file(path_str) {
-                let content = String::from_utf8_lossy(&content_bytes);
-                let slides = slides_from_classic_song(
-                    &content,
-                    &slide_settings,
-                    selected_item.source_file.name.clone(),
-                );
-                presentation.extend(slides);
-            }
-            return Ok(presentation);
-        }
+        // The slide directives of the song file take precedence over the slide settings
+        let content = read_song_content(&selected_item.source_file);
+        let slide_settings = slide_directives::effective_slide_settings(
+            default_song_slide_settings,
+            selected_item.slide_settings_option.as_ref(),
+            content.as_deref(),
+        );
+        let slides = song_slides(selected_item, &slide_settings.slide_settings)?;
+        presentation.extend(apply_cantara_slide_settings(
+            slides,
+            content.as_deref(),
+            &slide_settings,
+        ));
 
-        #[cfg(not(target_arch = "wasm32"))]
-        match cantara_songlib::create_presentation_from_file(
-            selected_item.source_file.path.clone(),
-            slide_settings,
-        ) {
-            Ok(slides) => presentation.extend(slides),
-            Err(err) => return Err(err),
+        if slide_settings.end_slide != EndSlide::None {
+            // A song which is presented without a title slide gets one for the end
+            let title_slide = match presentation.first().filter(|slide| is_title_slide(slide)) {
+                Some(title_slide) => Some(title_slide.clone()),
+                None if slide_settings.end_slide == EndSlide::RepeatTitle => {
+                    let slide_settings_with_title = SlideSettings {
+                        title_slide: true,
+                        ..slide_settings.slide_settings.clone()
+                    };
+                    song_slides(selected_item, &slide_settings_with_title)?
+                        .into_iter()
+                        .next()
+                        .filter(is_title_slide)
+                }
+                None => None,
+            };
+            presentation.extend(end_slide(slide_settings.end_slide, title_slide.as_ref()));
         }
     }
 
@@ -270,6 +708,22 @@ fn create_presentation_slides(
         }
     }
 
+    if selected_item.source_file.file_type == SourceFileType::Blank {
+        // The background of the blank slide is taken from the presentation design of the item
+        presentation.push(Slide {
+            slide_content: SlideContent::Empty(EmptySlide { black_background: false }),
+            linked_file: None,
+        });
+        return Ok(presentation);
+    }
+
+    if selected_item.source_file.file_type == SourceFileType::Text {
+        if let Some(ref text) = selected_item.inline_text {
+            presentation.push(slide_from_text(text)?);
+        }
+        return Ok(presentation);
+    }
+
     if selected_item.source_file.file_type == SourceFileType::Markdown {
         // Check for inline markdown content first (spontaneous text)
         if let Some(ref inline_content) = selected_item.inline_markdown {
@@ -299,53 +753,288 @@ fn create_presentation_slides(
     Ok(presentation)
 }
 
-/// Adds a presentation to the global running presentations signal
-/// Returns the number (id) of the created presentation
-pub fn add_presentation(
-    selected_items: &Vec<SelectedItemRepresentation>,
-    running_presentations: &mut Signal<Vec<RunningPresentation>>,
+/// Creates the chapter of a presentation for a selected item.
+/// The defaults are used if the item has no presentation design or slide settings of its own.
+pub fn create_slide_chapter(
+    selected_item: &SelectedItemRepresentation,
     default_presentation_design: &PresentationDesign,
-    default_slide_settings: &SlideSettings,
-) -> Option<usize> {
-    // Right now, we only allow one running presentation at the same time.
-    // Later, Cantara is going to support multiple presentations.
-    if running_presentations.len() > 0 {
-        running_presentations.write().clear();
+    default_slide_settings: &SlideSettingsProfile,
+) -> Result<SlideChapter, Box<dyn Error>> {
+    let used_presentation_design = selected_item
+        .presentation_design_option
+        .clone()
+        .unwrap_or(default_presentation_design.clone());
+
+    let used_slide_settings = selected_item
+        .slide_settings_option
+        .clone()
+        .unwrap_or(default_slide_settings.clone());
+
+    let slides = create_presentation_slides(selected_item, &used_slide_settings)?;
+    let meta_text = song_meta_text(selected_item, &used_slide_settings.slide_settings, &slides);
+    let song_number = shown_song_number(selected_item, &used_slide_settings);
+    let mut chapter = SlideChapter {
+        slides,
+        source_file: selected_item.source_file.clone(),
+        presentation_design_option: Some(used_presentation_design),
+        slide_settings_option: Some(used_slide_settings),
+        timer_settings_option: selected_item.timer_settings_option.clone(),
+        transition_option: selected_item.transition_effect,
+        uses_default_design: selected_item.presentation_design_option.is_none(),
+        uses_default_slide_settings: selected_item.slide_settings_option.is_none(),
+        picture_settings: selected_item.picture_settings,
+        meta_text,
+        shown_languages: selected_item.shown_languages,
+        song_number: None,
+        separator_slide: false,
+        selected_item_index: None,
+    };
+    show_song_number(&mut chapter, song_number);
+    Ok(chapter)
+}
+
+/// Returns the song number of a song (see [search::song_number_text]) if the slide settings
+/// show it on the first slide.
+fn shown_song_number(
+    selected_item: &SelectedItemRepresentation,
+    slide_settings: &SlideSettingsProfile,
+) -> Option<String> {
+    if !slide_settings.show_song_number
+        || selected_item.source_file.file_type != SourceFileType::Song
+    {
+        return None;
     }
+    let content = read_song_content(&selected_item.source_file);
+    search::song_number_text(&selected_item.source_file.name, content.as_deref())
+}
 
+/// Shows the song number on the first slide of a chapter. If the chapter starts with a title
+/// slide, the number is added to its meta text, otherwise it is shown in the meta area of the
+/// first slide, see [SlideChapter::song_number]. `None` removes a song number of the chapter
+/// which is not part of a title slide.
+pub fn show_song_number(chapter: &mut SlideChapter, song_number: Option<String>) {
+    chapter.song_number = None;
+    let Some(song_number) = song_number else {
+        return;
+    };
+    if !chapter.has_title_slide() {
+        chapter.song_number = Some(song_number);
+        return;
+    }
+    if let Some(SlideContent::Title(title_slide)) =
+        chapter.slides.first().map(|slide| &slide.slide_content)
+    {
+        let Ok(mut value) = serde_json::to_value(title_slide) else {
+            return;
+        };
+        let meta_text = value
+            .get("meta_text")
+            .and_then(|meta_text| meta_text.as_str())
+            .unwrap_or_default()
+            .trim_end()
+            .to_string();
+        value["meta_text"] = serde_json::Value::String(match meta_text.is_empty() {
+            true => song_number,
+            false => format!("{}\n{}", meta_text, song_number),
+        });
+        match serde_json::from_value(value) {
+            Ok(title_slide) => chapter.slides[0].slide_content = SlideContent::Title(title_slide),
+            Err(err) => log::warn!("Could not add the song number to the title slide: {}", err),
+        }
+    }
+}
+
+/// Returns the meta information (e.g. author and copyright) of the given slides, which
+/// cantara_songlib writes into the title slide according to the meta syntax of the slide settings.
+pub fn meta_text_of_slides(slides: &[Slide]) -> Option<String> {
+    slides
+        .iter()
+        .find_map(|slide| match &slide.slide_content {
+            SlideContent::Title(title_slide) => Some(title_slide.meta_text.trim().to_string()),
+            _ => None,
+        })
+        .filter(|meta_text| !meta_text.is_empty())
+}
+
+/// Returns the meta information of a song for the footer of its slides. If the slides have no
+/// title slide (because of the slide settings or a slide directive of the song), the slides are
+/// created once more with one to get the meta information. Other items than songs have no meta
+/// information.
+fn song_meta_text(
+    selected_item: &SelectedItemRepresentation,
+    slide_settings: &SlideSettings,
+    slides: &[Slide],
+) -> Option<String> {
+    if selected_item.source_file.file_type != SourceFileType::Song {
+        return None;
+    }
+    if slides.first().is_some_and(is_title_slide) {
+        return meta_text_of_slides(slides);
+    }
+    let slide_settings_with_title = SlideSettings {
+        title_slide: true,
+        ..slide_settings.clone()
+    };
+    song_slides(selected_item, &slide_settings_with_title)
+        .ok()
+        .and_then(|slides| meta_text_of_slides(&slides))
+}
+
+/// Applies changed designs and slide settings to a running presentation, which has copied them
+/// when it has been started.
+///
+/// The chapters are matched with the selected items by the index of the item from which they have
+/// been created, as long as the item at this index still has the source file of the chapter. A
+/// chapter gets the design and slide settings of its selected item, or the defaults if the item
+/// has none. Chapters without a selected item (e.g. songs which have been sent from the
+/// repositories) only get the defaults if they have used them before. The slides of songs are
+/// created again if their slide settings have changed, the position stays at the current chapter.
+///
+/// Returns true if the presentation has changed.
+pub fn apply_design_changes(
+    running_presentation: &mut RunningPresentation,
+    selected_items: &[SelectedItemRepresentation],
+    default_presentation_design: &PresentationDesign,
+    default_slide_settings: &SlideSettingsProfile,
+) -> bool {
+    let mut changed = false;
+
+    for chapter in running_presentation.presentation.iter_mut() {
+        let selected_item = chapter
+            .selected_item_index
+            .and_then(|index| selected_items.get(index))
+            .filter(|item| {
+                item.source_file.file_type == chapter.source_file.file_type
+                    && item.source_file.path == chapter.source_file.path
+            });
+
+        let (presentation_design, uses_default_design) = match selected_item {
+            Some(item) => match &item.presentation_design_option {
+                Some(design) => (design.clone(), false),
+                None => (default_presentation_design.clone(), true),
+            },
+            None if chapter.uses_default_design => (default_presentation_design.clone(), true),
+            None => (
+                chapter.presentation_design_option.clone().unwrap_or_default(),
+                false,
+            ),
+        };
+        if chapter.presentation_design_option.as_ref() != Some(&presentation_design) {
+            chapter.presentation_design_option = Some(presentation_design);
+            changed = true;
+        }
+        chapter.uses_default_design = uses_default_design;
+
+        let (slide_settings, uses_default_slide_settings) = match selected_item {
+            Some(item) => match &item.slide_settings_option {
+                Some(slide_settings) => (slide_settings.clone(), false),
+                None => (default_slide_settings.clone(), true),
+            },
+            None if chapter.uses_default_slide_settings => (default_slide_settings.clone(), true),
+            None => (chapter.slide_settings_option.clone().unwrap_or_default(), false),
+        };
+        chapter.uses_default_slide_settings = uses_default_slide_settings;
+        if chapter.slide_settings_option.as_ref() == Some(&slide_settings) {
+            continue;
+        }
+        // Only the slides of songs depend on the slide settings
+        if chapter.source_file.file_type == SourceFileType::Song {
+            let item = selected_item.cloned().unwrap_or_else(|| {
+                SelectedItemRepresentation::new_with_sourcefile(chapter.source_file.clone())
+            });
+            match create_presentation_slides(&item, &slide_settings) {
+                Ok(slides) if !slides.is_empty() => {
+                    chapter.meta_text = song_meta_text(&item, &slide_settings.slide_settings, &slides);
+                    chapter.slides = slides;
+                    show_song_number(chapter, shown_song_number(&item, &slide_settings));
+                    if chapter.separator_slide {
+                        chapter.slides.push(blank_slide());
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => log::warn!(
+                    "Could not create the slides of {} again: {}",
+                    chapter.source_file.name,
+                    err
+                ),
+            }
+        }
+        chapter.slide_settings_option = Some(slide_settings);
+        changed = true;
+    }
+
+    // The current chapter might have less slides now
+    if let Some(position) = running_presentation.position.as_mut() {
+        let chapter = position.chapter();
+        let last_slide = running_presentation
+            .presentation
+            .get(chapter)
+            .map(|chapter| chapter.slides.len().saturating_sub(1))
+            .unwrap_or(0);
+        let slide = position.chapter_slide().min(last_slide);
+        let _ = position.go_to(chapter, slide, &running_presentation.presentation);
+    }
+
+    changed
+}
+
+/// Creates the chapters of a presentation for the selected items, as they are shown when the
+/// presentation is started. Items whose slides can't be created are left out, their source
+/// files are returned next to the chapters.
+/// If `blank_slide_between_chapters` is set, a blank slide separates the chapters, see [insert_separator_slides].
+pub fn create_presentation_chapters(
+    selected_items: &[SelectedItemRepresentation],
+    default_presentation_design: &PresentationDesign,
+    default_slide_settings: &SlideSettingsProfile,
+    blank_slide_between_chapters: bool,
+) -> (Vec<SlideChapter>, Vec<SourceFile>) {
     let mut presentation: Vec<SlideChapter> = vec![];
+    let mut failed_items: Vec<SourceFile> = vec![];
 
-    for selected_item in selected_items {
-        let used_presentation_design = selected_item
-            .presentation_design_option
-            .clone()
-            .unwrap_or(default_presentation_design.clone());
-
-        let used_slide_settings = selected_item
-            .slide_settings_option
-            .clone()
-            .unwrap_or(default_slide_settings.clone());
-
-        match create_presentation_slides(selected_item, &used_slide_settings) {
-            Ok(slides) => presentation.push(SlideChapter {
-                slides,
-                source_file: selected_item.source_file.clone(),
-                presentation_design_option: Some(used_presentation_design),
-                slide_settings_option: Some(used_slide_settings),
-                timer_settings_option: selected_item.timer_settings_option.clone(),
-                transition_option: selected_item.transition_effect,
-            }),
-            Err(_) => {
-                // TODO: Implement error handling, the user should get a message if an error occurs...
+    for (index, selected_item) in selected_items.iter().enumerate() {
+        match create_slide_chapter(selected_item, default_presentation_design, default_slide_settings) {
+            Ok(mut chapter) => {
+                chapter.selected_item_index = Some(index);
+                presentation.push(chapter);
+            }
+            Err(e) => {
+                log::error!("Could not create the slides of {}: {}", selected_item.source_file.name, e);
+                failed_items.push(selected_item.source_file.clone());
             }
         }
     }
 
+    if blank_slide_between_chapters {
+        insert_separator_slides(&mut presentation);
+    }
+
+    (presentation, failed_items)
+}
+
+/// Adds a presentation to the global running presentations signal, next to the ones which are already running.
+/// The chapters are created with [create_presentation_chapters].
+/// Returns the id of the created presentation
+pub fn add_presentation(
+    selected_items: &Vec<SelectedItemRepresentation>,
+    running_presentations: &mut Signal<Vec<RunningPresentation>>,
+    default_presentation_design: &PresentationDesign,
+    default_slide_settings: &SlideSettingsProfile,
+    blank_slide_between_chapters: bool,
+) -> Option<usize> {
+    let (presentation, _) = create_presentation_chapters(
+        selected_items,
+        default_presentation_design,
+        default_slide_settings,
+        blank_slide_between_chapters,
+    );
+
     if !presentation.is_empty() {
-        running_presentations
-            .write()
-            .push(RunningPresentation::new(presentation));
-        return Some(running_presentations.len() - 1);
+        recently_used::record_presented_songs(selected_items);
+        let mut running_presentation = RunningPresentation::new(presentation);
+        running_presentation.id = next_presentation_id(&running_presentations.read());
+        let id = running_presentation.id;
+        running_presentations.write().push(running_presentation);
+        return Some(id);
     }
 
     None
@@ -356,7 +1045,7 @@ pub fn add_presentation(
 pub fn create_single_item_presentation(
     selected_item: &SelectedItemRepresentation,
     default_presentation_design: &PresentationDesign,
-    default_slide_settings: &SlideSettings,
+    default_slide_settings: &SlideSettingsProfile,
 ) -> RunningPresentation {
     let used_presentation_design = selected_item
         .presentation_design_option
@@ -370,43 +1059,161 @@ pub fn create_single_item_presentation(
 
     let slides = create_presentation_slides(selected_item, &used_slide_settings)
         .unwrap_or_default();
+    let meta_text = song_meta_text(selected_item, &used_slide_settings.slide_settings, &slides);
+    let song_number = shown_song_number(selected_item, &used_slide_settings);
 
-    let chapter = SlideChapter {
+    let mut chapter = SlideChapter {
         slides,
         source_file: selected_item.source_file.clone(),
         presentation_design_option: Some(used_presentation_design),
         slide_settings_option: Some(used_slide_settings),
         timer_settings_option: selected_item.timer_settings_option.clone(),
         transition_option: selected_item.transition_effect,
+        uses_default_design: selected_item.presentation_design_option.is_none(),
+        uses_default_slide_settings: selected_item.slide_settings_option.is_none(),
+        picture_settings: selected_item.picture_settings,
+        meta_text,
+        shown_languages: selected_item.shown_languages,
+        song_number: None,
+        separator_slide: false,
+        selected_item_index: None,
     };
+    show_song_number(&mut chapter, song_number);
 
     RunningPresentation::new(vec![chapter])
 }
 
+/// The number of slide counts which are kept at most. If there are more, the cache is cleared.
+const MAX_CACHED_SLIDE_COUNTS: usize = 256;
+
+/// Cache for the number of slides of selected items, see [slide_count].
+static SLIDE_COUNT_CACHE: OnceLock<Mutex<HashMap<u64, usize>>> = OnceLock::new();
+
+/// Returns the number of slides which are generated for a selected item.
+/// The result is cached by the source file, the inline content, the verse order and the effective
+/// slide settings, so changing the individual slide settings of the item leads to a fresh count.
+/// Edited files are counted again after [clear_slide_count_cache].
+/// Items whose slides can't be generated have zero slides.
+pub fn slide_count(
+    selected_item: &SelectedItemRepresentation,
+    default_slide_settings: &SlideSettingsProfile,
+) -> usize {
+    let used_slide_settings = selected_item
+        .slide_settings_option
+        .clone()
+        .unwrap_or(default_slide_settings.clone());
+
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(&(
+        &selected_item.source_file,
+        &selected_item.inline_markdown,
+        &selected_item.inline_text,
+        &selected_item.verse_order,
+        &used_slide_settings,
+    ))
+    .unwrap_or_default()
+    .hash(&mut hasher);
+    let key = hasher.finish();
+
+    let cache = SLIDE_COUNT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(count) = cache.lock().ok().and_then(|cache| cache.get(&key).copied()) {
+        return count;
+    }
+
+    let count = create_presentation_slides(selected_item, &used_slide_settings)
+        .map(|slides| slides.len())
+        .unwrap_or(0);
+    if let Ok(mut cache) = cache.lock() {
+        // Counts of edited announcements and settings are rarely used again, so they are dropped
+        // from time to time
+        if cache.len() >= MAX_CACHED_SLIDE_COUNTS {
+            cache.clear();
+        }
+        cache.insert(key, count);
+    }
+    count
+}
+
+/// Forgets the cached slide counts, so that files which have been edited are counted again.
+/// This is called when the source files are loaded from the repositories.
+pub fn clear_slide_count_cache() {
+    if let Some(Ok(mut cache)) = SLIDE_COUNT_CACHE.get().map(|cache| cache.lock()) {
+        cache.clear();
+    }
+}
+
 /// Creates an example presentation with the song Amazing Grace and a given presentation design
 pub fn create_amazing_grace_presentation(
     presentation_design: &PresentationDesign,
     slide_settings: &SlideSettings,
 ) -> RunningPresentation {
-    let slides = slides_from_classic_song(
-        AMAZING_GRACE_SONG,
+    create_amazing_grace_presentation_with_profile(presentation_design, &slide_settings.clone().into())
+}
+
+/// Creates an example presentation with the song Amazing Grace, which shows the end slide and
+/// the song number of the given slide settings as well.
+pub fn create_amazing_grace_presentation_with_profile(
+    presentation_design: &PresentationDesign,
+    slide_settings: &SlideSettingsProfile,
+) -> RunningPresentation {
+    let mut slides = apply_cantara_slide_settings(
+        slides_from_classic_song(
+            AMAZING_GRACE_SONG,
+            &slide_settings.slide_settings,
+            "Amazing Grace".to_string(),
+        ),
+        Some(AMAZING_GRACE_SONG),
         slide_settings,
+    );
+    // The author line is shown in the meta footer, even if the example has no title slide
+    let slide_settings_with_title = SlideSettings {
+        title_slide: true,
+        ..slide_settings.slide_settings.clone()
+    };
+    let slides_with_title = slides_from_classic_song(
+        AMAZING_GRACE_SONG,
+        &slide_settings_with_title,
         "Amazing Grace".to_string(),
     );
+    let meta_text = meta_text_of_slides(&slides_with_title);
+    slides.extend(end_slide(
+        slide_settings.end_slide,
+        slides_with_title.first().filter(|slide| is_title_slide(slide)),
+    ));
     let source_file = SourceFile {
         name: "Amazing Grace (Example)".to_string(),
         path: PathBuf::new(),
         file_type: SourceFileType::Song,
         md5_hash: None,
     };
-    let slide_chapter = SlideChapter::new(
+    let mut slide_chapter = SlideChapter::new(
         slides,
         source_file,
         Some(presentation_design.clone()),
         Some(slide_settings.clone()),
     );
+    slide_chapter.meta_text = meta_text;
+    if slide_settings.show_song_number {
+        show_song_number(&mut slide_chapter, Some(EXAMPLE_SONG_NUMBER.to_string()));
+    }
 
-    RunningPresentation::new(vec![slide_chapter])
+    example_presentation(presentation_design, vec![slide_chapter])
+}
+
+/// Creates a presentation of the given chapters, which is shown in the aspect ratio for which the
+/// presentation design is made.
+pub fn example_presentation(
+    presentation_design: &PresentationDesign,
+    chapters: Vec<SlideChapter>,
+) -> RunningPresentation {
+    let mut running_presentation = RunningPresentation::new(chapters);
+    if let PresentationDesignSettings::Template(ref template) =
+        presentation_design.presentation_design_settings
+    {
+        let (width, _) = running_presentation.presentation_resolution;
+        running_presentation.presentation_resolution = template.aspect_ratio.resolution(width);
+    }
+    running_presentation
 }
 
 #[cfg(test)]
@@ -414,8 +1221,10 @@ mod tests {
     use std::{path::PathBuf, str::FromStr};
 
     use crate::logic::{
+        settings::PictureFit,
         sourcefiles::{SourceFile, SourceFileType},
         states::SelectedItemRepresentation,
+        test_helpers::{song_item, stanza, title_slide},
     };
 
     use super::*;
@@ -432,10 +1241,35 @@ mod tests {
             presentation_design_option: None,
             slide_settings_option: None,
             inline_markdown: None,
+            inline_text: None,
             timer_settings_option: None,
             transition_effect: Default::default(),
+            picture_settings: Default::default(),
+            verse_order: None,
+            shown_languages: Default::default(),
         };
-        assert!(create_presentation_slides(&select_item, &SlideSettings::default()).is_ok());
+        assert!(create_presentation_slides(&select_item, &SlideSettingsProfile::default()).is_ok());
+    }
+
+    #[test]
+    fn test_presentation_creation_with_verse_order() {
+        let mut select_item = SelectedItemRepresentation::new_with_sourcefile(SourceFile {
+            name: "Alas, and Did My Savior Bleed".to_string(),
+            path: PathBuf::from_str("testfiles/Alas, and Did My Savior Bleed.song").unwrap(),
+            file_type: SourceFileType::Song,
+            md5_hash: None,
+        });
+        let slide_settings = SlideSettingsProfile::default();
+        let all_slides = create_presentation_slides(&select_item, &slide_settings).unwrap();
+
+        select_item.verse_order = Some("V1 C".to_string());
+        let ordered_slides = create_presentation_slides(&select_item, &slide_settings).unwrap();
+        assert!(ordered_slides.len() < all_slides.len());
+
+        // Unknown parts are left out instead of failing
+        select_item.verse_order = Some("V1 C B V99".to_string());
+        let slides_with_unknown_parts = create_presentation_slides(&select_item, &slide_settings).unwrap();
+        assert_eq!(slides_with_unknown_parts.len(), ordered_slides.len());
     }
 
     #[test]
@@ -450,10 +1284,14 @@ mod tests {
             presentation_design_option: None,
             slide_settings_option: None,
             inline_markdown: None,
+            inline_text: None,
             timer_settings_option: None,
             transition_effect: Default::default(),
+            picture_settings: Default::default(),
+            verse_order: None,
+            shown_languages: Default::default(),
         };
-        let result = create_presentation_slides(&select_item, &SlideSettings::default());
+        let result = create_presentation_slides(&select_item, &SlideSettingsProfile::default());
         assert!(result.is_ok());
         let slides = result.unwrap();
         // Example.pdf has 1 page, so 1 slide
@@ -481,10 +1319,14 @@ mod tests {
             presentation_design_option: None,
             slide_settings_option: None,
             inline_markdown: None,
+            inline_text: None,
             timer_settings_option: None,
             transition_effect: Default::default(),
+            picture_settings: Default::default(),
+            verse_order: None,
+            shown_languages: Default::default(),
         };
-        let result = create_presentation_slides(&select_item, &SlideSettings::default());
+        let result = create_presentation_slides(&select_item, &SlideSettingsProfile::default());
         assert!(result.is_ok());
         let slides = result.unwrap();
         // MultiPage.pdf has 3 pages, so 3 slides
@@ -510,10 +1352,14 @@ mod tests {
             presentation_design_option: None,
             slide_settings_option: None,
             inline_markdown: None,
+            inline_text: None,
             timer_settings_option: None,
             transition_effect: Default::default(),
+            picture_settings: Default::default(),
+            verse_order: None,
+            shown_languages: Default::default(),
         };
-        let result = create_presentation_slides(&select_item, &SlideSettings::default());
+        let result = create_presentation_slides(&select_item, &SlideSettingsProfile::default());
         assert!(result.is_ok());
         let slides = result.unwrap();
         assert_eq!(slides.len(), 1);
@@ -535,10 +1381,14 @@ mod tests {
             presentation_design_option: None,
             slide_settings_option: None,
             inline_markdown: None,
+            inline_text: None,
             timer_settings_option: None,
             transition_effect: Default::default(),
+            picture_settings: Default::default(),
+            verse_order: None,
+            shown_languages: Default::default(),
         };
-        let result = create_presentation_slides(&select_item, &SlideSettings::default());
+        let result = create_presentation_slides(&select_item, &SlideSettingsProfile::default());
         assert!(result.is_ok());
         let slides = result.unwrap();
         // example.md has 3 sections separated by ---
@@ -551,6 +1401,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_presentation_creation_from_announcement() {
+        let select_item = SelectedItemRepresentation::new_announcement(
+            "Coffee after the service\nin room 2".to_string(),
+        );
+        let slides = create_presentation_slides(&select_item, &SlideSettingsProfile::default()).unwrap();
+        assert_eq!(slides.len(), 1);
+        match &slides[0].slide_content {
+            SlideContent::SingleLanguageMainContent(main_slide) => assert_eq!(
+                main_slide.clone().main_text(),
+                "Coffee after the service\nin room 2"
+            ),
+            _ => panic!("An announcement should be shown as main content slide"),
+        }
+    }
+
+    #[test]
+    fn test_presentation_creation_from_blank_item() {
+        let slides =
+            create_presentation_slides(&SelectedItemRepresentation::new_blank(), &SlideSettingsProfile::default())
+                .unwrap();
+        assert_eq!(slides.len(), 1);
+        assert!(matches!(slides[0].slide_content, SlideContent::Empty(_)));
+    }
+
+    #[test]
+    fn test_slide_chapter_uses_the_default_design() {
+        let item = SelectedItemRepresentation::new_announcement("Coffee after the service".to_string());
+        let design = PresentationDesign::default();
+        let chapter = create_slide_chapter(&item, &design, &SlideSettingsProfile::default()).unwrap();
+        assert_eq!(chapter.slides.len(), 1);
+        assert!(chapter.presentation_design_option == Some(design));
+        assert_eq!(chapter.source_file, item.source_file);
+    }
+
     #[test]
     fn test_slides_from_markdown() {
         let md = "# Hello\n\nWorld\n\n---\n\n## Slide 2\n\n- a\n- b";
@@ -620,4 +1505,367 @@ mod tests {
         assert_eq!(html_to_plain_text("&amp;lt;"), "&lt;");
         assert_eq!(html_to_plain_text("plain text"), "plain text");
     }
+
+    #[test]
+    fn slide_count_matches_generated_slides() {
+        let mut select_item = SelectedItemRepresentation::new_with_sourcefile(SourceFile {
+            name: "Amazing Grace".to_string(),
+            path: PathBuf::from_str("testfiles/Amazing Grace.song").unwrap(),
+            file_type: SourceFileType::Song,
+            md5_hash: None,
+        });
+        let default_settings = SlideSettingsProfile::default();
+        let expected = create_presentation_slides(&select_item, &default_settings)
+            .unwrap()
+            .len();
+        assert_eq!(slide_count(&select_item, &default_settings), expected);
+        // A second call is answered from the cache
+        assert_eq!(slide_count(&select_item, &default_settings), expected);
+
+        // Individual slide settings are taken into account
+        let mut individual_settings = SlideSettingsProfile::default();
+        individual_settings.slide_settings.max_lines = Some(1);
+        select_item.slide_settings_option = Some(individual_settings.clone());
+        let expected_individual = create_presentation_slides(&select_item, &individual_settings)
+            .unwrap()
+            .len();
+        assert_eq!(slide_count(&select_item, &default_settings), expected_individual);
+    }
+
+    #[test]
+    fn slide_count_follows_edited_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Edited.song");
+        std::fs::write(&path, "#title: Edited\n\nFirst stanza\n").unwrap();
+        let select_item = SelectedItemRepresentation::new_with_sourcefile(SourceFile {
+            name: "Edited".to_string(),
+            path: path.clone(),
+            file_type: SourceFileType::Song,
+            md5_hash: None,
+        });
+        let default_settings = SlideSettingsProfile::default();
+        let count = slide_count(&select_item, &default_settings);
+
+        std::fs::write(&path, "#title: Edited\n\nFirst stanza\n\nSecond stanza\n").unwrap();
+        clear_slide_count_cache();
+        assert_eq!(slide_count(&select_item, &default_settings), count + 1);
+    }
+
+    #[test]
+    fn meta_text_is_taken_from_the_title_slide() {
+        let verse = slide_from_text("Amazing grace, how sweet the sound").unwrap();
+
+        assert_eq!(
+            meta_text_of_slides(&[title_slide("Amazing Grace", "John Newton\n"), verse.clone()]),
+            Some("John Newton".to_string())
+        );
+        assert_eq!(meta_text_of_slides(&[title_slide("Amazing Grace", "  "), verse.clone()]), None);
+        assert_eq!(meta_text_of_slides(&[verse]), None);
+    }
+
+    #[test]
+    fn test_first_main_content_line() {
+        let slide = slide_from_text("\n  Amazing grace, how sweet the sound\nThat saved a wretch like me").unwrap();
+        assert_eq!(
+            first_main_content_line(&slide),
+            Some("Amazing grace, how sweet the sound".to_string())
+        );
+
+        let slides = slides_from_markdown("# Welcome\n\nto our service");
+        assert_eq!(first_main_content_line(&slides[0]), Some("Welcome".to_string()));
+
+        let empty_slide = Slide {
+            slide_content: SlideContent::Empty(EmptySlide { black_background: false }),
+            linked_file: None,
+        };
+        assert_eq!(first_main_content_line(&empty_slide), None);
+    }
+
+    #[test]
+    fn pictures_are_presented_on_a_single_slide_with_their_settings() {
+        let mut item = SelectedItemRepresentation::new_with_sourcefile(SourceFile {
+            name: "Sunrise".to_string(),
+            path: PathBuf::from("pictures/sunrise.jpg"),
+            file_type: SourceFileType::Image,
+            md5_hash: None,
+        });
+        item.picture_settings.fit = PictureFit::Cover;
+        item.picture_settings.show_caption = true;
+
+        let chapter =
+            create_slide_chapter(&item, &PresentationDesign::default(), &SlideSettingsProfile::default())
+                .unwrap();
+        assert_eq!(chapter.slides.len(), 1);
+        assert!(matches!(chapter.slides[0].slide_content, SlideContent::SimplePicture(_)));
+        assert_eq!(chapter.picture_settings, item.picture_settings);
+
+        let rp = RunningPresentation::new(vec![chapter]);
+        assert_eq!(rp.get_current_picture_settings().fit, PictureFit::Cover);
+    }
+
+    #[test]
+    fn design_changes_are_applied_to_a_running_presentation() {
+        let design = |name: &str| PresentationDesign {
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        let default_item = song_item("Amazing Grace");
+        let mut override_item = song_item("Alas, and Did My Savior Bleed");
+        override_item.presentation_design_option = Some(design("Override"));
+        let mut selected_items = vec![default_item, override_item];
+        let slide_settings = SlideSettingsProfile::default();
+
+        // The same song is selected twice, once with an individual design
+        selected_items.push(song_item("Amazing Grace"));
+        selected_items[2].presentation_design_option = Some(design("Second override"));
+        let (chapters, _) =
+            create_presentation_chapters(&selected_items, &design("Default"), &slide_settings, false);
+        let mut rp = RunningPresentation::new(chapters);
+        rp.next_slide();
+
+        // Only the chapter with the default design gets the changed default design
+        assert!(apply_design_changes(&mut rp, &selected_items, &design("Changed"), &slide_settings));
+        assert!(rp.presentation[0].presentation_design_option == Some(design("Changed")));
+        assert!(rp.presentation[1].presentation_design_option == Some(design("Override")));
+        assert!(rp.presentation[2].presentation_design_option == Some(design("Second override")));
+        assert_eq!(rp.position.as_ref().map(|p| p.slide_total()), Some(1));
+
+        // A changed override is applied as well
+        selected_items[1].presentation_design_option = Some(design("Other override"));
+        assert!(apply_design_changes(&mut rp, &selected_items, &design("Changed"), &slide_settings));
+        assert!(rp.presentation[1].presentation_design_option == Some(design("Other override")));
+
+        assert!(!apply_design_changes(&mut rp, &selected_items, &design("Changed"), &slide_settings));
+
+        // Items which have been moved in the selection don't change other chapters
+        selected_items.swap(0, 1);
+        assert!(!apply_design_changes(&mut rp, &selected_items, &design("Changed"), &slide_settings));
+        assert!(rp.presentation[1].presentation_design_option == Some(design("Other override")));
+    }
+
+    #[test]
+    fn separator_slides_are_inserted_between_chapters() {
+        let selected_items = vec![song_item("Amazing Grace"), song_item("Alas, and Did My Savior Bleed")];
+        let slide_settings = SlideSettingsProfile::default();
+        let mut chapters: Vec<SlideChapter> = selected_items
+            .iter()
+            .map(|item| create_slide_chapter(item, &PresentationDesign::default(), &slide_settings).unwrap())
+            .collect();
+        let slide_counts: Vec<usize> = chapters.iter().map(|chapter| chapter.slides.len()).collect();
+
+        insert_separator_slides(&mut chapters);
+        assert_eq!(chapters[0].slides.len(), slide_counts[0] + 1);
+        assert_eq!(chapters[0].content_slide_count(), slide_counts[0]);
+        assert!(matches!(
+            chapters[0].slides.last().map(|slide| &slide.slide_content),
+            Some(SlideContent::Empty(_))
+        ));
+        assert_eq!(chapters[1].slides.len(), slide_counts[1]);
+
+        // The position counts the separator slide
+        let mut rp = RunningPresentation::new(chapters);
+        rp.go_to_chapter(1).unwrap();
+        assert_eq!(rp.position.as_ref().map(|p| p.slide_total()), Some(slide_counts[0] + 1));
+
+        // The separator is kept when the slides are created again
+        let mut changed_slide_settings = slide_settings.clone();
+        changed_slide_settings.slide_settings.title_slide = !slide_settings.slide_settings.title_slide;
+        assert!(apply_design_changes(
+            &mut rp,
+            &selected_items,
+            &PresentationDesign::default(),
+            &changed_slide_settings
+        ));
+        assert!(matches!(
+            rp.presentation[0].slides.last().map(|slide| &slide.slide_content),
+            Some(SlideContent::Empty(_))
+        ));
+        assert!(rp.presentation[0].separator_slide && !rp.presentation[1].separator_slide);
+    }
+
+    #[test]
+    fn song_numbers_are_shown_on_the_first_slide() {
+        let source_file = SourceFile {
+            name: "0017 - Amazing Grace".to_string(),
+            path: PathBuf::from("testfiles/Amazing Grace.song"),
+            file_type: SourceFileType::Song,
+            md5_hash: None,
+        };
+        let title_slide = title_slide("Amazing Grace", "John Newton\n");
+        let verse = slide_from_text("Amazing grace").unwrap();
+
+        let mut chapter = SlideChapter::new(
+            vec![title_slide, verse.clone()],
+            source_file.clone(),
+            None,
+            None,
+        );
+        show_song_number(&mut chapter, Some("17".to_string()));
+        assert_eq!(meta_text_of_slides(&chapter.slides), Some("John Newton\n17".to_string()));
+        assert_eq!(chapter.song_number, None);
+
+        let mut chapter = SlideChapter::new(vec![verse.clone()], source_file, None, None);
+        show_song_number(&mut chapter, Some("17".to_string()));
+        assert_eq!(chapter.song_number, Some("17".to_string()));
+        assert!(chapter.slides == vec![verse]);
+        show_song_number(&mut chapter, None);
+        assert_eq!(chapter.song_number, None);
+    }
+
+    #[test]
+    fn short_stanzas_are_combined_with_their_spoilers() {
+        let spoiler = |slide: &Slide| match &slide.slide_content {
+            SlideContent::SingleLanguageMainContent(main_slide) => main_slide.spoiler_text(),
+            _ => None,
+        };
+        let title_slide = title_slide("Song", "");
+        let slides = vec![
+            title_slide,
+            stanza("Verse one\nline two", "Verse two"),
+            stanza("Verse two\nline two", "Chorus"),
+            stanza("Chorus\nline two", "Verse three"),
+            stanza("Verse three\nline two\nline three", "Verse four"),
+            stanza("Verse four\nline two", ""),
+        ];
+
+        let combined = combine_stanzas(slides.clone(), 4, |text| text.starts_with("Chorus"));
+        let texts: Vec<String> = combined.iter().filter_map(main_content_text).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Song",
+                "Verse one\nline two\nVerse two\nline two",
+                "Chorus\nline two",
+                "Verse three\nline two\nline three",
+                "Verse four\nline two",
+            ]
+        );
+        // The combined slide announces the slide after both stanzas
+        assert_eq!(spoiler(&combined[1]), spoiler(&slides[2]));
+        assert_eq!(spoiler(&combined[2]), spoiler(&slides[3]));
+
+        // With a higher limit, stanzas are combined further, but never with the chorus
+        let combined = combine_stanzas(slides.clone(), 6, |text| text.starts_with("Chorus"));
+        assert_eq!(combined.len(), 4);
+        assert_eq!(
+            main_content_text(&combined[3]),
+            Some("Verse three\nline two\nline three\nVerse four\nline two".to_string())
+        );
+        assert!(combine_stanzas(slides.clone(), 3, |_| false) == slides);
+    }
+
+    #[test]
+    fn songs_end_with_their_end_slide() {
+        let title_slide = title_slide("Song", "");
+        assert!(end_slide(EndSlide::None, Some(&title_slide)).is_none());
+        assert!(end_slide(EndSlide::RepeatTitle, Some(&title_slide)) == Some(title_slide.clone()));
+        assert!(end_slide(EndSlide::RepeatTitle, None).is_none());
+        assert!(matches!(
+            end_slide(EndSlide::Empty, Some(&title_slide)).map(|slide| slide.slide_content),
+            Some(SlideContent::Empty(_))
+        ));
+
+        // The example repeats its title slide, even if it is presented without one
+        let mut slide_settings = SlideSettingsProfile::default();
+        slide_settings.slide_settings.title_slide = false;
+        slide_settings.end_slide = EndSlide::RepeatTitle;
+        let example =
+            create_amazing_grace_presentation_with_profile(&PresentationDesign::default(), &slide_settings);
+        let slides = &example.presentation[0].slides;
+        assert!(!is_title_slide(&slides[0]));
+        assert!(slides.last().is_some_and(is_title_slide));
+    }
+
+    #[test]
+    fn spoilers_show_the_chorus_in_the_chorus_mode() {
+        let song = "#title: Song\n\nVerse one\n\nChorus\nline two\n\nVerse two\n\nChorus\nline two";
+        let slides = vec![
+            stanza("Verse one", "Chorus"),
+            stanza("Chorus\nline two", "Verse two"),
+            stanza("Verse two", "Chorus"),
+            stanza("Chorus\nline two", ""),
+        ];
+        let mut slide_settings = SlideSettingsProfile::default();
+
+        slide_settings.set_spoiler_mode(SpoilerMode::NextLines);
+        assert!(apply_cantara_slide_settings(slides.clone(), Some(song), &slide_settings) == slides);
+
+        slide_settings.set_spoiler_mode(SpoilerMode::Chorus);
+        let with_chorus = apply_cantara_slide_settings(slides.clone(), Some(song), &slide_settings);
+        assert!(with_chorus[0] == stanza("Verse one", "Chorus\nline two"));
+        // The chorus itself and the last slide keep their spoilers
+        assert!(with_chorus[1] == slides[1]);
+        assert!(with_chorus[2] == stanza("Verse two", "Chorus\nline two"));
+        assert!(with_chorus[3] == slides[3]);
+
+        // Songs without a chorus show the next lines
+        assert!(apply_cantara_slide_settings(slides.clone(), Some("Verse"), &slide_settings) == slides);
+    }
+
+    #[test]
+    fn choruses_are_recognized_by_their_lines() {
+        let song = "#title: Song\n\nVerse one\n\nChorus\nline two\n\nVerse two\n\nChorus\nline two";
+        let chorus_texts = verse_order::chorus_texts(song);
+        assert!(is_chorus_text(&chorus_texts, "Chorus\nline two"));
+        assert!(is_chorus_text(&chorus_texts, "line two"));
+        assert!(!is_chorus_text(&chorus_texts, "Verse one"));
+        assert!(!is_chorus_text(&chorus_texts, ""));
+    }
+
+    #[test]
+    fn multi_language_texts_are_extracted() {
+        let multi_language_slide: MultiLanguageMainContentSlide = serde_json::from_value(
+            serde_json::json!({
+                "main_text_list": ["Stille Nacht", "Silent night"],
+                "spoiler_text_list": ["Alles schläft", "All is calm"],
+            }),
+        )
+        .unwrap();
+        let texts = get_multi_language_texts(&multi_language_slide);
+        assert_eq!(texts.main_texts, vec!["Stille Nacht", "Silent night"]);
+        assert_eq!(texts.spoiler_texts, vec!["Alles schläft", "All is calm"]);
+
+        let slide = Slide {
+            slide_content: SlideContent::MultiLanguageMainContent(multi_language_slide),
+            linked_file: None,
+        };
+        assert_eq!(
+            main_content_text(&slide),
+            Some("Stille Nacht\nSilent night".to_string())
+        );
+    }
+
+    #[test]
+    fn long_lines_are_wrapped_at_word_boundaries() {
+        assert_eq!(
+            wrap_lines("Amazing grace how sweet the sound\nthat saved", 15),
+            "Amazing grace\nhow sweet the\nsound\nthat saved"
+        );
+        // Multi-byte characters count as one character each
+        assert_eq!(
+            wrap_lines("Größer ist Gottes Güte, öffne Tür", 12),
+            "Größer ist\nGottes Güte,\nöffne Tür"
+        );
+        assert_eq!(wrap_lines("主 我 神 你 何 等 偉 大", 5), "主 我 神\n你 何 等\n偉 大");
+        // Manual line breaks, empty lines and overlong words are kept
+        assert_eq!(
+            wrap_lines("Hallelujah\n\nHosianna in der Höhe", 8),
+            "Hallelujah\n\nHosianna\nin der\nHöhe"
+        );
+        assert_eq!(wrap_lines("Unchanged line", 0), "Unchanged line");
+    }
+
+    #[test]
+    fn slide_texts_are_wrapped() {
+        let slide = slide_from_text("Amazing grace how sweet the sound").unwrap();
+        let wrapped = wrap_slide_lines(slide, 15);
+        assert_eq!(
+            main_content_text(&wrapped),
+            Some("Amazing grace\nhow sweet the\nsound".to_string())
+        );
+
+        let markdown_slide = slides_from_markdown("# Amazing grace how sweet the sound").remove(0);
+        assert!(wrap_slide_lines(markdown_slide.clone(), 15) == markdown_slide);
+    }
 }
@@ -0,0 +1,202 @@
+//! This module keeps track of the songs which have actually been presented.
+//!
+//! The history is stored next to the settings (or in the local storage in the web version),
+//! so that recently used songs can be added again with one click after a restart.
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::logic::states::get_settings_folder;
+use crate::logic::sourcefiles::{SourceFile, SourceFileType};
+use crate::logic::states::SelectedItemRepresentation;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The maximal number of songs which are kept in the history.
+pub const RECENTLY_USED_LENGTH: usize = 15;
+
+/// The name of the file in the settings folder which contains the recently used songs.
+#[cfg(not(target_arch = "wasm32"))]
+const RECENTLY_USED_FILE_NAME: &str = "recently_used.json";
+
+/// The local storage key which contains the recently used songs in the web version.
+#[cfg(target_arch = "wasm32")]
+const RECENTLY_USED_STORAGE_KEY: &str = "cantara-recently-used";
+
+/// A song which has been presented.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct RecentlyUsedEntry {
+    /// The name of the song
+    pub name: String,
+
+    /// The MD5 hash of the song file which is used as its identity, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub md5_hash: Option<String>,
+
+    /// The path of the song file
+    pub path: PathBuf,
+
+    /// The time of the presentation in seconds since the UNIX epoch
+    pub used_at: u64,
+}
+
+impl RecentlyUsedEntry {
+    /// Returns true if the entry refers to the given source file.
+    fn refers_to(&self, source_file: &SourceFile) -> bool {
+        match (&self.md5_hash, &source_file.md5_hash) {
+            (Some(hash), Some(other_hash)) => hash == other_hash,
+            _ => self.path == source_file.path,
+        }
+    }
+}
+
+/// The history of the recently presented songs (newest first).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default, Debug)]
+pub struct RecentlyUsedSongs {
+    pub entries: Vec<RecentlyUsedEntry>,
+}
+
+impl RecentlyUsedSongs {
+    /// Loads the history from storage. An empty history is returned if none has been stored yet.
+    pub fn load() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        let json = web_sys::window()
+            .and_then(|w| w.local_storage().ok().flatten())
+            .and_then(|s| s.get_item(RECENTLY_USED_STORAGE_KEY).ok().flatten());
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let json = get_settings_folder()
+            .and_then(|folder| std::fs::read_to_string(folder.join(RECENTLY_USED_FILE_NAME)).ok());
+
+        json.and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Saves the history to storage.
+    pub fn save(&self) {
+        let Ok(json) = serde_json::to_string_pretty(self) else {
+            return;
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let _ = web_sys::window()
+                .and_then(|w| w.local_storage().ok().flatten())
+                .map(|s| s.set_item(RECENTLY_USED_STORAGE_KEY, &json));
+            return;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(folder) = get_settings_folder() {
+                let _ = std::fs::create_dir_all(&folder);
+                let _ = std::fs::write(folder.join(RECENTLY_USED_FILE_NAME), json);
+            }
+        }
+    }
+
+    /// Records that a song has been presented. The song is moved to the front if it is already
+    /// part of the history and the history is limited to [RECENTLY_USED_LENGTH] entries.
+    pub fn record(&mut self, source_file: &SourceFile, used_at: u64) {
+        self.entries.retain(|entry| !entry.refers_to(source_file));
+        self.entries.insert(
+            0,
+            RecentlyUsedEntry {
+                name: source_file.name.clone(),
+                md5_hash: source_file.md5_hash.clone(),
+                path: source_file.path.clone(),
+                used_at,
+            },
+        );
+        self.entries.truncate(RECENTLY_USED_LENGTH);
+    }
+
+    /// Returns the indices of the source files which belong to the entries of the history
+    /// (newest first). Entries whose song can't be found anymore are skipped.
+    pub fn source_file_indices(&self, source_files: &[SourceFile]) -> Vec<usize> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                source_files.iter().position(|source_file| {
+                    source_file.file_type == SourceFileType::Song && entry.refers_to(source_file)
+                })
+            })
+            .collect()
+    }
+}
+
+/// Adds the songs of the given selected items to the stored history of recently used songs.
+/// Spontaneous texts and other file types are ignored.
+pub fn record_presented_songs(selected_items: &[SelectedItemRepresentation]) {
+    let used_at = current_timestamp();
+    let mut recently_used = RecentlyUsedSongs::load();
+    // Record in reverse order, so that the first song of the presentation ends up in front
+    for item in selected_items.iter().rev() {
+        if item.source_file.file_type == SourceFileType::Song && item.inline_markdown.is_none() {
+            recently_used.record(&item.source_file, used_at);
+        }
+    }
+    recently_used.save();
+}
+
+/// Returns the current time in seconds since the UNIX epoch.
+pub(crate) fn current_timestamp() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    return (web_sys::js_sys::Date::now() / 1000.0) as u64;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(name: &str, md5_hash: Option<&str>) -> SourceFile {
+        SourceFile {
+            name: name.to_string(),
+            path: PathBuf::from(format!("songs/{}.song", name)),
+            file_type: SourceFileType::Song,
+            md5_hash: md5_hash.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn record_keeps_newest_first_without_duplicates() {
+        let mut recently_used = RecentlyUsedSongs::default();
+        recently_used.record(&song("Amazing Grace", Some("a")), 1);
+        recently_used.record(&song("Holy Holy Holy", Some("b")), 2);
+        recently_used.record(&song("Amazing Grace", Some("a")), 3);
+
+        let names: Vec<&str> = recently_used.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Amazing Grace", "Holy Holy Holy"]);
+        assert_eq!(recently_used.entries[0].used_at, 3);
+    }
+
+    #[test]
+    fn record_limits_the_history_length() {
+        let mut recently_used = RecentlyUsedSongs::default();
+        for index in 0..RECENTLY_USED_LENGTH + 5 {
+            recently_used.record(&song(&format!("Song {}", index), None), index as u64);
+        }
+        assert_eq!(recently_used.entries.len(), RECENTLY_USED_LENGTH);
+    }
+
+    #[test]
+    fn source_file_indices_resolve_by_hash_and_path() {
+        let mut recently_used = RecentlyUsedSongs::default();
+        recently_used.record(&song("Missing", Some("x")), 1);
+        recently_used.record(&song("Without Hash", None), 2);
+        recently_used.record(&song("Renamed", Some("a")), 3);
+
+        let source_files = vec![
+            song("Without Hash", None),
+            SourceFile {
+                path: PathBuf::from("elsewhere/Renamed.song"),
+                ..song("Renamed", Some("a"))
+            },
+        ];
+        assert_eq!(recently_used.source_file_indices(&source_files), vec![1, 0]);
+    }
+}
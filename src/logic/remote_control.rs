@@ -0,0 +1,527 @@
+//! This module provides a small HTTP server which allows to control a running presentation
+//! from another device in the local network, e.g. a phone.
+//!
+//! The server runs in its own thread and never touches the Dioxus state. Received commands are
+//! sent over a channel and taken by the UI with [take_remote_commands], which in turn publishes
+//! the current position for `GET /state` with [publish_remote_state].
+//!
+//! Every request has to carry the token of the server, either as `token` query parameter or in
+//! the `X-Cantara-Token` header. The token is part of the URL which is shown as QR code in the
+//! presenter console.
+
+use crate::logic::states::RunningPresentation;
+use serde::Serialize;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// The minimal control page which is served at `/`.
+const CONTROL_PAGE: &str = include_str!("../../assets/remote_control.html");
+
+/// The header which may carry the token instead of the query parameter.
+const TOKEN_HEADER: &str = "x-cantara-token";
+
+/// The maximal number of bytes which are read of the request line and the headers.
+const MAX_REQUEST_SIZE: u64 = 8 * 1024;
+
+/// The maximal number of headers which are read of a request.
+const MAX_HEADERS: usize = 64;
+
+/// The time in which a client has to send its request and receive the response, before the
+/// connection is closed.
+const REQUEST_DEADLINE: Duration = Duration::from_secs(5);
+
+/// The maximal number of connections which are handled at the same time. Further connections are
+/// closed right away.
+const MAX_CONNECTIONS: usize = 16;
+
+/// The remote control server of the application, `None` if it is not running.
+static SERVER: Mutex<Option<RemoteControlServer>> = Mutex::new(None);
+
+/// A command which has been received by the remote control server.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RemoteCommand {
+    Next,
+    Previous,
+    /// Jumps to the chapter with the given index (starting at 0).
+    GoToChapter(usize),
+}
+
+/// The state of the running presentation as returned by `GET /state`.
+/// Chapter and slide numbers start at 1, as they are shown to the user.
+#[derive(Serialize, Debug, Clone, PartialEq, Default)]
+pub struct RemoteState {
+    pub running: bool,
+    pub chapter: usize,
+    pub chapters: Vec<String>,
+    pub slide: usize,
+    pub total_slides: usize,
+}
+
+impl From<&RunningPresentation> for RemoteState {
+    fn from(running_presentation: &RunningPresentation) -> Self {
+        let position = running_presentation.position.as_ref();
+        RemoteState {
+            running: true,
+            chapter: position.map(|p| p.chapter() + 1).unwrap_or(0),
+            chapters: running_presentation
+                .presentation
+                .iter()
+                .map(|chapter| chapter.source_file.name.clone())
+                .collect(),
+            slide: position.map(|p| p.slide_total() + 1).unwrap_or(0),
+            total_slides: running_presentation.total_slides(),
+        }
+    }
+}
+
+/// A running remote control server. The server thread ends when this is dropped.
+struct RemoteControlServer {
+    port: u16,
+    token: String,
+    commands: Receiver<RemoteCommand>,
+    state: Arc<Mutex<RemoteState>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl RemoteControlServer {
+    fn start(port: u16) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let token = generate_token();
+        let (sender, commands) = channel();
+        let state = Arc::new(Mutex::new(RemoteState::default()));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let thread_token: Arc<str> = token.clone().into();
+        let thread_state = state.clone();
+        let thread_stopped = stopped.clone();
+        let connections = Arc::new(AtomicUsize::new(0));
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if thread_stopped.load(Ordering::Relaxed) {
+                    break;
+                }
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(error) => {
+                        log::warn!("Remote control connection failed: {}", error);
+                        continue;
+                    }
+                };
+                let Some(slot) = ConnectionSlot::acquire(&connections) else {
+                    log::warn!("Remote control connection refused, too many connections are open");
+                    continue;
+                };
+                // Each connection is handled in its own thread, so that a slow client does not
+                // block the others
+                let token = thread_token.clone();
+                let sender = sender.clone();
+                let state = thread_state.clone();
+                std::thread::spawn(move || {
+                    let _slot = slot;
+                    if let Err(error) = handle_connection(stream, &token, &sender, &state) {
+                        log::warn!("Remote control request failed: {}", error);
+                    }
+                });
+            }
+        });
+
+        Ok(RemoteControlServer {
+            port,
+            token,
+            commands,
+            state,
+            stopped,
+        })
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "http://{}:{}/?token={}",
+            local_ip_address(),
+            self.port,
+            self.token
+        )
+    }
+}
+
+impl Drop for RemoteControlServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        // Wake up the server thread which is waiting for the next connection, so that it ends
+        let _ = TcpStream::connect(("127.0.0.1", self.port));
+    }
+}
+
+/// Starts the remote control server on the given port, if it is not already running there.
+/// Returns the URL of the control page including the token.
+pub fn start_remote_control(port: u16) -> std::io::Result<String> {
+    let mut server = SERVER.lock().unwrap_or_else(|error| error.into_inner());
+    if let Some(running_server) = server.as_ref() {
+        if running_server.port == port {
+            return Ok(running_server.url());
+        }
+    }
+    // Stop a server on another port before the new one is started
+    *server = None;
+    let new_server = RemoteControlServer::start(port)?;
+    let url = new_server.url();
+    *server = Some(new_server);
+    Ok(url)
+}
+
+/// Stops the remote control server if it is running.
+pub fn stop_remote_control() {
+    let mut server = SERVER.lock().unwrap_or_else(|error| error.into_inner());
+    *server = None;
+}
+
+/// Returns whether the remote control server is running.
+pub fn remote_control_running() -> bool {
+    let server = SERVER.lock().unwrap_or_else(|error| error.into_inner());
+    server.is_some()
+}
+
+/// Returns the URL of the control page (including the token) if the server is running.
+pub fn remote_control_url() -> Option<String> {
+    let server = SERVER.lock().unwrap_or_else(|error| error.into_inner());
+    server.as_ref().map(|server| server.url())
+}
+
+/// Takes the commands which have been received since the last call.
+pub fn take_remote_commands() -> Vec<RemoteCommand> {
+    let server = SERVER.lock().unwrap_or_else(|error| error.into_inner());
+    server
+        .as_ref()
+        .map(|server| server.commands.try_iter().collect())
+        .unwrap_or_default()
+}
+
+/// Publishes the state of the controlled presentation for `GET /state`.
+pub fn publish_remote_state(state: RemoteState) {
+    let server = SERVER.lock().unwrap_or_else(|error| error.into_inner());
+    if let Some(server) = server.as_ref() {
+        *server
+            .state
+            .lock()
+            .unwrap_or_else(|error| error.into_inner()) = state;
+    }
+}
+
+/// Applies a remote command to a running presentation.
+pub fn apply_remote_command(
+    running_presentation: &mut RunningPresentation,
+    command: RemoteCommand,
+) {
+    match command {
+        RemoteCommand::Next => running_presentation.next_slide(),
+        RemoteCommand::Previous => running_presentation.previous_slide(),
+        RemoteCommand::GoToChapter(chapter) => {
+            if running_presentation.go_to_chapter(chapter).is_err() {
+                log::warn!(
+                    "Remote control requested the unknown chapter {}",
+                    chapter + 1
+                );
+            }
+        }
+    }
+}
+
+/// The endpoints of the remote control server.
+#[derive(Debug, PartialEq)]
+enum Endpoint {
+    ControlPage,
+    State,
+    Command(RemoteCommand),
+}
+
+/// Maps the method and path of a request to an endpoint.
+/// The chapter of `/goto/{chapter}` starts at 1, as on the control page.
+fn route(method: &str, path: &str) -> Option<Endpoint> {
+    match (method, path) {
+        ("GET", "/") => Some(Endpoint::ControlPage),
+        ("GET", "/state") => Some(Endpoint::State),
+        ("POST", "/next") => Some(Endpoint::Command(RemoteCommand::Next)),
+        ("POST", "/prev") => Some(Endpoint::Command(RemoteCommand::Previous)),
+        ("POST", path) => {
+            let chapter: usize = path.strip_prefix("/goto/")?.parse().ok()?;
+            let chapter = chapter.checked_sub(1)?;
+            Some(Endpoint::Command(RemoteCommand::GoToChapter(chapter)))
+        }
+        _ => None,
+    }
+}
+
+/// Splits a request target into the path and the value of the `token` query parameter.
+fn split_target(target: &str) -> (&str, Option<&str>) {
+    match target.split_once('?') {
+        Some((path, query)) => {
+            let token = query
+                .split('&')
+                .find_map(|parameter| parameter.strip_prefix("token="));
+            (path, token)
+        }
+        None => (target, None),
+    }
+}
+
+/// Compares the given token with the token of the server in constant time, so that the token
+/// cannot be guessed from the response times.
+fn token_matches(given_token: Option<&str>, token: &str) -> bool {
+    given_token.is_some_and(|given_token| {
+        given_token.len() == token.len()
+            && given_token
+                .bytes()
+                .zip(token.bytes())
+                .fold(0u8, |difference, (a, b)| difference | (a ^ b))
+                == 0
+    })
+}
+
+/// A slot of the limited number of connections which are handled at the same time, see
+/// [MAX_CONNECTIONS]. The slot is freed when this is dropped.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    /// Takes a free slot, returns `None` if all slots are taken.
+    fn acquire(connections: &Arc<AtomicUsize>) -> Option<Self> {
+        connections
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_CONNECTIONS).then_some(count + 1)
+            })
+            .ok()?;
+        Some(ConnectionSlot(connections.clone()))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// A connection which has to be finished until the deadline. Every read and write only waits for
+/// the remaining time, so a client can't keep the connection open by sending slowly.
+struct DeadlineStream<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl DeadlineStream<'_> {
+    /// Returns the time until the deadline, or an error if it has passed.
+    fn remaining_time(&self) -> std::io::Result<Duration> {
+        self.deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "the request has not been answered in time",
+                )
+            })
+    }
+}
+
+impl Read for DeadlineStream<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.stream.set_read_timeout(Some(self.remaining_time()?))?;
+        self.stream.read(buf)
+    }
+}
+
+impl Write for DeadlineStream<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.stream.set_write_timeout(Some(self.remaining_time()?))?;
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+/// Reads a single request from the stream, answers it and closes the connection.
+/// The size of the request and the time to read and answer it are limited.
+fn handle_connection(
+    stream: TcpStream,
+    token: &str,
+    sender: &Sender<RemoteCommand>,
+    state: &Mutex<RemoteState>,
+) -> std::io::Result<()> {
+    let mut stream = DeadlineStream {
+        stream: &stream,
+        deadline: Instant::now() + REQUEST_DEADLINE,
+    };
+    let mut reader = BufReader::new((&mut stream).take(MAX_REQUEST_SIZE));
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Read the headers, only the token header is of interest
+    let mut header_token = None;
+    for _ in 0..MAX_HEADERS {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case(TOKEN_HEADER) {
+                header_token = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let (path, query_token) = split_target(parts.next().unwrap_or_default());
+
+    let (status, content_type, body) = if !token_matches(query_token.or(header_token.as_deref()), token) {
+        ("401 Unauthorized", "text/plain", "Unauthorized".to_string())
+    } else {
+        match route(method, path) {
+            Some(Endpoint::ControlPage) => (
+                "200 OK",
+                "text/html; charset=utf-8",
+                CONTROL_PAGE.to_string(),
+            ),
+            Some(Endpoint::State) => {
+                let state = state
+                    .lock()
+                    .unwrap_or_else(|error| error.into_inner())
+                    .clone();
+                (
+                    "200 OK",
+                    "application/json",
+                    serde_json::to_string(&state).unwrap_or_default(),
+                )
+            }
+            Some(Endpoint::Command(command)) => match sender.send(command) {
+                Ok(()) => ("200 OK", "application/json", "{\"ok\":true}".to_string()),
+                Err(_) => (
+                    "503 Service Unavailable",
+                    "text/plain",
+                    "Unavailable".to_string(),
+                ),
+            },
+            None => ("404 Not Found", "text/plain", "Not Found".to_string()),
+        }
+    };
+
+    drop(reader);
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Generates a random token which protects the server against other devices in the network.
+fn generate_token() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(nanos);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Returns the address of this computer in the local network, which is the address of the
+/// interface that would be used to reach the internet. No packet is sent for this.
+fn local_ip_address() -> String {
+    UdpSocket::bind("0.0.0.0:0")
+        .and_then(|socket| {
+            socket.connect("8.8.8.8:80")?;
+            socket.local_addr()
+        })
+        .map(|address| address.ip().to_string())
+        .unwrap_or_else(|_| "127.0.0.1".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_routed_to_their_endpoints() {
+        assert_eq!(route("GET", "/"), Some(Endpoint::ControlPage));
+        assert_eq!(route("GET", "/state"), Some(Endpoint::State));
+        assert_eq!(
+            route("POST", "/next"),
+            Some(Endpoint::Command(RemoteCommand::Next))
+        );
+        assert_eq!(
+            route("POST", "/prev"),
+            Some(Endpoint::Command(RemoteCommand::Previous))
+        );
+        assert_eq!(
+            route("POST", "/goto/3"),
+            Some(Endpoint::Command(RemoteCommand::GoToChapter(2)))
+        );
+        assert_eq!(route("POST", "/goto/0"), None);
+        assert_eq!(route("POST", "/goto/abc"), None);
+        assert_eq!(route("GET", "/next"), None);
+    }
+
+    #[test]
+    fn the_token_is_taken_from_the_query() {
+        assert_eq!(split_target("/state?token=abc"), ("/state", Some("abc")));
+        assert_eq!(split_target("/?lang=de&token=abc"), ("/", Some("abc")));
+        assert_eq!(split_target("/next"), ("/next", None));
+    }
+
+    #[test]
+    fn only_the_exact_token_matches() {
+        assert!(token_matches(Some("abc123"), "abc123"));
+        assert!(!token_matches(Some("abc124"), "abc123"));
+        assert!(!token_matches(Some("abc"), "abc123"));
+        assert!(!token_matches(Some(""), "abc123"));
+        assert!(!token_matches(None, "abc123"));
+    }
+
+    #[test]
+    fn connections_are_limited() {
+        let connections = Arc::new(AtomicUsize::new(0));
+        let mut slots: Vec<_> = (0..MAX_CONNECTIONS)
+            .map(|_| ConnectionSlot::acquire(&connections).unwrap())
+            .collect();
+        assert!(ConnectionSlot::acquire(&connections).is_none());
+
+        // A closed connection frees its slot
+        slots.pop();
+        assert!(ConnectionSlot::acquire(&connections).is_some());
+    }
+
+    #[test]
+    fn reads_end_at_the_deadline() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let _client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        let mut stream = DeadlineStream {
+            stream: &stream,
+            deadline: Instant::now() + Duration::from_millis(50),
+        };
+
+        // The client sends nothing, so the read waits until the deadline
+        let mut buffer = [0; 1];
+        assert!(stream.read(&mut buffer).is_err());
+        assert_eq!(
+            stream.read(&mut buffer).unwrap_err().kind(),
+            std::io::ErrorKind::TimedOut
+        );
+    }
+
+    #[test]
+    fn tokens_are_not_repeated() {
+        assert_ne!(generate_token(), generate_token());
+    }
+}
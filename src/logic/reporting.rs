@@ -0,0 +1,245 @@
+//! This module records which songs have actually been projected, e.g. for the CCLI reporting.
+//!
+//! Every day gets its own file in the session log folder next to the settings, which contains
+//! one JSON entry per line, so that entries can be appended without reading the file first.
+//! Dates are calculated in local time. The web version has no settings folder and doesn't record anything.
+
+use crate::logic::recently_used::current_timestamp;
+use crate::logic::sourcefiles::SourceFileType;
+use crate::logic::states::SlideChapter;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::logic::states::get_settings_folder;
+use jiff::Timestamp;
+use jiff::civil::DateTime;
+use jiff::tz::TimeZone;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The name of the folder in the settings folder which contains the session log files.
+#[cfg(not(target_arch = "wasm32"))]
+const SESSION_LOG_FOLDER_NAME: &str = "session_log";
+
+/// A song which has been shown during a presentation.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SessionLogEntry {
+    /// The time when the song was shown first in seconds since the UNIX epoch
+    pub timestamp: u64,
+    /// The title of the song
+    pub title: String,
+    /// The path of the song file
+    pub path: PathBuf,
+}
+
+/// Returns the date and time of a timestamp in seconds since the UNIX epoch in the given time zone.
+fn date_time_of_timestamp(timestamp: u64, time_zone: &TimeZone) -> DateTime {
+    let timestamp = i64::try_from(timestamp)
+        .ok()
+        .and_then(|seconds| Timestamp::from_second(seconds).ok())
+        .unwrap_or(Timestamp::UNIX_EPOCH);
+    time_zone.to_datetime(timestamp)
+}
+
+/// Returns the date (year, month, day) of a timestamp in the given time zone.
+fn date_in_time_zone(timestamp: u64, time_zone: &TimeZone) -> (i64, u32, u32) {
+    let date = date_time_of_timestamp(timestamp, time_zone).date();
+    (
+        i64::from(date.year()),
+        u32::from(date.month().unsigned_abs()),
+        u32::from(date.day().unsigned_abs()),
+    )
+}
+
+/// Formats a timestamp as `YYYY-MM-DD HH:MM` in the given time zone.
+fn format_timestamp_in_time_zone(timestamp: u64, time_zone: &TimeZone) -> String {
+    date_time_of_timestamp(timestamp, time_zone)
+        .strftime("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// Returns the local date (year, month, day) of a timestamp in seconds since the UNIX epoch.
+pub fn date_of_timestamp(timestamp: u64) -> (i64, u32, u32) {
+    date_in_time_zone(timestamp, &TimeZone::system())
+}
+
+/// Formats a timestamp as `YYYY-MM-DD HH:MM` in local time.
+pub fn format_timestamp(timestamp: u64) -> String {
+    format_timestamp_in_time_zone(timestamp, &TimeZone::system())
+}
+
+/// Returns the name of the log file which contains the entries of the day of the timestamp.
+fn log_file_name(timestamp: u64) -> String {
+    let (year, month, day) = date_of_timestamp(timestamp);
+    format!("{:04}-{:02}-{:02}.jsonl", year, month, day)
+}
+
+/// Returns the entries of the lines of a log file. Lines which can't be read are skipped.
+pub fn parse_entries(content: &str) -> Vec<SessionLogEntry> {
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Formats the entries as tab separated lines with time, title and path, e.g. for the clipboard.
+pub fn format_entries(entries: &[SessionLogEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| {
+            format!(
+                "{}\t{}\t{}",
+                format_timestamp(entry.timestamp),
+                entry.title,
+                entry.path.to_string_lossy()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Appends an entry to the log file of its day.
+pub fn append_entry(entry: &SessionLogEntry) {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::io::Write;
+
+        let Some(folder) = get_settings_folder().map(|folder| folder.join(SESSION_LOG_FOLDER_NAME))
+        else {
+            return;
+        };
+        let Ok(line) = serde_json::to_string(entry) else {
+            return;
+        };
+        let _ = std::fs::create_dir_all(&folder);
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(folder.join(log_file_name(entry.timestamp)));
+        match file {
+            Ok(mut file) => {
+                if let Err(error) = writeln!(file, "{}", line) {
+                    log::warn!("The session log could not be written: {}", error);
+                }
+            }
+            Err(error) => log::warn!("The session log could not be opened: {}", error),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    let _ = entry;
+}
+
+/// Loads all entries of the given month, sorted by their time.
+pub fn load_month(year: i64, month: u32) -> Vec<SessionLogEntry> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let prefix = format!("{:04}-{:02}-", year, month);
+        let Some(read_dir) = get_settings_folder()
+            .and_then(|folder| std::fs::read_dir(folder.join(SESSION_LOG_FOLDER_NAME)).ok())
+        else {
+            return vec![];
+        };
+        let mut entries: Vec<SessionLogEntry> = read_dir
+            .flatten()
+            .filter(|file| {
+                let name = file.file_name().to_string_lossy().to_string();
+                name.starts_with(&prefix) && name.ends_with(".jsonl")
+            })
+            .filter_map(|file| std::fs::read_to_string(file.path()).ok())
+            .flat_map(|content| parse_entries(&content))
+            .collect();
+        entries.sort_by_key(|entry| entry.timestamp);
+        entries
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = (year, month);
+        vec![]
+    }
+}
+
+/// Loads all entries of the current month, sorted by their time.
+pub fn load_current_month() -> Vec<SessionLogEntry> {
+    let (year, month, _) = date_of_timestamp(current_timestamp());
+    load_month(year, month)
+}
+
+/// Returns the entry for a chapter which is shown, if it is a song. Other chapters
+/// (e.g. pictures or spontaneous texts) aren't recorded.
+pub fn entry_for_chapter(chapter: &SlideChapter, timestamp: u64) -> Option<SessionLogEntry> {
+    (chapter.source_file.file_type == SourceFileType::Song).then(|| SessionLogEntry {
+        timestamp,
+        title: chapter.source_file.name.clone(),
+        path: chapter.source_file.path.clone(),
+    })
+}
+
+/// Records that a chapter has been shown for the first time in a presentation.
+pub fn record_shown_chapter(chapter: &SlideChapter) {
+    if let Some(entry) = entry_for_chapter(chapter, current_timestamp()) {
+        append_entry(&entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::test_helpers::chapter;
+
+    #[test]
+    fn timestamps_are_converted_to_dates() {
+        let utc = TimeZone::UTC;
+        assert_eq!(date_in_time_zone(0, &utc), (1970, 1, 1));
+        assert_eq!(date_in_time_zone(951_782_400, &utc), (2000, 2, 29));
+        assert_eq!(date_in_time_zone(1_735_689_599, &utc), (2024, 12, 31));
+        assert_eq!(format_timestamp_in_time_zone(1_735_689_599, &utc), "2024-12-31 23:59");
+
+        // The log files are named after the local date
+        let (year, month, day) = date_of_timestamp(1_735_689_600);
+        assert_eq!(
+            log_file_name(1_735_689_600),
+            format!("{:04}-{:02}-{:02}.jsonl", year, month, day)
+        );
+    }
+
+    #[test]
+    fn timestamps_are_converted_to_the_local_date() {
+        // Shortly before midnight in UTC, it is already the next day in Central Europe
+        let berlin = TimeZone::fixed(jiff::tz::offset(1));
+        assert_eq!(date_in_time_zone(1_735_689_599, &berlin), (2025, 1, 1));
+        assert_eq!(format_timestamp_in_time_zone(1_735_689_599, &berlin), "2025-01-01 00:59");
+
+        let new_york = TimeZone::fixed(jiff::tz::offset(-5));
+        assert_eq!(date_in_time_zone(1_735_689_600, &new_york), (2024, 12, 31));
+        assert_eq!(format_timestamp_in_time_zone(1_735_689_600, &new_york), "2024-12-31 19:00");
+    }
+
+    #[test]
+    fn entries_are_written_as_lines_and_read_back() {
+        let entry = SessionLogEntry {
+            timestamp: 1_735_689_600,
+            title: "Amazing Grace".to_string(),
+            path: PathBuf::from("songs/Amazing Grace.song"),
+        };
+        let entry_timestamp = entry.timestamp;
+        let content = format!("{}\nbroken line\n", serde_json::to_string(&entry).unwrap());
+
+        let entries = parse_entries(&content);
+        assert_eq!(entries, vec![entry]);
+        assert_eq!(
+            format_entries(&entries),
+            format!("{}\tAmazing Grace\tsongs/Amazing Grace.song", format_timestamp(entry_timestamp))
+        );
+    }
+
+    #[test]
+    fn only_songs_are_recorded() {
+        assert_eq!(
+            entry_for_chapter(&chapter("Amazing Grace", vec![]), 5).map(|entry| entry.title),
+            Some("Amazing Grace".to_string())
+        );
+        let mut announcements = chapter("Announcements", vec![]);
+        announcements.source_file.file_type = SourceFileType::Markdown;
+        assert!(entry_for_chapter(&announcements, 5).is_none());
+    }
+}
@@ -0,0 +1,442 @@
+//! This module provides the schedule file format of Cantara.
+//!
+//! A schedule contains the selected items of a service (including their individual overrides)
+//! and can be saved to and loaded from a `.cantara-schedule` JSON file.
+//! The same format is used to remember the last selection across restarts.
+
+use crate::logic::settings::{
+    PictureSettings, PresentationDesign, Repository, ShownLanguages, SlideSettingsProfile,
+    SlideTimerSettings, SlideTransition,
+};
+use crate::logic::sourcefiles::{SourceFile, SourceFileType};
+use crate::logic::states::SelectedItemRepresentation;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::logic::states::get_settings_folder;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The file extension of schedule files (without the leading dot).
+pub const SCHEDULE_FILE_EXTENSION: &str = "cantara-schedule";
+
+/// The version of the schedule file format which is written by this version of Cantara.
+pub const SCHEDULE_FORMAT_VERSION: u32 = 1;
+
+/// The name of the file in the settings folder which contains the last selection.
+#[cfg(not(target_arch = "wasm32"))]
+const LAST_SELECTION_FILE_NAME: &str = "last_selection.json";
+
+/// The local storage key which contains the last selection in the web version.
+#[cfg(target_arch = "wasm32")]
+const LAST_SELECTION_STORAGE_KEY: &str = "cantara-last-selection";
+
+/// A schedule represents an ordered list of selected items which can be stored in a file.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Schedule {
+    /// The version of the file format, see [SCHEDULE_FORMAT_VERSION].
+    pub version: u32,
+
+    /// The items of the schedule in the order of the presentation.
+    pub items: Vec<ScheduleItem>,
+}
+
+/// A single item in a [Schedule].
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScheduleItem {
+    /// The name of the source file
+    pub name: String,
+
+    /// The type of the source file
+    pub file_type: SourceFileType,
+
+    /// The MD5 hash of the source file which is used as its identity, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub md5_hash: Option<String>,
+
+    /// The path of the source file on the machine where the schedule has been created.
+    pub path: PathBuf,
+
+    /// The path of the source file relative to the root of the repository which contains it.
+    /// This makes schedules portable between machines which share the same repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_path: Option<PathBuf>,
+
+    /// Inline markdown content for spontaneous markdown text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inline_markdown: Option<String>,
+
+    /// Inline text of an announcement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inline_text: Option<String>,
+
+    /// The individual presentation design of the item, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presentation_design: Option<PresentationDesign>,
+
+    /// The individual slide settings of the item, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slide_settings: Option<SlideSettingsProfile>,
+
+    /// The timer settings of the item, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timer_settings: Option<SlideTimerSettings>,
+
+    /// The transition effect of the item.
+    #[serde(default)]
+    pub transition_effect: SlideTransition,
+
+    /// How the item is presented if it is a picture.
+    #[serde(default)]
+    pub picture_settings: PictureSettings,
+
+    /// The verse order of a song, if it has been set for the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verse_order: Option<String>,
+
+    /// Which languages are shown if the item is a multi-language song.
+    #[serde(default)]
+    pub shown_languages: ShownLanguages,
+}
+
+impl ScheduleItem {
+    /// Creates a schedule item from a selected item.
+    ///
+    /// # Arguments
+    /// * `item` - The selected item
+    /// * `repositories` - The configured repositories, used to determine the relative path
+    pub fn from_selected_item(item: &SelectedItemRepresentation, repositories: &[Repository]) -> Self {
+        let relative_path = repositories
+            .iter()
+            .filter_map(|repository| repository.local_root())
+            .find_map(|root| relative_path_in(&item.source_file.path, &root));
+
+        ScheduleItem {
+            name: item.source_file.name.clone(),
+            file_type: item.source_file.file_type.clone(),
+            md5_hash: item.source_file.md5_hash.clone(),
+            path: item.source_file.path.clone(),
+            relative_path,
+            inline_markdown: item.inline_markdown.clone(),
+            inline_text: item.inline_text.clone(),
+            presentation_design: item.presentation_design_option.clone(),
+            slide_settings: item.slide_settings_option.clone(),
+            timer_settings: item.timer_settings_option.clone(),
+            transition_effect: item.transition_effect,
+            picture_settings: item.picture_settings,
+            verse_order: item.verse_order.clone(),
+            shown_languages: item.shown_languages,
+        }
+    }
+}
+
+impl ScheduleItem {
+    /// Finds the source file of this item in the given source files.
+    /// The source file is looked up by its id (MD5 hash) first, then by its path relative to
+    /// the repository and finally by its name.
+    pub fn find_source_file<'a>(&self, source_files: &'a [SourceFile]) -> Option<&'a SourceFile> {
+        let same_type = |source_file: &&SourceFile| source_file.file_type == self.file_type;
+
+        let by_id = || {
+            let md5_hash = self.md5_hash.as_ref()?;
+            source_files
+                .iter()
+                .filter(same_type)
+                .find(|source_file| source_file.md5_hash.as_ref() == Some(md5_hash))
+        };
+        let by_relative_path = || {
+            let relative_path = self.relative_path.as_ref()?;
+            source_files
+                .iter()
+                .filter(same_type)
+                .find(|source_file| source_file.path.ends_with(relative_path))
+        };
+        let by_name = || {
+            source_files
+                .iter()
+                .filter(same_type)
+                .find(|source_file| source_file.name == self.name)
+        };
+
+        by_id().or_else(by_relative_path).or_else(by_name)
+    }
+
+    /// Creates a selected item from this schedule item and the resolved source file.
+    fn to_selected_item(&self, source_file: SourceFile) -> SelectedItemRepresentation {
+        SelectedItemRepresentation {
+            source_file,
+            presentation_design_option: self.presentation_design.clone(),
+            slide_settings_option: self.slide_settings.clone(),
+            inline_markdown: self.inline_markdown.clone(),
+            inline_text: self.inline_text.clone(),
+            timer_settings_option: self.timer_settings.clone(),
+            transition_effect: self.transition_effect,
+            picture_settings: self.picture_settings,
+            verse_order: self.verse_order.clone(),
+            shown_languages: self.shown_languages,
+        }
+    }
+}
+
+/// The result of resolving a [Schedule] against the available source files.
+pub struct ResolvedSchedule {
+    /// The selected items for all entries whose source file could be found
+    pub items: Vec<SelectedItemRepresentation>,
+
+    /// The entries whose source file could not be found
+    pub missing: Vec<ScheduleItem>,
+}
+
+impl Schedule {
+    /// Creates a schedule from the selected items.
+    pub fn from_selected_items(
+        selected_items: &[SelectedItemRepresentation],
+        repositories: &[Repository],
+    ) -> Self {
+        Schedule {
+            version: SCHEDULE_FORMAT_VERSION,
+            items: selected_items
+                .iter()
+                .map(|item| ScheduleItem::from_selected_item(item, repositories))
+                .collect(),
+        }
+    }
+
+    /// Serializes the schedule into the JSON representation used in schedule files.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Could not serialize the schedule: {}", e))
+    }
+
+    /// Parses a schedule from the JSON representation used in schedule files.
+    /// Schedules written by a newer version of Cantara are rejected.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let schedule: Schedule = serde_json::from_str(json)
+            .map_err(|e| format!("Could not parse the schedule: {}", e))?;
+        if schedule.version > SCHEDULE_FORMAT_VERSION {
+            return Err(format!(
+                "The schedule has the unsupported format version {}",
+                schedule.version
+            ));
+        }
+        Ok(schedule)
+    }
+
+    /// Loads a schedule from the given file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_from_file(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read schedule file {}: {}", path.display(), e))?;
+        Self::from_json(&json)
+    }
+
+    /// Resolves the entries of the schedule against the available source files.
+    /// Entries with inline markdown content, announcements and blank slides do not need a
+    /// source file and are always resolved.
+    pub fn resolve(&self, source_files: &[SourceFile]) -> ResolvedSchedule {
+        let mut items = Vec::new();
+        let mut missing = Vec::new();
+
+        for schedule_item in &self.items {
+            if schedule_item.inline_markdown.is_some()
+                || schedule_item.inline_text.is_some()
+                || schedule_item.file_type == SourceFileType::Blank
+            {
+                let source_file = SourceFile {
+                    name: schedule_item.name.clone(),
+                    path: PathBuf::new(),
+                    file_type: schedule_item.file_type.clone(),
+                    md5_hash: None,
+                };
+                items.push(schedule_item.to_selected_item(source_file));
+                continue;
+            }
+
+            match schedule_item.find_source_file(source_files) {
+                Some(source_file) => {
+                    items.push(schedule_item.to_selected_item(source_file.clone()));
+                }
+                None => missing.push(schedule_item.clone()),
+            }
+        }
+
+        ResolvedSchedule { items, missing }
+    }
+
+    /// Saves the schedule as JSON into the given file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_to_file(&self, path: &Path) -> Result<(), String> {
+        let json = self.to_json()?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Could not write schedule file {}: {}", path.display(), e))
+    }
+
+    /// Stores the schedule as the last selection, so that it can be restored after a restart.
+    pub fn save_as_last_selection(&self) {
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Ok(json) = self.to_json() {
+                let _ = web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .map(|s| s.set_item(LAST_SELECTION_STORAGE_KEY, &json));
+            }
+            return;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(folder) = get_settings_folder() {
+                let _ = std::fs::create_dir_all(&folder);
+                let _ = self.save_to_file(&folder.join(LAST_SELECTION_FILE_NAME));
+            }
+        }
+    }
+
+    /// Loads the last selection which has been stored with [Schedule::save_as_last_selection].
+    /// Returns [None] if there is no last selection or if it has been empty.
+    pub fn load_last_selection() -> Option<Self> {
+        #[cfg(target_arch = "wasm32")]
+        let json = web_sys::window()
+            .and_then(|w| w.local_storage().ok().flatten())
+            .and_then(|s| s.get_item(LAST_SELECTION_STORAGE_KEY).ok().flatten());
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let json = get_settings_folder()
+            .and_then(|folder| std::fs::read_to_string(folder.join(LAST_SELECTION_FILE_NAME)).ok());
+
+        json.and_then(|json| Self::from_json(&json).ok())
+            .filter(|schedule| !schedule.items.is_empty())
+    }
+}
+
+/// Returns the path of `path` relative to `root` if `path` is located below `root`.
+fn relative_path_in(path: &Path, root: &Path) -> Option<PathBuf> {
+    if root.as_os_str().is_empty() {
+        return None;
+    }
+    path.strip_prefix(root).ok().map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a selected item of the song Amazing Grace, which is stored at the given path.
+    fn song_item_at(path: &str) -> SelectedItemRepresentation {
+        SelectedItemRepresentation::new_with_sourcefile(SourceFile {
+            name: "Amazing Grace".to_string(),
+            path: PathBuf::from(path),
+            file_type: SourceFileType::Song,
+            md5_hash: Some("abc".to_string()),
+        })
+    }
+
+    #[test]
+    fn schedule_stores_relative_paths_for_repository_files() {
+        let repositories = vec![Repository::new_local_folder(
+            "Songs".to_string(),
+            "/home/user/songs".to_string(),
+        )];
+        let items = vec![
+            song_item_at("/home/user/songs/hymns/Amazing Grace.song"),
+            song_item_at("/tmp/Amazing Grace.song"),
+        ];
+        let schedule = Schedule::from_selected_items(&items, &repositories);
+
+        assert_eq!(schedule.version, SCHEDULE_FORMAT_VERSION);
+        assert_eq!(
+            schedule.items[0].relative_path,
+            Some(PathBuf::from("hymns/Amazing Grace.song"))
+        );
+        assert_eq!(schedule.items[1].relative_path, None);
+    }
+
+    #[test]
+    fn schedule_serialization_keeps_overrides() {
+        let mut item = song_item_at("testfiles/Amazing Grace.song");
+        item.presentation_design_option = Some(PresentationDesign::default());
+        item.transition_effect = SlideTransition::ZoomIn;
+        item.verse_order = Some("V1 C V2 C".to_string());
+        let schedule = Schedule::from_selected_items(&[item], &[]);
+
+        let json = schedule.to_json().unwrap();
+        let deserialized: Schedule = serde_json::from_str(&json).unwrap();
+        assert!(deserialized == schedule);
+        assert!(deserialized.items[0].presentation_design.is_some());
+        assert_eq!(deserialized.items[0].transition_effect, SlideTransition::ZoomIn);
+        assert_eq!(deserialized.items[0].verse_order, Some("V1 C V2 C".to_string()));
+    }
+
+    #[test]
+    fn resolve_schedule_by_id_relative_path_and_name() {
+        let available = vec![
+            SourceFile {
+                name: "Renamed Song".to_string(),
+                path: PathBuf::from("/other/machine/songs/renamed.song"),
+                file_type: SourceFileType::Song,
+                md5_hash: Some("abc".to_string()),
+            },
+            SourceFile {
+                name: "Alas".to_string(),
+                path: PathBuf::from("/other/machine/songs/hymns/Alas.song"),
+                file_type: SourceFileType::Song,
+                md5_hash: Some("def".to_string()),
+            },
+        ];
+        let schedule_item = |name: &str, md5_hash: Option<&str>, relative_path: Option<&str>| {
+            ScheduleItem {
+                name: name.to_string(),
+                file_type: SourceFileType::Song,
+                md5_hash: md5_hash.map(str::to_string),
+                path: PathBuf::from("/home/user/songs/song.song"),
+                relative_path: relative_path.map(PathBuf::from),
+                inline_markdown: None,
+                inline_text: None,
+                presentation_design: None,
+                slide_settings: None,
+                timer_settings: None,
+                transition_effect: SlideTransition::default(),
+                picture_settings: PictureSettings::default(),
+                verse_order: None,
+                shown_languages: ShownLanguages::default(),
+            }
+        };
+        let schedule = Schedule {
+            version: SCHEDULE_FORMAT_VERSION,
+            items: vec![
+                schedule_item("Amazing Grace", Some("abc"), None),
+                schedule_item("Alas, and Did My Savior Bleed", None, Some("hymns/Alas.song")),
+                schedule_item("Alas", None, None),
+                schedule_item("Unknown", Some("xyz"), Some("unknown.song")),
+            ],
+        };
+
+        let resolved = schedule.resolve(&available);
+        assert_eq!(resolved.items.len(), 3);
+        assert_eq!(resolved.items[0].source_file, available[0]);
+        assert_eq!(resolved.items[1].source_file, available[1]);
+        assert_eq!(resolved.items[2].source_file, available[1]);
+        assert_eq!(resolved.missing.len(), 1);
+        assert_eq!(resolved.missing[0].name, "Unknown");
+    }
+
+    #[test]
+    fn announcements_and_blank_slides_survive_export_and_import() {
+        let items = vec![
+            SelectedItemRepresentation::new_announcement("Coffee in room 2".to_string()),
+            SelectedItemRepresentation::new_blank(),
+        ];
+        let schedule = Schedule::from_selected_items(&items, &[]);
+
+        let json = schedule.to_json().unwrap();
+        let resolved = Schedule::from_json(&json).unwrap().resolve(&[]);
+        assert!(resolved.missing.is_empty());
+        assert!(resolved.items == items);
+    }
+
+    #[test]
+    fn reject_schedules_from_newer_versions() {
+        let json = format!(r#"{{"version":{},"items":[]}}"#, SCHEDULE_FORMAT_VERSION + 1);
+        assert!(Schedule::from_json(&json).is_err());
+        let json = format!(r#"{{"version":{},"items":[]}}"#, SCHEDULE_FORMAT_VERSION);
+        assert!(Schedule::from_json(&json).is_ok());
+    }
+}
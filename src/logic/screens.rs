@@ -1,6 +1,6 @@
 //! This module provides monitor/screen enumeration for multi-screen presentation support.
 
-use dioxus::desktop::DesktopContext;
+use dioxus::desktop::{DesktopContext, tao::monitor::MonitorHandle};
 use serde::{Deserialize, Serialize};
 
 /// Information about a connected monitor/screen.
@@ -46,8 +46,18 @@ pub fn enumerate_monitors(desktop: &DesktopContext) -> Vec<MonitorInfo> {
         .collect()
 }
 
+/// Returns the handle of an enumerated monitor, e.g. for opening a fullscreen window on it.
+/// Returns `None` if the monitor has been disconnected in the meantime.
+pub fn find_monitor_handle(desktop: &DesktopContext, monitor: &MonitorInfo) -> Option<MonitorHandle> {
+    desktop
+        .available_monitors()
+        .nth(monitor.id)
+        .filter(|handle| handle.name().unwrap_or_default() == monitor.name)
+}
+
 /// Resolves which monitor to use for presentation based on settings.
-/// If `configured_name` is Some, tries to find a monitor with that name.
+/// If `configured_name` is Some, tries to find a monitor with that name. If that monitor
+/// is not connected anymore, the primary monitor is used so that no window opens off-screen.
 /// Otherwise, prefers a non-primary monitor (for presentation) or primary monitor (for presenter console).
 pub fn resolve_monitor(
     monitors: &[MonitorInfo],
@@ -59,10 +69,16 @@ pub fn resolve_monitor(
     }
 
     // If a specific monitor is configured, try to find it
+    let mut prefer_primary = prefer_primary;
     if let Some(name) = configured_name {
         if let Some(monitor) = monitors.iter().find(|m| &m.name == name) {
             return Some(monitor.clone());
         }
+        log::warn!(
+            "The configured monitor \"{}\" is not connected, falling back to the primary monitor",
+            name
+        );
+        prefer_primary = true;
     }
 
     // Auto-select: prefer primary or non-primary based on the flag
@@ -80,3 +96,32 @@ pub fn resolve_monitor(
             .cloned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(id: usize, name: &str, is_primary: bool) -> MonitorInfo {
+        MonitorInfo {
+            id,
+            name: name.to_string(),
+            position: (id as i32 * 1920, 0),
+            size: (1920, 1080),
+            is_primary,
+        }
+    }
+
+    #[test]
+    fn resolve_monitor_falls_back_to_the_primary_monitor() {
+        let monitors = vec![monitor(0, "Laptop", true), monitor(1, "Projector", false)];
+
+        let configured = Some("Projector".to_string());
+        assert_eq!(resolve_monitor(&monitors, &configured, false).map(|m| m.id), Some(1));
+        assert_eq!(resolve_monitor(&monitors, &None, false).map(|m| m.id), Some(1));
+
+        // The remembered projector has been unplugged
+        let unplugged = Some("Beamer".to_string());
+        assert_eq!(resolve_monitor(&monitors, &unplugged, false).map(|m| m.id), Some(0));
+        assert!(resolve_monitor(&[], &unplugged, false).is_none());
+    }
+}
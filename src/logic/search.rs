@@ -3,8 +3,9 @@
 use crate::logic::sourcefiles::{SourceFile, SourceFileType};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use std::sync::{Mutex, OnceLock};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 
 // Cache for full-document content keyed by file path (Song, Markdown, full PDF text).
 static SONG_CONTENT_CACHE: OnceLock<Mutex<HashMap<PathBuf, String>>> = OnceLock::new();
@@ -34,6 +35,27 @@ pub fn invalidate_search_cache() {
     }
 }
 
+/// Removes the cached content of a single file from the search cache.
+/// If `path` is a directory, the cached content of all files below it is removed as well.
+/// This is preferred over [invalidate_search_cache] if only some files have changed.
+pub fn invalidate_path(path: &Path) {
+    if let Some(m) = SONG_CONTENT_CACHE.get() {
+        if let Ok(mut map) = m.lock() {
+            map.retain(|cached_path, _| !cached_path.starts_with(path));
+        }
+    }
+    if let Some(m) = PDF_PAGE_CACHE.get() {
+        if let Ok(mut map) = m.lock() {
+            map.retain(|key, _| !pdf_page_cache_key_path(key).starts_with(path));
+        }
+    }
+}
+
+/// Returns the file path part of a `"{path}#page={N}"` key of the PDF page cache.
+fn pdf_page_cache_key_path(key: &str) -> &Path {
+    Path::new(key.rsplit_once("#page=").map_or(key, |(path, _)| path))
+}
+
 /// Shared helper: extracts all page texts from an already-loaded `lopdf::Document`.
 /// Returns the concatenated text, or `None` if no text could be extracted.
 fn extract_text_from_pdf_document(doc: &lopdf::Document) -> Option<String> {
@@ -219,20 +241,228 @@ pub struct SearchResult {
     pub source_file: SourceFile,
     pub matched_content: Option<String>,
     pub is_title_match: bool,
+    /// Whether a purely numeric query matched the song number exactly, see [song_number].
+    pub is_number_match: bool,
+}
+
+/// Returns the song number of a song source file.
+/// The number is either given as a leading numeric prefix of the name
+/// (e.g. `0123 - Great Is Thy Faithfulness`) or as a `#number:` header in the song file.
+/// The header is only taken into account if the file content has already been cached,
+/// so that calling this function never reads from disk.
+pub fn song_number(source_file: &SourceFile) -> Option<u32> {
+    if source_file.file_type != SourceFileType::Song {
+        return None;
+    }
+    song_number_from_name(&source_file.name).or_else(|| {
+        let content = cache().lock().ok()?.get(&source_file.path).cloned()?;
+        song_number_from_content(&content)
+    })
+}
+
+/// Returns the text which identifies a song for the licensing, e.g. `123` or `123 · CCLI 22025`.
+/// It consists of the song number (see [song_number]) and the CCLI number of a `#ccli:` header.
+/// Unlike [song_number], the header is read from the given content of the song file.
+pub fn song_number_text(name: &str, content: Option<&str>) -> Option<String> {
+    let number = song_number_from_name(name)
+        .or_else(|| content.and_then(song_number_from_content))
+        .map(|number| number.to_string());
+    let ccli_number = content
+        .and_then(|content| header_value(content, "ccli"))
+        .map(|ccli_number| format!("CCLI {}", ccli_number));
+    let text = number.into_iter().chain(ccli_number).collect::<Vec<_>>().join(" · ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Returns the name of a song without its leading song number, e.g. `Great Is Thy Faithfulness`
+/// for `0123 - Great Is Thy Faithfulness`. Names without a song number are returned unchanged.
+pub fn name_without_song_number(name: &str) -> &str {
+    if song_number_from_name(name).is_none() {
+        return name;
+    }
+    let title = name
+        .trim_start_matches(|c: char| c.is_ascii_digit())
+        .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '.' | '_' | ':'));
+    if title.is_empty() {
+        name
+    } else {
+        title
+    }
+}
+
+/// Returns the letter (A–Z) under which a song is listed in the alphabet jump bar.
+/// The song number is ignored and diacritics are folded, so that e.g. `Ärger` is listed under `A`.
+/// Names which don't start with a latin letter return `None`.
+pub fn index_letter(name: &str) -> Option<char> {
+    let first = name_without_song_number(name)
+        .chars()
+        .find(|c| c.is_alphanumeric())?;
+    let letter = fold_diacritic(first).to_ascii_uppercase();
+    letter.is_ascii_uppercase().then_some(letter)
+}
+
+/// Maps a latin letter with a diacritic to its base letter.
+fn fold_diacritic(c: char) -> char {
+    match c {
+        'À'..='Å' | 'à'..='å' => 'A',
+        'Ç' | 'ç' => 'C',
+        'È'..='Ë' | 'è'..='ë' => 'E',
+        'Ì'..='Ï' | 'ì'..='ï' => 'I',
+        'Ñ' | 'ñ' => 'N',
+        'Ò'..='Ö' | 'Ø' | 'ò'..='ö' | 'ø' => 'O',
+        'Ù'..='Ü' | 'ù'..='ü' => 'U',
+        'Ý' | 'ý' | 'ÿ' => 'Y',
+        'ß' => 'S',
+        _ => c,
+    }
+}
+
+/// Parses a leading numeric prefix of a name which is separated from the rest of the name.
+fn song_number_from_name(name: &str) -> Option<u32> {
+    let digits_length = name.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits_length == 0 {
+        return None;
+    }
+    // Names like "1st Song" do not start with a song number
+    if name[digits_length..]
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric())
+    {
+        return None;
+    }
+    name[..digits_length].parse().ok()
+}
+
+/// Parses a `#number:` header from the header block at the beginning of a song file.
+fn song_number_from_content(content: &str) -> Option<u32> {
+    header_value(content, "number")?.parse().ok()
+}
+
+/// Returns the value of a header (e.g. `#number: 123`) from the header block at the beginning
+/// of a song file. Empty values are ignored.
+fn header_value<'a>(content: &'a str, name: &str) -> Option<&'a str> {
+    content
+        .lines()
+        .map(str::trim)
+        .take_while(|line| line.starts_with('#'))
+        .find_map(|line| {
+            let (key, value) = line[1..].split_once(':')?;
+            Some(value.trim())
+                .filter(|value| key.trim().eq_ignore_ascii_case(name) && !value.is_empty())
+        })
+}
+
+/// The number of search results which are shown at once, further results are shown on demand.
+pub const SEARCH_RESULTS_PAGE_SIZE: usize = 25;
+
+/// Returns whether a name matches a search query. The comparison is case-insensitive.
+/// This is the same matching which is used for titles in [search_source_files].
+pub fn name_matches_query(name: &str, query: &str) -> bool {
+    name.to_lowercase().contains(&query.to_lowercase())
+}
+
+/// Hands out [SearchToken]s for consecutive search queries.
+/// Starting a new search invalidates all tokens which have been handed out before,
+/// so that stale queries can stop early and their results can be discarded.
+#[derive(Clone, Default, Debug)]
+pub struct SearchGeneration(Arc<AtomicU64>);
+
+impl SearchGeneration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new search generation and returns its token.
+    /// All previously issued tokens are cancelled by this call.
+    pub fn next_token(&self) -> SearchToken {
+        let generation = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+        SearchToken {
+            generation,
+            latest: Arc::clone(&self.0),
+        }
+    }
+
+    /// Cancels all issued tokens without starting a new search.
+    pub fn cancel_all(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// A token which belongs to a single search query, see [SearchGeneration].
+#[derive(Clone, Debug)]
+pub struct SearchToken {
+    generation: u64,
+    latest: Arc<AtomicU64>,
+}
+
+impl SearchToken {
+    /// Returns `true` if a newer search has been started since this token was issued.
+    pub fn is_cancelled(&self) -> bool {
+        self.latest.load(Ordering::SeqCst) != self.generation
+    }
 }
 
 /// Helper function to perform fuzzy search on source files
 pub fn search_source_files(source_files: &[SourceFile], query: &str) -> Vec<SearchResult> {
+    search_source_files_inner(source_files, query, None).unwrap_or_default()
+}
+
+/// Performs the same search as [search_source_files], but stops as soon as the given token
+/// has been cancelled by a newer query.
+///
+/// # Returns
+/// - `Some` with the search results if the search was completed while the token was still valid
+/// - `None` if the search has been cancelled; partial results are discarded
+pub fn search_source_files_cancellable(
+    source_files: &[SourceFile],
+    query: &str,
+    token: &SearchToken,
+) -> Option<Vec<SearchResult>> {
+    search_source_files_inner(source_files, query, Some(token))
+}
+
+fn search_source_files_inner(
+    source_files: &[SourceFile],
+    query: &str,
+    token: Option<&SearchToken>,
+) -> Option<Vec<SearchResult>> {
+    let is_cancelled = || token.is_some_and(|token| token.is_cancelled());
+
+    if is_cancelled() {
+        return None;
+    }
+
     if query.is_empty() {
-        return Vec::new();
+        return Some(Vec::new());
     }
 
     let query = query.to_lowercase();
     let mut results = Vec::new();
 
+    // A purely numeric query is matched against the song numbers as well
+    let number_query: Option<u32> = if query.trim().chars().all(|c| c.is_ascii_digit()) {
+        query.trim().parse().ok()
+    } else {
+        None
+    };
+
     for source_file in source_files {
-        let name_lower = source_file.name.to_lowercase();
-        let is_title_match = name_lower.contains(&query);
+        if is_cancelled() {
+            return None;
+        }
+
+        if number_query.is_some() && song_number(source_file) == number_query {
+            results.push(SearchResult {
+                source_file: source_file.clone(),
+                matched_content: None,
+                is_title_match: true,
+                is_number_match: true,
+            });
+            continue;
+        }
+
+        let is_title_match = name_matches_query(&source_file.name, &query);
 
         // Check if the query matches the title
         if is_title_match {
@@ -240,6 +470,7 @@ pub fn search_source_files(source_files: &[SourceFile], query: &str) -> Vec<Sear
                 source_file: source_file.clone(),
                 matched_content: None,
                 is_title_match: true,
+                is_number_match: false,
             });
             continue;
         }
@@ -285,24 +516,35 @@ pub fn search_source_files(source_files: &[SourceFile], query: &str) -> Vec<Sear
                         source_file: source_file.clone(),
                         matched_content: Some(context),
                         is_title_match: false,
+                        is_number_match: false,
                     });
                 }
             }
         }
     }
 
-    // Sort results: title matches first, then content matches
+    // Sort results: song number matches first, then title matches, then content matches
     results.sort_by(|a, b| {
-        if a.is_title_match && !b.is_title_match {
+        if a.is_number_match != b.is_number_match {
+            b.is_number_match.cmp(&a.is_number_match)
+        } else if a.is_title_match && !b.is_title_match {
             std::cmp::Ordering::Less
         } else if !a.is_title_match && b.is_title_match {
             std::cmp::Ordering::Greater
         } else {
-            a.source_file.name.cmp(&b.source_file.name)
+            // The path is used as a tie-breaker, so that the order is stable between searches
+            a.source_file
+                .name
+                .cmp(&b.source_file.name)
+                .then_with(|| a.source_file.path.cmp(&b.source_file.path))
         }
     });
 
-    results
+    if is_cancelled() {
+        return None;
+    }
+
+    Some(results)
 }
 
 #[cfg(test)]
@@ -311,6 +553,17 @@ mod tests {
     use crate::logic::sourcefiles::{SourceFile, SourceFileType};
     use std::path::PathBuf;
 
+    #[test]
+    fn index_letter_folds_diacritics_and_skips_song_numbers() {
+        assert_eq!(index_letter("Amazing Grace"), Some('A'));
+        assert_eq!(index_letter("Ärger und Freude"), Some('A'));
+        assert_eq!(index_letter("élan"), Some('E'));
+        assert_eq!(index_letter("0123 - Wunderbarer König"), Some('W'));
+        assert_eq!(index_letter("\"Quoted\" Title"), Some('Q'));
+        assert_eq!(index_letter("1st Song"), None);
+        assert_eq!(index_letter("Ωmega"), None);
+    }
+
     #[test]
     fn search_markdown_content() {
         let sf = SourceFile {
@@ -408,4 +661,139 @@ mod tests {
             "Cached PDF content should contain 'Page'"
         );
     }
+
+    #[test]
+    fn cancelled_search_does_not_return_results() {
+        let sf = SourceFile {
+            name: "example".to_string(),
+            path: PathBuf::from("testfiles/example.md"),
+            file_type: SourceFileType::Markdown,
+            md5_hash: None,
+        };
+        let generation = SearchGeneration::new();
+        let stale_token = generation.next_token();
+        let latest_token = generation.next_token();
+
+        assert!(stale_token.is_cancelled());
+        assert!(!latest_token.is_cancelled());
+        assert!(
+            search_source_files_cancellable(&[sf.clone()], "example", &stale_token).is_none(),
+            "A stale search must not produce results"
+        );
+
+        let results = search_source_files_cancellable(&[sf], "example", &latest_token)
+            .expect("The latest search should not be cancelled");
+        assert_eq!(results.len(), 1);
+
+        generation.cancel_all();
+        assert!(latest_token.is_cancelled());
+    }
+
+    #[test]
+    fn pdf_page_cache_key_path_strips_page_number() {
+        assert_eq!(
+            pdf_page_cache_key_path("testfiles/MultiPage.pdf#page=2"),
+            Path::new("testfiles/MultiPage.pdf")
+        );
+        assert_eq!(
+            pdf_page_cache_key_path("testfiles/MultiPage.pdf"),
+            Path::new("testfiles/MultiPage.pdf")
+        );
+    }
+
+    #[test]
+    fn invalidate_path_removes_file_and_directory_entries() {
+        let song_path = PathBuf::from("testfiles/invalidate/songs/Song.song");
+        let pdf_key = "testfiles/invalidate/songs/Sheet.pdf#page=1".to_string();
+        cache().lock().unwrap().insert(song_path.clone(), "content".to_string());
+        pdf_page_cache().lock().unwrap().insert(pdf_key.clone(), "page".to_string());
+
+        invalidate_path(&song_path);
+        assert!(!cache().lock().unwrap().contains_key(&song_path));
+
+        invalidate_path(Path::new("testfiles/invalidate/songs"));
+        assert!(!pdf_page_cache().lock().unwrap().contains_key(&pdf_key));
+    }
+
+    #[test]
+    fn search_order_is_stable_for_equal_names() {
+        let first = SourceFile {
+            name: "example".to_string(),
+            path: PathBuf::from("a/example.md"),
+            file_type: SourceFileType::Image,
+            md5_hash: None,
+        };
+        let second = SourceFile {
+            path: PathBuf::from("b/example.md"),
+            ..first.clone()
+        };
+        let results = search_source_files(&[second.clone(), first.clone()], "example");
+        let reversed = search_source_files(&[first.clone(), second.clone()], "example");
+        assert_eq!(results.len(), 2);
+        assert!(results == reversed, "The order must not depend on the input order");
+        assert_eq!(results[0].source_file, first);
+    }
+
+    #[test]
+    fn parse_song_number_from_name() {
+        assert_eq!(song_number_from_name("0123 - Great Is Thy Faithfulness"), Some(123));
+        assert_eq!(song_number_from_name("42"), Some(42));
+        assert_eq!(song_number_from_name("1st Song"), None);
+        assert_eq!(song_number_from_name("Amazing Grace"), None);
+        assert_eq!(
+            name_without_song_number("0123 - Great Is Thy Faithfulness"),
+            "Great Is Thy Faithfulness"
+        );
+        assert_eq!(name_without_song_number("Amazing Grace"), "Amazing Grace");
+    }
+
+    #[test]
+    fn parse_song_number_from_header() {
+        let content = "#title: Amazing Grace\n#number: 17\n\nAmazing grace";
+        assert_eq!(song_number_from_content(content), Some(17));
+        assert_eq!(song_number_from_content("#title: Amazing Grace\n\n#number: 17"), None);
+    }
+
+    #[test]
+    fn song_number_text_contains_the_ccli_number() {
+        let content = "#title: Amazing Grace\n#ccli: 22025\n\nAmazing grace";
+        assert_eq!(
+            song_number_text("0123 - Amazing Grace", Some(content)),
+            Some("123 · CCLI 22025".to_string())
+        );
+        assert_eq!(
+            song_number_text("Amazing Grace", Some("#number: 17\n#ccli:\n\nAmazing grace")),
+            Some("17".to_string())
+        );
+        assert_eq!(song_number_text("Amazing Grace", None), None);
+    }
+
+    #[test]
+    fn numeric_query_ranks_song_number_first() {
+        let numbered = SourceFile {
+            name: "0123 - Great Is Thy Faithfulness".to_string(),
+            path: PathBuf::from("songs/0123 - Great Is Thy Faithfulness.song"),
+            file_type: SourceFileType::Song,
+            md5_hash: None,
+        };
+        let other = SourceFile {
+            name: "1123 - Another Song".to_string(),
+            path: PathBuf::from("songs/1123 - Another Song.song"),
+            file_type: SourceFileType::Song,
+            md5_hash: None,
+        };
+        let results = search_source_files(&[other, numbered.clone()], "123");
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_number_match);
+        assert_eq!(results[0].source_file, numbered);
+        assert!(!results[1].is_number_match);
+    }
+
+    #[test]
+    fn name_matching_ignores_case() {
+        assert!(name_matches_query("Amazing Grace", "grace"));
+        assert!(name_matches_query("Amazing Grace", "AMAZING"));
+        assert!(name_matches_query("Amazing Grace", ""));
+        assert!(!name_matches_query("Amazing Grace", "faithfulness"));
+    }
 }
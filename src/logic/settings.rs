@@ -1,7 +1,10 @@
 //! This module contains the logic and structures for managing, loading and saving the program's settings.
 
+use crate::logic::conversions::{InvertLightness, ToHexAlphaString};
 use crate::logic::css::{CssFontFamily, CssString};
-use crate::logic::sourcefiles::{ImageSourceFile, SourceFile, get_source_files};
+use crate::logic::sourcefiles::{
+    ImageSourceFile, SourceFile, SourceFileType, VideoSourceFile, get_source_files,
+};
 use cantara_songlib::slides::SlideSettings;
 use dioxus::prelude::*;
 use reqwest::Client as AsyncClient;
@@ -46,7 +49,17 @@ pub struct Settings {
     /// The configured song slide settings in Cantara
     /// There is a default added when none is found.
     #[serde(default = "default_song_slide_vec")]
-    pub song_slide_settings: Vec<SlideSettings>,
+    pub song_slide_settings: Vec<SlideSettingsProfile>,
+
+    /// The index of the presentation design which is used for items without a design of their
+    /// own, see [Settings::default_presentation_design].
+    #[serde(default)]
+    pub default_presentation_design_index: usize,
+
+    /// The index of the song slide settings which are used for items without slide settings of
+    /// their own, see [Settings::default_slide_settings].
+    #[serde(default)]
+    pub default_slide_settings_index: usize,
 
     /// A boolean variable which determines if presentations should start in fullscreen mode by default.
     #[serde(default = "default_always_start_fullscreen")]
@@ -80,8 +93,75 @@ pub struct Settings {
     /// When `None` or empty, the default order (Songs → Pictures → PDFs) is used.
     #[serde(default)]
     pub sidebar_order: Vec<SelectionSidebarType>,
+
+    /// The most recent successful search queries (newest first), see [SEARCH_HISTORY_LENGTH].
+    #[serde(default)]
+    pub search_history: Vec<String>,
+
+    /// Whether successful search queries should be remembered in the search history.
+    #[serde(default = "default_search_history_enabled")]
+    pub search_history_enabled: bool,
+
+    /// What happens when a source file is selected which is already part of the selection.
+    #[serde(default)]
+    pub duplicate_selection_behavior: DuplicateSelectionBehavior,
+
+    /// Whether new items are inserted after the active selected item instead of being appended.
+    #[serde(default = "default_insert_after_active_item")]
+    pub insert_after_active_item: bool,
+
+    /// The alphabet jump bar is shown next to the song list if it contains at least this many songs.
+    #[serde(default = "default_alphabet_jump_bar_threshold")]
+    pub alphabet_jump_bar_threshold: usize,
+
+    /// The logo which can be shown instead of the slides during a presentation (key `L`).
+    #[serde(default)]
+    pub logo_image: Option<ImageSourceFile>,
+
+    /// Advance every presentation automatically after this number of seconds (0 disables it).
+    /// Chapters with their own timer settings keep their timer.
+    #[serde(default)]
+    pub auto_advance_seconds: u32,
+
+    /// Whether an automatically advancing presentation starts again after its last slide.
+    #[serde(default)]
+    pub auto_advance_loop: bool,
+
+    /// Whether scrolling the mouse wheel down goes to the previous slide in presentations
+    #[serde(default)]
+    pub invert_wheel_direction: bool,
+
+    /// What happens when the next slide is requested on the last slide of a presentation
+    #[serde(default)]
+    pub end_of_presentation: EndOfPresentationBehavior,
+
+    /// Whether a blank slide is inserted between the songs of a presentation, independently of
+    /// the end slides of the song slide settings.
+    #[serde(default)]
+    pub blank_slide_between_chapters: bool,
+
+    /// Whether running presentations can be controlled over a local HTTP server, e.g. from a phone.
+    #[serde(default)]
+    pub remote_control_enabled: bool,
+
+    /// The port of the remote control server
+    #[serde(default = "default_remote_control_port")]
+    pub remote_control_port: u16,
+
+    /// Whether the songs which are shown in presentations are recorded in the session log,
+    /// see [crate::logic::reporting].
+    #[serde(default)]
+    pub session_log_enabled: bool,
+
+    /// Whether the CSS of custom designs may load external resources (`@import` and `url()`
+    /// with web addresses). Otherwise, they are removed before the CSS is used.
+    #[serde(default)]
+    pub allow_external_css_resources: bool,
 }
 
+/// The maximal number of queries which are kept in the search history.
+pub const SEARCH_HISTORY_LENGTH: usize = 20;
+
 /// The view mode for the presenter console left panel.
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
 pub enum PresenterConsoleView {
@@ -92,6 +172,18 @@ pub enum PresenterConsoleView {
     Grid,
 }
 
+/// Determines what happens when a source file is selected a second time.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum DuplicateSelectionBehavior {
+    /// Don't add the file again, but highlight the existing entry in the selection.
+    Highlight,
+    /// Ask the user whether the file should be added again (default).
+    #[default]
+    Confirm,
+    /// Add the file again without asking.
+    Allow,
+}
+
 /// Represents an individual source-type button in the selection sidebar.
 /// The order of these values in `Settings::sidebar_order` determines the
 /// display order of the sidebar icons.
@@ -101,15 +193,17 @@ pub enum SelectionSidebarType {
     Pictures,
     Pdfs,
     Markdown,
+    Presentations,
 }
 
-/// Returns the default sidebar order: Songs → Pictures → PDFs → Markdown.
+/// Returns the default sidebar order: Songs → Pictures → PDFs → Markdown → Presentations.
 pub fn default_sidebar_order() -> Vec<SelectionSidebarType> {
     vec![
         SelectionSidebarType::Songs,
         SelectionSidebarType::Pictures,
         SelectionSidebarType::Pdfs,
         SelectionSidebarType::Markdown,
+        SelectionSidebarType::Presentations,
     ]
 }
 
@@ -123,6 +217,18 @@ pub enum AfterLastSlide {
     RestartCurrentChapter,
 }
 
+/// Specifies what happens when the next slide is requested on the last slide of a presentation.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum EndOfPresentationBehavior {
+    /// Stay on the last slide (default).
+    #[default]
+    Hold,
+    /// Show the black screen.
+    Blank,
+    /// Start again with the first slide.
+    WrapAround,
+}
+
 /// Settings for the automatic slide advance timer.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct SlideTimerSettings {
@@ -141,6 +247,228 @@ impl Default for SlideTimerSettings {
     }
 }
 
+/// How a picture fills the slide.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum PictureFit {
+    /// The whole picture is shown, the background of the design fills the remaining space.
+    #[default]
+    Contain,
+    /// The picture covers the whole slide and is cropped.
+    Cover,
+}
+
+impl PictureFit {
+    /// Returns the value of the CSS `object-fit` property.
+    pub fn object_fit(&self) -> &'static str {
+        match self {
+            PictureFit::Contain => "contain",
+            PictureFit::Cover => "cover",
+        }
+    }
+}
+
+/// The settings for the slides of songs. They contain the [SlideSettings] with which
+/// cantara_songlib creates the slides and the settings which Cantara applies to the created slides.
+///
+/// The [SlideSettings] are flattened, so that slide settings which have been saved before
+/// are read as a profile without a name and further settings.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(from = "StoredSlideSettingsProfile")]
+pub struct SlideSettingsProfile {
+    /// The name of the profile, which is shown in the selections. Profiles which have been saved
+    /// before they could be named have an empty name.
+    #[serde(default)]
+    pub name: String,
+
+    /// A description of the profile, e.g. for which occasions it is made
+    #[serde(default)]
+    pub description: String,
+
+    /// The settings with which cantara_songlib creates the slides
+    #[serde(flatten)]
+    pub slide_settings: SlideSettings,
+
+    /// The maximal number of characters per line. Longer lines are wrapped at word boundaries,
+    /// e.g. for projectors with a low resolution.
+    #[serde(default)]
+    pub max_chars_per_line: Option<u16>,
+
+    /// Whether the song number (and the CCLI number) is shown on the first slide of a song,
+    /// which is required for the licensing of some songs.
+    #[serde(default)]
+    pub show_song_number: bool,
+
+    /// The maximal number of lines up to which consecutive short stanzas are combined on a
+    /// single slide. Stanzas are not combined if it is not set.
+    #[serde(default)]
+    pub combine_stanzas_up_to: Option<u16>,
+
+    /// The slide which is shown at the end of each song. It replaces
+    /// [SlideSettings::empty_last_slide], which is always turned off.
+    #[serde(default)]
+    pub end_slide: EndSlide,
+
+    /// What the spoilers of the slides show. It replaces [SlideSettings::show_spoiler], which is
+    /// kept in sync by [SlideSettingsProfile::set_spoiler_mode].
+    #[serde(default)]
+    pub spoiler_mode: SpoilerMode,
+}
+
+/// A [SlideSettingsProfile] as it is saved. Profiles which have been saved before the
+/// [EndSlide] and the [SpoilerMode] existed have neither of them, they are migrated from
+/// [SlideSettings::empty_last_slide] and [SlideSettings::show_spoiler].
+#[derive(Deserialize)]
+struct StoredSlideSettingsProfile {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(flatten)]
+    slide_settings: SlideSettings,
+    #[serde(default)]
+    max_chars_per_line: Option<u16>,
+    #[serde(default)]
+    show_song_number: bool,
+    #[serde(default)]
+    combine_stanzas_up_to: Option<u16>,
+    #[serde(default)]
+    end_slide: Option<EndSlide>,
+    #[serde(default)]
+    spoiler_mode: Option<SpoilerMode>,
+}
+
+impl From<StoredSlideSettingsProfile> for SlideSettingsProfile {
+    fn from(stored: StoredSlideSettingsProfile) -> Self {
+        let end_slide = stored.end_slide.unwrap_or(match stored.slide_settings.empty_last_slide {
+            true => EndSlide::Empty,
+            false => EndSlide::None,
+        });
+        let spoiler_mode = stored.spoiler_mode.unwrap_or(match stored.slide_settings.show_spoiler {
+            true => SpoilerMode::NextLines,
+            false => SpoilerMode::None,
+        });
+        let mut profile = SlideSettingsProfile {
+            name: stored.name,
+            description: stored.description,
+            slide_settings: SlideSettings {
+                empty_last_slide: false,
+                ..stored.slide_settings
+            },
+            max_chars_per_line: stored.max_chars_per_line,
+            show_song_number: stored.show_song_number,
+            combine_stanzas_up_to: stored.combine_stanzas_up_to,
+            end_slide,
+            spoiler_mode: SpoilerMode::None,
+        };
+        profile.set_spoiler_mode(spoiler_mode);
+        profile
+    }
+}
+
+/// The slide which is shown at the end of each song, e.g. while the band finishes instrumentally.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum EndSlide {
+    /// The song ends with its last slide
+    #[default]
+    None,
+    /// The title slide of the song is shown again
+    RepeatTitle,
+    /// An empty slide is shown
+    Empty,
+}
+
+/// What the spoiler of a slide, which announces the lines after the slide, shows.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum SpoilerMode {
+    /// No spoilers are shown
+    #[default]
+    None,
+    /// The first lines of the next slide are shown
+    NextLines,
+    /// The beginning of the chorus is shown, so that the congregation knows how it goes on.
+    /// Songs without a chorus and the slides of the chorus show the next lines instead.
+    Chorus,
+}
+
+impl SlideSettingsProfile {
+    /// Sets the spoiler mode and turns the spoilers of cantara_songlib on or off accordingly.
+    pub fn set_spoiler_mode(&mut self, spoiler_mode: SpoilerMode) {
+        self.spoiler_mode = spoiler_mode;
+        self.slide_settings.show_spoiler = spoiler_mode != SpoilerMode::None;
+    }
+
+    /// Returns the name of the profile if it has one.
+    pub fn name(&self) -> Option<&str> {
+        Some(self.name.trim()).filter(|name| !name.is_empty())
+    }
+}
+
+impl From<SlideSettings> for SlideSettingsProfile {
+    fn from(slide_settings: SlideSettings) -> Self {
+        let end_slide = match slide_settings.empty_last_slide {
+            true => EndSlide::Empty,
+            false => EndSlide::None,
+        };
+        let spoiler_mode = match slide_settings.show_spoiler {
+            true => SpoilerMode::NextLines,
+            false => SpoilerMode::None,
+        };
+        SlideSettingsProfile {
+            name: String::new(),
+            description: String::new(),
+            slide_settings: SlideSettings {
+                empty_last_slide: false,
+                ..slide_settings
+            },
+            max_chars_per_line: None,
+            show_song_number: false,
+            combine_stanzas_up_to: None,
+            end_slide,
+            spoiler_mode,
+        }
+    }
+}
+
+impl Default for SlideSettingsProfile {
+    fn default() -> Self {
+        SlideSettings::default().into()
+    }
+}
+
+/// Which languages of a multi-language song are shown on its slides.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum ShownLanguages {
+    /// The primary language and the secondary languages beneath it
+    #[default]
+    All,
+    /// Only the primary (first) language
+    Primary,
+    /// Only the secondary languages
+    Secondary,
+}
+
+impl ShownLanguages {
+    /// Returns true if the language with the given index (0 is the primary language) is shown.
+    pub fn shows(&self, language_index: usize) -> bool {
+        match self {
+            ShownLanguages::All => true,
+            ShownLanguages::Primary => language_index == 0,
+            ShownLanguages::Secondary => language_index > 0,
+        }
+    }
+}
+
+/// Settings for presenting a picture on a full-screen slide.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub struct PictureSettings {
+    /// How the picture fills the slide.
+    #[serde(default)]
+    pub fit: PictureFit,
+    /// Whether the file name is shown as caption below the picture.
+    #[serde(default)]
+    pub show_caption: bool,
+}
+
 /// The transition effect to use between slides.
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
 pub enum SlideTransition {
@@ -164,6 +492,8 @@ impl Default for Settings {
             wizard_completed: false,
             presentation_designs: default_presentation_design_vec(),
             song_slide_settings: default_song_slide_vec(),
+            default_presentation_design_index: 0,
+            default_slide_settings_index: 0,
             always_start_fullscreen: default_always_start_fullscreen(),
             presentation_screen: None,
             presenter_screen: None,
@@ -172,6 +502,21 @@ impl Default for Settings {
             presenter_console_view: PresenterConsoleView::default(),
             presenter_console_grid_size: default_presenter_console_grid_size(),
             sidebar_order: default_sidebar_order(),
+            search_history: vec![],
+            search_history_enabled: default_search_history_enabled(),
+            duplicate_selection_behavior: DuplicateSelectionBehavior::default(),
+            insert_after_active_item: default_insert_after_active_item(),
+            alphabet_jump_bar_threshold: default_alphabet_jump_bar_threshold(),
+            logo_image: None,
+            auto_advance_seconds: 0,
+            auto_advance_loop: false,
+            invert_wheel_direction: false,
+            end_of_presentation: EndOfPresentationBehavior::default(),
+            blank_slide_between_chapters: false,
+            remote_control_enabled: false,
+            remote_control_port: default_remote_control_port(),
+            session_log_enabled: false,
+            allow_external_css_resources: false,
         }
     }
 }
@@ -180,14 +525,25 @@ fn default_presenter_console_grid_size() -> u32 {
     250
 }
 
+/// Returns the index of the default entry of a list after the entry at `removed_index` has been
+/// removed, and whether the default entry itself has been removed. In this case, the first entry
+/// becomes the default.
+fn default_index_after_removal(default_index: usize, removed_index: usize) -> (usize, bool) {
+    match removed_index.cmp(&default_index) {
+        std::cmp::Ordering::Less => (default_index - 1, false),
+        std::cmp::Ordering::Equal => (0, true),
+        std::cmp::Ordering::Greater => (default_index, false),
+    }
+}
+
 /// This creates the default presentation designs
 fn default_presentation_design_vec() -> Vec<PresentationDesign> {
     vec![PresentationDesign::default()]
 }
 
 /// This creates the default slide settings
-fn default_song_slide_vec() -> Vec<SlideSettings> {
-    vec![SlideSettings::default()]
+fn default_song_slide_vec() -> Vec<SlideSettingsProfile> {
+    vec![SlideSettingsProfile::default()]
 }
 
 /// This returns the default value for always_start_fullscreen
@@ -205,6 +561,26 @@ fn default_presenter_console_in_main_window() -> bool {
     true
 }
 
+/// This returns the default value for search_history_enabled
+fn default_search_history_enabled() -> bool {
+    true
+}
+
+/// This returns the default value for insert_after_active_item
+fn default_insert_after_active_item() -> bool {
+    true
+}
+
+/// This returns the default value for alphabet_jump_bar_threshold
+fn default_alphabet_jump_bar_threshold() -> usize {
+    100
+}
+
+/// This returns the default value for remote_control_port
+fn default_remote_control_port() -> u16 {
+    8077
+}
+
 impl Settings {
     /// Cleans up all temporary resources associated with all repositories
     pub fn cleanup_all_repositories(&self) {
@@ -281,6 +657,39 @@ impl Settings {
         }
     }
 
+    /// Remembers a query which led to the selection of an item in the search history.
+    /// The query is moved to the front if it is already present (case-insensitive) and the
+    /// history is limited to [SEARCH_HISTORY_LENGTH] entries.
+    /// Nothing is recorded if the search history has been disabled.
+    pub fn add_to_search_history(&mut self, query: &str) {
+        let query = query.trim();
+        if !self.search_history_enabled || query.is_empty() {
+            return;
+        }
+        let query_lower = query.to_lowercase();
+        self.search_history
+            .retain(|entry| entry.to_lowercase() != query_lower);
+        self.search_history.insert(0, query.to_string());
+        self.search_history.truncate(SEARCH_HISTORY_LENGTH);
+    }
+
+    /// Returns the order of the selection sidebar buttons. Buttons which are missing in the stored
+    /// order (e.g. because they have been added in a newer version) are appended at the end.
+    pub fn effective_sidebar_order(&self) -> Vec<SelectionSidebarType> {
+        let mut order = self.sidebar_order.clone();
+        for sidebar_type in default_sidebar_order() {
+            if !order.contains(&sidebar_type) {
+                order.push(sidebar_type);
+            }
+        }
+        order
+    }
+
+    /// Removes all entries from the search history.
+    pub fn clear_search_history(&mut self) {
+        self.search_history.clear();
+    }
+
     /// Add a new repository to the settings if the repository is not already present (avoiding duplicates).
     pub fn add_repository(&mut self, repo: Repository) {
         if !self.repositories.contains(&repo) {
@@ -387,6 +796,46 @@ impl Settings {
         source_files
     }
 
+    /// Returns the presentation design which is used for items without a design of their own.
+    /// If the default index is out of range, the first design is used.
+    pub fn default_presentation_design(&self) -> PresentationDesign {
+        self.presentation_designs
+            .get(self.default_presentation_design_index)
+            .or(self.presentation_designs.first())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the song slide settings which are used for items without slide settings of their
+    /// own. If the default index is out of range, the first slide settings are used.
+    pub fn default_slide_settings(&self) -> SlideSettingsProfile {
+        self.song_slide_settings
+            .get(self.default_slide_settings_index)
+            .or(self.song_slide_settings.first())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Updates the default indices after the presentation design at the given index has been
+    /// removed. Returns true if the default design itself has been removed, so that the first
+    /// design has become the default.
+    pub fn presentation_design_removed(&mut self, index: usize) -> bool {
+        let (default_index, reassigned) =
+            default_index_after_removal(self.default_presentation_design_index, index);
+        self.default_presentation_design_index = default_index;
+        reassigned
+    }
+
+    /// Updates the default indices after the song slide settings at the given index have been
+    /// removed. Returns true if the default slide settings themselves have been removed, so that
+    /// the first slide settings have become the default.
+    pub fn slide_settings_removed(&mut self, index: usize) -> bool {
+        let (default_index, reassigned) =
+            default_index_after_removal(self.default_slide_settings_index, index);
+        self.default_slide_settings_index = default_index;
+        reassigned
+    }
+
     /// Ensures that at least one presentation design exists.
     /// If there are no presentation designs, a default one is created.
     pub fn ensure_default_presentation_design(&mut self) {
@@ -404,7 +853,7 @@ impl Settings {
         if slide_count < design_count {
             // Add default slide settings until there are at least as many as presentation designs
             for _ in 0..(design_count - slide_count) {
-                self.song_slide_settings.push(SlideSettings::default());
+                self.song_slide_settings.push(SlideSettingsProfile::default());
             }
         }
     }
@@ -550,6 +999,31 @@ impl Repository {
         }
     }
 
+    /// Removes the cached search content of all files which belong to this repository.
+    /// This has to be called before [Repository::cleanup], because the temporary directory
+    /// of remote repositories is needed to determine the affected files.
+    pub fn invalidate_search_cache(&self) {
+        if let Some(root) = self.local_root() {
+            crate::logic::search::invalidate_path(&root);
+        }
+    }
+
+    /// Returns the local directory which contains the files of this repository, if available.
+    /// For remote repositories, this is the temporary directory where they have been extracted
+    /// to (desktop only).
+    pub fn local_root(&self) -> Option<PathBuf> {
+        match &self.repository_type {
+            RepositoryType::LocaleFilePath(path) => Some(PathBuf::from(path)),
+            #[cfg(not(target_arch = "wasm32"))]
+            RepositoryType::RemoteZip(url) => RepositoryType::temp_dir_path(url),
+            #[cfg(not(target_arch = "wasm32"))]
+            RepositoryType::GitHub { owner, repo, .. } => {
+                RepositoryType::temp_dir_path(&RepositoryType::github_cache_key(owner, repo))
+            }
+            _ => None,
+        }
+    }
+
     pub fn new_local_folder(name: String, path: String) -> Self {
         Repository {
             name,
@@ -712,6 +1186,18 @@ impl RepositoryType {
     #[cfg(target_arch = "wasm32")]
     pub fn cleanup_temp_dir(_url: &str) {}
 
+    /// Returns the path of the temporary directory for a specific URL if it has already been
+    /// downloaded (desktop only).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn temp_dir_path(url: &str) -> Option<PathBuf> {
+        TEMP_DIRS.with(|temp_dirs| {
+            temp_dirs
+                .borrow()
+                .get(url)
+                .map(|temp_dir| temp_dir.path().to_path_buf())
+        })
+    }
+
     /// Cleans up all temporary directories (desktop only).
     #[cfg(not(target_arch = "wasm32"))]
     pub fn cleanup_all_temp_dirs() {
@@ -1384,6 +1870,10 @@ pub struct PresentationDesign {
 
     /// Presentation Design settings for that PresentationDesign
     pub presentation_design_settings: PresentationDesignSettings,
+
+    /// Colors which are saved with the design, so that they can be reused in all color inputs
+    #[serde(default)]
+    pub palette: Vec<PaletteColor>,
 }
 
 impl Default for PresentationDesign {
@@ -1392,8 +1882,136 @@ impl Default for PresentationDesign {
             name: "Default".to_string(),
             description: "".to_string(),
             presentation_design_settings: PresentationDesignSettings::default(),
+            palette: vec![],
+        }
+    }
+}
+
+impl PresentationDesign {
+    /// Returns true if the background of the design lets the desktop shine through, so that the
+    /// presentation window has to be transparent (e.g. for a chroma key in streaming software).
+    pub fn has_transparent_background(&self) -> bool {
+        match self.presentation_design_settings {
+            PresentationDesignSettings::Template(ref template) => template.has_transparent_background(),
+            PresentationDesignSettings::Custom(_) => false,
+        }
+    }
+}
+
+/// A named color of the palette of a [PresentationDesign]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PaletteColor {
+    /// A name which helps to identify the color (can be empty)
+    pub name: String,
+
+    /// The color including its transparency
+    pub color: RGBA8,
+}
+
+impl PaletteColor {
+    /// Returns the name of the color, or its hex code if it has no name
+    pub fn label(&self) -> String {
+        match self.name.trim() {
+            "" => self.color.to_hex_with_alpha(),
+            name => name.to_string(),
+        }
+    }
+}
+
+/// Returns the given name for a design, or the name with a number appended if another design
+/// already has it. The design at `own_index` (e.g. the design which is renamed) is skipped.
+pub fn unique_design_name(
+    designs: &[PresentationDesign],
+    name: &str,
+    own_index: Option<usize>,
+) -> String {
+    let name = name.trim();
+    let is_taken = |candidate: &str| {
+        designs
+            .iter()
+            .enumerate()
+            .any(|(index, design)| Some(index) != own_index && design.name == candidate)
+    };
+    if !is_taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|number| format!("{} ({})", name, number))
+        .find(|candidate| !is_taken(candidate))
+        .unwrap_or_default()
+}
+
+/// The ready-made designs of the gallery, see [design_gallery].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GalleryDesign {
+    Dark,
+    Light,
+    WarmGradient,
+    Photo,
+}
+
+/// Returns the ready-made designs of the gallery, which can be added to the designs of the user.
+/// The presets are created again on every call, so that an added design is always a copy which
+/// can be changed without affecting the gallery. `texts` returns the (translated) name and
+/// description of a design.
+pub fn design_gallery(texts: impl Fn(GalleryDesign) -> (String, String)) -> Vec<PresentationDesign> {
+    let gallery_design = |gallery_design: GalleryDesign, template: PresentationDesignTemplate| {
+        let (name, description) = texts(gallery_design);
+        PresentationDesign {
+            name,
+            description,
+            presentation_design_settings: PresentationDesignSettings::Template(template),
+            palette: vec![],
         }
+    };
+    let with_font_color = |mut template: PresentationDesignTemplate, color: RGBA8| {
+        for font in template.fonts.iter_mut() {
+            font.color = color;
+        }
+        template
+    };
+
+    let mut warm_gradient = PresentationDesignTemplate {
+        background_gradient: Some(BackgroundGradient {
+            kind: GradientKind::Linear,
+            angle: 135,
+            color_stops: vec![
+                GradientColorStop {
+                    color: RGB8::new(110, 30, 20),
+                    position: 0,
+                },
+                GradientColorStop {
+                    color: RGB8::new(225, 140, 50),
+                    position: 100,
+                },
+            ],
+        }),
+        ..Default::default()
+    };
+    let mut photo = PresentationDesignTemplate {
+        background_transparency: 50,
+        ..Default::default()
+    };
+    // Light text needs a shadow to stay readable on a gradient or a picture
+    for font in warm_gradient.fonts.iter_mut().chain(photo.fonts.iter_mut()) {
+        font.shadow = true;
     }
+
+    vec![
+        gallery_design(GalleryDesign::Dark, PresentationDesignTemplate::default()),
+        gallery_design(
+            GalleryDesign::Light,
+            with_font_color(
+                PresentationDesignTemplate {
+                    background_color: RGB8::new(250, 248, 240),
+                    ..Default::default()
+                },
+                RGBA8::new(30, 30, 30, 255),
+            ),
+        ),
+        gallery_design(GalleryDesign::WarmGradient, warm_gradient),
+        gallery_design(GalleryDesign::Photo, photo),
+    ]
 }
 
 /// This enum describes the general design of the presentation (background color, font-colors etc.).
@@ -1403,10 +2021,39 @@ pub enum PresentationDesignSettings {
     /// Describe the design via a template set up in Cantara
     Template(PresentationDesignTemplate),
 
-    /// Manually specified template with HTML/CSS/Javascript (not implemented yet)
+    /// A stylesheet written by the user. It is applied above the default template and can
+    /// address the elements of the slides by their class names, see [CUSTOM_CSS_EXAMPLE].
     Custom(String),
 }
 
+/// The CSS with which a new custom design starts. It lists the class names which can be used.
+pub const CUSTOM_CSS_EXAMPLE: &str = "/* The whole slide */
+.presentation {
+    background: #102040 !important;
+}
+
+/* The layer with the background picture */
+.background { }
+
+/* The container of the content of a slide */
+.slide-container { }
+
+/* The title of a song on its title slide */
+.headline p { }
+
+/* The text of a slide */
+.main-content p {
+    color: #fff4d0 !important;
+}
+
+/* The preview of the next slide below the text */
+.spoiler-content p { }
+
+/* The slide number and the clock */
+.slide-counter { }
+.slide-clock { }
+";
+
 impl Default for PresentationDesignSettings {
     fn default() -> Self {
         PresentationDesignSettings::Template(PresentationDesignTemplate::default())
@@ -1427,6 +2074,10 @@ pub struct PresentationDesignTemplate {
     /// The index of the font configuration for default meta-block
     pub meta_index: Option<u16>,
 
+    /// The index of the font configuration for the secondary languages of multi-language songs
+    #[serde(default)]
+    pub secondary_language_index: Option<u16>,
+
     /// The vertical alignment of the content
     pub vertical_alignment: VerticalAlign,
 
@@ -1439,31 +2090,150 @@ pub struct PresentationDesignTemplate {
     /// The background color transparancy towards an image (0-255)
     pub background_transparency: u8,
 
+    /// The transparency of the background color itself in percent (0-100). A transparent
+    /// background is needed if the slides are laid over a video, e.g. in streaming software.
+    #[serde(default)]
+    pub background_color_transparency: u8,
+
     /// The padding of the presentation (top, bottom, left, right)
     pub padding: TopBottomLeftRight,
 
-    /// An optional background picture
-    pub background_image: Option<ImageSourceFile>,
+    /// An optional background picture or video
+    pub background_image: Option<BackgroundMedia>,
+
+    /// Whether a background video is paused on slides without text (e.g. pictures) and while
+    /// the black, white or logo screen is shown, which saves resources
+    #[serde(default = "default_pause_background_video_on_still_slides")]
+    pub pause_background_video_on_still_slides: bool,
+
+    /// Pictures which are shown in turn instead of the background picture or video. They are
+    /// only used if at least two pictures are given.
+    #[serde(default)]
+    pub background_rotation_images: Vec<ImageSourceFile>,
+
+    /// Whether the rotating background pictures change with every chapter or every slide
+    #[serde(default)]
+    pub background_rotation: BackgroundRotation,
+
+    /// An optional gradient which replaces the background color. A background picture is
+    /// shown above it with its transparency.
+    #[serde(default)]
+    pub background_gradient: Option<BackgroundGradient>,
+
+    /// How the background picture or video fills the slide
+    #[serde(default)]
+    pub background_fit: BackgroundFit,
+
+    /// The part of the background which stays visible if it is cropped
+    #[serde(default)]
+    pub background_position: BackgroundPosition,
+
+    /// An optional blur radius for the background, e.g. to make text on busy pictures readable
+    #[serde(default)]
+    pub background_blur: Option<CssSize>,
 
     /// The distance between the main content and the spoiler content
     pub main_content_spoiler_content_padding: CssSize,
-}
 
-impl PresentationDesignTemplate {
-    /// Returns the background color as an RGB string which can be used in CSS
-    /// for example: pure black would equal to (0, 0, 0)
-    pub fn get_background_as_rgb_string(&self) -> String {
-        format!(
-            "{}, {}, {}",
-            self.background_color.r, self.background_color.g, self.background_color.b
-        )
-    }
+    /// The maximal number of lines of the spoiler. Longer spoilers are cut off with an ellipsis.
+    #[serde(default)]
+    pub spoiler_max_lines: Option<u8>,
 
-    /// Returns the background color as a hexadecimal string
-    /// for example, pure black would equal to #000000
-    pub fn get_background_color_as_hex_string(&self) -> String {
-        rgb_to_hex_string(&self.background_color)
-    }
+    /// The maximal width of the content (within the padding), so that lines don't stretch over
+    /// ultra-wide screens. The content is centered if it is narrower than the slide.
+    #[serde(default)]
+    pub max_content_width: Option<CssSize>,
+
+    /// Whether the font size of the main and spoiler content is reduced stepwise until
+    /// long slides fit on the screen
+    #[serde(default)]
+    pub auto_fit: bool,
+
+    /// The smallest scale (e.g. `0.5` for half of the font size) to which auto fit may reduce
+    /// the fonts. Slides which don't fit with it are cut off as before.
+    #[serde(default = "default_auto_fit_min_scale")]
+    pub auto_fit_min_scale: f64,
+
+    /// Deviations from this design which are used on the title slides of the chapters
+    #[serde(default)]
+    pub title_slide_overrides: Option<TitleSlideOverrides>,
+
+    /// An optional emphasis of the first letter or word of the titles on title slides
+    #[serde(default)]
+    pub title_emphasis: Option<TitleEmphasis>,
+
+    /// Whether the slide number and/or a progress bar are shown on the slides
+    #[serde(default)]
+    pub slide_counter: SlideCounterStyle,
+
+    /// The corner in which the slide number is shown
+    #[serde(default)]
+    pub slide_counter_corner: OverlayCorner,
+
+    /// Whether a small clock is shown on the slides
+    #[serde(default)]
+    pub show_clock: bool,
+
+    /// The corner in which the clock is shown
+    #[serde(default = "default_clock_corner")]
+    pub clock_corner: OverlayCorner,
+
+    /// An optional picture (e.g. the logo of the church) which is shown in a corner of every slide
+    #[serde(default)]
+    pub watermark: Option<Watermark>,
+
+    /// Whether the clock shows the time in the 24 or 12 hour format
+    #[serde(default)]
+    pub clock_format: ClockFormat,
+
+    /// Whether the meta information of songs (e.g. author and copyright) is shown in a footer
+    #[serde(default)]
+    pub show_meta_footer: bool,
+
+    /// On which slides of a song the meta footer is shown
+    #[serde(default)]
+    pub meta_footer_slides: MetaFooterSlides,
+
+    /// The aspect ratio of the screens for which the design is made
+    #[serde(default)]
+    pub aspect_ratio: AspectRatio,
+
+    /// The order of the text blocks on the slides and their own vertical alignments
+    #[serde(default)]
+    pub slide_layout: SlideLayout,
+}
+
+impl PresentationDesignTemplate {
+    /// Returns the background color as an RGB string which can be used in CSS
+    /// for example: pure black would equal to (0, 0, 0)
+    pub fn get_background_as_rgb_string(&self) -> String {
+        format!(
+            "{}, {}, {}",
+            self.background_color.r, self.background_color.g, self.background_color.b
+        )
+    }
+
+    /// Returns the background color with the alpha channel of its transparency
+    pub fn get_background_color_with_alpha(&self) -> RGBA8 {
+        let transparency = self.background_color_transparency.min(100) as f32 / 100.0;
+        RGBA8::new(
+            self.background_color.r,
+            self.background_color.g,
+            self.background_color.b,
+            ((1.0 - transparency) * 255.0).round() as u8,
+        )
+    }
+
+    /// Returns true if the background color is transparent and not covered by a gradient.
+    pub fn has_transparent_background(&self) -> bool {
+        self.background_color_transparency > 0 && self.background_gradient.is_none()
+    }
+
+    /// Returns the background color as a hexadecimal string
+    /// for example, pure black would equal to #000000
+    pub fn get_background_color_as_hex_string(&self) -> String {
+        rgb_to_hex_string(&self.background_color)
+    }
 
     /// Set the background color from a hex str if the hex string is valid.
     /// Returns `Ok(())` if the setting was successfully and `Err(())` if the validation of the string failed.
@@ -1477,6 +2247,65 @@ impl PresentationDesignTemplate {
         }
     }
 
+    /// Returns true if the background changes between several pictures during a presentation.
+    pub fn rotates_background(&self) -> bool {
+        self.background_rotation_images.len() > 1
+    }
+
+    /// Returns the background for the given chapter and slide (counted over all chapters).
+    /// Without rotating pictures, this is the background picture or video of the design.
+    pub fn background_at(&self, chapter: usize, slide_total: usize) -> Option<BackgroundMedia> {
+        if !self.rotates_background() {
+            return self.background_image.clone();
+        }
+        let index = match self.background_rotation {
+            BackgroundRotation::PerChapter => chapter,
+            BackgroundRotation::PerSlide => slide_total,
+        } % self.background_rotation_images.len();
+        self.background_rotation_images
+            .get(index)
+            .cloned()
+            .map(BackgroundMedia::Image)
+    }
+
+    /// Returns the design which is used on title slides, which is this design with its
+    /// [TitleSlideOverrides] applied.
+    pub fn for_title_slide(&self) -> PresentationDesignTemplate {
+        let mut template = self.clone();
+        let Some(ref overrides) = self.title_slide_overrides else {
+            return template;
+        };
+        if let Some(ref background) = overrides.background_image {
+            template.background_image = Some(background.clone());
+            // The background of the title slide doesn't take part in the rotation
+            template.background_rotation_images.clear();
+        }
+        if overrides.headline_index.is_some() {
+            template.set_headline_index(overrides.headline_index);
+        }
+        if let Some(ref vertical_alignment) = overrides.vertical_alignment {
+            template.vertical_alignment = vertical_alignment.clone();
+        }
+        template
+    }
+
+    /// Returns the next smaller font scale which auto fit tries if a slide doesn't fit with the
+    /// given scale, or `None` if the minimal scale has been reached already.
+    pub fn next_auto_fit_scale(&self, scale: f64) -> Option<f64> {
+        let min_scale = self.auto_fit_min_scale.clamp(AUTO_FIT_STEP, 1.0);
+        (scale > min_scale + f64::EPSILON).then(|| (scale - AUTO_FIT_STEP).max(min_scale))
+    }
+
+    /// Returns the main content and spoiler fonts with their sizes multiplied by the given scale.
+    /// Both fonts are scaled by the same factor, so that the spoiler keeps its size relative
+    /// to the main content.
+    pub fn scaled_content_fonts(&self, scale: f64) -> (FontRepresentation, FontRepresentation) {
+        (
+            self.get_default_font().scaled(scale),
+            self.get_default_spoiler_font().scaled(scale),
+        )
+    }
+
     pub fn headline_index(&self) -> Option<u16> {
         self.headline_index
     }
@@ -1511,6 +2340,143 @@ impl PresentationDesignTemplate {
         }
     }
 
+    /// Sets the meta index if it does exist.
+    /// If it does not exist, no change will occur.
+    pub fn set_meta_index(&mut self, meta_index: Option<u16>) {
+        match meta_index {
+            Some(index) => {
+                if (index as usize) < self.fonts.len() {
+                    self.meta_index = Some(index);
+                }
+            }
+            None => self.meta_index = None,
+        }
+    }
+
+    /// Sets the secondary language index if it does exist.
+    /// If it does not exist, no change will occur.
+    pub fn set_secondary_language_index(&mut self, secondary_language_index: Option<u16>) {
+        match secondary_language_index {
+            Some(index) => {
+                if (index as usize) < self.fonts.len() {
+                    self.secondary_language_index = Some(index);
+                }
+            }
+            None => self.secondary_language_index = None,
+        }
+    }
+
+    /// Adds, removes or moves a font or assigns a role to a font. The indices of the fonts for
+    /// the headline, spoiler and meta information (also of the title slides) are adjusted, so
+    /// that they keep referring to the same fonts. The first font can't be removed, because it
+    /// is the main font.
+    pub fn change_fonts(&mut self, change: FontListChange) {
+        match change {
+            FontListChange::Add => self.fonts.push(FontRepresentation::default()),
+            FontListChange::Remove(index) => {
+                if index == 0 || index >= self.fonts.len() {
+                    return;
+                }
+                self.fonts.remove(index);
+                let removed = index as u16;
+                self.remap_font_indices(|i| match i.cmp(&removed) {
+                    std::cmp::Ordering::Less => Some(i),
+                    std::cmp::Ordering::Equal => None,
+                    std::cmp::Ordering::Greater => Some(i - 1),
+                });
+            }
+            FontListChange::Move { from, to } => {
+                if from >= self.fonts.len() || to >= self.fonts.len() || from == to {
+                    return;
+                }
+                let font = self.fonts.remove(from);
+                self.fonts.insert(to, font);
+                let (from, to) = (from as u16, to as u16);
+                self.remap_font_indices(|i| {
+                    Some(if i == from {
+                        to
+                    } else if from < to && i > from && i <= to {
+                        i - 1
+                    } else if to < from && i >= to && i < from {
+                        i + 1
+                    } else {
+                        i
+                    })
+                });
+            }
+            FontListChange::SetRole(role, index) => match role {
+                FontRole::Headline => self.set_headline_index(index),
+                FontRole::Spoiler => self.set_spoiler_index(index),
+                FontRole::Meta => self.set_meta_index(index),
+                FontRole::SecondaryLanguage => self.set_secondary_language_index(index),
+            },
+        }
+    }
+
+    /// Changes all indices which refer to fonts with the given mapping.
+    fn remap_font_indices(&mut self, map: impl Fn(u16) -> Option<u16>) {
+        self.headline_index = self.headline_index.and_then(&map);
+        self.spoiler_index = self.spoiler_index.and_then(&map);
+        self.meta_index = self.meta_index.and_then(&map);
+        self.secondary_language_index = self.secondary_language_index.and_then(&map);
+        if let Some(ref mut overrides) = self.title_slide_overrides {
+            overrides.headline_index = overrides.headline_index.and_then(&map);
+        }
+    }
+
+    /// Sets the background color and the colors of all fonts to the colors of a preset. A
+    /// background gradient is removed, so that the background color is shown. Shadows and
+    /// outlines get the background color (with their opacity), so that the text stands out.
+    pub fn apply_color_preset(&mut self, preset: ColorPreset) {
+        let colors = preset.colors();
+        let background = colors.background;
+        self.background_color = background;
+        self.background_gradient = None;
+
+        let spoiler_index = self.spoiler_index.map(usize::from);
+        let meta_index = self.meta_index.map(usize::from);
+        for (idx, font) in self.fonts.iter_mut().enumerate() {
+            let color = if idx == 0 {
+                colors.main
+            } else if Some(idx) == spoiler_index {
+                colors.spoiler
+            } else if Some(idx) == meta_index {
+                colors.meta
+            } else {
+                colors.main
+            };
+            font.color = color;
+            font.shadow_color = RGBA8::new(
+                background.r,
+                background.g,
+                background.b,
+                font.shadow_color.a,
+            );
+            if let Some(ref mut outline) = font.outline {
+                outline.color =
+                    RGBA8::new(background.r, background.g, background.b, outline.color.a);
+            }
+        }
+    }
+
+    /// Inverts the lightness of the background (including a gradient) and of all font colors,
+    /// e.g. to turn a dark design into a light one. The hues and the opacities stay the same.
+    pub fn invert_colors(&mut self) {
+        self.background_color = self.background_color.invert_lightness();
+        if let Some(ref mut gradient) = self.background_gradient {
+            for stop in gradient.color_stops.iter_mut() {
+                stop.color = stop.color.invert_lightness();
+            }
+        }
+        for font in self.fonts.iter_mut() {
+            font.color = font.color.invert_lightness();
+            font.shadow_color = font.shadow_color.invert_lightness();
+            if let Some(ref mut outline) = font.outline {
+                outline.color = outline.color.invert_lightness();
+            }
+        }
+    }
+
     /// Gets the default [FontRepresentation] (the first element of the `fonts` vector or the configured default
     /// font as a fallback
     pub fn get_default_font(&self) -> FontRepresentation {
@@ -1521,14 +2487,31 @@ impl PresentationDesignTemplate {
     }
 
     /// Gets the default font [FontRepresentation] for the spoiler part.
-    /// If none is defined, the system default will be returned as a fallback.
+    /// If none is defined, the main content font is used with its size multiplied by the
+    /// `spoiler_content_fontsize_factor`.
     pub fn get_default_spoiler_font(&self) -> FontRepresentation {
-        match self.spoiler_index {
-            Some(spoiler_index) => match self.fonts.get(spoiler_index as usize) {
-                Some(font) => font.clone(),
-                None => FontRepresentation::default_spoiler(),
-            },
-            None => FontRepresentation::default_spoiler(),
+        match self
+            .spoiler_index
+            .and_then(|spoiler_index| self.fonts.get(spoiler_index as usize))
+        {
+            Some(font) => font.clone(),
+            None => self
+                .get_default_font()
+                .scaled(self.spoiler_content_fontsize_factor.clamp(0.1, 1.0)),
+        }
+    }
+
+    /// Gets the font [FontRepresentation] for the secondary languages of multi-language songs.
+    /// If none is defined, the main content font is used with a smaller size.
+    pub fn get_default_secondary_language_font(&self) -> FontRepresentation {
+        match self
+            .secondary_language_index
+            .and_then(|index| self.fonts.get(index as usize))
+        {
+            Some(font) => font.clone(),
+            None => self
+                .get_default_font()
+                .scaled(SECONDARY_LANGUAGE_FONTSIZE_FACTOR),
         }
     }
 
@@ -1568,13 +2551,586 @@ impl Default for PresentationDesignTemplate {
             headline_index: Some(0),
             spoiler_index: Some(1),
             meta_index: Some(2),
+            secondary_language_index: None,
             vertical_alignment: VerticalAlign::default(),
             spoiler_content_fontsize_factor: 0.6,
             background_color: Rgb::new(0, 0, 0),
             background_transparency: 0,
+            background_color_transparency: 0,
             padding: default_padding(),
             background_image: None,
+            pause_background_video_on_still_slides: default_pause_background_video_on_still_slides(),
+            background_rotation_images: vec![],
+            background_rotation: BackgroundRotation::default(),
+            background_gradient: None,
+            background_fit: BackgroundFit::default(),
+            background_position: BackgroundPosition::default(),
+            background_blur: None,
             main_content_spoiler_content_padding: CssSize::Px(20.0),
+            spoiler_max_lines: None,
+            max_content_width: None,
+            auto_fit: false,
+            auto_fit_min_scale: default_auto_fit_min_scale(),
+            title_slide_overrides: None,
+            title_emphasis: None,
+            slide_counter: SlideCounterStyle::default(),
+            slide_counter_corner: OverlayCorner::default(),
+            show_clock: false,
+            clock_corner: default_clock_corner(),
+            watermark: None,
+            clock_format: ClockFormat::default(),
+            show_meta_footer: false,
+            meta_footer_slides: MetaFooterSlides::default(),
+            aspect_ratio: AspectRatio::default(),
+            slide_layout: SlideLayout::default(),
+        }
+    }
+}
+
+fn default_pause_background_video_on_still_slides() -> bool {
+    true
+}
+
+fn default_auto_fit_min_scale() -> f64 {
+    0.5
+}
+
+/// The settings of a design which differ on title slides. Settings which are not given are
+/// taken from the design.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct TitleSlideOverrides {
+    /// A background picture or video instead of the background of the design
+    #[serde(default)]
+    pub background_image: Option<BackgroundMedia>,
+
+    /// The index of the font which is used for the title instead of the headline font
+    #[serde(default)]
+    pub headline_index: Option<u16>,
+
+    /// The vertical alignment of the title instead of the alignment of the design
+    #[serde(default)]
+    pub vertical_alignment: Option<VerticalAlign>,
+}
+
+/// The part of a title which is emphasized, see [TitleEmphasis].
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum TitleEmphasisUnit {
+    /// The first letter, like a drop cap (default)
+    #[default]
+    FirstLetter,
+    /// The whole first word
+    FirstWord,
+}
+
+/// The emphasis of the beginning of the titles on title slides, which are shown bigger and
+/// optionally in another color.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct TitleEmphasis {
+    /// The emphasized part of the title
+    #[serde(default)]
+    pub unit: TitleEmphasisUnit,
+
+    /// The size of the emphasized part relative to the rest of the title (e.g. `1.5`)
+    #[serde(default = "default_title_emphasis_scale")]
+    pub scale: f64,
+
+    /// The color of the emphasized part. Without a color, the color of the title is used.
+    #[serde(default)]
+    pub color: Option<RGBA8>,
+}
+
+impl Default for TitleEmphasis {
+    fn default() -> Self {
+        TitleEmphasis {
+            unit: TitleEmphasisUnit::default(),
+            scale: default_title_emphasis_scale(),
+            color: None,
+        }
+    }
+}
+
+fn default_title_emphasis_scale() -> f64 {
+    1.5
+}
+
+impl TitleEmphasis {
+    /// Splits a title into the emphasized part and the rest. Leading whitespace is removed.
+    /// Returns `None` if the title consists only of the emphasized part (or less), so that
+    /// short titles are shown unchanged.
+    pub fn split_title(&self, title: &str) -> Option<(String, String)> {
+        let title = title.trim_start();
+        let split_index = match self.unit {
+            TitleEmphasisUnit::FirstLetter => title.chars().next()?.len_utf8(),
+            TitleEmphasisUnit::FirstWord => title.find(char::is_whitespace)?,
+        };
+        let (emphasized, rest) = title.split_at(split_index);
+        if rest.trim().is_empty() {
+            return None;
+        }
+        Some((emphasized.to_string(), rest.to_string()))
+    }
+}
+
+/// The amount by which auto fit reduces the font scale in every step
+pub const AUTO_FIT_STEP: f64 = 0.05;
+
+/// The factor for the font size of the secondary languages relative to the main content font
+/// size, if no font has been assigned to them
+pub const SECONDARY_LANGUAGE_FONTSIZE_FACTOR: f64 = 0.8;
+
+/// A block of text on the slides whose position can be changed in the [SlideLayout].
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum SlideBlock {
+    /// The title on the title slides
+    Headline,
+    /// The main content of the song slides
+    MainContent,
+    /// The spoiler (the beginning of the next slide) below or above the main content
+    Spoiler,
+    /// The meta footer (e.g. author and copyright)
+    Meta,
+}
+
+impl SlideBlock {
+    /// All blocks in the order of the default layout
+    pub const ALL: [SlideBlock; 4] = [
+        SlideBlock::Headline,
+        SlideBlock::MainContent,
+        SlideBlock::Spoiler,
+        SlideBlock::Meta,
+    ];
+
+    /// Returns true if the block can have its own vertical alignment. The meta footer is placed
+    /// above or below the content by its position in the order instead.
+    pub fn can_be_aligned(&self) -> bool {
+        *self != SlideBlock::Meta
+    }
+}
+
+/// A block of text together with its own vertical alignment within the slide
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SlideBlockLayout {
+    pub block: SlideBlock,
+
+    /// The vertical alignment of the block. If not given, the block follows the vertical
+    /// alignment of the design together with the other blocks.
+    #[serde(default)]
+    pub vertical_alignment: Option<VerticalAlign>,
+}
+
+/// The order of the text blocks on the slides and their own vertical alignments.
+/// The default layout shows the blocks in the order of [SlideBlock::ALL], all following the
+/// vertical alignment of the design.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SlideLayout {
+    blocks: Vec<SlideBlockLayout>,
+}
+
+impl Default for SlideLayout {
+    fn default() -> Self {
+        SlideLayout {
+            blocks: SlideBlock::ALL
+                .iter()
+                .map(|block| SlideBlockLayout {
+                    block: *block,
+                    vertical_alignment: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl SlideLayout {
+    /// Returns the blocks in their order. Duplicate blocks are left out and missing blocks
+    /// (e.g. from a changed settings file) are appended in their default order.
+    pub fn blocks(&self) -> Vec<SlideBlockLayout> {
+        let mut blocks: Vec<SlideBlockLayout> = vec![];
+        for block_layout in self.blocks.iter().chain(SlideLayout::default().blocks.iter()) {
+            if !blocks.iter().any(|existing| existing.block == block_layout.block) {
+                blocks.push(block_layout.clone());
+            }
+        }
+        blocks
+    }
+
+    /// Returns the position of a block in the order, starting with 0.
+    pub fn position(&self, block: SlideBlock) -> usize {
+        self.blocks()
+            .iter()
+            .position(|block_layout| block_layout.block == block)
+            .unwrap_or_default()
+    }
+
+    /// Returns the own vertical alignment of a block, if it has one.
+    pub fn vertical_alignment(&self, block: SlideBlock) -> Option<VerticalAlign> {
+        self.blocks()
+            .into_iter()
+            .find(|block_layout| block_layout.block == block)
+            .and_then(|block_layout| block_layout.vertical_alignment)
+            .filter(|_| block.can_be_aligned())
+    }
+
+    /// Sets the own vertical alignment of a block, or removes it with `None`.
+    pub fn set_vertical_alignment(&mut self, block: SlideBlock, alignment: Option<VerticalAlign>) {
+        let mut blocks = self.blocks();
+        if let Some(block_layout) = blocks.iter_mut().find(|block_layout| block_layout.block == block) {
+            block_layout.vertical_alignment = alignment;
+        }
+        self.blocks = blocks;
+    }
+
+    /// Moves the block at position `from` to position `to`. Invalid positions are ignored.
+    pub fn move_block(&mut self, from: usize, to: usize) {
+        let mut blocks = self.blocks();
+        if from >= blocks.len() || to >= blocks.len() {
+            return;
+        }
+        let block_layout = blocks.remove(from);
+        blocks.insert(to, block_layout);
+        self.blocks = blocks;
+    }
+
+    /// Returns true if any block has its own vertical alignment.
+    pub fn has_block_alignments(&self) -> bool {
+        SlideBlock::ALL
+            .iter()
+            .any(|block| self.vertical_alignment(*block).is_some())
+    }
+
+    /// Returns true if the meta footer is placed above the main content.
+    pub fn meta_above_content(&self) -> bool {
+        self.position(SlideBlock::Meta) < self.position(SlideBlock::MainContent)
+    }
+
+    /// Returns true if the layout differs from the default one.
+    pub fn is_customized(&self) -> bool {
+        self.blocks() != SlideLayout::default().blocks
+    }
+}
+
+/// The roles which a font of a design template can have next to being the main font.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FontRole {
+    Headline,
+    Spoiler,
+    Meta,
+    SecondaryLanguage,
+}
+
+/// A change of the fonts of a design template, see [PresentationDesignTemplate::change_fonts].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FontListChange {
+    /// Adds a new font at the end
+    Add,
+    /// Removes the font with the given index
+    Remove(usize),
+    /// Moves a font to another position
+    Move { from: usize, to: usize },
+    /// Assigns a role to the font with the given index, or to no font
+    SetRole(FontRole, Option<u16>),
+}
+
+/// A color scheme which sets the background and all font colors of a design at once, see
+/// [PresentationDesignTemplate::apply_color_preset].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ColorPreset {
+    /// Light text on a black background
+    Dark,
+    /// Dark text on a white background
+    Light,
+}
+
+/// The colors of a [ColorPreset]
+pub struct ColorPresetColors {
+    pub background: RGB8,
+    pub main: RGBA8,
+    pub spoiler: RGBA8,
+    pub meta: RGBA8,
+}
+
+impl ColorPreset {
+    /// Returns the background color and the font colors of the preset. The spoiler and the meta
+    /// information are a bit less prominent than the main content.
+    pub fn colors(&self) -> ColorPresetColors {
+        match self {
+            ColorPreset::Dark => ColorPresetColors {
+                background: RGB8::new(0, 0, 0),
+                main: RGBA8::new(255, 255, 255, 255),
+                spoiler: RGBA8::new(190, 190, 190, 255),
+                meta: RGBA8::new(160, 160, 160, 255),
+            },
+            ColorPreset::Light => ColorPresetColors {
+                background: RGB8::new(255, 255, 255),
+                main: RGBA8::new(20, 20, 20, 255),
+                spoiler: RGBA8::new(80, 80, 80, 255),
+                meta: RGBA8::new(110, 110, 110, 255),
+            },
+        }
+    }
+}
+
+/// Determines when the next of several rotating background pictures is shown.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum BackgroundRotation {
+    /// Every chapter (e.g. every song) gets the next picture.
+    #[default]
+    PerChapter,
+    /// Every slide gets the next picture.
+    PerSlide,
+}
+
+/// The aspect ratio of the screens for which a design is made. On screens with a different
+/// aspect ratio, the slides are letterboxed.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum AspectRatio {
+    /// 16:9, the format of most current screens and projectors (default)
+    #[default]
+    Ratio16x9,
+    /// 4:3, the format of older projectors
+    Ratio4x3,
+    /// 16:10, the format of many notebook screens
+    Ratio16x10,
+    /// Any other aspect ratio
+    Custom { width: u32, height: u32 },
+}
+
+impl AspectRatio {
+    /// Returns the aspect ratio as (width, height). Invalid custom ratios are treated as 16:9.
+    pub fn ratio(&self) -> (u32, u32) {
+        match *self {
+            AspectRatio::Ratio16x9 => (16, 9),
+            AspectRatio::Ratio4x3 => (4, 3),
+            AspectRatio::Ratio16x10 => (16, 10),
+            AspectRatio::Custom { width, height } if width > 0 && height > 0 => (width, height),
+            AspectRatio::Custom { .. } => (16, 9),
+        }
+    }
+
+    /// Returns a resolution with the given width and this aspect ratio.
+    pub fn resolution(&self, width: u32) -> (u32, u32) {
+        let (ratio_w, ratio_h) = self.ratio();
+        (width, (width as u64 * ratio_h as u64 / ratio_w as u64) as u32)
+    }
+}
+
+/// Determines how a background picture or video fills the slide.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum BackgroundFit {
+    /// The background covers the whole slide and is cropped if necessary (default).
+    #[default]
+    Cover,
+    /// The whole background is shown, the remaining space shows the background color.
+    Contain,
+    /// The background is stretched to the size of the slide.
+    Stretch,
+    /// The picture is repeated in its original size. Videos are shown like with [BackgroundFit::Cover].
+    Tile,
+}
+
+impl BackgroundFit {
+    /// Returns the value of the CSS property `background-size` for a background picture.
+    pub fn background_size(&self) -> &'static str {
+        match self {
+            BackgroundFit::Cover => "cover",
+            BackgroundFit::Contain => "contain",
+            BackgroundFit::Stretch => "100% 100%",
+            BackgroundFit::Tile => "auto",
+        }
+    }
+
+    /// Returns the value of the CSS property `background-repeat` for a background picture.
+    pub fn background_repeat(&self) -> &'static str {
+        match self {
+            BackgroundFit::Tile => "repeat",
+            _ => "no-repeat",
+        }
+    }
+
+    /// Returns the value of the CSS property `object-fit` for a background video.
+    pub fn object_fit(&self) -> &'static str {
+        match self {
+            BackgroundFit::Cover | BackgroundFit::Tile => "cover",
+            BackgroundFit::Contain => "contain",
+            BackgroundFit::Stretch => "fill",
+        }
+    }
+}
+
+/// The point of the background which stays visible if it is cropped, on a 3×3 grid.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum BackgroundPosition {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl BackgroundPosition {
+    /// All positions row by row, as they are arranged in the grid.
+    pub const ALL: [BackgroundPosition; 9] = [
+        BackgroundPosition::TopLeft,
+        BackgroundPosition::Top,
+        BackgroundPosition::TopRight,
+        BackgroundPosition::Left,
+        BackgroundPosition::Center,
+        BackgroundPosition::Right,
+        BackgroundPosition::BottomLeft,
+        BackgroundPosition::Bottom,
+        BackgroundPosition::BottomRight,
+    ];
+
+    /// Returns the value of the CSS properties `background-position` and `object-position`.
+    pub fn to_css_string(&self) -> &'static str {
+        match self {
+            BackgroundPosition::TopLeft => "left top",
+            BackgroundPosition::Top => "center top",
+            BackgroundPosition::TopRight => "right top",
+            BackgroundPosition::Left => "left center",
+            BackgroundPosition::Center => "center",
+            BackgroundPosition::Right => "right center",
+            BackgroundPosition::BottomLeft => "left bottom",
+            BackgroundPosition::Bottom => "center bottom",
+            BackgroundPosition::BottomRight => "right bottom",
+        }
+    }
+}
+
+/// A picture or video which is shown in the background of a presentation.
+/// It is stored as its source file, so that designs which have been saved with a background
+/// picture before videos were supported can still be loaded.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(try_from = "SourceFile", into = "SourceFile")]
+pub enum BackgroundMedia {
+    Image(ImageSourceFile),
+    Video(VideoSourceFile),
+}
+
+impl BackgroundMedia {
+    /// Returns the source file of the picture or video.
+    pub fn as_source(&self) -> &SourceFile {
+        match self {
+            BackgroundMedia::Image(image) => image.as_source(),
+            BackgroundMedia::Video(video) => video.as_source(),
+        }
+    }
+}
+
+impl TryFrom<SourceFile> for BackgroundMedia {
+    type Error = String;
+
+    fn try_from(source_file: SourceFile) -> Result<Self, Self::Error> {
+        match source_file.file_type {
+            SourceFileType::Image => ImageSourceFile::new(source_file).map(BackgroundMedia::Image),
+            SourceFileType::Video => VideoSourceFile::new(source_file).map(BackgroundMedia::Video),
+            _ => None,
+        }
+        .ok_or_else(|| "A background must be a picture or a video".to_string())
+    }
+}
+
+impl From<BackgroundMedia> for SourceFile {
+    fn from(background_media: BackgroundMedia) -> Self {
+        match background_media {
+            BackgroundMedia::Image(image) => image.into_inner(),
+            BackgroundMedia::Video(video) => video.into_inner(),
+        }
+    }
+}
+
+/// The shape of a background gradient.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum GradientKind {
+    /// The colors change along a line with the angle of the gradient.
+    #[default]
+    Linear,
+    /// The colors change from the center to the edges.
+    Radial,
+}
+
+/// A color of a gradient at a position between 0 and 100 percent.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct GradientColorStop {
+    pub color: RGB8,
+    pub position: u8,
+}
+
+/// A gradient with at least two colors which can be used as background of a presentation.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct BackgroundGradient {
+    pub kind: GradientKind,
+    /// The direction of a linear gradient in degrees (0 is upwards, 90 to the right)
+    pub angle: u16,
+    pub color_stops: Vec<GradientColorStop>,
+}
+
+impl BackgroundGradient {
+    /// Adds a color stop in the middle between the last two stops.
+    pub fn add_color_stop(&mut self) {
+        let last = self.color_stops.last().copied().unwrap_or(GradientColorStop {
+            color: RGB8::new(0, 0, 0),
+            position: 100,
+        });
+        let previous_position = self
+            .color_stops
+            .iter()
+            .rev()
+            .nth(1)
+            .map(|stop| stop.position)
+            .unwrap_or(0);
+        let index = self.color_stops.len().saturating_sub(1);
+        self.color_stops.insert(
+            index,
+            GradientColorStop {
+                color: last.color,
+                position: ((previous_position as u16 + last.position as u16) / 2) as u8,
+            },
+        );
+    }
+
+    /// Removes a color stop if the gradient keeps at least two of them.
+    pub fn remove_color_stop(&mut self, index: usize) {
+        if self.color_stops.len() > 2 && index < self.color_stops.len() {
+            self.color_stops.remove(index);
+        }
+    }
+}
+
+impl Default for BackgroundGradient {
+    fn default() -> Self {
+        BackgroundGradient {
+            kind: GradientKind::default(),
+            angle: 180,
+            color_stops: vec![
+                GradientColorStop {
+                    color: RGB8::new(0, 0, 0),
+                    position: 0,
+                },
+                GradientColorStop {
+                    color: RGB8::new(40, 60, 110),
+                    position: 100,
+                },
+            ],
+        }
+    }
+}
+
+impl CssString for BackgroundGradient {
+    fn to_css_string(&self) -> String {
+        let color_stops = self
+            .color_stops
+            .iter()
+            .map(|stop| format!("{} {}%", rgb_to_hex_string(&stop.color), stop.position))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self.kind {
+            GradientKind::Linear => format!("linear-gradient({}deg, {})", self.angle, color_stops),
+            GradientKind::Radial => format!("radial-gradient(circle, {})", color_stops),
         }
     }
 }
@@ -1591,6 +3147,26 @@ pub struct FontRepresentation {
     /// Whether to show a shadow around the font
     pub shadow: bool,
 
+    /// The color of the shadow
+    #[serde(default = "default_shadow_color")]
+    pub shadow_color: RGBA8,
+
+    /// The blur radius of the shadow. The default is given in `em`, so that it fits the font size.
+    #[serde(default = "default_shadow_blur")]
+    pub shadow_blur: CssSize,
+
+    /// An optional outline around the letters, e.g. for light text on bright pictures
+    #[serde(default)]
+    pub outline: Option<FontOutline>,
+
+    /// The additional space between the letters, if any
+    #[serde(default)]
+    pub letter_spacing: Option<CssSize>,
+
+    /// Whether the text is shown in upper or lower case, if set
+    #[serde(default)]
+    pub text_transform: Option<TextTransform>,
+
     /// The height of the line (distance above and below)
     pub line_height: f64,
 
@@ -1601,6 +3177,21 @@ pub struct FontRepresentation {
     pub horizontal_alignment: HorizontalAlign,
 }
 
+/// The smallest line height which can be set for a font
+pub const LINE_HEIGHT_MIN: f64 = 0.8;
+
+/// The largest line height which can be set for a font
+pub const LINE_HEIGHT_MAX: f64 = 3.0;
+
+/// Limits a line height to the range from [LINE_HEIGHT_MIN] to [LINE_HEIGHT_MAX] and rounds it
+/// to one decimal place.
+pub fn clamp_line_height(line_height: f64) -> f64 {
+    if line_height.is_nan() {
+        return FontRepresentation::default().line_height;
+    }
+    (line_height.clamp(LINE_HEIGHT_MIN, LINE_HEIGHT_MAX) * 10.0).round() / 10.0
+}
+
 impl FontRepresentation {
     pub fn get_color_as_rgba_string(&self) -> String {
         format!(
@@ -1617,6 +3208,17 @@ impl FontRepresentation {
         default
     }
 
+    /// Returns the font with its size multiplied by the given factor.
+    pub fn scaled(&self, factor: f64) -> Self {
+        let mut scaled = self.clone();
+        if !scaled.font_size.is_null() {
+            scaled
+                .font_size
+                .set_float((scaled.font_size.get_float() as f64 * factor) as f32);
+        }
+        scaled
+    }
+
     fn default_meta() -> FontRepresentation {
         let mut default = Self::default();
         default
@@ -1632,6 +3234,11 @@ impl Default for FontRepresentation {
             font_family: None,
             font_size: CssSize::Pt(32.0),
             shadow: false,
+            shadow_color: default_shadow_color(),
+            shadow_blur: default_shadow_blur(),
+            outline: None,
+            letter_spacing: None,
+            text_transform: None,
             line_height: 1.2,
             color: Rgba::new(255, 255, 255, 255),
             horizontal_alignment: HorizontalAlign::default(),
@@ -1639,6 +3246,33 @@ impl Default for FontRepresentation {
     }
 }
 
+/// An outline (stroke) around the letters of a font
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct FontOutline {
+    /// The width of the outline
+    pub width: CssSize,
+
+    /// The color of the outline
+    pub color: RGBA8,
+}
+
+impl Default for FontOutline {
+    fn default() -> Self {
+        FontOutline {
+            width: CssSize::Px(2.0),
+            color: Rgba::new(0, 0, 0, 255),
+        }
+    }
+}
+
+fn default_shadow_color() -> RGBA8 {
+    Rgba::new(0, 0, 0, 255)
+}
+
+fn default_shadow_blur() -> CssSize {
+    CssSize::Em(0.1)
+}
+
 /// The horizontal alignment of a block
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Default)]
 pub enum HorizontalAlign {
@@ -1668,7 +3302,160 @@ impl CssString for HorizontalAlign {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+/// Changes the case of a text, e.g. for titles in capital letters.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Default)]
+pub enum TextTransform {
+    /// The text is shown as it is written
+    #[default]
+    None,
+    Uppercase,
+    Lowercase,
+    /// The first letter of every word is a capital letter
+    Capitalize,
+}
+
+impl CssString for TextTransform {
+    fn to_css_string(&self) -> String {
+        match self {
+            TextTransform::None => "none".to_string(),
+            TextTransform::Uppercase => "uppercase".to_string(),
+            TextTransform::Lowercase => "lowercase".to_string(),
+            TextTransform::Capitalize => "capitalize".to_string(),
+        }
+    }
+}
+
+/// Specifies how the progress of a presentation is shown on its slides.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum SlideCounterStyle {
+    /// Neither the slide number nor a progress bar is shown (default).
+    #[default]
+    Hidden,
+    /// The slide number is shown, e.g. "4 / 23".
+    Number,
+    /// A thin progress bar is shown along the bottom edge.
+    ProgressBar,
+    /// Both the slide number and the progress bar are shown.
+    NumberAndProgressBar,
+}
+
+impl SlideCounterStyle {
+    pub fn shows_number(&self) -> bool {
+        matches!(self, SlideCounterStyle::Number | SlideCounterStyle::NumberAndProgressBar)
+    }
+
+    pub fn shows_progress_bar(&self) -> bool {
+        matches!(self, SlideCounterStyle::ProgressBar | SlideCounterStyle::NumberAndProgressBar)
+    }
+}
+
+/// Specifies on which slides of a song the meta information is shown in a footer.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum MetaFooterSlides {
+    /// Only on the first slide of the song, e.g. the title slide (default)
+    #[default]
+    FirstSlide,
+    /// On every slide of the song
+    EverySlide,
+    /// Only on the last slide of the song
+    LastSlide,
+}
+
+impl MetaFooterSlides {
+    /// Returns whether the footer is shown on the slide with the given index in a chapter
+    /// with the given number of slides.
+    pub fn shows_on(&self, chapter_slide: usize, slide_count: usize) -> bool {
+        match self {
+            MetaFooterSlides::FirstSlide => chapter_slide == 0,
+            MetaFooterSlides::EverySlide => true,
+            MetaFooterSlides::LastSlide => chapter_slide + 1 == slide_count,
+        }
+    }
+}
+
+/// The corner of a slide in which an overlay (e.g. the slide number) is shown.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum OverlayCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// A small picture which is shown in a corner of every slide, above the background and below
+/// the text. It lies within the padding of the design.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Watermark {
+    /// The picture, e.g. the logo of the church
+    pub image: ImageSourceFile,
+
+    /// The corner in which the picture is shown
+    #[serde(default)]
+    pub corner: OverlayCorner,
+
+    /// The width of the picture. The height follows from the aspect ratio of the picture.
+    #[serde(default = "default_watermark_size")]
+    pub size: CssSize,
+
+    /// The opacity of the picture in percent (0-100)
+    #[serde(default = "default_watermark_opacity")]
+    pub opacity: u8,
+}
+
+impl Watermark {
+    /// Creates a watermark with the given picture, which is shown in the default corner and size.
+    pub fn new(image: ImageSourceFile) -> Self {
+        Watermark {
+            image,
+            corner: OverlayCorner::default(),
+            size: default_watermark_size(),
+            opacity: default_watermark_opacity(),
+        }
+    }
+}
+
+fn default_watermark_size() -> CssSize {
+    CssSize::Em(4.0)
+}
+
+fn default_watermark_opacity() -> u8 {
+    80
+}
+
+/// The clock is placed opposite of the slide number by default.
+fn default_clock_corner() -> OverlayCorner {
+    OverlayCorner::TopRight
+}
+
+/// The format in which the clock overlay shows the time.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum ClockFormat {
+    /// E.g. "14:05"
+    #[default]
+    TwentyFourHours,
+    /// E.g. "2:05 PM"
+    TwelveHours,
+}
+
+impl ClockFormat {
+    /// Formats the given time of the day (hours from 0 to 23) in this format.
+    pub fn format(&self, hours: u32, minutes: u32) -> String {
+        match self {
+            ClockFormat::TwentyFourHours => format!("{:02}:{:02}", hours, minutes),
+            ClockFormat::TwelveHours => {
+                let suffix = if hours < 12 { "AM" } else { "PM" };
+                let hours = match hours % 12 {
+                    0 => 12,
+                    hours => hours,
+                };
+                format!("{}:{:02} {}", hours, minutes, suffix)
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default, Debug)]
 pub enum VerticalAlign {
     Top,
 
@@ -1808,6 +3595,498 @@ fn hex_string_to_rgb(hex_string: &str) -> Option<RGB8> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logic::test_helpers::without_fields;
+
+    #[test]
+    fn designs_from_the_gallery_are_copies() {
+        let texts = |design: GalleryDesign| (format!("{:?}", design), String::new());
+        let mut gallery = design_gallery(texts);
+        assert_eq!(gallery.len(), 4);
+        gallery[0].name = "Changed".to_string();
+        assert_eq!(design_gallery(texts)[0].name, "Dark");
+        assert_eq!(design_gallery(texts)[2].name, "WarmGradient");
+        assert!(
+            design_gallery(texts)
+                .iter()
+                .all(|design| matches!(
+                    design.presentation_design_settings,
+                    PresentationDesignSettings::Template(_)
+                ))
+        );
+    }
+
+    #[test]
+    fn title_slide_overrides_replace_only_the_given_settings() {
+        let mut template = PresentationDesignTemplate::default();
+        template.fonts.push(FontRepresentation::default());
+        assert!(template.for_title_slide() == template);
+
+        template.title_slide_overrides = Some(TitleSlideOverrides {
+            background_image: None,
+            headline_index: Some(3),
+            vertical_alignment: Some(VerticalAlign::Top),
+        });
+        let title_template = template.for_title_slide();
+        assert_eq!(title_template.headline_index(), Some(3));
+        assert!(title_template.vertical_alignment == VerticalAlign::Top);
+        assert!(title_template.background_image.is_none());
+        assert!(title_template.padding == template.padding);
+
+        // Fonts which don't exist are ignored
+        template.title_slide_overrides = Some(TitleSlideOverrides {
+            headline_index: Some(7),
+            ..Default::default()
+        });
+        assert_eq!(template.for_title_slide().headline_index(), Some(0));
+    }
+
+    #[test]
+    fn auto_fit_scales_down_stepwise_to_the_minimum() {
+        let mut template = PresentationDesignTemplate::default();
+        template.auto_fit_min_scale = 0.82;
+
+        let mut scales = vec![1.0];
+        while let Some(scale) = template.next_auto_fit_scale(*scales.last().unwrap()) {
+            scales.push(scale);
+        }
+        assert_eq!(scales.len(), 5);
+        assert!((scales[1] - 0.95).abs() < 1e-9);
+        assert!((scales[4] - 0.82).abs() < 1e-9);
+
+        let (main_font, spoiler_font) = template.scaled_content_fonts(0.5);
+        assert_eq!(main_font.font_size, CssSize::Pt(16.0));
+        assert_eq!(
+            spoiler_font.font_size.get_float(),
+            FontRepresentation::default_spoiler().font_size.get_float() * 0.5
+        );
+    }
+
+    #[test]
+    fn spoiler_font_is_derived_from_the_main_font_without_a_spoiler_font() {
+        let mut template = PresentationDesignTemplate::default();
+        assert!(template.get_default_spoiler_font() == template.fonts[1]);
+
+        template.spoiler_index = None;
+        template.spoiler_content_fontsize_factor = 0.5;
+        let spoiler_font = template.get_default_spoiler_font();
+        assert_eq!(spoiler_font.font_size, CssSize::Pt(16.0));
+        assert!(spoiler_font.color == template.get_default_font().color);
+    }
+
+    #[test]
+    fn background_pictures_rotate_per_chapter_or_slide() {
+        let picture = |name: &str| {
+            ImageSourceFile::new(SourceFile {
+                name: name.to_string(),
+                path: PathBuf::from(format!("pictures/{}.jpg", name)),
+                file_type: SourceFileType::Image,
+                md5_hash: None,
+            })
+            .unwrap()
+        };
+        let background_name = |template: &PresentationDesignTemplate, chapter, slide| {
+            template
+                .background_at(chapter, slide)
+                .map(|media| media.as_source().name.clone())
+        };
+
+        // A single picture is shown on every slide as before
+        let mut template = PresentationDesignTemplate::default();
+        template.background_image = Some(BackgroundMedia::Image(picture("Single")));
+        template.background_rotation_images = vec![picture("Only")];
+        assert_eq!(background_name(&template, 3, 7), Some("Single".to_string()));
+
+        template.background_rotation_images = vec![picture("A"), picture("B"), picture("C")];
+        assert_eq!(background_name(&template, 0, 0), Some("A".to_string()));
+        assert_eq!(background_name(&template, 4, 9), Some("B".to_string()));
+
+        template.background_rotation = BackgroundRotation::PerSlide;
+        assert_eq!(background_name(&template, 4, 8), Some("C".to_string()));
+    }
+
+    #[test]
+    fn background_media_is_stored_as_source_file() {
+        let picture = SourceFile {
+            name: "Sunrise".to_string(),
+            path: PathBuf::from("pictures/sunrise.jpg"),
+            file_type: SourceFileType::Image,
+            md5_hash: None,
+        };
+        let mut template = PresentationDesignTemplate::default();
+        template.background_image = Some(BackgroundMedia::try_from(picture.clone()).unwrap());
+
+        // Designs which have been saved with a picture keep it
+        let json = serde_json::to_value(&template).unwrap();
+        assert_eq!(json["background_image"], serde_json::to_value(&picture).unwrap());
+        let loaded: PresentationDesignTemplate = serde_json::from_value(json).unwrap();
+        assert!(matches!(loaded.background_image, Some(BackgroundMedia::Image(_))));
+
+        let video = SourceFile {
+            file_type: SourceFileType::Video,
+            ..picture.clone()
+        };
+        assert!(matches!(
+            BackgroundMedia::try_from(video),
+            Ok(BackgroundMedia::Video(_))
+        ));
+        assert!(
+            BackgroundMedia::try_from(SourceFile {
+                file_type: SourceFileType::Song,
+                ..picture
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn gradients_are_converted_to_css() {
+        let mut gradient = BackgroundGradient::default();
+        gradient.angle = 45;
+        gradient.add_color_stop();
+        assert_eq!(
+            gradient.to_css_string(),
+            "linear-gradient(45deg, #000000 0%, #283C6E 50%, #283C6E 100%)"
+        );
+
+        gradient.kind = GradientKind::Radial;
+        gradient.remove_color_stop(1);
+        gradient.remove_color_stop(1);
+        assert_eq!(gradient.color_stops.len(), 2);
+        assert_eq!(gradient.to_css_string(), "radial-gradient(circle, #000000 0%, #283C6E 100%)");
+    }
+
+    #[test]
+    fn templates_without_a_slide_counter_hide_it() {
+        let template = without_fields(
+            &PresentationDesignTemplate::default(),
+            &["slide_counter", "slide_counter_corner"],
+        );
+        assert_eq!(template.slide_counter, SlideCounterStyle::Hidden);
+        assert!(!template.slide_counter.shows_number() && !template.slide_counter.shows_progress_bar());
+        assert_eq!(template.slide_counter_corner, OverlayCorner::BottomRight);
+    }
+
+    #[test]
+    fn aspect_ratios_give_preview_resolutions() {
+        assert_eq!(AspectRatio::default().resolution(1920), (1920, 1080));
+        assert_eq!(AspectRatio::Ratio4x3.resolution(1920), (1920, 1440));
+        assert_eq!(AspectRatio::Ratio16x10.resolution(1920), (1920, 1200));
+        assert_eq!(
+            AspectRatio::Custom { width: 21, height: 9 }.resolution(2100),
+            (2100, 900)
+        );
+        assert_eq!(
+            AspectRatio::Custom { width: 0, height: 9 }.ratio(),
+            (16, 9)
+        );
+
+        // Existing designs are made for 16:9
+        let template = without_fields(&PresentationDesignTemplate::default(), &["aspect_ratio"]);
+        assert_eq!(template.aspect_ratio, AspectRatio::Ratio16x9);
+    }
+
+    #[test]
+    fn slide_layouts_keep_every_block_once() {
+        let mut layout = SlideLayout::default();
+        assert!(!layout.is_customized());
+        assert!(!layout.has_block_alignments());
+        assert!(!layout.meta_above_content());
+        assert_eq!(layout.position(SlideBlock::MainContent), 1);
+
+        // The spoiler above the main content
+        layout.move_block(2, 1);
+        assert!(layout.is_customized());
+        assert_eq!(layout.position(SlideBlock::Spoiler), 1);
+        assert_eq!(layout.position(SlideBlock::MainContent), 2);
+        layout.move_block(2, 7);
+        assert_eq!(layout.position(SlideBlock::MainContent), 2);
+
+        // The meta footer is never aligned on its own
+        layout.set_vertical_alignment(SlideBlock::Headline, Some(VerticalAlign::Top));
+        layout.set_vertical_alignment(SlideBlock::Meta, Some(VerticalAlign::Top));
+        assert_eq!(layout.vertical_alignment(SlideBlock::Headline), Some(VerticalAlign::Top));
+        assert_eq!(layout.vertical_alignment(SlideBlock::Meta), None);
+        assert!(layout.has_block_alignments());
+
+        // Missing and duplicate blocks from a settings file are repaired
+        let layout: SlideLayout = serde_json::from_str(
+            r#"{"blocks":[{"block":"Meta"},{"block":"Meta"},{"block":"Spoiler"}]}"#,
+        )
+        .unwrap();
+        let order: Vec<SlideBlock> = layout.blocks().iter().map(|b| b.block).collect();
+        assert_eq!(
+            order,
+            vec![SlideBlock::Meta, SlideBlock::Spoiler, SlideBlock::Headline, SlideBlock::MainContent]
+        );
+        assert!(layout.meta_above_content());
+
+        // Existing designs get the default layout
+        let template = without_fields(&PresentationDesignTemplate::default(), &["slide_layout"]);
+        assert_eq!(template.slide_layout, SlideLayout::default());
+    }
+
+    #[test]
+    fn color_presets_and_inversion_change_all_colors() {
+        let mut template = PresentationDesignTemplate::default();
+        template.fonts[0].outline = Some(FontOutline::default());
+        template.background_gradient = Some(BackgroundGradient::default());
+
+        template.apply_color_preset(ColorPreset::Light);
+        let light = ColorPreset::Light.colors();
+        assert_eq!(template.background_color, light.background);
+        assert_eq!(template.background_gradient, None);
+        assert_eq!(template.fonts[0].color, light.main);
+        assert_eq!(template.get_default_spoiler_font().color, light.spoiler);
+        assert_eq!(template.get_default_meta_font().color, light.meta);
+        assert_eq!(template.fonts[0].outline.as_ref().unwrap().color, RGBA8::new(255, 255, 255, 255));
+
+        // Inverting the light preset gives light text on a black background
+        template.invert_colors();
+        assert_eq!(template.background_color, RGB8::new(0, 0, 0));
+        assert_eq!(template.fonts[0].color, RGBA8::new(235, 235, 235, 255));
+        template.invert_colors();
+        assert_eq!(template.fonts[0].color, light.main);
+    }
+
+    #[test]
+    fn background_colors_can_be_transparent() {
+        let mut template = PresentationDesignTemplate::default();
+        assert_eq!(template.get_background_color_with_alpha(), RGBA8::new(0, 0, 0, 255));
+        assert!(!template.has_transparent_background());
+
+        template.background_color = RGB8::new(0, 255, 0);
+        template.background_color_transparency = 50;
+        assert_eq!(template.get_background_color_with_alpha(), RGBA8::new(0, 255, 0, 128));
+        template.background_color_transparency = 100;
+        assert_eq!(template.get_background_color_with_alpha().a, 0);
+        assert!(template.has_transparent_background());
+        let design = PresentationDesign {
+            presentation_design_settings: PresentationDesignSettings::Template(template.clone()),
+            ..Default::default()
+        };
+        assert!(design.has_transparent_background());
+
+        // A gradient covers the whole background
+        template.background_gradient = Some(BackgroundGradient::default());
+        assert!(!template.has_transparent_background());
+
+        // Existing designs stay opaque
+        let template = without_fields(
+            &PresentationDesignTemplate::default(),
+            &["background_color_transparency"],
+        );
+        assert_eq!(template.background_color_transparency, 0);
+    }
+
+    #[test]
+    fn spoilers_are_not_limited_in_existing_designs() {
+        let template =
+            without_fields(&PresentationDesignTemplate::default(), &["spoiler_max_lines"]);
+        assert_eq!(template.spoiler_max_lines, None);
+    }
+
+    #[test]
+    fn title_emphasis_splits_long_titles_only() {
+        let letter = TitleEmphasis::default();
+        let word = TitleEmphasis {
+            unit: TitleEmphasisUnit::FirstWord,
+            ..Default::default()
+        };
+        assert_eq!(
+            letter.split_title("Amazing Grace"),
+            Some(("A".to_string(), "mazing Grace".to_string()))
+        );
+        assert_eq!(
+            word.split_title("  Amazing Grace"),
+            Some(("Amazing".to_string(), " Grace".to_string()))
+        );
+        assert_eq!(
+            letter.split_title("Ärger"),
+            Some(("Ä".to_string(), "rger".to_string()))
+        );
+
+        // Titles which are not longer than the emphasized part are unchanged
+        assert_eq!(letter.split_title("A"), None);
+        assert_eq!(letter.split_title(""), None);
+        assert_eq!(word.split_title("Hallelujah"), None);
+        assert_eq!(word.split_title("Hallelujah  "), None);
+    }
+
+    #[test]
+    fn watermarks_get_default_placement() {
+        let image = ImageSourceFile::new(SourceFile {
+            name: "Logo".to_string(),
+            path: PathBuf::from("logo.png"),
+            file_type: SourceFileType::Image,
+            md5_hash: None,
+        })
+        .unwrap();
+        let watermark =
+            without_fields(&Watermark::new(image.clone()), &["corner", "size", "opacity"]);
+        assert_eq!(watermark, Watermark::new(image));
+        assert_eq!(watermark.corner, OverlayCorner::BottomRight);
+        assert_eq!(watermark.opacity, 80);
+        assert_eq!(PresentationDesignTemplate::default().watermark, None);
+    }
+
+    #[test]
+    fn design_names_are_made_unique() {
+        let design = |name: &str| PresentationDesign {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let designs = vec![design("Worship"), design("Worship (copy)"), design("Worship (2)")];
+        assert_eq!(unique_design_name(&designs, "Evening", None), "Evening");
+        assert_eq!(unique_design_name(&designs, " Worship ", None), "Worship (3)");
+        assert_eq!(
+            unique_design_name(&designs, "Worship (copy)", None),
+            "Worship (copy) (2)"
+        );
+
+        // A design can keep its own name when it is renamed
+        assert_eq!(unique_design_name(&designs, "Worship", Some(0)), "Worship");
+        assert_eq!(unique_design_name(&designs, "Worship", Some(1)), "Worship (3)");
+    }
+
+    #[test]
+    fn secondary_languages_get_their_own_font() {
+        let mut template = PresentationDesignTemplate::default();
+        let main_font = template.get_default_font();
+        assert!(
+            template.get_default_secondary_language_font()
+                == main_font.scaled(SECONDARY_LANGUAGE_FONTSIZE_FACTOR)
+        );
+
+        template.change_fonts(FontListChange::SetRole(FontRole::SecondaryLanguage, Some(1)));
+        assert!(template.get_default_secondary_language_font() == template.fonts[1]);
+        template.change_fonts(FontListChange::Remove(1));
+        assert_eq!(template.secondary_language_index, None);
+
+        assert!(ShownLanguages::All.shows(0) && ShownLanguages::All.shows(1));
+        assert!(ShownLanguages::Primary.shows(0) && !ShownLanguages::Primary.shows(1));
+        assert!(!ShownLanguages::Secondary.shows(0) && ShownLanguages::Secondary.shows(2));
+    }
+
+    #[test]
+    fn font_roles_stay_valid_when_fonts_are_changed() {
+        let mut template = PresentationDesignTemplate::default();
+        template.title_slide_overrides = Some(TitleSlideOverrides {
+            headline_index: Some(2),
+            ..Default::default()
+        });
+        let roles = |template: &PresentationDesignTemplate| {
+            (
+                template.headline_index(),
+                template.spoiler_index(),
+                template.meta_index,
+                template.title_slide_overrides.as_ref().unwrap().headline_index,
+            )
+        };
+        assert_eq!(roles(&template), (Some(0), Some(1), Some(2), Some(2)));
+
+        template.change_fonts(FontListChange::Add);
+        assert_eq!(template.fonts.len(), 4);
+        template.change_fonts(FontListChange::SetRole(FontRole::Headline, Some(3)));
+        template.change_fonts(FontListChange::SetRole(FontRole::Meta, Some(7)));
+        assert_eq!(roles(&template), (Some(3), Some(1), Some(2), Some(2)));
+
+        // Moving the meta font to the front shifts the fonts in between
+        template.change_fonts(FontListChange::Move { from: 2, to: 0 });
+        assert_eq!(roles(&template), (Some(3), Some(2), Some(0), Some(0)));
+        template.change_fonts(FontListChange::Move { from: 0, to: 3 });
+        assert_eq!(roles(&template), (Some(2), Some(1), Some(3), Some(3)));
+
+        // Removing a font removes its roles, the main font can't be removed
+        template.change_fonts(FontListChange::Remove(1));
+        assert_eq!(roles(&template), (Some(1), None, Some(2), Some(2)));
+        template.change_fonts(FontListChange::Remove(0));
+        template.change_fonts(FontListChange::Remove(5));
+        assert_eq!(template.fonts.len(), 3);
+        assert_eq!(roles(&template), (Some(1), None, Some(2), Some(2)));
+    }
+
+    #[test]
+    fn line_heights_are_clamped_and_persisted() {
+        assert_eq!(clamp_line_height(0.5), LINE_HEIGHT_MIN);
+        assert_eq!(clamp_line_height(4.0), LINE_HEIGHT_MAX);
+        assert_eq!(clamp_line_height(1.54), 1.5);
+        assert_eq!(clamp_line_height(f64::NAN), 1.2);
+
+        let mut font = FontRepresentation::default();
+        font.line_height = 2.5;
+        font.horizontal_alignment = HorizontalAlign::Right;
+        let json = serde_json::to_string(&font).unwrap();
+        let loaded: FontRepresentation = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.line_height, 2.5);
+        assert_eq!(loaded.horizontal_alignment, HorizontalAlign::Right);
+    }
+
+    #[test]
+    fn templates_without_background_placement_cover_the_slide() {
+        let template = without_fields(
+            &PresentationDesignTemplate::default(),
+            &["background_fit", "background_position", "background_blur"],
+        );
+        assert_eq!(template.background_fit.background_size(), "cover");
+        assert_eq!(template.background_fit.background_repeat(), "no-repeat");
+        assert_eq!(template.background_position.to_css_string(), "center");
+        assert!(template.background_blur.is_none());
+
+        assert_eq!(BackgroundFit::Tile.background_repeat(), "repeat");
+        assert_eq!(BackgroundFit::Stretch.object_fit(), "fill");
+        assert_eq!(BackgroundPosition::BottomLeft.to_css_string(), "left bottom");
+    }
+
+    #[test]
+    fn clock_formats_the_time_of_the_day() {
+        assert_eq!(ClockFormat::TwentyFourHours.format(9, 5), "09:05");
+        assert_eq!(ClockFormat::TwentyFourHours.format(23, 59), "23:59");
+        assert_eq!(ClockFormat::TwelveHours.format(0, 30), "12:30 AM");
+        assert_eq!(ClockFormat::TwelveHours.format(12, 0), "12:00 PM");
+        assert_eq!(ClockFormat::TwelveHours.format(14, 5), "2:05 PM");
+    }
+
+    #[test]
+    fn the_clock_is_disabled_by_default() {
+        assert!(!PresentationDesignTemplate::default().show_clock);
+
+        let template = without_fields(
+            &PresentationDesignTemplate::default(),
+            &["show_clock", "clock_corner", "clock_format"],
+        );
+        assert!(!template.show_clock);
+        assert_eq!(template.clock_format, ClockFormat::TwentyFourHours);
+    }
+
+    #[test]
+    fn search_history_keeps_newest_first_without_duplicates() {
+        let mut settings = Settings::default();
+        settings.add_to_search_history("Amazing");
+        settings.add_to_search_history("  grace ");
+        settings.add_to_search_history("amazing");
+        settings.add_to_search_history("");
+        assert_eq!(settings.search_history, vec!["amazing", "grace"]);
+
+        for i in 0..SEARCH_HISTORY_LENGTH + 5 {
+            settings.add_to_search_history(&format!("query {}", i));
+        }
+        assert_eq!(settings.search_history.len(), SEARCH_HISTORY_LENGTH);
+        assert_eq!(
+            settings.search_history.first().unwrap(),
+            &format!("query {}", SEARCH_HISTORY_LENGTH + 4)
+        );
+
+        settings.clear_search_history();
+        assert!(settings.search_history.is_empty());
+    }
+
+    #[test]
+    fn search_history_respects_opt_out() {
+        let mut settings = Settings::default();
+        settings.search_history_enabled = false;
+        settings.add_to_search_history("Amazing");
+        assert!(settings.search_history.is_empty());
+    }
 
     #[cfg(target_os = "linux")]
     #[test]
@@ -1930,6 +4209,74 @@ mod tests {
         assert_eq!(settings.presentation_designs.len(), 1);
     }
 
+    #[test]
+    fn saved_slide_settings_are_read_as_profiles() {
+        let mut slide_settings = SlideSettings::default();
+        slide_settings.max_lines = Some(3);
+        let json = serde_json::to_string(&vec![slide_settings.clone()]).unwrap();
+
+        let profiles: Vec<SlideSettingsProfile> = serde_json::from_str(&json).unwrap();
+        assert_eq!(profiles, vec![SlideSettingsProfile::from(slide_settings)]);
+        assert_eq!(profiles[0].max_chars_per_line, None);
+        assert_eq!(profiles[0].name(), None);
+    }
+
+    #[test]
+    fn empty_last_slides_are_migrated_to_end_slides() {
+        let mut slide_settings = SlideSettings::default();
+        slide_settings.empty_last_slide = true;
+        let json = serde_json::to_string(&slide_settings).unwrap();
+
+        let profile: SlideSettingsProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(profile.end_slide, EndSlide::Empty);
+        assert!(!profile.slide_settings.empty_last_slide);
+
+        // A saved end slide is kept and not migrated again
+        let profile = SlideSettingsProfile {
+            end_slide: EndSlide::RepeatTitle,
+            ..profile
+        };
+        let json = serde_json::to_string(&profile).unwrap();
+        let deserialized: SlideSettingsProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, profile);
+
+        slide_settings.empty_last_slide = false;
+        assert_eq!(SlideSettingsProfile::from(slide_settings).end_slide, EndSlide::None);
+    }
+
+    #[test]
+    fn shown_spoilers_are_migrated_to_spoiler_modes() {
+        let mut slide_settings = SlideSettings::default();
+        for (show_spoiler, spoiler_mode) in [(true, SpoilerMode::NextLines), (false, SpoilerMode::None)] {
+            slide_settings.show_spoiler = show_spoiler;
+            let json = serde_json::to_string(&slide_settings).unwrap();
+            let profile: SlideSettingsProfile = serde_json::from_str(&json).unwrap();
+            assert_eq!(profile.spoiler_mode, spoiler_mode);
+            assert_eq!(profile.slide_settings.show_spoiler, show_spoiler);
+        }
+
+        // The spoilers of cantara_songlib are needed for the chorus as well
+        let mut profile = SlideSettingsProfile::from(slide_settings);
+        profile.set_spoiler_mode(SpoilerMode::Chorus);
+        let json = serde_json::to_string(&profile).unwrap();
+        let deserialized: SlideSettingsProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, profile);
+        assert!(deserialized.slide_settings.show_spoiler);
+    }
+
+    #[test]
+    fn named_profiles_keep_their_names() {
+        let profile = SlideSettingsProfile {
+            name: " Sunday service ".to_string(),
+            description: "Large lines for the old projector".to_string(),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&profile).unwrap();
+        let deserialized: SlideSettingsProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, profile);
+        assert_eq!(deserialized.name(), Some("Sunday service"));
+    }
+
     #[test]
     fn test_github_zipball_url() {
         assert_eq!(
@@ -2127,6 +4474,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn defaults_are_chosen_by_their_index() {
+        let design = |name: &str| PresentationDesign {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let mut settings = Settings::default();
+        settings.presentation_designs = vec![design("First"), design("Second"), design("Third")];
+        settings.default_presentation_design_index = 2;
+        assert_eq!(settings.default_presentation_design().name, "Third");
+
+        // Removing a design before the default keeps the default design
+        settings.presentation_designs.remove(0);
+        assert!(!settings.presentation_design_removed(0));
+        assert_eq!(settings.default_presentation_design().name, "Third");
+
+        // Removing the default design makes the first one the default
+        settings.presentation_designs.remove(1);
+        assert!(settings.presentation_design_removed(1));
+        assert_eq!(settings.default_presentation_design().name, "Second");
+
+        // An index out of range falls back to the first entry
+        settings.default_slide_settings_index = 5;
+        assert_eq!(settings.default_slide_settings(), settings.song_slide_settings[0]);
+        assert!(!settings.slide_settings_removed(6));
+        assert_eq!(settings.default_slide_settings_index, 5);
+    }
+
     #[test]
     fn test_migrate_github_zip_repos() {
         let mut settings = Settings::default();
@@ -2158,4 +4533,23 @@ mod tests {
             other => panic!("Expected RemoteZip repository type, got {:?}", other),
         }
     }
+
+    #[test]
+    fn effective_sidebar_order_appends_missing_buttons() {
+        let mut settings = Settings::default();
+        settings.sidebar_order = vec![SelectionSidebarType::Pdfs, SelectionSidebarType::Songs];
+        assert_eq!(
+            settings.effective_sidebar_order(),
+            vec![
+                SelectionSidebarType::Pdfs,
+                SelectionSidebarType::Songs,
+                SelectionSidebarType::Pictures,
+                SelectionSidebarType::Markdown,
+                SelectionSidebarType::Presentations,
+            ]
+        );
+
+        settings.sidebar_order = Vec::new();
+        assert_eq!(settings.effective_sidebar_order(), default_sidebar_order());
+    }
 }
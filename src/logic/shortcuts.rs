@@ -0,0 +1,287 @@
+//! This module contains the keyboard shortcuts of the presentation window as data.
+//!
+//! The key handlers of the presentation window look up the action of a key here, and the
+//! overview of the shortcuts which is shown to the user is generated from the same list,
+//! so both can't drift apart.
+
+/// An action of the presentation window which is triggered with a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresentationAction {
+    NextSlide,
+    PreviousSlide,
+    FirstSlide,
+    LastSlide,
+    /// Starts the current chapter again from its title slide.
+    RestartChapter,
+    /// Goes to the first slide of the current chapter after its title slide.
+    ChapterStart,
+    ChapterTitle,
+    /// Types the number of a chapter, which is jumped to with Enter.
+    GoToChapterNumber,
+    BlackScreen,
+    WhiteScreen,
+    LogoScreen,
+    Fullscreen,
+    Overview,
+    NextSlideStrip,
+    ChapterSidebar,
+    ShortcutHelp,
+    Quit,
+}
+
+impl PresentationAction {
+    /// Returns the translation key of the description of the action.
+    pub fn description_key(&self) -> &'static str {
+        match self {
+            PresentationAction::NextSlide => "shortcuts.next_slide",
+            PresentationAction::PreviousSlide => "shortcuts.previous_slide",
+            PresentationAction::FirstSlide => "shortcuts.first_slide",
+            PresentationAction::LastSlide => "shortcuts.last_slide",
+            PresentationAction::RestartChapter => "shortcuts.restart_chapter",
+            PresentationAction::ChapterStart => "shortcuts.chapter_start",
+            PresentationAction::ChapterTitle => "shortcuts.chapter_title",
+            PresentationAction::GoToChapterNumber => "shortcuts.go_to_chapter_number",
+            PresentationAction::BlackScreen => "shortcuts.black_screen",
+            PresentationAction::WhiteScreen => "shortcuts.white_screen",
+            PresentationAction::LogoScreen => "shortcuts.logo_screen",
+            PresentationAction::Fullscreen => "shortcuts.fullscreen",
+            PresentationAction::Overview => "shortcuts.overview",
+            PresentationAction::NextSlideStrip => "shortcuts.next_slide_strip",
+            PresentationAction::ChapterSidebar => "shortcuts.chapter_sidebar",
+            PresentationAction::ShortcutHelp => "shortcuts.shortcut_help",
+            PresentationAction::Quit => "shortcuts.quit",
+        }
+    }
+}
+
+/// A key of a shortcut, independent of the keyboard events of the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutKey {
+    /// A character key, always stored in lower case.
+    Character(char),
+    /// Any of the digits 0 to 9.
+    Digit,
+    Space,
+    Enter,
+    Backspace,
+    Escape,
+    ArrowLeft,
+    ArrowRight,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    F1,
+    F5,
+    F11,
+}
+
+impl ShortcutKey {
+    /// Creates the key of a typed character. Character keys don't distinguish upper and lower case.
+    pub fn from_character(character: &str) -> Option<Self> {
+        let mut chars = character.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return None;
+        };
+        Some(match c {
+            ' ' => ShortcutKey::Space,
+            c if c.is_ascii_digit() => ShortcutKey::Digit,
+            c => ShortcutKey::Character(c.to_ascii_lowercase()),
+        })
+    }
+
+    /// Returns the label of the key as it is shown to the user.
+    pub fn label(&self) -> String {
+        match self {
+            ShortcutKey::Character(c) => c.to_ascii_uppercase().to_string(),
+            ShortcutKey::Digit => "0–9".to_string(),
+            ShortcutKey::Space => "Space".to_string(),
+            ShortcutKey::Enter => "Enter".to_string(),
+            ShortcutKey::Backspace => "Backspace".to_string(),
+            ShortcutKey::Escape => "Esc".to_string(),
+            ShortcutKey::ArrowLeft => "←".to_string(),
+            ShortcutKey::ArrowRight => "→".to_string(),
+            ShortcutKey::PageUp => "Page Up".to_string(),
+            ShortcutKey::PageDown => "Page Down".to_string(),
+            ShortcutKey::Home => "Home".to_string(),
+            ShortcutKey::End => "End".to_string(),
+            ShortcutKey::F1 => "F1".to_string(),
+            ShortcutKey::F5 => "F5".to_string(),
+            ShortcutKey::F11 => "F11".to_string(),
+        }
+    }
+}
+
+/// A key (optionally with Ctrl) which triggers an action of the presentation window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shortcut {
+    pub key: ShortcutKey,
+    pub ctrl: bool,
+    pub action: PresentationAction,
+}
+
+impl Shortcut {
+    const fn new(key: ShortcutKey, action: PresentationAction) -> Self {
+        Shortcut {
+            key,
+            ctrl: false,
+            action,
+        }
+    }
+
+    const fn with_ctrl(key: ShortcutKey, action: PresentationAction) -> Self {
+        Shortcut {
+            key,
+            ctrl: true,
+            action,
+        }
+    }
+
+    /// Returns the label of the shortcut as it is shown to the user, e.g. `Ctrl + Home`.
+    pub fn label(&self) -> String {
+        if self.ctrl {
+            format!("Ctrl + {}", self.key.label())
+        } else {
+            self.key.label()
+        }
+    }
+}
+
+/// All shortcuts of the presentation window. The shortcuts of an action are listed next to each
+/// other, in the order in which they are shown to the user.
+pub const PRESENTATION_SHORTCUTS: &[Shortcut] = &[
+    Shortcut::new(ShortcutKey::ArrowRight, PresentationAction::NextSlide),
+    Shortcut::new(ShortcutKey::Space, PresentationAction::NextSlide),
+    Shortcut::new(ShortcutKey::Enter, PresentationAction::NextSlide),
+    Shortcut::new(ShortcutKey::PageDown, PresentationAction::NextSlide),
+    Shortcut::new(ShortcutKey::ArrowLeft, PresentationAction::PreviousSlide),
+    Shortcut::new(ShortcutKey::Backspace, PresentationAction::PreviousSlide),
+    Shortcut::new(ShortcutKey::PageUp, PresentationAction::PreviousSlide),
+    Shortcut::new(ShortcutKey::Home, PresentationAction::FirstSlide),
+    Shortcut::new(ShortcutKey::End, PresentationAction::LastSlide),
+    Shortcut::with_ctrl(ShortcutKey::Home, PresentationAction::RestartChapter),
+    Shortcut::with_ctrl(ShortcutKey::ArrowLeft, PresentationAction::ChapterStart),
+    Shortcut::new(
+        ShortcutKey::Character('t'),
+        PresentationAction::ChapterTitle,
+    ),
+    Shortcut::new(ShortcutKey::Digit, PresentationAction::GoToChapterNumber),
+    Shortcut::new(ShortcutKey::Character('g'), PresentationAction::Overview),
+    Shortcut::new(
+        ShortcutKey::Character('c'),
+        PresentationAction::ChapterSidebar,
+    ),
+    Shortcut::new(
+        ShortcutKey::Character('n'),
+        PresentationAction::NextSlideStrip,
+    ),
+    Shortcut::new(ShortcutKey::Character('b'), PresentationAction::BlackScreen),
+    Shortcut::new(ShortcutKey::Character('w'), PresentationAction::WhiteScreen),
+    Shortcut::new(ShortcutKey::Character('l'), PresentationAction::LogoScreen),
+    Shortcut::new(ShortcutKey::F11, PresentationAction::Fullscreen),
+    Shortcut::new(ShortcutKey::F5, PresentationAction::Fullscreen),
+    Shortcut::new(
+        ShortcutKey::Character('?'),
+        PresentationAction::ShortcutHelp,
+    ),
+    Shortcut::new(ShortcutKey::F1, PresentationAction::ShortcutHelp),
+    Shortcut::new(ShortcutKey::Escape, PresentationAction::Quit),
+];
+
+/// Returns the action of a key. A shortcut with Ctrl is preferred if Ctrl is pressed,
+/// otherwise the shortcut without Ctrl is used.
+pub fn find_action(key: ShortcutKey, ctrl: bool) -> Option<PresentationAction> {
+    let find = |ctrl: bool| {
+        PRESENTATION_SHORTCUTS
+            .iter()
+            .find(|shortcut| shortcut.key == key && shortcut.ctrl == ctrl)
+            .map(|shortcut| shortcut.action)
+    };
+    if ctrl {
+        find(true).or_else(|| find(false))
+    } else {
+        find(false)
+    }
+}
+
+/// Returns the shortcuts grouped by their action, in the order of [PRESENTATION_SHORTCUTS].
+pub fn shortcuts_by_action() -> Vec<(PresentationAction, Vec<Shortcut>)> {
+    let mut groups: Vec<(PresentationAction, Vec<Shortcut>)> = Vec::new();
+    for shortcut in PRESENTATION_SHORTCUTS {
+        match groups
+            .iter_mut()
+            .find(|(action, _)| *action == shortcut.action)
+        {
+            Some((_, shortcuts)) => shortcuts.push(*shortcut),
+            None => groups.push((shortcut.action, vec![*shortcut])),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_mapped_to_their_actions() {
+        assert_eq!(
+            find_action(ShortcutKey::ArrowRight, false),
+            Some(PresentationAction::NextSlide)
+        );
+        assert_eq!(
+            find_action(ShortcutKey::ArrowLeft, true),
+            Some(PresentationAction::ChapterStart)
+        );
+        assert_eq!(
+            find_action(ShortcutKey::Home, true),
+            Some(PresentationAction::RestartChapter)
+        );
+        // Without a shortcut of its own, Ctrl doesn't change the action
+        assert_eq!(
+            find_action(ShortcutKey::Character('b'), true),
+            Some(PresentationAction::BlackScreen)
+        );
+        assert_eq!(find_action(ShortcutKey::Character('x'), false), None);
+    }
+
+    #[test]
+    fn characters_are_case_insensitive() {
+        assert_eq!(
+            ShortcutKey::from_character("B"),
+            Some(ShortcutKey::Character('b'))
+        );
+        assert_eq!(ShortcutKey::from_character("7"), Some(ShortcutKey::Digit));
+        assert_eq!(ShortcutKey::from_character(" "), Some(ShortcutKey::Space));
+        assert_eq!(ShortcutKey::from_character("ab"), None);
+    }
+
+    #[test]
+    fn no_key_triggers_two_actions() {
+        for (index, shortcut) in PRESENTATION_SHORTCUTS.iter().enumerate() {
+            assert!(
+                !PRESENTATION_SHORTCUTS[index + 1..]
+                    .iter()
+                    .any(|other| other.key == shortcut.key && other.ctrl == shortcut.ctrl),
+                "{} is bound twice",
+                shortcut.label()
+            );
+        }
+    }
+
+    #[test]
+    fn shortcuts_of_an_action_are_grouped() {
+        let groups = shortcuts_by_action();
+        let (action, next_slide_shortcuts) = &groups[0];
+        assert_eq!(*action, PresentationAction::NextSlide);
+        assert_eq!(next_slide_shortcuts.len(), 4);
+        assert_eq!(
+            groups.len(),
+            groups
+                .iter()
+                .map(|(action, _)| action.description_key())
+                .collect::<std::collections::HashSet<_>>()
+                .len()
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! This module reads slide directives from song files, with which a single song overrides the
+//! slide settings, e.g. `#max_lines: 2` or `#no_title_slide`.
+//!
+//! The slide settings of a song are taken from (later ones take precedence):
+//! 1. the default song slide settings of the settings,
+//! 2. the slide settings of the selected item, which replace the defaults as a whole,
+//! 3. the directives in the header of the song file, which only change the named settings.
+//!
+//! Headers with a value which are no directives (e.g. `#title:` or `#author:`) are meta
+//! information of the song. Headers without a value are always taken as directives, so that
+//! misspelled directives are noticed in the log.
+
+use crate::logic::settings::{EndSlide, SlideSettingsProfile, SpoilerMode};
+
+/// The directives which set a value, e.g. `#max_lines: 2`. A value of `0` removes the limit.
+const VALUE_DIRECTIVES: [&str; 2] = ["max_lines", "max_chars_per_line"];
+
+/// A slide directive of a song file, e.g. `#max_lines: 2` with the name `max_lines` and the
+/// value `2`, or `#no_title_slide` without a value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideDirective {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+/// Parses a header line of a song file into a directive, if it is one.
+fn parse_directive(line: &str) -> Option<SlideDirective> {
+    let header = line.trim().strip_prefix('#')?;
+    match header.split_once(':') {
+        Some((name, value)) => {
+            let name = name.trim().to_lowercase();
+            VALUE_DIRECTIVES.contains(&name.as_str()).then(|| SlideDirective {
+                name,
+                value: Some(value.trim().to_string()),
+            })
+        }
+        None => Some(SlideDirective {
+            name: header.trim().to_lowercase(),
+            value: None,
+        })
+        .filter(|directive| !directive.name.is_empty()),
+    }
+}
+
+/// Returns the lines of the header block at the beginning of a song file.
+fn header_lines(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .lines()
+        .take_while(|line| line.trim_start().starts_with('#'))
+}
+
+/// Returns the slide directives in the header of a song file in their order.
+pub fn slide_directives(content: &str) -> Vec<SlideDirective> {
+    header_lines(content).filter_map(parse_directive).collect()
+}
+
+/// Returns the content of a song file without its slide directives, so that they don't end up
+/// on the slides.
+pub fn without_slide_directives(content: &str) -> String {
+    let header_length = header_lines(content).count();
+    content
+        .lines()
+        .enumerate()
+        .filter(|(index, line)| *index >= header_length || parse_directive(line).is_none())
+        .map(|(_, line)| line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Applies the slide directives to slide settings. Unknown directives and directives with an
+/// invalid value are ignored.
+pub fn apply_slide_directives(
+    slide_settings: &SlideSettingsProfile,
+    directives: &[SlideDirective],
+) -> SlideSettingsProfile {
+    let mut slide_settings = slide_settings.clone();
+    for directive in directives {
+        let value = directive.value.as_deref().unwrap_or_default();
+        match directive.name.as_str() {
+            "max_lines" => match value.parse() {
+                Ok(0) => slide_settings.slide_settings.max_lines = None,
+                Ok(max_lines) => slide_settings.slide_settings.max_lines = Some(max_lines),
+                Err(_) => log::debug!("Ignoring the slide directive max_lines: {}", value),
+            },
+            "max_chars_per_line" => match value.parse::<u16>() {
+                Ok(max_chars) => {
+                    slide_settings.max_chars_per_line = Some(max_chars).filter(|max_chars| *max_chars > 0)
+                }
+                Err(_) => log::debug!("Ignoring the slide directive max_chars_per_line: {}", value),
+            },
+            "title_slide" => slide_settings.slide_settings.title_slide = true,
+            "no_title_slide" => slide_settings.slide_settings.title_slide = false,
+            "spoiler" => slide_settings.set_spoiler_mode(SpoilerMode::NextLines),
+            "chorus_spoiler" => slide_settings.set_spoiler_mode(SpoilerMode::Chorus),
+            "no_spoiler" => slide_settings.set_spoiler_mode(SpoilerMode::None),
+            "empty_last_slide" => slide_settings.end_slide = EndSlide::Empty,
+            "repeat_title_slide" => slide_settings.end_slide = EndSlide::RepeatTitle,
+            "no_end_slide" | "no_empty_last_slide" => slide_settings.end_slide = EndSlide::None,
+            name => log::debug!("Ignoring the unknown slide directive {}", name),
+        }
+    }
+    slide_settings
+}
+
+/// Returns the slide settings with which the slides of a song are created, see the module
+/// documentation for the precedence.
+///
+/// # Arguments
+/// * `default_slide_settings` - The default song slide settings
+/// * `item_slide_settings` - The slide settings of the selected item, if it has its own
+/// * `content` - The content of the song file, if it could be read
+pub fn effective_slide_settings(
+    default_slide_settings: &SlideSettingsProfile,
+    item_slide_settings: Option<&SlideSettingsProfile>,
+    content: Option<&str>,
+) -> SlideSettingsProfile {
+    let slide_settings = item_slide_settings.unwrap_or(default_slide_settings);
+    match content {
+        Some(content) => apply_slide_directives(slide_settings, &slide_directives(content)),
+        None => slide_settings.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SONG: &str = "#title: Amazing Grace\n#max_lines: 2\n#no_title_slide\n#author: John Newton\n\nAmazing grace\n#not a header";
+
+    #[test]
+    fn directives_are_read_from_the_header() {
+        assert_eq!(
+            slide_directives(SONG),
+            vec![
+                SlideDirective { name: "max_lines".to_string(), value: Some("2".to_string()) },
+                SlideDirective { name: "no_title_slide".to_string(), value: None },
+            ]
+        );
+        assert_eq!(
+            without_slide_directives(SONG),
+            "#title: Amazing Grace\n#author: John Newton\n\nAmazing grace\n#not a header"
+        );
+        assert!(slide_directives("Amazing grace\n#no_title_slide").is_empty());
+    }
+
+    #[test]
+    fn each_directive_changes_its_setting() {
+        let mut slide_settings = SlideSettingsProfile::default();
+        slide_settings.slide_settings.title_slide = true;
+        slide_settings.set_spoiler_mode(SpoilerMode::NextLines);
+        slide_settings.end_slide = EndSlide::Empty;
+        slide_settings.slide_settings.max_lines = Some(4);
+        slide_settings.max_chars_per_line = Some(40);
+
+        let content = "#no_title_slide\n#no_spoiler\n#no_empty_last_slide\n#max_lines: 0\n#max_chars_per_line: 0\n\nVerse";
+        let changed = apply_slide_directives(&slide_settings, &slide_directives(content));
+        assert!(!changed.slide_settings.title_slide);
+        assert_eq!(changed.spoiler_mode, SpoilerMode::None);
+        assert_eq!(changed.end_slide, EndSlide::None);
+        assert_eq!(changed.slide_settings.max_lines, None);
+        assert_eq!(changed.max_chars_per_line, None);
+
+        let content = "#title_slide\n#spoiler\n#empty_last_slide\n#max_lines: 2\n#max_chars_per_line: 30\n\nVerse";
+        let changed = apply_slide_directives(&changed, &slide_directives(content));
+        assert!(changed.slide_settings.title_slide);
+        assert_eq!(changed.spoiler_mode, SpoilerMode::NextLines);
+        assert_eq!(changed.end_slide, EndSlide::Empty);
+        assert_eq!(changed.slide_settings.max_lines, Some(2));
+        assert_eq!(changed.max_chars_per_line, Some(30));
+
+        let changed = apply_slide_directives(
+            &changed,
+            &slide_directives("#repeat_title_slide\n#chorus_spoiler\n\nVerse"),
+        );
+        assert_eq!(changed.end_slide, EndSlide::RepeatTitle);
+        assert_eq!(changed.spoiler_mode, SpoilerMode::Chorus);
+    }
+
+    #[test]
+    fn unknown_and_invalid_directives_are_ignored() {
+        let slide_settings = SlideSettingsProfile::default();
+        let content = "#no_tilte_slide\n#max_lines: two\n#max_chars_per_line: -1\n\nVerse";
+        assert_eq!(
+            apply_slide_directives(&slide_settings, &slide_directives(content)),
+            slide_settings
+        );
+    }
+
+    #[test]
+    fn directives_take_precedence_over_the_slide_settings_of_the_item() {
+        let mut default_slide_settings = SlideSettingsProfile::default();
+        default_slide_settings.slide_settings.max_lines = Some(4);
+        default_slide_settings.slide_settings.title_slide = true;
+        let mut item_slide_settings = default_slide_settings.clone();
+        item_slide_settings.slide_settings.max_lines = Some(3);
+
+        let effective = effective_slide_settings(&default_slide_settings, None, None);
+        assert_eq!(effective, default_slide_settings);
+
+        let effective =
+            effective_slide_settings(&default_slide_settings, Some(&item_slide_settings), Some("Verse"));
+        assert_eq!(effective, item_slide_settings);
+
+        let effective = effective_slide_settings(
+            &default_slide_settings,
+            Some(&item_slide_settings),
+            Some("#max_lines: 2\n\nVerse"),
+        );
+        assert_eq!(effective.slide_settings.max_lines, Some(2));
+        assert!(effective.slide_settings.title_slide);
+    }
+}
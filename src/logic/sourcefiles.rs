@@ -103,6 +103,12 @@ pub enum SourceFileType {
 
     /// A Markdown document which Cantara can render and display
     Markdown,
+
+    /// A free text such as an announcement which has been typed in by the user
+    Text,
+
+    /// A deliberately blank slide, e.g. between two songs
+    Blank,
 }
 
 /// A source file which contains content which Cantara can use to generate content from
@@ -233,7 +239,63 @@ impl PdfSourceFile {
     }
 }
 
+/// This is a wrapper around [SourceFile] which ensures that the [SourceFile] is a video
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VideoSourceFile(SourceFile);
+
+impl VideoSourceFile {
+    // Constructor that enforces the FileType::Video constraint
+    pub fn new(source_file: SourceFile) -> Option<Self> {
+        if matches!(source_file.file_type, SourceFileType::Video) {
+            Some(VideoSourceFile(source_file))
+        } else {
+            None
+        }
+    }
+
+    // Accessor to get the inner SourceFile
+    pub fn into_inner(self) -> SourceFile {
+        self.0
+    }
+
+    // Optional: Reference accessor for convenience
+    pub fn as_source(&self) -> &SourceFile {
+        &self.0
+    }
+}
+
 impl SourceFile {
+    /// Opens the file manager of the system and shows the source file in it.
+    /// On Linux, the folder containing the file is opened because there is no common way to
+    /// select a file in the file manager.
+    #[cfg(feature = "desktop")]
+    pub fn reveal_in_file_manager(&self) -> std::io::Result<()> {
+        use std::process::Command;
+
+        #[cfg(target_os = "macos")]
+        let mut command = {
+            let mut command = Command::new("open");
+            command.arg("-R").arg(&self.path);
+            command
+        };
+
+        #[cfg(target_os = "windows")]
+        let mut command = {
+            let mut command = Command::new("explorer");
+            command.arg(format!("/select,{}", self.path.display()));
+            command
+        };
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        let mut command = {
+            let mut command = Command::new("xdg-open");
+            command.arg(self.path.parent().unwrap_or(&self.path));
+            command
+        };
+
+        command.spawn().map(|_| ())
+    }
+
     /// Creates a [SourceFile] from a web VFS path (e.g., `web-zip://url/path/to/file.song`).
     /// Only available on WASM targets.
     #[cfg(target_arch = "wasm32")]
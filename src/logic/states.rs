@@ -3,8 +3,15 @@ use std::{fs, path::PathBuf};
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use super::{settings::{PresentationDesign, SlideTimerSettings, SlideTransition}, sourcefiles::SourceFile};
-use cantara_songlib::slides::{Slide, SlideSettings};
+use super::{
+    settings::{
+        AfterLastSlide, EndOfPresentationBehavior, MetaFooterSlides, PictureSettings,
+        PresentationDesign, ShownLanguages, SlideSettingsProfile, SlideTimerSettings,
+        SlideTransition,
+    },
+    sourcefiles::{ImageSourceFile, SourceFile, SourceFileType},
+};
+use cantara_songlib::slides::{EmptySlide, Slide, SlideContent, SlideSettings};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Default)]
 pub struct Settings {
@@ -74,18 +81,32 @@ pub struct SelectedItemRepresentation {
     /// The [PresentationDesignSettings] as an option. If [None], the default [PresentationDesign] will be used.
     pub presentation_design_option: Option<PresentationDesign>,
 
-    /// The [PresentationDesign] as an option. If [None], the default [PresentationDesign] will be used.
-    pub slide_settings_option: Option<SlideSettings>,
+    /// The [SlideSettingsProfile] as an option. If [None], the default slide settings will be used.
+    pub slide_settings_option: Option<SlideSettingsProfile>,
 
     /// Optional inline markdown content for spontaneous markdown text.
     /// When set, this content is used instead of reading from the source file path.
     pub inline_markdown: Option<String>,
 
+    /// Optional inline text of an announcement. When set, the item is presented as a single slide
+    /// which shows this text.
+    pub inline_text: Option<String>,
+
     /// Optional timer settings for automatic slide advance. If [None], no timer is used.
     pub timer_settings_option: Option<SlideTimerSettings>,
 
     /// The transition effect for this selection. Uses the default (Fade) when not set.
     pub transition_effect: SlideTransition,
+
+    /// How the item is presented if it is a picture.
+    pub picture_settings: PictureSettings,
+
+    /// The verse order of a song (e.g. `V1 C V2 C`), which overrides the `#order:` line of the
+    /// song file. If [None], the order of the song file is used.
+    pub verse_order: Option<String>,
+
+    /// Which languages are shown if the item is a multi-language song.
+    pub shown_languages: ShownLanguages,
 }
 
 impl SelectedItemRepresentation {
@@ -95,10 +116,69 @@ impl SelectedItemRepresentation {
             presentation_design_option: None,
             slide_settings_option: None,
             inline_markdown: None,
+            inline_text: None,
             timer_settings_option: None,
             transition_effect: SlideTransition::default(),
+            picture_settings: PictureSettings::default(),
+            verse_order: None,
+            shown_languages: ShownLanguages::default(),
         }
     }
+
+    /// Creates an announcement item which shows the given text on a single slide.
+    pub fn new_announcement(text: String) -> Self {
+        let source_file = SourceFile {
+            name: announcement_name(&text),
+            path: PathBuf::new(),
+            file_type: SourceFileType::Text,
+            md5_hash: None,
+        };
+        let mut item = Self::new_with_sourcefile(source_file);
+        item.inline_text = Some(text);
+        item
+    }
+
+    /// Creates an item which shows a single blank slide.
+    pub fn new_blank() -> Self {
+        Self::new_with_sourcefile(SourceFile {
+            name: BLANK_ITEM_NAME.to_string(),
+            path: PathBuf::new(),
+            file_type: SourceFileType::Blank,
+            md5_hash: None,
+        })
+    }
+
+    /// Changes the text of an announcement item and updates its name accordingly.
+    pub fn set_inline_text(&mut self, text: String) {
+        self.source_file.name = announcement_name(&text);
+        self.inline_text = Some(text);
+    }
+
+    /// Returns true if the item has been created from the given source file.
+    /// Spontaneous markdown texts and announcements never refer to a source file.
+    pub fn refers_to(&self, source_file: &SourceFile) -> bool {
+        self.inline_markdown.is_none()
+            && self.inline_text.is_none()
+            && self.source_file.file_type == source_file.file_type
+            && self.source_file.path == source_file.path
+    }
+}
+
+/// The name of blank items, see [SelectedItemRepresentation::new_blank].
+const BLANK_ITEM_NAME: &str = "— blank —";
+
+/// The maximal number of characters of the name of an announcement.
+const ANNOUNCEMENT_NAME_LENGTH: usize = 40;
+
+/// Returns the name of an announcement, which is its first non-empty line (shortened if necessary).
+fn announcement_name(text: &str) -> String {
+    let first_line = text.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("");
+    if first_line.chars().count() > ANNOUNCEMENT_NAME_LENGTH {
+        let shortened: String = first_line.chars().take(ANNOUNCEMENT_NAME_LENGTH).collect();
+        format!("{}…", shortened.trim_end())
+    } else {
+        first_line.to_string()
+    }
 }
 
 /// A running presentation that holds all state needed to display and navigate slides.
@@ -118,10 +198,34 @@ impl SelectedItemRepresentation {
 /// `previous_slide`, `jump_to`) automatically reset the scroll position to 0.
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct RunningPresentation {
+    /// Identifies the presentation among all running presentations, see [`RunningPresentationId`]
+    #[serde(default)]
+    pub id: usize,
     pub presentation: Vec<SlideChapter>,
     pub position: Option<RunningPresentationPosition>,
     /// Whether the presentation is currently showing a black screen
     pub is_black_screen: bool,
+    /// Whether the presentation is currently showing a white screen
+    #[serde(default)]
+    pub is_white_screen: bool,
+    /// Whether the presentation is currently showing the logo instead of the slide
+    #[serde(default)]
+    pub is_logo_screen: bool,
+    /// The logo which is shown by the logo screen, taken from the settings
+    #[serde(default)]
+    pub logo_image: Option<ImageSourceFile>,
+    /// Advance automatically after this number of seconds on chapters without their own timer
+    #[serde(default)]
+    pub auto_advance_seconds: Option<u32>,
+    /// Whether the automatic advance wraps from the last slide back to the first one
+    #[serde(default)]
+    pub loop_at_end: bool,
+    /// Whether scrolling the mouse wheel down goes to the previous instead of the next slide
+    #[serde(default)]
+    pub invert_wheel: bool,
+    /// What happens when the next slide is requested on the last slide
+    #[serde(default)]
+    pub end_behavior: EndOfPresentationBehavior,
     /// The resolution of the presentation screen in pixels (width, height).
     /// Defaults to 1920x1080 (16:9) when no monitor info is available.
     #[serde(default = "default_presentation_resolution")]
@@ -132,61 +236,217 @@ pub struct RunningPresentation {
     /// and is synced by a dedicated polling loop in `MarkdownSlideComponent`.
     #[serde(default)]
     pub markdown_scroll_position: f64,
+    /// Incremented to ask the presentation window to bring itself to the front
+    #[serde(default)]
+    pub focus_requests: u32,
+    /// Whether the songs of the presentation are recorded in the session log when they are shown
+    #[serde(default)]
+    pub session_log: bool,
+    /// Whether custom designs may load external resources, see
+    /// [crate::logic::settings::Settings::allow_external_css_resources]
+    #[serde(default)]
+    pub allow_external_css_resources: bool,
+}
+
+/// The id of the running presentation which is shown by a presentation window or presenter console.
+/// It is passed as root context into the windows of a presentation, so that they don't have to
+/// assume that they show the first running presentation.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RunningPresentationId(pub usize);
+
+/// Returns the running presentation with the given id (if it is still running)
+pub fn find_presentation(presentations: &[RunningPresentation], id: usize) -> Option<&RunningPresentation> {
+    presentations.iter().find(|rp| rp.id == id)
+}
+
+/// Returns the running presentation with the given id mutably (if it is still running)
+pub fn find_presentation_mut(
+    presentations: &mut [RunningPresentation],
+    id: usize,
+) -> Option<&mut RunningPresentation> {
+    presentations.iter_mut().find(|rp| rp.id == id)
+}
+
+/// Returns an id which isn't used by any of the running presentations
+pub fn next_presentation_id(presentations: &[RunningPresentation]) -> usize {
+    presentations.iter().map(|rp| rp.id + 1).max().unwrap_or(0)
 }
 
 impl RunningPresentation {
     /// Helper function to create a new [RunningPresentation] data structure
     pub fn new(presentation: Vec<SlideChapter>) -> Self {
         RunningPresentation {
+            id: 0,
             presentation: presentation.clone(),
             position: RunningPresentationPosition::new(&presentation),
             is_black_screen: false,
+            is_white_screen: false,
+            is_logo_screen: false,
+            logo_image: None,
+            auto_advance_seconds: None,
+            loop_at_end: false,
+            invert_wheel: false,
+            end_behavior: EndOfPresentationBehavior::default(),
             presentation_resolution: default_presentation_resolution(),
             markdown_scroll_position: 0.0,
+            focus_requests: 0,
+            session_log: false,
+            allow_external_css_resources: false,
         }
     }
 
+    /// Takes the presentation settings (e.g. the logo, auto advance and the end behavior) from
+    /// the settings when the presentation is started.
+    pub fn apply_presentation_settings(&mut self, settings: &super::settings::Settings) {
+        self.logo_image = settings.logo_image.clone();
+        self.auto_advance_seconds =
+            Some(settings.auto_advance_seconds).filter(|seconds| *seconds > 0);
+        self.loop_at_end = settings.auto_advance_loop;
+        self.invert_wheel = settings.invert_wheel_direction;
+        self.end_behavior = settings.end_of_presentation;
+        self.session_log = settings.session_log_enabled;
+        self.allow_external_css_resources = settings.allow_external_css_resources;
+    }
+
     /// Go to the next slide (if any exists).
-    /// Resets `markdown_scroll_position` to 0 so the new slide starts at the top.
+    /// Resets `markdown_scroll_position` to 0 so the new slide starts at the top
+    /// and ends a black, white or logo screen.
+    /// On the last slide, the `end_behavior` of the presentation is applied.
     pub fn next_slide(&mut self) {
-        if let Some(ref mut pos) = self.position {
-            if pos.try_next(&self.presentation).is_ok() {
-                self.markdown_scroll_position = 0.0;
-            }
+        self.clear_screen_overrides();
+        let Some(ref mut pos) = self.position else {
+            return;
+        };
+        if pos.try_next(&self.presentation).is_ok() {
+            self.markdown_scroll_position = 0.0;
+            return;
+        }
+        match self.end_behavior {
+            EndOfPresentationBehavior::Hold => {}
+            EndOfPresentationBehavior::Blank => self.is_black_screen = true,
+            EndOfPresentationBehavior::WrapAround => self.first_slide(),
         }
     }
 
     /// Go to the previous slide (if any exists).
-    /// Resets `markdown_scroll_position` to 0 so the new slide starts at the top.
+    /// Resets `markdown_scroll_position` to 0 so the new slide starts at the top
+    /// and ends a black, white or logo screen.
     pub fn previous_slide(&mut self) {
         if let Some(ref mut pos) = self.position {
             if pos.try_back(&self.presentation).is_ok() {
                 self.markdown_scroll_position = 0.0;
             }
         }
+        self.clear_screen_overrides();
     }
 
     /// Jump to a specific chapter and slide position.
-    /// Resets `markdown_scroll_position` to 0 so the new slide starts at the top.
+    /// Resets `markdown_scroll_position` to 0 so the new slide starts at the top
+    /// and ends a black, white or logo screen.
     pub fn jump_to(&mut self, chapter: usize, slide: usize) {
-        if chapter < self.presentation.len() {
-            let chapter_slides = &self.presentation[chapter].slides;
-            if slide < chapter_slides.len() {
-                // Calculate the total slide number
-                let mut total: usize = 0;
-                for i in 0..chapter {
-                    total += self.presentation[i].slides.len();
-                }
-                total += slide;
+        let mut position = self.position.clone().unwrap_or(RunningPresentationPosition {
+            chapter: 0,
+            chapter_slide: 0,
+            slide_total: 0,
+        });
+        if position.go_to(chapter, slide, &self.presentation).is_ok() {
+            self.position = Some(position);
+            self.markdown_scroll_position = 0.0;
+            self.clear_screen_overrides();
+        }
+    }
 
-                self.position = Some(RunningPresentationPosition {
-                    chapter,
-                    chapter_slide: slide,
-                    slide_total: total,
-                });
+    /// Jump to the very first slide of the presentation.
+    pub fn first_slide(&mut self) {
+        if let Some(ref mut pos) = self.position {
+            if pos.go_to_first(&self.presentation).is_ok() {
                 self.markdown_scroll_position = 0.0;
             }
         }
+        self.clear_screen_overrides();
+    }
+
+    /// Jump to the very last slide of the presentation.
+    pub fn last_slide(&mut self) {
+        if let Some(ref mut pos) = self.position {
+            if pos.go_to_last(&self.presentation).is_ok() {
+                self.markdown_scroll_position = 0.0;
+            }
+        }
+        self.clear_screen_overrides();
+    }
+
+    /// Jump to the first slide of the given chapter.
+    /// Returns an error (and stays at the current position) if the chapter doesn't exist or is empty.
+    pub fn go_to_chapter(&mut self, chapter: usize) -> Result<(), ()> {
+        let Some(ref mut pos) = self.position else {
+            return Err(());
+        };
+        pos.go_to_chapter(chapter, &self.presentation)?;
+        self.markdown_scroll_position = 0.0;
+        self.clear_screen_overrides();
+        Ok(())
+    }
+
+    /// Jump to the first content slide of the current chapter, skipping its title slide.
+    pub fn go_to_chapter_start(&mut self) {
+        if let Some(ref mut pos) = self.position {
+            if pos.go_to_chapter_start(&self.presentation).is_ok() {
+                self.markdown_scroll_position = 0.0;
+            }
+        }
+        self.clear_screen_overrides();
+    }
+
+    /// Jump to the title slide of the current chapter.
+    /// Returns an error (and stays at the current position) if the chapter has no title slide.
+    pub fn go_to_chapter_title(&mut self) -> Result<(), ()> {
+        let Some(ref mut pos) = self.position else {
+            return Err(());
+        };
+        pos.go_to_chapter_title(&self.presentation)?;
+        self.markdown_scroll_position = 0.0;
+        self.clear_screen_overrides();
+        Ok(())
+    }
+
+    /// Appends a chapter at the end of the running presentation, e.g. a song which has been
+    /// requested spontaneously. The current position stays the same.
+    /// If `separator_slide` is set, a blank slide separates the chapter from the one before.
+    pub fn append_chapter(&mut self, chapter: SlideChapter, separator_slide: bool) {
+        if separator_slide {
+            if let Some(last_chapter) = self.presentation.last_mut() {
+                last_chapter.add_separator_slide();
+            }
+        }
+        self.presentation.push(chapter);
+        if self.position.is_none() {
+            self.position = RunningPresentationPosition::new(&self.presentation);
+        }
+    }
+
+    /// Removes a chapter which hasn't been reached yet.
+    /// If the last chapter is removed, the separator slide before it is removed as well.
+    /// Returns an error if the chapter doesn't exist or is the current or an earlier chapter.
+    pub fn remove_upcoming_chapter(&mut self, chapter: usize) -> Result<(), ()> {
+        let current_chapter = self.position.as_ref().map(|p| p.chapter()).ok_or(())?;
+        if chapter <= current_chapter || chapter >= self.presentation.len() {
+            return Err(());
+        }
+        self.presentation.remove(chapter);
+        if chapter == self.presentation.len() {
+            if let Some(last_chapter) = self.presentation.last_mut() {
+                last_chapter.remove_separator_slide();
+            }
+            // The position might have been on the removed separator slide
+            if let Some(position) = self.position.as_mut() {
+                let last_slide = self.presentation[current_chapter].slides.len().saturating_sub(1);
+                if position.chapter_slide() > last_slide {
+                    let _ = position.go_to(current_chapter, last_slide, &self.presentation);
+                }
+            }
+        }
+        Ok(())
     }
 
     /// Returns the total number of slides across all chapters
@@ -194,9 +454,40 @@ impl RunningPresentation {
         self.presentation.iter().map(|ch| ch.slides.len()).sum()
     }
 
-    /// Toggle the black screen state
+    /// Toggle the black screen state. The position is kept while the screen is black.
     pub fn toggle_black_screen(&mut self) {
-        self.is_black_screen = !self.is_black_screen;
+        let is_black_screen = !self.is_black_screen;
+        self.clear_screen_overrides();
+        self.is_black_screen = is_black_screen;
+    }
+
+    /// Toggle the white screen state. The position is kept while the screen is white.
+    pub fn toggle_white_screen(&mut self) {
+        let is_white_screen = !self.is_white_screen;
+        self.clear_screen_overrides();
+        self.is_white_screen = is_white_screen;
+    }
+
+    /// Toggle the logo screen state. Nothing happens if no logo has been configured.
+    pub fn toggle_logo_screen(&mut self) {
+        if self.logo_image.is_none() {
+            return;
+        }
+        let is_logo_screen = !self.is_logo_screen;
+        self.clear_screen_overrides();
+        self.is_logo_screen = is_logo_screen;
+    }
+
+    /// Returns true if a black, white or logo screen is shown instead of the current slide.
+    pub fn hides_slide(&self) -> bool {
+        self.is_black_screen || self.is_white_screen || self.is_logo_screen
+    }
+
+    /// Ends a black, white or logo screen, so that the current slide is shown again.
+    fn clear_screen_overrides(&mut self) {
+        self.is_black_screen = false;
+        self.is_white_screen = false;
+        self.is_logo_screen = false;
     }
 
     pub fn get_current_slide(&self) -> Option<Slide> {
@@ -211,6 +502,46 @@ impl RunningPresentation {
         })
     }
 
+    /// Returns how the pictures of the current chapter are presented.
+    pub fn get_current_picture_settings(&self) -> PictureSettings {
+        self.position
+            .as_ref()
+            .and_then(|position| self.presentation.get(position.chapter()))
+            .map(|chapter| chapter.picture_settings)
+            .unwrap_or_default()
+    }
+
+    /// Returns which languages of a multi-language song are shown in the current chapter.
+    pub fn get_current_shown_languages(&self) -> ShownLanguages {
+        self.position
+            .as_ref()
+            .and_then(|position| self.presentation.get(position.chapter()))
+            .map(|chapter| chapter.shown_languages)
+            .unwrap_or_default()
+    }
+
+    /// Returns the source file of the chapter which is currently presented.
+    pub fn current_source_file(&self) -> Option<&SourceFile> {
+        let position = self.position.as_ref()?;
+        self.presentation
+            .get(position.chapter())
+            .map(|chapter| &chapter.source_file)
+    }
+
+    /// Returns the slide after the current position together with the number of its chapter
+    /// without changing the position. Returns [None] on the last slide.
+    pub fn peek_next_slide(&self) -> Option<(usize, Slide)> {
+        let mut position = self.position.clone()?;
+        position.try_next(&self.presentation).ok()?;
+        let slide = self
+            .presentation
+            .get(position.chapter())?
+            .slides
+            .get(position.chapter_slide())?
+            .clone();
+        Some((position.chapter(), slide))
+    }
+
     pub fn get_current_presentation_design(&self) -> PresentationDesign {
         match self.position.clone() {
             Some(pos) => self
@@ -237,9 +568,20 @@ impl RunningPresentation {
     /// to trigger full component re-renders and race with slide navigation,
     /// leading to slide changes being reverted.
     pub fn eq_ignoring_scroll(&self, other: &Self) -> bool {
-        self.presentation == other.presentation
+        self.id == other.id
+            && self.presentation == other.presentation
             && self.position == other.position
             && self.is_black_screen == other.is_black_screen
+            && self.is_white_screen == other.is_white_screen
+            && self.is_logo_screen == other.is_logo_screen
+            && self.logo_image == other.logo_image
+            && self.auto_advance_seconds == other.auto_advance_seconds
+            && self.loop_at_end == other.loop_at_end
+            && self.invert_wheel == other.invert_wheel
+            && self.end_behavior == other.end_behavior
+            && self.focus_requests == other.focus_requests
+            && self.session_log == other.session_log
+            && self.allow_external_css_resources == other.allow_external_css_resources
             && self.presentation_resolution == other.presentation_resolution
     }
 
@@ -251,6 +593,7 @@ impl RunningPresentation {
                 .unwrap()
                 .slide_settings_option
                 .clone()
+                .map(|profile| profile.slide_settings)
                 .unwrap_or(SlideSettings::default()),
             None => SlideSettings::default(),
         }
@@ -279,6 +622,32 @@ impl RunningPresentation {
         }
     }
 
+    /// Returns the number of seconds after which the current slide advances automatically.
+    /// The timer of the current chapter takes precedence over the presentation-wide auto-advance.
+    pub fn get_auto_advance_seconds(&self) -> Option<u32> {
+        match self.get_current_timer_settings() {
+            Some(timer) => Some(timer.timer_seconds),
+            None => self.auto_advance_seconds,
+        }
+        .map(|seconds| seconds.max(1))
+    }
+
+    /// Advances the presentation when the auto-advance timer fires.
+    /// Follows the `after_last_slide` setting of the chapter timer and wraps around
+    /// at the end of the presentation if `loop_at_end` is set.
+    pub fn auto_advance(&mut self) {
+        let restarts_chapter = self
+            .get_current_timer_settings()
+            .is_some_and(|timer| timer.after_last_slide == AfterLastSlide::RestartCurrentChapter);
+        if restarts_chapter && self.is_last_slide_in_chapter() {
+            self.restart_current_chapter();
+        } else if self.loop_at_end && self.peek_next_slide().is_none() {
+            self.first_slide();
+        } else {
+            self.next_slide();
+        }
+    }
+
     /// Returns true if the current slide is the last slide in its chapter.
     pub fn is_last_slide_in_chapter(&self) -> bool {
         match self.position.clone() {
@@ -303,6 +672,27 @@ impl RunningPresentation {
         }
     }
 
+    /// Returns the meta information of the current chapter, if it should be shown in the footer
+    /// of the current slide.
+    pub fn current_meta_footer_text(&self, footer_slides: MetaFooterSlides) -> Option<String> {
+        let position = self.position.as_ref()?;
+        let chapter = self.presentation.get(position.chapter())?;
+        let slide_count = chapter.content_slide_count();
+        chapter
+            .meta_text
+            .clone()
+            .filter(|_| position.chapter_slide() < slide_count)
+            .filter(|_| footer_slides.shows_on(position.chapter_slide(), slide_count))
+    }
+
+    /// Returns the song number of the current chapter, if it should be shown in the meta area of
+    /// the current slide. It is only shown on the first slide, see [SlideChapter::song_number].
+    pub fn current_song_number(&self) -> Option<String> {
+        let position = self.position.as_ref()?;
+        let chapter = self.presentation.get(position.chapter())?;
+        chapter.song_number.clone().filter(|_| position.chapter_slide() == 0)
+    }
+
     /// Restart the current chapter from its first slide.
     pub fn restart_current_chapter(&mut self) {
         if let Some(ref pos) = self.position {
@@ -374,6 +764,55 @@ impl RunningPresentationPosition {
         }
     }
 
+    /// Goes to the first slide of the given chapter and recomputes the total slide number.
+    /// Returns an error (without changing the position) if the chapter doesn't exist or is empty.
+    pub fn go_to_chapter(&mut self, chapter: usize, presentation: &Vec<SlideChapter>) -> Result<(), ()> {
+        self.go_to(chapter, 0, presentation)
+    }
+
+    /// Goes to the first content slide of the current chapter, which is the slide after the
+    /// title slide if the chapter has one. Returns an error if the chapter doesn't exist.
+    pub fn go_to_chapter_start(&mut self, presentation: &Vec<SlideChapter>) -> Result<(), ()> {
+        let chapter = presentation.get(self.chapter).ok_or(())?;
+        let slide = if chapter.has_title_slide() && chapter.slides.len() > 1 { 1 } else { 0 };
+        self.go_to(self.chapter, slide, presentation)
+    }
+
+    /// Goes to the title slide (the first slide) of the current chapter.
+    /// Returns an error (without changing the position) if the chapter has no title slide.
+    pub fn go_to_chapter_title(&mut self, presentation: &Vec<SlideChapter>) -> Result<(), ()> {
+        if !presentation.get(self.chapter).ok_or(())?.has_title_slide() {
+            return Err(());
+        }
+        self.go_to(self.chapter, 0, presentation)
+    }
+
+    /// Goes to the first slide of the presentation, skipping chapters without slides.
+    /// Returns an error if the presentation has no slides at all.
+    pub fn go_to_first(&mut self, presentation: &Vec<SlideChapter>) -> Result<(), ()> {
+        let chapter = presentation.iter().position(|ch| !ch.slides.is_empty()).ok_or(())?;
+        self.go_to(chapter, 0, presentation)
+    }
+
+    /// Goes to the last slide of the presentation, skipping chapters without slides.
+    /// Returns an error if the presentation has no slides at all.
+    pub fn go_to_last(&mut self, presentation: &Vec<SlideChapter>) -> Result<(), ()> {
+        let chapter = presentation.iter().rposition(|ch| !ch.slides.is_empty()).ok_or(())?;
+        self.go_to(chapter, presentation[chapter].slides.len() - 1, presentation)
+    }
+
+    /// Goes to the given slide of the given chapter and recomputes the total slide number.
+    /// Returns an error (without changing the position) if the slide doesn't exist.
+    pub fn go_to(&mut self, chapter: usize, slide: usize, presentation: &Vec<SlideChapter>) -> Result<(), ()> {
+        if presentation.get(chapter).is_none_or(|ch| slide >= ch.slides.len()) {
+            return Err(());
+        }
+        self.chapter = chapter;
+        self.chapter_slide = slide;
+        self.slide_total = presentation[..chapter].iter().map(|ch| ch.slides.len()).sum::<usize>() + slide;
+        Ok(())
+    }
+
     /// Helper function for getting the current slide length
     fn cur_chapter_slide_length(&self, presentation: &Vec<SlideChapter>) -> usize {
         presentation.get(self.chapter).unwrap().slides.len()
@@ -401,13 +840,42 @@ pub struct SlideChapter {
     pub slides: Vec<Slide>,
     pub source_file: SourceFile,
     pub presentation_design_option: Option<PresentationDesign>,
-    pub slide_settings_option: Option<SlideSettings>,
+    pub slide_settings_option: Option<SlideSettingsProfile>,
     /// Optional timer settings for automatic slide advance.
     #[serde(default)]
     pub timer_settings_option: Option<SlideTimerSettings>,
     /// The transition effect for this chapter.
     #[serde(default)]
     pub transition_option: SlideTransition,
+    /// Whether the chapter uses the default design instead of one of its selected item, so that
+    /// changes of the default design can be applied while presenting.
+    #[serde(default)]
+    pub uses_default_design: bool,
+    /// Whether the chapter uses the default slide settings instead of ones of its selected item.
+    #[serde(default)]
+    pub uses_default_slide_settings: bool,
+    /// How the pictures of this chapter are presented.
+    #[serde(default)]
+    pub picture_settings: PictureSettings,
+    /// The meta information of a song (e.g. author and copyright), which can be shown in a
+    /// footer of its slides.
+    #[serde(default)]
+    pub meta_text: Option<String>,
+    /// Which languages are shown on the slides of a multi-language song.
+    #[serde(default)]
+    pub shown_languages: ShownLanguages,
+    /// The song number which is shown in the meta area of the first slide. It is only set for
+    /// songs without a title slide, otherwise the number is part of the title slide.
+    #[serde(default)]
+    pub song_number: Option<String>,
+    /// Whether the last slide of the chapter is a blank separator slide before the next chapter,
+    /// see [crate::logic::settings::Settings::blank_slide_between_chapters].
+    #[serde(default)]
+    pub separator_slide: bool,
+    /// The index of the selected item from which the chapter has been created, so that later
+    /// changes of the item can be applied, see [crate::logic::presentation::apply_design_changes].
+    #[serde(default)]
+    pub selected_item_index: Option<usize>,
 }
 
 impl SlideChapter {
@@ -415,7 +883,7 @@ impl SlideChapter {
         slides: Vec<Slide>,
         source_file: SourceFile,
         presentation_design: Option<PresentationDesign>,
-        slide_settings: Option<SlideSettings>,
+        slide_settings: Option<SlideSettingsProfile>,
     ) -> Self {
         SlideChapter {
             slides,
@@ -424,8 +892,49 @@ impl SlideChapter {
             slide_settings_option: slide_settings,
             timer_settings_option: None,
             transition_option: SlideTransition::default(),
+            uses_default_design: false,
+            uses_default_slide_settings: false,
+            picture_settings: PictureSettings::default(),
+            meta_text: None,
+            shown_languages: ShownLanguages::default(),
+            song_number: None,
+            separator_slide: false,
+            selected_item_index: None,
+        }
+    }
+
+    /// Returns the number of slides of the chapter without its separator slide.
+    pub fn content_slide_count(&self) -> usize {
+        self.slides.len() - usize::from(self.separator_slide && !self.slides.is_empty())
+    }
+
+    /// Appends a blank separator slide, unless the chapter already ends with one.
+    pub fn add_separator_slide(&mut self) {
+        if !self.separator_slide {
+            self.slides.push(Slide {
+                slide_content: SlideContent::Empty(EmptySlide {
+                    black_background: false,
+                }),
+                linked_file: None,
+            });
+            self.separator_slide = true;
         }
     }
+
+    /// Removes the separator slide of the chapter, if it has one.
+    pub fn remove_separator_slide(&mut self) {
+        if self.separator_slide {
+            self.slides.pop();
+            self.separator_slide = false;
+        }
+    }
+
+    /// Returns true if the chapter starts with a title slide, see [SlideSettings::title_slide].
+    pub fn has_title_slide(&self) -> bool {
+        self.slides
+            .first()
+            .is_some_and(|slide| matches!(slide.slide_content, SlideContent::Title(_)))
+    }
 }
 
 fn default_presentation_resolution() -> (u32, u32) {
@@ -435,6 +944,7 @@ fn default_presentation_resolution() -> (u32, u32) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logic::test_helpers::{test_chapter, test_chapter_with_title};
 
     #[cfg(target_os = "linux")]
     #[test]
@@ -483,4 +993,308 @@ mod tests {
         assert!(rp2.position.is_some());
         assert!(!rp2.is_black_screen);
     }
+
+    #[test]
+    fn navigation_ends_the_black_screen() {
+        let mut rp = RunningPresentation::new(vec![test_chapter("Test Song", 2)]);
+
+        rp.toggle_black_screen();
+        assert!(rp.is_black_screen);
+        assert_eq!(rp.position.as_ref().map(|p| p.slide_total()), Some(0));
+        rp.toggle_black_screen();
+        assert!(!rp.is_black_screen);
+
+        rp.toggle_black_screen();
+        rp.next_slide();
+        assert!(!rp.is_black_screen);
+        assert_eq!(rp.position.as_ref().map(|p| p.slide_total()), Some(1));
+    }
+
+    #[test]
+    fn screen_overrides_are_mutually_exclusive() {
+        use crate::logic::sourcefiles::{ImageSourceFile, SourceFile, SourceFileType};
+
+        let mut rp = RunningPresentation::new(vec![test_chapter("Test Song", 2)]);
+
+        // Without a logo, the logo screen can't be shown
+        rp.toggle_logo_screen();
+        assert!(!rp.hides_slide());
+
+        rp.logo_image = ImageSourceFile::new(SourceFile {
+            name: "Logo".to_string(),
+            path: PathBuf::from("test/logo.png"),
+            file_type: SourceFileType::Image,
+            md5_hash: None,
+        });
+        rp.toggle_black_screen();
+        rp.toggle_white_screen();
+        assert!(rp.is_white_screen && !rp.is_black_screen);
+        rp.toggle_logo_screen();
+        assert!(rp.is_logo_screen && !rp.is_white_screen);
+
+        rp.previous_slide();
+        assert!(!rp.hides_slide());
+    }
+
+    #[test]
+    fn the_end_behavior_is_applied_after_the_last_slide() {
+        let mut rp = RunningPresentation::new(vec![test_chapter("a", 2), test_chapter("b", 1)]);
+        rp.last_slide();
+
+        rp.next_slide();
+        assert_eq!(rp.position.as_ref().unwrap().slide_total(), 2);
+        assert!(!rp.is_black_screen);
+
+        rp.end_behavior = EndOfPresentationBehavior::Blank;
+        rp.next_slide();
+        assert_eq!(rp.position.as_ref().unwrap().slide_total(), 2);
+        assert!(rp.is_black_screen);
+
+        rp.end_behavior = EndOfPresentationBehavior::WrapAround;
+        rp.next_slide();
+        let position = rp.position.as_ref().unwrap();
+        assert_eq!((position.chapter(), position.chapter_slide(), position.slide_total()), (0, 0, 0));
+        assert!(!rp.is_black_screen);
+    }
+
+    #[test]
+    fn go_to_chapter_start_skips_the_title_slide() {
+        let presentation = vec![test_chapter("a", 2), test_chapter_with_title("b", 3)];
+        let mut position = RunningPresentationPosition::new(&presentation).unwrap();
+        position.go_to(1, 3, &presentation).unwrap();
+        assert_eq!(position.slide_total(), 5);
+
+        position.go_to_chapter_start(&presentation).unwrap();
+        assert_eq!((position.chapter(), position.chapter_slide(), position.slide_total()), (1, 1, 3));
+
+        position.go_to_chapter_title(&presentation).unwrap();
+        assert_eq!((position.chapter(), position.chapter_slide(), position.slide_total()), (1, 0, 2));
+    }
+
+    #[test]
+    fn chapters_without_a_title_slide_start_at_their_first_slide() {
+        let presentation = vec![test_chapter("a", 2), test_chapter("b", 3)];
+        let mut position = RunningPresentationPosition::new(&presentation).unwrap();
+        position.go_to(1, 2, &presentation).unwrap();
+
+        assert!(position.go_to_chapter_title(&presentation).is_err());
+        assert_eq!((position.chapter(), position.chapter_slide(), position.slide_total()), (1, 2, 4));
+
+        position.go_to_chapter_start(&presentation).unwrap();
+        assert_eq!((position.chapter(), position.chapter_slide(), position.slide_total()), (1, 0, 2));
+    }
+
+    #[test]
+    fn go_to_chapter_recomputes_the_total_slide_number() {
+        let mut rp = RunningPresentation::new(vec![
+            test_chapter("First", 3),
+            test_chapter("Second", 2),
+            test_chapter("Third", 4),
+        ]);
+
+        assert!(rp.go_to_chapter(2).is_ok());
+        let position = rp.position.clone().unwrap();
+        assert_eq!((position.chapter(), position.chapter_slide(), position.slide_total()), (2, 0, 5));
+
+        // Out of range chapters don't move the position
+        assert!(rp.go_to_chapter(3).is_err());
+        assert_eq!(rp.position.as_ref().map(|p| p.slide_total()), Some(5));
+    }
+
+    #[test]
+    fn go_to_keeps_the_total_slide_number_consistent() {
+        let presentation = vec![test_chapter("First", 2), test_chapter("Second", 3)];
+        let mut position = RunningPresentationPosition::new(&presentation).unwrap();
+
+        assert!(position.go_to(1, 2, &presentation).is_ok());
+        assert_eq!((position.chapter(), position.chapter_slide(), position.slide_total()), (1, 2, 4));
+        assert!(position.go_to(0, 1, &presentation).is_ok());
+        assert_eq!(position.slide_total(), 1);
+
+        // Positions outside of the presentation are rejected
+        assert!(position.go_to(1, 3, &presentation).is_err());
+        assert!(position.go_to(2, 0, &presentation).is_err());
+        assert_eq!((position.chapter(), position.chapter_slide()), (0, 1));
+    }
+
+    #[test]
+    fn go_to_first_and_last_skip_empty_chapters() {
+        let presentation = vec![
+            test_chapter("First", 2),
+            test_chapter("Second", 3),
+            test_chapter("Empty", 0),
+        ];
+        let mut position = RunningPresentationPosition::new(&presentation).unwrap();
+
+        assert!(position.go_to_last(&presentation).is_ok());
+        assert_eq!((position.chapter(), position.chapter_slide(), position.slide_total()), (1, 2, 4));
+        assert!(position.go_to_first(&presentation).is_ok());
+        assert_eq!((position.chapter(), position.chapter_slide(), position.slide_total()), (0, 0, 0));
+
+        // After going to the last slide, going back has to count down consistently
+        position.go_to_last(&presentation).unwrap();
+        position.try_back(&presentation).unwrap();
+        assert_eq!(position.slide_total(), 3);
+
+        assert!(position.go_to_last(&vec![test_chapter("Empty", 0)]).is_err());
+    }
+
+    #[test]
+    fn auto_advance_loops_at_the_end() {
+        let mut rp = RunningPresentation::new(vec![test_chapter("First", 1), test_chapter("Second", 2)]);
+        assert_eq!(rp.get_auto_advance_seconds(), None);
+        rp.auto_advance_seconds = Some(0);
+        assert_eq!(rp.get_auto_advance_seconds(), Some(1));
+
+        rp.last_slide();
+        rp.auto_advance();
+        assert_eq!(rp.position.as_ref().map(|p| p.slide_total()), Some(2));
+
+        rp.loop_at_end = true;
+        rp.auto_advance();
+        assert_eq!(rp.position.as_ref().map(|p| p.slide_total()), Some(0));
+    }
+
+    #[test]
+    fn presentations_are_found_by_their_id() {
+        let mut presentations = vec![RunningPresentation::new(vec![test_chapter("First", 1)])];
+        assert_eq!(next_presentation_id(&[]), 0);
+        assert_eq!(next_presentation_id(&presentations), 1);
+
+        let mut second = RunningPresentation::new(vec![test_chapter("Second", 2)]);
+        second.id = next_presentation_id(&presentations);
+        presentations.push(second);
+        // Ids of ended presentations are not reused while a later one is running
+        presentations.remove(0);
+        assert_eq!(next_presentation_id(&presentations), 2);
+
+        assert!(find_presentation(&presentations, 0).is_none());
+        assert_eq!(find_presentation(&presentations, 1).map(|rp| rp.total_slides()), Some(2));
+        find_presentation_mut(&mut presentations, 1).unwrap().next_slide();
+        assert_eq!(presentations[0].position.as_ref().map(|p| p.slide_total()), Some(1));
+    }
+
+    #[test]
+    fn chapters_can_be_appended_and_removed_while_presenting() {
+        let mut rp = RunningPresentation::new(vec![test_chapter("First", 2), test_chapter("Second", 1)]);
+        rp.jump_to(1, 0);
+
+        rp.append_chapter(test_chapter("Requested", 3), false);
+        assert_eq!(rp.total_slides(), 6);
+        assert_eq!(rp.peek_next_slide().map(|(chapter, _)| chapter), Some(2));
+
+        // Only chapters which haven't been reached yet can be removed
+        assert!(rp.remove_upcoming_chapter(1).is_err());
+        assert!(rp.remove_upcoming_chapter(0).is_err());
+        assert!(rp.remove_upcoming_chapter(2).is_ok());
+        assert!(rp.remove_upcoming_chapter(2).is_err());
+        let position = rp.position.clone().unwrap();
+        assert_eq!((position.chapter(), position.slide_total()), (1, 2));
+    }
+
+    #[test]
+    fn separator_slides_follow_appended_and_removed_chapters() {
+        let mut rp = RunningPresentation::new(vec![test_chapter("First", 2), test_chapter("Second", 1)]);
+        rp.jump_to(1, 0);
+
+        rp.append_chapter(test_chapter("Requested", 3), true);
+        assert!(rp.presentation[1].separator_slide);
+        assert_eq!(rp.presentation[1].slides.len(), 2);
+        assert_eq!(rp.total_slides(), 7);
+
+        // The separator is only added once
+        rp.append_chapter(test_chapter("Another", 1), true);
+        assert!(rp.presentation[2].separator_slide);
+        assert_eq!(rp.presentation[1].slides.len(), 2);
+
+        // The last chapter doesn't end with a separator
+        assert!(rp.remove_upcoming_chapter(3).is_ok());
+        assert!(!rp.presentation[2].separator_slide);
+        assert!(rp.remove_upcoming_chapter(2).is_ok());
+        assert!(!rp.presentation[1].separator_slide);
+        assert_eq!(rp.total_slides(), 3);
+    }
+
+    #[test]
+    fn peek_next_slide_does_not_move_the_position() {
+        let mut rp = RunningPresentation::new(vec![test_chapter("First", 2), test_chapter("Second", 1)]);
+
+        assert_eq!(rp.peek_next_slide().map(|(chapter, _)| chapter), Some(0));
+        rp.next_slide();
+        assert_eq!(rp.peek_next_slide().map(|(chapter, _)| chapter), Some(1));
+        assert_eq!(rp.position.as_ref().map(|p| p.slide_total()), Some(1));
+        rp.next_slide();
+        assert!(rp.peek_next_slide().is_none());
+    }
+
+    #[test]
+    fn current_source_file_follows_the_chapters() {
+        let mut rp = RunningPresentation::new(vec![test_chapter("First", 1), test_chapter("Second", 1)]);
+
+        assert_eq!(rp.current_source_file().map(|file| file.name.as_str()), Some("First"));
+        rp.next_slide();
+        assert_eq!(rp.current_source_file().map(|file| file.name.as_str()), Some("Second"));
+        rp.position = None;
+        assert!(rp.current_source_file().is_none());
+    }
+
+    #[test]
+    fn selected_item_refers_to_its_source_file() {
+        use crate::logic::sourcefiles::{SourceFile, SourceFileType};
+        use std::path::PathBuf;
+
+        let source_file = SourceFile {
+            name: "Test Song".to_string(),
+            path: PathBuf::from("test/path.song"),
+            file_type: SourceFileType::Song,
+            md5_hash: None,
+        };
+        let other_file = SourceFile {
+            path: PathBuf::from("test/other.song"),
+            ..source_file.clone()
+        };
+
+        let item = SelectedItemRepresentation::new_with_sourcefile(source_file.clone());
+        assert!(item.refers_to(&source_file));
+        assert!(!item.refers_to(&other_file));
+
+        let mut markdown_item = SelectedItemRepresentation::new_with_sourcefile(source_file.clone());
+        markdown_item.inline_markdown = Some("Text".to_string());
+        assert!(!markdown_item.refers_to(&source_file));
+    }
+
+    #[test]
+    fn meta_footer_is_shown_on_the_chosen_slides() {
+        let mut chapter = test_chapter_with_title("Amazing Grace", 2);
+        chapter.meta_text = Some("John Newton".to_string());
+        let mut rp = RunningPresentation::new(vec![chapter, test_chapter("Other", 1)]);
+        let author = Some("John Newton".to_string());
+
+        assert_eq!(rp.current_meta_footer_text(MetaFooterSlides::FirstSlide), author);
+        assert_eq!(rp.current_meta_footer_text(MetaFooterSlides::LastSlide), None);
+        rp.jump_to(0, 2);
+        assert_eq!(rp.current_meta_footer_text(MetaFooterSlides::FirstSlide), None);
+        assert_eq!(rp.current_meta_footer_text(MetaFooterSlides::LastSlide), author);
+        assert_eq!(rp.current_meta_footer_text(MetaFooterSlides::EverySlide), author);
+
+        // Chapters without meta information have no footer
+        rp.jump_to(1, 0);
+        assert_eq!(rp.current_meta_footer_text(MetaFooterSlides::EverySlide), None);
+    }
+
+    #[test]
+    fn announcement_name_is_its_first_line() {
+        let mut item = SelectedItemRepresentation::new_announcement(
+            "\n  Coffee after the service  \nin room 2".to_string(),
+        );
+        assert_eq!(item.source_file.name, "Coffee after the service");
+        assert_eq!(item.source_file.file_type, SourceFileType::Text);
+
+        item.set_inline_text("A very long announcement which does not fit into the list".to_string());
+        assert_eq!(item.source_file.name, "A very long announcement which does not…");
+        assert_eq!(
+            item.inline_text.as_deref(),
+            Some("A very long announcement which does not fit into the list")
+        );
+    }
 }
@@ -0,0 +1,70 @@
+//! This module lists the font families which are installed on the system, so that they can be
+//! chosen for the presentation designs.
+//!
+//! The fonts are enumerated on the desktop only. Other platforms return an empty list and
+//! offer the generic font families only.
+
+use std::sync::OnceLock;
+
+/// The font families of the system, which are enumerated only once per run.
+static SYSTEM_FONT_FAMILIES: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Returns the names of the installed font families, sorted alphabetically.
+pub fn system_font_families() -> &'static [String] {
+    SYSTEM_FONT_FAMILIES.get_or_init(|| sorted_family_names(enumerate_font_families()))
+}
+
+/// Reads the family names of all fonts installed on the system.
+#[cfg(feature = "desktop")]
+fn enumerate_font_families() -> Vec<String> {
+    let mut database = fontdb::Database::new();
+    database.load_system_fonts();
+    database
+        .faces()
+        .flat_map(|face| face.families.iter().map(|(name, _)| name.clone()))
+        .collect()
+}
+
+/// Reads the family names of all fonts installed on the system.
+#[cfg(not(feature = "desktop"))]
+fn enumerate_font_families() -> Vec<String> {
+    vec![]
+}
+
+/// Sorts the family names case-insensitively and removes duplicates and empty names.
+/// Names starting with a dot are hidden fonts (e.g. on macOS) and are left out as well.
+fn sorted_family_names(names: Vec<String>) -> Vec<String> {
+    let mut names: Vec<String> = names
+        .into_iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty() && !name.starts_with('.'))
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn family_names_are_sorted_without_duplicates() {
+        let names = vec![
+            "Noto Sans".to_string(),
+            "DejaVu Serif".to_string(),
+            " ".to_string(),
+            "Noto Sans".to_string(),
+            ".SF NS".to_string(),
+            "cantarell".to_string(),
+        ];
+        assert_eq!(
+            sorted_family_names(names),
+            vec![
+                "cantarell".to_string(),
+                "DejaVu Serif".to_string(),
+                "Noto Sans".to_string()
+            ]
+        );
+    }
+}
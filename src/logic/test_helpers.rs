@@ -0,0 +1,89 @@
+//! This module provides the slides, chapters and selected items which are shared by the tests
+//! of the logic modules.
+
+use crate::logic::sourcefiles::{SourceFile, SourceFileType};
+use crate::logic::states::{SelectedItemRepresentation, SlideChapter};
+use cantara_songlib::slides::{EmptySlide, Slide, SlideContent};
+use serde::{Serialize, de::DeserializeOwned};
+use std::path::PathBuf;
+
+/// Returns the source file of a song in the `testfiles` folder, e.g. `testfiles/Amazing Grace.song`.
+pub fn song_file(name: &str) -> SourceFile {
+    SourceFile {
+        name: name.to_string(),
+        path: PathBuf::from(format!("testfiles/{}.song", name)),
+        file_type: SourceFileType::Song,
+        md5_hash: None,
+    }
+}
+
+/// Returns a selected item of a song in the `testfiles` folder, see [song_file].
+pub fn song_item(name: &str) -> SelectedItemRepresentation {
+    SelectedItemRepresentation::new_with_sourcefile(song_file(name))
+}
+
+/// Returns a title slide with the given title and meta text.
+pub fn title_slide(title_text: &str, meta_text: &str) -> Slide {
+    Slide {
+        slide_content: SlideContent::Title(
+            serde_json::from_value(serde_json::json!({
+                "title_text": title_text,
+                "meta_text": meta_text
+            }))
+            .unwrap(),
+        ),
+        linked_file: None,
+    }
+}
+
+/// Returns a song slide with the given main text and spoiler.
+pub fn stanza(main_text: &str, spoiler_text: &str) -> Slide {
+    Slide {
+        slide_content: SlideContent::SingleLanguageMainContent(
+            serde_json::from_value(serde_json::json!({
+                "main_text": main_text,
+                "spoiler_text": spoiler_text
+            }))
+            .unwrap(),
+        ),
+        linked_file: None,
+    }
+}
+
+/// Returns a blank slide.
+pub fn empty_slide() -> Slide {
+    Slide {
+        slide_content: SlideContent::Empty(EmptySlide {
+            black_background: false,
+        }),
+        linked_file: None,
+    }
+}
+
+/// Returns a chapter of the song with the given name, which consists of the given slides.
+pub fn chapter(name: &str, slides: Vec<Slide>) -> SlideChapter {
+    SlideChapter::new(slides, song_file(name), None, None)
+}
+
+/// Returns a chapter which consists of `slide_count` blank slides.
+pub fn test_chapter(name: &str, slide_count: usize) -> SlideChapter {
+    chapter(name, vec![empty_slide(); slide_count])
+}
+
+/// Returns a chapter which starts with a title slide followed by `slide_count` blank slides.
+pub fn test_chapter_with_title(name: &str, slide_count: usize) -> SlideChapter {
+    let mut chapter = test_chapter(name, slide_count);
+    chapter.slides.insert(0, title_slide(name, ""));
+    chapter
+}
+
+/// Serializes the value without the given fields and loads it again, like settings which have
+/// been saved before these fields existed.
+pub fn without_fields<T: Serialize + DeserializeOwned>(value: &T, fields: &[&str]) -> T {
+    let mut json = serde_json::to_value(value).unwrap();
+    let object = json.as_object_mut().unwrap();
+    for field in fields {
+        object.remove(*field);
+    }
+    serde_json::from_value(json).unwrap()
+}
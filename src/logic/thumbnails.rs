@@ -0,0 +1,80 @@
+//! This module caches the thumbnails of presentation designs, so that a selector with many
+//! designs doesn't have to render a live presentation for every design.
+//!
+//! A thumbnail is stored under a key which is derived from the design and the slide settings.
+//! When a design is edited, its key changes and the old thumbnail is not used anymore.
+
+use crate::logic::settings::PresentationDesign;
+use cantara_songlib::slides::SlideSettings;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+
+/// The number of thumbnails which are kept at most. If there are more, the cache is cleared.
+const MAX_CACHED_THUMBNAILS: usize = 64;
+
+/// The thumbnails (as data URLs) by their key, see [design_thumbnail_key].
+static DESIGN_THUMBNAILS: OnceLock<Mutex<HashMap<u64, String>>> = OnceLock::new();
+
+fn design_thumbnails() -> &'static Mutex<HashMap<u64, String>> {
+    DESIGN_THUMBNAILS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Returns the key of the thumbnail of a design which is shown with the given slide settings.
+pub fn design_thumbnail_key(design: &PresentationDesign, slide_settings: &SlideSettings) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(design)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    serde_json::to_string(slide_settings)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the cached thumbnail with the given key, if there is one.
+pub fn cached_thumbnail(key: u64) -> Option<String> {
+    design_thumbnails()
+        .lock()
+        .ok()
+        .and_then(|thumbnails| thumbnails.get(&key).cloned())
+}
+
+/// Stores a thumbnail (as data URL) under the given key.
+pub fn store_thumbnail(key: u64, thumbnail: String) {
+    if let Ok(mut thumbnails) = design_thumbnails().lock() {
+        // Thumbnails of edited designs are never used again, so they are dropped from time to time
+        if thumbnails.len() >= MAX_CACHED_THUMBNAILS && !thumbnails.contains_key(&key) {
+            thumbnails.clear();
+        }
+        thumbnails.insert(key, thumbnail);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edited_designs_get_a_new_thumbnail_key() {
+        let design = PresentationDesign::default();
+        let slide_settings = SlideSettings::default();
+        let key = design_thumbnail_key(&design, &slide_settings);
+        assert_eq!(key, design_thumbnail_key(&design.clone(), &slide_settings));
+
+        let mut edited_design = design.clone();
+        edited_design.description = "Edited".to_string();
+        assert_ne!(key, design_thumbnail_key(&edited_design, &slide_settings));
+    }
+
+    #[test]
+    fn thumbnails_are_cached_by_key() {
+        let key = design_thumbnail_key(&PresentationDesign::default(), &SlideSettings::default());
+        store_thumbnail(key, "data:image/svg+xml,thumbnail".to_string());
+        assert_eq!(
+            cached_thumbnail(key),
+            Some("data:image/svg+xml,thumbnail".to_string())
+        );
+        assert_eq!(cached_thumbnail(key.wrapping_add(1)), None);
+    }
+}
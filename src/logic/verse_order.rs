@@ -0,0 +1,209 @@
+//! This module reorders the parts of a song before its slides are created, so that a song can
+//! be presented e.g. as `V1 C V2 C V3 C` instead of the order in its file.
+//!
+//! Songs in the classic format have no labels for their parts. The parts are labeled implicitly:
+//! a part which occurs more than once in the file is a chorus (`C`, `C2`, …), all other parts
+//! are verses (`V1`, `V2`, …). The order can be given by an `#order:` line in the song file or
+//! by an override of the selected item, which takes precedence.
+
+/// The name of the directive which sets the verse order in a song file.
+const ORDER_DIRECTIVE: &str = "#order:";
+
+/// A part (verse or chorus) of a song with its implicit label.
+#[derive(Debug, Clone, PartialEq)]
+struct SongPart {
+    label: String,
+    text: String,
+}
+
+/// A song whose parts have been brought into a verse order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderedSong {
+    /// The content of the song in the classic format, with its parts in the requested order
+    pub content: String,
+
+    /// The references of the order which don't name a part of the song
+    pub unknown_parts: Vec<String>,
+}
+
+/// Splits the content of a song into blocks which are separated by empty lines.
+fn blocks(content: &str) -> Vec<String> {
+    let mut blocks: Vec<String> = vec![];
+    let mut current: Vec<&str> = vec![];
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(current.join("\n"));
+                current.clear();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current.join("\n"));
+    }
+    blocks
+}
+
+/// Returns true if the block consists of header lines only (e.g. `#title:` and `#author:`).
+fn is_header(block: &str) -> bool {
+    block.lines().all(|line| line.trim_start().starts_with('#'))
+}
+
+/// Returns the parts of a song with their implicit labels, each distinct part once.
+fn song_parts(content: &str) -> Vec<SongPart> {
+    let texts: Vec<String> = blocks(content)
+        .into_iter()
+        .filter(|block| !is_header(block))
+        .collect();
+
+    let mut parts: Vec<SongPart> = vec![];
+    let (mut verses, mut choruses) = (0, 0);
+    for text in &texts {
+        if parts.iter().any(|part| part.text == *text) {
+            continue;
+        }
+        let label = if texts.iter().filter(|other| *other == text).count() > 1 {
+            choruses += 1;
+            if choruses == 1 { "C".to_string() } else { format!("C{}", choruses) }
+        } else {
+            verses += 1;
+            format!("V{}", verses)
+        };
+        parts.push(SongPart { label, text: text.clone() });
+    }
+    parts
+}
+
+/// Returns the labels of the parts of a song in the order of their first occurrence.
+pub fn part_labels(content: &str) -> Vec<String> {
+    song_parts(content).into_iter().map(|part| part.label).collect()
+}
+
+/// Returns the texts of the choruses of a song, see [part_labels].
+pub fn chorus_texts(content: &str) -> Vec<String> {
+    song_parts(content)
+        .into_iter()
+        .filter(|part| part.label.starts_with('C'))
+        .map(|part| part.text)
+        .collect()
+}
+
+/// Returns the verse order which is given by an `#order:` line of a song file, if there is one.
+pub fn order_directive(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix(ORDER_DIRECTIVE))
+        .map(|order| order.trim().to_string())
+        .filter(|order| !order.is_empty())
+}
+
+/// Returns the verse order which is used for a song: the override if it is not empty,
+/// otherwise the `#order:` line of the song file.
+pub fn effective_order(content: &str, order_override: Option<&str>) -> Option<String> {
+    order_override
+        .map(str::trim)
+        .filter(|order| !order.is_empty())
+        .map(str::to_string)
+        .or_else(|| order_directive(content))
+}
+
+/// Normalizes a reference of a verse order, so that e.g. `v1`, `1` and `V1` name the same part.
+/// `R` (refrain) is accepted for the chorus as well.
+fn normalize_reference(reference: &str) -> String {
+    let reference = reference.trim().to_uppercase();
+    if reference.chars().all(|c| c.is_ascii_digit()) {
+        return format!("V{}", reference);
+    }
+    match reference.strip_prefix('R') {
+        Some(number) => format!("C{}", number),
+        None => reference,
+    }
+}
+
+/// Brings the parts of a song into the given verse order. Parts can be named more than once
+/// and parts which are not named are left out. References which don't name a part of the
+/// song are returned as unknown parts and skipped. If no reference names a part, the song
+/// keeps the order of its file.
+///
+/// # Arguments
+/// * `content` - The content of the song file in the classic format
+/// * `order` - The references of the parts, separated by spaces or commas
+pub fn apply_verse_order(content: &str, order: &str) -> OrderedSong {
+    let parts = song_parts(content);
+    let mut unknown_parts: Vec<String> = vec![];
+    let mut ordered_parts: Vec<&str> = vec![];
+    for reference in order.split(|c: char| c.is_whitespace() || c == ',').filter(|r| !r.is_empty()) {
+        let label = normalize_reference(reference);
+        match parts.iter().find(|part| part.label == label) {
+            Some(part) => ordered_parts.push(&part.text),
+            None => unknown_parts.push(reference.to_string()),
+        }
+    }
+
+    let header: Vec<String> = blocks(content)
+        .into_iter()
+        .filter(|block| is_header(block))
+        .map(|block| {
+            block
+                .lines()
+                .filter(|line| !line.trim_start().starts_with(ORDER_DIRECTIVE))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .filter(|block| !block.is_empty())
+        .collect();
+    let body: Vec<String> = if ordered_parts.is_empty() {
+        blocks(content).into_iter().filter(|block| !is_header(block)).collect()
+    } else {
+        ordered_parts.into_iter().map(str::to_string).collect()
+    };
+
+    OrderedSong {
+        content: header.into_iter().chain(body).collect::<Vec<_>>().join("\n\n"),
+        unknown_parts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SONG: &str = "#title: Song\n#order: V1 C V2 C C\n\nVerse one\n\nChorus\nline two\n\nVerse two\n\nChorus\nline two\n";
+
+    #[test]
+    fn parts_are_labeled_by_their_repetitions() {
+        assert_eq!(part_labels(SONG), vec!["V1", "C", "V2"]);
+        assert_eq!(chorus_texts(SONG), vec!["Chorus\nline two"]);
+        assert_eq!(order_directive(SONG), Some("V1 C V2 C C".to_string()));
+        assert_eq!(effective_order(SONG, Some(" ")), Some("V1 C V2 C C".to_string()));
+        assert_eq!(effective_order(SONG, Some("2, 1")), Some("2, 1".to_string()));
+        assert_eq!(effective_order("Verse", None), None);
+    }
+
+    #[test]
+    fn verse_orders_repeat_and_reorder_parts() {
+        let ordered = apply_verse_order(SONG, "v2, R 1 c");
+        assert_eq!(
+            ordered.content,
+            "#title: Song\n\nVerse two\n\nChorus\nline two\n\nVerse one\n\nChorus\nline two"
+        );
+        assert!(ordered.unknown_parts.is_empty());
+    }
+
+    #[test]
+    fn unknown_parts_are_reported_and_skipped() {
+        let ordered = apply_verse_order(SONG, "V1 B V3");
+        assert_eq!(ordered.content, "#title: Song\n\nVerse one");
+        assert_eq!(ordered.unknown_parts, vec!["B", "V3"]);
+
+        let ordered = apply_verse_order(SONG, "B");
+        assert_eq!(
+            ordered.content,
+            "#title: Song\n\nVerse one\n\nChorus\nline two\n\nVerse two\n\nChorus\nline two"
+        );
+        assert_eq!(ordered.unknown_parts, vec!["B"]);
+    }
+}